@@ -5,7 +5,7 @@ use syn::parse::{Error, Result};
 use syn::spanned::Spanned;
 use syn::{Attribute, Ident, Lit, LitStr, Meta, NestedMeta, Path};
 
-use crate::structures::{Checks, Colour, HelpBehaviour, OnlyIn, Permissions};
+use crate::structures::{Checks, Choice, Colour, HelpBehaviour, Number, OnlyIn, Permissions};
 use crate::util::{AsOption, LitExt};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -278,6 +278,31 @@ impl AttributeOption for Permissions {
     }
 }
 
+impl AttributeOption for Choice {
+    fn parse(values: Values) -> Result<Self> {
+        validate(&values, &[ValueKind::List])?;
+
+        match &values.literals[..] {
+            [name, value] => Ok(Choice {
+                name: name.to_str(),
+                value: value.clone(),
+            }),
+            _ => Err(Error::new(values.span, "expected a name and a value")),
+        }
+    }
+}
+
+impl AttributeOption for Option<Number> {
+    fn parse(values: Values) -> Result<Self> {
+        validate(&values, &[ValueKind::Equals, ValueKind::SingleList])?;
+
+        match &values.literals[0] {
+            l @ (Lit::Int(_) | Lit::Float(_)) => Ok(Some(Number(l.clone()))),
+            l => Err(Error::new(l.span(), "expected a number")),
+        }
+    }
+}
+
 impl<T: AttributeOption> AttributeOption for AsOption<T> {
     #[inline]
     fn parse(values: Values) -> Result<Self> {
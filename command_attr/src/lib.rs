@@ -941,3 +941,216 @@ pub fn hook(_attr: TokenStream, input: TokenStream) -> TokenStream {
         },
     }
 }
+
+/// A derive macro implementing `CommandOptions` for a struct, turning each of
+/// its fields into an option of a slash command.
+///
+/// The struct is used with the `InteractionFramework`: the options it defines
+/// are added to a command with `SlashCommand::options_from`, and the options
+/// given to the command are parsed into it with `CommandOptions::from_options`
+/// or `CommandOptions::from_command_data`.
+///
+/// The type of each field must implement `OptionValue`, which decides the
+/// type of the option. These are `String`, `i64`, `f64`, `bool`, `User`,
+/// `UserId`, `PartialMember`, `Role`, `RoleId`, `PartialChannel`, `ChannelId`,
+/// `Attachment` and `AttachmentId`. Fields of an `Option` of one of these
+/// types are optional options, all others are required.
+///
+/// ## Options
+///
+/// Options are given to each field as attributes:
+///
+/// | Syntax                                               | Description                                                      | Argument explanation                                                                                                   |
+/// | ---------------------------------------------------- | ---------------------------------------------------------------- | ---------------------------------------------------------------------------------------------------------------------- |
+/// | `#[name(s)]` </br> `#[name = s]`                     | The name of the option.                                          | `s` is a string. If this option isn't provided, the name of the field is used.                                         |
+/// | `#[description(s)]` </br> `#[description = s]`       | The description of the option. Required.                         | `s` is a string.                                                                                                       |
+/// | `#[choice(name, value)]`                             | A choice the option is restricted to. May be given several times. | `name` is a string, `value` is a string, an integer or a float literal, depending on the type of the option.          |
+/// | `#[min_value(n)]` </br> `#[max_value(n)]`            | The bounds of an integer or number option.                       | `n` is an integer or a float literal.                                                                                  |
+/// | `#[min_length(n)]` </br> `#[max_length(n)]`          | The bounds of the length of a string option.                     | `n` is a 16-bit, unsigned integer.                                                                                     |
+/// | `#[channel_types(types)]`                            | The types of channels a channel option is restricted to.         | `types` is a comma separated list of snake case names of `ChannelType` variants, such as `text` or `public_thread`.   |
+/// | `#[autocomplete]` </br> `#[autocomplete(b)]`         | If the option is autocompleted.                                  | `b` is a boolean. If no boolean is provided, the value is assumed to be `true`.                                        |
+///
+/// Documentation comments (`///`) applied onto a field are interpreted as sugar for the
+/// `#[description]` option, the same way as for [`command`].
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use serenity::framework::interaction::CommandOptions;
+/// use serenity::model::user::User;
+///
+/// #[derive(CommandOptions)]
+/// struct Ban {
+///     /// The user to ban.
+///     user: User,
+///     /// The number of days of messages to delete.
+///     #[min_value(0)]
+///     #[max_value(7)]
+///     days: Option<i64>,
+///     /// Why the user is banned.
+///     #[max_length(512)]
+///     reason: Option<String>,
+/// }
+/// ```
+///
+/// [`command`]: macro@command
+#[proc_macro_derive(
+    CommandOptions,
+    attributes(
+        name,
+        description,
+        choice,
+        min_value,
+        max_value,
+        min_length,
+        max_length,
+        channel_types,
+        autocomplete
+    )
+)]
+pub fn command_options(input: TokenStream) -> TokenStream {
+    let options_struct = parse_macro_input!(input as OptionsStruct);
+
+    let mut creates = Vec::with_capacity(options_struct.fields.len());
+    let mut parses = Vec::with_capacity(options_struct.fields.len());
+
+    let path = quote!(serenity::framework::interaction);
+    let builder_path = quote!(serenity::builder::CreateApplicationCommandOption);
+
+    for field in &options_struct.fields {
+        let mut options = OptionFieldOptions::default();
+
+        for attribute in &field.attributes {
+            let span = attribute.span();
+            let values = propagate_err!(parse_values(attribute));
+
+            let name = values.name.to_string();
+            let name = &name[..];
+
+            match name {
+                "description" => {
+                    let line: String = propagate_err!(attributes::parse(values));
+                    util::append_line(&mut options.description, line);
+                },
+                "choice" => {
+                    options.choices.push(propagate_err!(attributes::parse(values)));
+                },
+                _ => match_options!(name, values, options, span => [
+                    name;
+                    min_value;
+                    max_value;
+                    min_length;
+                    max_length;
+                    channel_types;
+                    autocomplete
+                ]),
+            }
+        }
+
+        let OptionFieldOptions {
+            name,
+            description,
+            choices,
+            min_value,
+            max_value,
+            min_length,
+            max_length,
+            channel_types,
+            autocomplete,
+        } = options;
+
+        let field_name = &field.name;
+        let kind = &field.kind;
+        let name = name.unwrap_or_else(|| field_name.to_string_non_raw());
+
+        let description = match description.0 {
+            Some(description) => description.trim_end().to_string(),
+            None => {
+                return Error::new(field_name.span(), "options require a description")
+                    .to_compile_error()
+                    .into();
+            },
+        };
+
+        let mut calls = Vec::new();
+
+        for choice in choices {
+            let method = propagate_err!(choice.method());
+            let Choice {
+                name,
+                value,
+            } = choice;
+
+            calls.push(quote!(#method(#name, #value)));
+        }
+
+        if let Some(value) = min_value {
+            let method = value.method("min");
+            calls.push(quote!(#method(#value)));
+        }
+
+        if let Some(value) = max_value {
+            let method = value.method("max");
+            calls.push(quote!(#method(#value)));
+        }
+
+        if let Some(length) = min_length {
+            calls.push(quote!(min_length(#length)));
+        }
+
+        if let Some(length) = max_length {
+            calls.push(quote!(max_length(#length)));
+        }
+
+        if !channel_types.is_empty() {
+            let variants = channel_types.iter().map(channel_type_variant);
+            calls.push(quote! {
+                channel_types(&[#(serenity::model::channel::ChannelType::#variants),*])
+            });
+        }
+
+        if autocomplete {
+            calls.push(quote!(set_autocomplete(true)));
+        }
+
+        creates.push(quote! {
+            {
+                let mut option = #builder_path::default();
+                option
+                    .name(#name)
+                    .description(#description)
+                    .kind(<#kind as #path::OptionValue>::KIND)
+                    .required(<#kind as #path::OptionValue>::REQUIRED);
+                #(option.#calls;)*
+                option
+            }
+        });
+
+        parses.push(quote! {
+            #field_name: <#kind as #path::OptionValue>::from_option(
+                options.iter().find(|option| option.name == #name),
+                #name,
+            )?
+        });
+    }
+
+    let name = &options_struct.name;
+    let (impl_generics, ty_generics, where_clause) = options_struct.generics.split_for_impl();
+
+    (quote! {
+        impl #impl_generics #path::CommandOptions for #name #ty_generics #where_clause {
+            fn create_options() -> Vec<#builder_path> {
+                vec![#(#creates),*]
+            }
+
+            fn from_options(
+                options: &[serenity::model::application::interaction::application_command::CommandDataOption],
+            ) -> std::result::Result<Self, #path::OptionError> {
+                Ok(Self {
+                    #(#parses,)*
+                })
+            }
+        }
+    })
+    .into()
+}
@@ -9,10 +9,16 @@ use syn::{
     braced,
     Attribute,
     Block,
+    Data,
+    DataStruct,
+    DeriveInput,
     Expr,
     ExprClosure,
+    Fields,
     FnArg,
+    Generics,
     Ident,
+    Lit,
     Pat,
     ReturnType,
     Stmt,
@@ -624,3 +630,152 @@ impl GroupOptions {
         }
     }
 }
+
+#[derive(Debug)]
+pub struct OptionsStruct {
+    pub name: Ident,
+    pub generics: Generics,
+    pub fields: Vec<OptionField>,
+}
+
+impl Parse for OptionsStruct {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let input = input.parse::<DeriveInput>()?;
+
+        let fields = match input.data {
+            Data::Struct(DataStruct {
+                fields: Fields::Named(fields), ..
+            }) => fields.named,
+            _ => {
+                return Err(Error::new(
+                    input.ident.span(),
+                    "`CommandOptions` can only be derived for structs with named fields",
+                ))
+            },
+        };
+
+        let fields = fields
+            .into_iter()
+            .map(|field| {
+                let mut attributes = field.attrs;
+
+                util::rename_attributes(&mut attributes, "doc", "description");
+                attributes.retain(is_option_attribute);
+
+                OptionField {
+                    attributes,
+                    // Named fields always have an identifier.
+                    name: field.ident.unwrap(),
+                    kind: field.ty,
+                }
+            })
+            .collect();
+
+        Ok(Self {
+            name: input.ident,
+            generics: input.generics,
+            fields,
+        })
+    }
+}
+
+/// Test if the attribute is one of the options of a field of `CommandOptions`.
+fn is_option_attribute(attr: &Attribute) -> bool {
+    const OPTION_ATTRIBUTE_NAMES: &[&str] = &[
+        "name",
+        "description",
+        "choice",
+        "min_value",
+        "max_value",
+        "min_length",
+        "max_length",
+        "channel_types",
+        "autocomplete",
+    ];
+
+    OPTION_ATTRIBUTE_NAMES.iter().any(|n| attr.path.is_ident(n))
+}
+
+#[derive(Debug)]
+pub struct OptionField {
+    pub attributes: Vec<Attribute>,
+    pub name: Ident,
+    pub kind: Type,
+}
+
+#[derive(Debug, Default)]
+pub struct OptionFieldOptions {
+    pub name: Option<String>,
+    pub description: AsOption<String>,
+    pub choices: Vec<Choice>,
+    pub min_value: Option<Number>,
+    pub max_value: Option<Number>,
+    pub min_length: Option<u16>,
+    pub max_length: Option<u16>,
+    pub channel_types: Vec<Ident>,
+    pub autocomplete: bool,
+}
+
+#[derive(Debug)]
+pub struct Choice {
+    pub name: String,
+    pub value: Lit,
+}
+
+impl Choice {
+    /// The method of `CreateApplicationCommandOption` adding the choice.
+    pub fn method(&self) -> Result<Ident> {
+        let method = match &self.value {
+            Lit::Str(_) => "add_string_choice",
+            Lit::Int(value) => {
+                // `add_int_choice` takes an `i32`.
+                if value.base10_parse::<i32>().is_err() {
+                    return Err(Error::new(value.span(), "integer choices must fit in an `i32`"));
+                }
+
+                "add_int_choice"
+            },
+            Lit::Float(_) => "add_number_choice",
+            value => return Err(Error::new(value.span(), "expected a string or a number")),
+        };
+
+        Ok(Ident::new(method, self.value.span()))
+    }
+}
+
+#[derive(Debug)]
+pub struct Number(pub Lit);
+
+impl Number {
+    /// The method of `CreateApplicationCommandOption` setting the value as a
+    /// bound, with the given prefix.
+    pub fn method(&self, prefix: &str) -> Ident {
+        let kind = if let Lit::Float(_) = self.0 { "number" } else { "int" };
+
+        Ident::new(&format!("{}_{}_value", prefix, kind), self.0.span())
+    }
+}
+
+impl ToTokens for Number {
+    fn to_tokens(&self, stream: &mut TokenStream2) {
+        self.0.to_tokens(stream);
+    }
+}
+
+/// Converts the snake case name of a channel type to the name of its variant.
+pub fn channel_type_variant(name: &Ident) -> Ident {
+    let variant = name
+        .to_string_non_raw()
+        .split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect::<String>();
+
+    Ident::new(&variant, name.span())
+}
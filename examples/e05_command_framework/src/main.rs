@@ -364,7 +364,7 @@ async fn say(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
                 ContentSafeOptions::default().clean_channel(false).clean_role(false)
             };
 
-            let content = content_safe(&ctx.cache, x, &settings, &msg.mentions);
+            let content = content_safe(&ctx.cache, x, &settings, &msg.mentions).await;
 
             msg.channel_id.say(&ctx.http, &content).await?;
 
@@ -423,17 +423,21 @@ async fn some_long_command(ctx: &Context, msg: &Message, args: Args) -> CommandR
 async fn about_role(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
     let potential_role_name = args.rest();
 
-    if let Some(guild) = msg.guild(&ctx.cache) {
-        // `role_by_name()` allows us to attempt attaining a reference to a role
-        // via its name.
-        if let Some(role) = guild.role_by_name(potential_role_name) {
-            if let Err(why) = msg.channel_id.say(&ctx.http, &format!("Role-ID: {}", role.id)).await
-            {
-                println!("Error sending message: {:?}", why);
-            }
+    // `role_by_name()` allows us to attempt attaining a reference to a role
+    // via its name. Only its ID is copied out of the cached guild.
+    let role_id = msg
+        .guild_field(&ctx.cache, |guild| {
+            guild.role_by_name(potential_role_name).map(|role| role.id)
+        })
+        .await
+        .flatten();
 
-            return Ok(());
+    if let Some(role_id) = role_id {
+        if let Err(why) = msg.channel_id.say(&ctx.http, &format!("Role-ID: {}", role_id)).await {
+            println!("Error sending message: {:?}", why);
         }
+
+        return Ok(());
     }
 
     msg.channel_id
@@ -554,6 +558,7 @@ async fn am_i_admin(ctx: &Context, msg: &Message, _args: Args) -> CommandResult
         for role in &member.roles {
             if role
                 .to_role_cached(&ctx.cache)
+                .await
                 .map_or(false, |r| r.has_permission(Permissions::ADMINISTRATOR))
             {
                 msg.channel_id.say(&ctx.http, "Yes, you are.").await?;
@@ -580,7 +585,8 @@ async fn slow_mode(ctx: &Context, msg: &Message, mut args: Args) -> CommandResul
         } else {
             format!("Successfully set slow mode rate to `{}` seconds.", slow_mode_rate_seconds)
         }
-    } else if let Some(Channel::Guild(channel)) = msg.channel_id.to_channel_cached(&ctx.cache) {
+    } else if let Some(Channel::Guild(channel)) = msg.channel_id.to_channel_cached(&ctx.cache).await
+    {
         let slow_mode_rate = channel.rate_limit_per_user.unwrap_or(0);
         format!("Current slow mode rate is `{}` seconds.", slow_mode_rate)
     } else {
@@ -26,7 +26,7 @@ use crate::model::invite::InviteTargetType;
 /// impl EventHandler for Handler {
 ///     async fn message(&self, context: Context, msg: Message) {
 ///         if msg.content == "!createinvite" {
-///             let channel = match context.cache.guild_channel(msg.channel_id) {
+///             let channel = match context.cache.guild_channel(msg.channel_id).await {
 ///                 Some(channel) => channel,
 ///                 None => {
 ///                     let _ = msg.channel_id.say(&context, "Error creating invite").await;
@@ -91,7 +91,7 @@ impl CreateInvite {
     /// # #[cfg(all(feature = "cache", feature = "client", feature = "framework", feature = "http"))]
     /// # #[command]
     /// # async fn example(context: &Context) -> CommandResult {
-    /// #     let channel = context.cache.guild_channel(81384788765712384).unwrap();
+    /// #     let channel = context.cache.guild_channel(81384788765712384).await.unwrap();
     /// #
     /// let invite = channel.create_invite(context, |i| i.max_age(3600)).await?;
     /// #     Ok(())
@@ -122,7 +122,7 @@ impl CreateInvite {
     /// # #[cfg(all(feature = "cache", feature = "client", feature = "framework", feature = "http"))]
     /// # #[command]
     /// # async fn example(context: &Context) -> CommandResult {
-    /// #     let channel = context.cache.guild_channel(81384788765712384).unwrap();
+    /// #     let channel = context.cache.guild_channel(81384788765712384).await.unwrap();
     /// #
     /// let invite = channel.create_invite(context, |i| i.max_uses(5)).await?;
     /// #     Ok(())
@@ -151,7 +151,7 @@ impl CreateInvite {
     /// # #[cfg(all(feature = "cache", feature = "client", feature = "framework", feature = "http"))]
     /// # #[command]
     /// # async fn example(context: &Context) -> CommandResult {
-    /// #     let channel = context.cache.guild_channel(81384788765712384).unwrap();
+    /// #     let channel = context.cache.guild_channel(81384788765712384).await.unwrap();
     /// #
     /// let invite = channel.create_invite(context, |i| i.temporary(true)).await?;
     /// #     Ok(())
@@ -182,7 +182,7 @@ impl CreateInvite {
     /// # #[cfg(all(feature = "cache", feature = "client", feature = "framework", feature = "http"))]
     /// # #[command]
     /// # async fn example(context: &Context) -> CommandResult {
-    /// #     let channel = context.cache.guild_channel(81384788765712384).unwrap();
+    /// #     let channel = context.cache.guild_channel(81384788765712384).await.unwrap();
     /// #
     /// let invite = channel.create_invite(context, |i| i.unique(true)).await?;
     /// #     Ok(())
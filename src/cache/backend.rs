@@ -0,0 +1,713 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+
+use async_trait::async_trait;
+use dashmap::mapref::entry::Entry;
+use dashmap::DashMap;
+
+use crate::model::prelude::*;
+
+/// The store that a [`Cache`] keeps guilds, channels, members, roles, users,
+/// messages and presences in.
+///
+/// By default, the cache keeps them in memory via an
+/// [`InMemoryCacheBackend`]. Implementing this trait allows to plug in another
+/// store via [`Settings::backend`], such as one that is shared between
+/// processes.
+///
+/// The methods are async, so a backend may query a remote store, such as
+/// Redis or a database, directly from them. Backends never hand out
+/// references into the store. Data is either returned by value, or lent to a
+/// callback for the duration of the call, so that a remote store can fetch
+/// and deserialize only the data that is asked for, and write it back after
+/// a callback modified it.
+///
+/// Members and roles are part of their [`Guild`] by default, so backends only
+/// need to override the methods for them if they store them separately. A
+/// remote store should do so, as the default methods read the whole guild to
+/// access a single member or role.
+///
+/// # Consistency
+///
+/// The [`Cache`] does not lock the backend between calls. When several
+/// processes share a store, a backend has to make each modifying method
+/// atomic itself, for example with a transaction or a lock per key, so that
+/// concurrent updates of the same guild, channel or message are not lost.
+///
+/// # Re-entrancy
+///
+/// Callbacks, such as the ones given to [`Self::read_guild`],
+/// [`Self::update_guild`] and [`Self::for_each_guild`], may be called while
+/// the backend holds a lock on the data lent to them, as the
+/// [`InMemoryCacheBackend`] does. Callbacks are synchronous and must not
+/// access the [`Cache`] or the backend again, as that can deadlock.
+///
+/// [`Cache`]: super::Cache
+/// [`Settings::backend`]: super::Settings::backend
+#[async_trait]
+pub trait CacheBackend: fmt::Debug + Send + Sync {
+    /// Gets a guild by Id.
+    async fn guild(&self, guild_id: GuildId) -> Option<Guild>;
+
+    /// Gets the Ids of all guilds.
+    async fn guild_ids(&self) -> Vec<GuildId>;
+
+    /// Gets the number of guilds.
+    async fn guild_count(&self) -> usize;
+
+    /// Inserts a guild, returning the guild that it replaced.
+    async fn insert_guild(&self, guild: Guild) -> Option<Guild>;
+
+    /// Removes a guild, returning it.
+    async fn remove_guild(&self, guild_id: GuildId) -> Option<Guild>;
+
+    /// Lends a guild to `f`, returning whether the guild exists.
+    async fn read_guild(
+        &self,
+        guild_id: GuildId,
+        f: &mut (dyn for<'a> FnMut(&'a Guild) + Send),
+    ) -> bool;
+
+    /// Lends a guild to `f` to modify it, returning whether the guild exists.
+    async fn update_guild(
+        &self,
+        guild_id: GuildId,
+        f: &mut (dyn for<'a> FnMut(&'a mut Guild) + Send),
+    ) -> bool;
+
+    /// Lends each guild to `f`, until `f` returns `false`.
+    async fn for_each_guild(&self, f: &mut (dyn for<'a> FnMut(&'a Guild) -> bool + Send));
+
+    /// Gets a member of a guild.
+    async fn member(&self, guild_id: GuildId, user_id: UserId) -> Option<Member> {
+        self.read_guild_with(guild_id, |guild| guild.members.get(&user_id).cloned()).await.flatten()
+    }
+
+    /// Lends a member of a guild to `f`, returning whether the member exists.
+    async fn read_member(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        f: &mut (dyn for<'a> FnMut(&'a Member) + Send),
+    ) -> bool {
+        self.read_guild_with(guild_id, |guild| guild.members.get(&user_id).map(f).is_some())
+            .await
+            .unwrap_or_default()
+    }
+
+    /// Inserts a member into its guild, returning the member that it replaced.
+    ///
+    /// Nothing is inserted if the guild does not exist.
+    async fn insert_member(&self, member: Member) -> Option<Member> {
+        self.update_guild_with(member.guild_id, |guild| {
+            guild.members.insert(member.user.id, member.clone())
+        })
+        .await
+        .flatten()
+    }
+
+    /// Removes a member from a guild, returning it.
+    async fn remove_member(&self, guild_id: GuildId, user_id: UserId) -> Option<Member> {
+        self.update_guild_with(guild_id, |guild| guild.members.remove(&user_id)).await.flatten()
+    }
+
+    /// Lends a member to `f` to modify it, returning whether the member exists.
+    async fn update_member(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        f: &mut (dyn for<'a> FnMut(&'a mut Member) + Send),
+    ) -> bool {
+        self.update_guild_with(guild_id, |guild| guild.members.get_mut(&user_id).map(f).is_some())
+            .await
+            .unwrap_or_default()
+    }
+
+    /// Removes the users from `user_ids` that are a member of any guild.
+    async fn retain_non_members(&self, user_ids: &mut HashSet<UserId>) {
+        self.for_each_guild(&mut |guild| {
+            user_ids.retain(|user_id| !guild.members.contains_key(user_id));
+
+            !user_ids.is_empty()
+        })
+        .await;
+    }
+
+    /// Gets a role of a guild.
+    async fn role(&self, guild_id: GuildId, role_id: RoleId) -> Option<Role> {
+        self.read_guild_with(guild_id, |guild| guild.roles.get(&role_id).cloned()).await.flatten()
+    }
+
+    /// Gets all roles of a guild.
+    async fn roles(&self, guild_id: GuildId) -> Option<HashMap<RoleId, Role>> {
+        self.read_guild_with(guild_id, |guild| guild.roles.clone()).await
+    }
+
+    /// Inserts a role into its guild, returning the role that it replaced.
+    ///
+    /// Nothing is inserted if the guild does not exist.
+    async fn insert_role(&self, role: Role) -> Option<Role> {
+        self.update_guild_with(role.guild_id, |guild| guild.roles.insert(role.id, role.clone()))
+            .await
+            .flatten()
+    }
+
+    /// Removes a role from a guild, returning it.
+    async fn remove_role(&self, guild_id: GuildId, role_id: RoleId) -> Option<Role> {
+        self.update_guild_with(guild_id, |guild| guild.roles.remove(&role_id)).await.flatten()
+    }
+
+    /// Gets a guild channel by Id.
+    async fn channel(&self, channel_id: ChannelId) -> Option<GuildChannel>;
+
+    /// Lends a guild channel to `f`, returning whether the channel exists.
+    async fn read_channel(
+        &self,
+        channel_id: ChannelId,
+        f: &mut (dyn for<'a> FnMut(&'a GuildChannel) + Send),
+    ) -> bool {
+        self.channel(channel_id).await.map(|channel| f(&channel)).is_some()
+    }
+
+    /// Gets the number of guild channels.
+    async fn channel_count(&self) -> usize;
+
+    /// Inserts a guild channel, returning the channel that it replaced.
+    async fn insert_channel(&self, channel: GuildChannel) -> Option<GuildChannel>;
+
+    /// Removes a guild channel, returning it.
+    async fn remove_channel(&self, channel_id: ChannelId) -> Option<GuildChannel>;
+
+    /// Lends a guild channel to `f` to modify it, returning whether the
+    /// channel exists.
+    async fn update_channel(
+        &self,
+        channel_id: ChannelId,
+        f: &mut (dyn for<'a> FnMut(&'a mut GuildChannel) + Send),
+    ) -> bool;
+
+    /// Lends each guild channel to `f`, until `f` returns `false`.
+    async fn for_each_channel(&self, f: &mut (dyn for<'a> FnMut(&'a GuildChannel) -> bool + Send));
+
+    /// Gets a user by Id.
+    async fn user(&self, user_id: UserId) -> Option<User>;
+
+    /// Lends a user to `f`, returning whether the user exists.
+    async fn read_user(
+        &self,
+        user_id: UserId,
+        f: &mut (dyn for<'a> FnMut(&'a User) + Send),
+    ) -> bool {
+        self.user(user_id).await.map(|user| f(&user)).is_some()
+    }
+
+    /// Gets the number of users.
+    async fn user_count(&self) -> usize;
+
+    /// Inserts a user, returning the user that it replaced.
+    async fn insert_user(&self, user: User) -> Option<User>;
+
+    /// Removes a user, returning it.
+    async fn remove_user(&self, user_id: UserId) -> Option<User>;
+
+    /// Lends each user to `f`, until `f` returns `false`.
+    async fn for_each_user(&self, f: &mut (dyn for<'a> FnMut(&'a User) -> bool + Send));
+
+    /// Gets a message of a channel.
+    async fn message(&self, channel_id: ChannelId, message_id: MessageId) -> Option<Message>;
+
+    /// Gets all messages of a channel, from oldest to newest.
+    ///
+    /// Returns `None` if no messages of the channel were ever inserted.
+    async fn channel_messages(&self, channel_id: ChannelId) -> Option<Vec<Message>>;
+
+    /// Lends all messages of a channel to `f`, from oldest to newest,
+    /// returning whether any messages of the channel were ever inserted.
+    async fn read_channel_messages(
+        &self,
+        channel_id: ChannelId,
+        f: &mut (dyn for<'a> FnMut(&'a [&'a Message]) + Send),
+    ) -> bool {
+        self.channel_messages(channel_id)
+            .await
+            .map(|messages| f(&messages.iter().collect::<Vec<_>>()))
+            .is_some()
+    }
+
+    /// Inserts a message into its channel. If the channel then holds more than
+    /// `max_messages` messages, the oldest message is removed and returned.
+    async fn insert_message(&self, message: Message, max_messages: usize) -> Option<Message>;
+
+    /// Lends a message to `f` to modify it, returning whether the message
+    /// exists.
+    async fn update_message(
+        &self,
+        channel_id: ChannelId,
+        message_id: MessageId,
+        f: &mut (dyn for<'a> FnMut(&'a mut Message) + Send),
+    ) -> bool;
+
+    /// Removes a message of a channel, returning it.
+    async fn remove_message(&self, channel_id: ChannelId, message_id: MessageId)
+        -> Option<Message>;
+
+    /// Removes all messages of a channel.
+    async fn remove_channel_messages(&self, channel_id: ChannelId);
+
+    /// Gets the number of messages over all channels.
+    async fn message_count(&self) -> usize;
+
+    /// Gets the presence of a user that is not tied to a guild.
+    async fn presence(&self, user_id: UserId) -> Option<Presence>;
+
+    /// Gets the number of presences that are not tied to a guild.
+    async fn presence_count(&self) -> usize;
+
+    /// Inserts a presence that is not tied to a guild, returning the presence
+    /// that it replaced.
+    async fn insert_presence(&self, presence: Presence) -> Option<Presence>;
+
+    /// Removes the presence of a user that is not tied to a guild, returning
+    /// it.
+    async fn remove_presence(&self, user_id: UserId) -> Option<Presence>;
+}
+
+/// Adapters from the `FnMut` callbacks of [`CacheBackend`] to closures
+/// returning a value.
+#[async_trait]
+pub(crate) trait CacheBackendExt: CacheBackend {
+    async fn read_guild_with<R, F>(&self, guild_id: GuildId, f: F) -> Option<R>
+    where
+        R: Send,
+        F: FnOnce(&Guild) -> R + Send,
+    {
+        let mut f = Some(f);
+        let mut ret = None;
+
+        self.read_guild(guild_id, &mut |guild| ret = f.take().map(|f| f(guild))).await;
+
+        ret
+    }
+
+    async fn update_guild_with<R, F>(&self, guild_id: GuildId, f: F) -> Option<R>
+    where
+        R: Send,
+        F: FnOnce(&mut Guild) -> R + Send,
+    {
+        let mut f = Some(f);
+        let mut ret = None;
+
+        self.update_guild(guild_id, &mut |guild| ret = f.take().map(|f| f(guild))).await;
+
+        ret
+    }
+
+    async fn read_member_with<R, F>(&self, guild_id: GuildId, user_id: UserId, f: F) -> Option<R>
+    where
+        R: Send,
+        F: FnOnce(&Member) -> R + Send,
+    {
+        let mut f = Some(f);
+        let mut ret = None;
+
+        self.read_member(guild_id, user_id, &mut |member| ret = f.take().map(|f| f(member))).await;
+
+        ret
+    }
+
+    async fn update_member_with<R, F>(&self, guild_id: GuildId, user_id: UserId, f: F) -> Option<R>
+    where
+        R: Send,
+        F: FnOnce(&mut Member) -> R + Send,
+    {
+        let mut f = Some(f);
+        let mut ret = None;
+
+        self.update_member(guild_id, user_id, &mut |member| ret = f.take().map(|f| f(member)))
+            .await;
+
+        ret
+    }
+
+    async fn read_channel_with<R, F>(&self, channel_id: ChannelId, f: F) -> Option<R>
+    where
+        R: Send,
+        F: FnOnce(&GuildChannel) -> R + Send,
+    {
+        let mut f = Some(f);
+        let mut ret = None;
+
+        self.read_channel(channel_id, &mut |channel| ret = f.take().map(|f| f(channel))).await;
+
+        ret
+    }
+
+    async fn read_channel_messages_with<R, F>(&self, channel_id: ChannelId, f: F) -> Option<R>
+    where
+        R: Send,
+        F: FnOnce(&[&Message]) -> R + Send,
+    {
+        let mut f = Some(f);
+        let mut ret = None;
+
+        self.read_channel_messages(channel_id, &mut |messages| {
+            ret = f.take().map(|f| f(messages));
+        })
+        .await;
+
+        ret
+    }
+
+    async fn read_user_with<R, F>(&self, user_id: UserId, f: F) -> Option<R>
+    where
+        R: Send,
+        F: FnOnce(&User) -> R + Send,
+    {
+        let mut f = Some(f);
+        let mut ret = None;
+
+        self.read_user(user_id, &mut |user| ret = f.take().map(|f| f(user))).await;
+
+        ret
+    }
+
+    async fn update_channel_with<R, F>(&self, channel_id: ChannelId, f: F) -> Option<R>
+    where
+        R: Send,
+        F: FnOnce(&mut GuildChannel) -> R + Send,
+    {
+        let mut f = Some(f);
+        let mut ret = None;
+
+        self.update_channel(channel_id, &mut |channel| ret = f.take().map(|f| f(channel))).await;
+
+        ret
+    }
+
+    async fn update_message_with<R, F>(
+        &self,
+        channel_id: ChannelId,
+        message_id: MessageId,
+        f: F,
+    ) -> Option<R>
+    where
+        R: Send,
+        F: FnOnce(&mut Message) -> R + Send,
+    {
+        let mut f = Some(f);
+        let mut ret = None;
+
+        self.update_message(channel_id, message_id, &mut |message| {
+            ret = f.take().map(|f| f(message));
+        })
+        .await;
+
+        ret
+    }
+
+    async fn find_map_guild<R, F>(&self, mut f: F) -> Option<R>
+    where
+        R: Send,
+        F: FnMut(&Guild) -> Option<R> + Send,
+    {
+        let mut ret = None;
+
+        self.for_each_guild(&mut |guild| {
+            ret = f(guild);
+            ret.is_none()
+        })
+        .await;
+
+        ret
+    }
+
+    async fn find_map_channel<R, F>(&self, mut f: F) -> Option<R>
+    where
+        R: Send,
+        F: FnMut(&GuildChannel) -> Option<R> + Send,
+    {
+        let mut ret = None;
+
+        self.for_each_channel(&mut |channel| {
+            ret = f(channel);
+            ret.is_none()
+        })
+        .await;
+
+        ret
+    }
+
+    async fn find_map_user<R, F>(&self, mut f: F) -> Option<R>
+    where
+        R: Send,
+        F: FnMut(&User) -> Option<R> + Send,
+    {
+        let mut ret = None;
+
+        self.for_each_user(&mut |user| {
+            ret = f(user);
+            ret.is_none()
+        })
+        .await;
+
+        ret
+    }
+}
+
+impl<B: CacheBackend + ?Sized> CacheBackendExt for B {}
+
+/// The default [`CacheBackend`], keeping all data in memory.
+///
+/// Data lent to callbacks is locked for the duration of the call, so
+/// callbacks must not access the cache again.
+#[derive(Debug, Default)]
+pub struct InMemoryCacheBackend {
+    channels: DashMap<ChannelId, GuildChannel>,
+    guilds: DashMap<GuildId, Guild>,
+    messages: DashMap<ChannelId, ChannelMessages>,
+    presences: DashMap<UserId, Presence>,
+    users: DashMap<UserId, User>,
+}
+
+/// The cached messages of a channel.
+#[derive(Debug, Default)]
+struct ChannelMessages {
+    messages: HashMap<MessageId, Message>,
+    /// The order that the messages were inserted in, so that the oldest
+    /// message can be removed once the channel's cache is full.
+    queue: VecDeque<MessageId>,
+}
+
+impl ChannelMessages {
+    /// Iterates over the messages from oldest to newest.
+    fn iter(&self) -> impl Iterator<Item = &Message> {
+        self.queue.iter().filter_map(move |id| self.messages.get(id))
+    }
+}
+
+impl InMemoryCacheBackend {
+    /// Creates an empty backend.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl CacheBackend for InMemoryCacheBackend {
+    async fn guild(&self, guild_id: GuildId) -> Option<Guild> {
+        self.guilds.get(&guild_id).map(|guild| guild.clone())
+    }
+
+    async fn guild_ids(&self) -> Vec<GuildId> {
+        self.guilds.iter().map(|guild| *guild.key()).collect()
+    }
+
+    async fn guild_count(&self) -> usize {
+        self.guilds.len()
+    }
+
+    async fn insert_guild(&self, guild: Guild) -> Option<Guild> {
+        self.guilds.insert(guild.id, guild)
+    }
+
+    async fn remove_guild(&self, guild_id: GuildId) -> Option<Guild> {
+        self.guilds.remove(&guild_id).map(|(_, guild)| guild)
+    }
+
+    async fn read_guild(
+        &self,
+        guild_id: GuildId,
+        f: &mut (dyn for<'a> FnMut(&'a Guild) + Send),
+    ) -> bool {
+        self.guilds.get(&guild_id).map(|guild| f(&guild)).is_some()
+    }
+
+    async fn update_guild(
+        &self,
+        guild_id: GuildId,
+        f: &mut (dyn for<'a> FnMut(&'a mut Guild) + Send),
+    ) -> bool {
+        self.guilds.get_mut(&guild_id).map(|mut guild| f(&mut guild)).is_some()
+    }
+
+    async fn for_each_guild(&self, f: &mut (dyn for<'a> FnMut(&'a Guild) -> bool + Send)) {
+        for guild in &self.guilds {
+            if !f(guild.value()) {
+                break;
+            }
+        }
+    }
+
+    async fn channel(&self, channel_id: ChannelId) -> Option<GuildChannel> {
+        self.channels.get(&channel_id).map(|channel| channel.clone())
+    }
+
+    async fn read_channel(
+        &self,
+        channel_id: ChannelId,
+        f: &mut (dyn for<'a> FnMut(&'a GuildChannel) + Send),
+    ) -> bool {
+        self.channels.get(&channel_id).map(|channel| f(&channel)).is_some()
+    }
+
+    async fn channel_count(&self) -> usize {
+        self.channels.len()
+    }
+
+    async fn insert_channel(&self, channel: GuildChannel) -> Option<GuildChannel> {
+        self.channels.insert(channel.id, channel)
+    }
+
+    async fn remove_channel(&self, channel_id: ChannelId) -> Option<GuildChannel> {
+        self.channels.remove(&channel_id).map(|(_, channel)| channel)
+    }
+
+    async fn update_channel(
+        &self,
+        channel_id: ChannelId,
+        f: &mut (dyn for<'a> FnMut(&'a mut GuildChannel) + Send),
+    ) -> bool {
+        self.channels.get_mut(&channel_id).map(|mut channel| f(&mut channel)).is_some()
+    }
+
+    async fn for_each_channel(&self, f: &mut (dyn for<'a> FnMut(&'a GuildChannel) -> bool + Send)) {
+        for channel in &self.channels {
+            if !f(channel.value()) {
+                break;
+            }
+        }
+    }
+
+    async fn user(&self, user_id: UserId) -> Option<User> {
+        self.users.get(&user_id).map(|user| user.clone())
+    }
+
+    async fn read_user(
+        &self,
+        user_id: UserId,
+        f: &mut (dyn for<'a> FnMut(&'a User) + Send),
+    ) -> bool {
+        self.users.get(&user_id).map(|user| f(&user)).is_some()
+    }
+
+    async fn user_count(&self) -> usize {
+        self.users.len()
+    }
+
+    async fn insert_user(&self, user: User) -> Option<User> {
+        match self.users.entry(user.id) {
+            Entry::Vacant(e) => {
+                e.insert(user);
+
+                None
+            },
+            Entry::Occupied(mut e) => Some(std::mem::replace(e.get_mut(), user)),
+        }
+    }
+
+    async fn remove_user(&self, user_id: UserId) -> Option<User> {
+        self.users.remove(&user_id).map(|(_, user)| user)
+    }
+
+    async fn for_each_user(&self, f: &mut (dyn for<'a> FnMut(&'a User) -> bool + Send)) {
+        for user in &self.users {
+            if !f(user.value()) {
+                break;
+            }
+        }
+    }
+
+    async fn message(&self, channel_id: ChannelId, message_id: MessageId) -> Option<Message> {
+        self.messages.get(&channel_id)?.messages.get(&message_id).cloned()
+    }
+
+    async fn channel_messages(&self, channel_id: ChannelId) -> Option<Vec<Message>> {
+        Some(self.messages.get(&channel_id)?.iter().cloned().collect())
+    }
+
+    async fn read_channel_messages(
+        &self,
+        channel_id: ChannelId,
+        f: &mut (dyn for<'a> FnMut(&'a [&'a Message]) + Send),
+    ) -> bool {
+        self.messages
+            .get(&channel_id)
+            .map(|messages| f(&messages.iter().collect::<Vec<_>>()))
+            .is_some()
+    }
+
+    async fn insert_message(&self, message: Message, max_messages: usize) -> Option<Message> {
+        let mut entry = self.messages.entry(message.channel_id).or_default();
+        let channel = entry.value_mut();
+
+        // A message that is already cached is replaced in place.
+        if let Some(cached) = channel.messages.get_mut(&message.id) {
+            *cached = message;
+
+            return None;
+        }
+
+        let mut removed_msg = None;
+
+        if channel.messages.len() >= max_messages {
+            if let Some(id) = channel.queue.pop_front() {
+                removed_msg = channel.messages.remove(&id);
+            }
+        }
+
+        channel.queue.push_back(message.id);
+        channel.messages.insert(message.id, message);
+
+        removed_msg
+    }
+
+    async fn update_message(
+        &self,
+        channel_id: ChannelId,
+        message_id: MessageId,
+        f: &mut (dyn for<'a> FnMut(&'a mut Message) + Send),
+    ) -> bool {
+        self.messages
+            .get_mut(&channel_id)
+            .and_then(|mut channel| channel.messages.get_mut(&message_id).map(f))
+            .is_some()
+    }
+
+    async fn remove_message(
+        &self,
+        channel_id: ChannelId,
+        message_id: MessageId,
+    ) -> Option<Message> {
+        let mut channel = self.messages.get_mut(&channel_id)?;
+        let message = channel.messages.remove(&message_id)?;
+
+        channel.queue.retain(|id| *id != message_id);
+
+        Some(message)
+    }
+
+    async fn remove_channel_messages(&self, channel_id: ChannelId) {
+        self.messages.remove(&channel_id);
+    }
+
+    async fn message_count(&self) -> usize {
+        self.messages.iter().map(|channel| channel.messages.len()).sum()
+    }
+
+    async fn presence(&self, user_id: UserId) -> Option<Presence> {
+        self.presences.get(&user_id).map(|presence| presence.clone())
+    }
+
+    async fn presence_count(&self) -> usize {
+        self.presences.len()
+    }
+
+    async fn insert_presence(&self, presence: Presence) -> Option<Presence> {
+        self.presences.insert(presence.user.id, presence)
+    }
+
+    async fn remove_presence(&self, user_id: UserId) -> Option<Presence> {
+        self.presences.remove(&user_id).map(|(_, presence)| presence)
+    }
+}
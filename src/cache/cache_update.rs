@@ -1,3 +1,5 @@
+use async_trait::async_trait;
+
 use super::Cache;
 
 /// Trait used for updating the cache with a type.
@@ -96,12 +98,13 @@ use super::Cache;
 /// cache.update(&mut update_message).await;
 /// # }
 /// ```
-pub trait CacheUpdate {
+#[async_trait]
+pub trait CacheUpdate: Send {
     /// The return type of an update.
     ///
     /// If there is nothing to return, specify this type as an unit (`()`).
     type Output;
 
     /// Updates the cache with the implementation.
-    fn update(&mut self, _: &Cache) -> Option<Self::Output>;
+    async fn update(&mut self, _: &Cache) -> Option<Self::Output>;
 }
@@ -0,0 +1,179 @@
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures::channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
+use futures::Stream;
+use parking_lot::RwLock;
+
+use super::Cache;
+use crate::model::prelude::*;
+
+/// How a value in the cache changed.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub enum Change<T> {
+    /// The value was not cached before.
+    Insert(T),
+    /// The value replaced a cached one.
+    Update {
+        /// The value before the change.
+        old: T,
+        /// The value after the change.
+        new: T,
+    },
+    /// The value was removed from the cache.
+    Remove(T),
+}
+
+impl<T> Change<T> {
+    pub(crate) fn new(old: Option<T>, new: T) -> Self {
+        match old {
+            Some(old) => Self::Update {
+                old,
+                new,
+            },
+            None => Self::Insert(new),
+        }
+    }
+
+    /// The value before the change, if it was cached.
+    pub fn old_value(&self) -> Option<&T> {
+        match self {
+            Self::Insert(_) => None,
+            Self::Update {
+                old, ..
+            }
+            | Self::Remove(old) => Some(old),
+        }
+    }
+
+    /// The value after the change, unless it was removed.
+    pub fn new_value(&self) -> Option<&T> {
+        match self {
+            Self::Insert(new)
+            | Self::Update {
+                new, ..
+            } => Some(new),
+            Self::Remove(_) => None,
+        }
+    }
+}
+
+/// A change to the [`Cache`], as yielded by [`CacheChanges`].
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub enum CacheChange {
+    /// A guild was inserted, updated or removed.
+    ///
+    /// Guilds are reported as a whole when they are received or removed, and
+    /// on updates to their own fields. Changes to their individual channels,
+    /// roles and members are reported as [`Self::Channel`], [`Self::Role`] and
+    /// [`Self::Member`].
+    Guild(Box<Change<Guild>>),
+    /// A channel or thread was inserted, updated or removed.
+    Channel(Box<Change<Channel>>),
+    /// A role was inserted, updated or removed.
+    Role(Box<Change<Role>>),
+    /// A member was inserted, updated or removed, including when it was
+    /// evicted by the [`Settings::member_eviction`] policy.
+    ///
+    /// [`Settings::member_eviction`]: super::Settings::member_eviction
+    Member(Box<Change<Member>>),
+    /// A message was inserted or updated, or removed to make room for a newer
+    /// one.
+    Message(Box<Change<Message>>),
+}
+
+/// A [`Stream`] of the changes made to a [`Cache`], returned by
+/// [`Cache::subscribe`].
+///
+/// Changes are buffered until they are polled, without a bound. Each change
+/// holds clones of the values it concerns, including whole guilds for
+/// [`CacheChange::Guild`], so a stream that is not polled keeps growing. Poll
+/// it promptly, and drop it to end the subscription.
+#[derive(Debug)]
+pub struct CacheChanges(UnboundedReceiver<CacheChange>);
+
+impl Stream for CacheChanges {
+    type Item = CacheChange;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.0).poll_next(cx)
+    }
+}
+
+/// The subscribers to changes of the cache.
+#[derive(Debug, Default)]
+pub(crate) struct Subscribers(RwLock<Vec<UnboundedSender<CacheChange>>>);
+
+impl Cache {
+    /// Subscribes to the changes made to the cache by [`CacheUpdate`]s,
+    /// including those applied to the events received by the client.
+    ///
+    /// This is independent of the [`EventHandler`], and reports the value
+    /// before and after each change, even for events whose handler methods
+    /// do not receive the old value.
+    ///
+    /// Values are only cloned for changes while there is at least one
+    /// subscriber. The changes are buffered for each subscriber until polled,
+    /// which can hold a lot of memory if a subscriber falls behind, as
+    /// changes to guilds hold clones of the whole guild. Refer to
+    /// [`CacheChanges`] for more information.
+    ///
+    /// # Examples
+    ///
+    /// Logging nickname changes:
+    ///
+    /// ```rust,no_run
+    /// use futures::StreamExt;
+    /// use serenity::cache::{Cache, CacheChange, Change};
+    ///
+    /// # async fn run(cache: &Cache) {
+    /// let mut changes = cache.subscribe();
+    ///
+    /// while let Some(change) = changes.next().await {
+    ///     if let CacheChange::Member(change) = change {
+    ///         if let Change::Update {
+    ///             old,
+    ///             new,
+    ///         } = *change
+    ///         {
+    ///             if old.nick != new.nick {
+    ///                 println!("{} is now known as {:?}", new.user.name, new.nick);
+    ///             }
+    ///         }
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    ///
+    /// [`CacheUpdate`]: super::CacheUpdate
+    /// [`EventHandler`]: crate::client::EventHandler
+    #[must_use]
+    pub fn subscribe(&self) -> CacheChanges {
+        let (tx, rx) = unbounded();
+        self.subscribers.0.write().push(tx);
+
+        CacheChanges(rx)
+    }
+
+    /// Whether anyone is subscribed to changes, so that they are worth
+    /// computing.
+    pub(crate) fn has_subscribers(&self) -> bool {
+        !self.subscribers.0.read().is_empty()
+    }
+
+    /// Sends a change to all subscribers, dropping those whose stream was
+    /// dropped.
+    pub(crate) fn notify(&self, change: CacheChange) {
+        let mut closed = false;
+
+        for subscriber in self.subscribers.0.read().iter() {
+            closed |= subscriber.unbounded_send(change.clone()).is_err();
+        }
+
+        if closed {
+            self.subscribers.0.write().retain(|subscriber| !subscriber.is_closed());
+        }
+    }
+}
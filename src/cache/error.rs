@@ -0,0 +1,37 @@
+use std::error::Error as StdError;
+use std::fmt;
+
+/// An error returned from the [`Cache`].
+///
+/// This is always wrapped within the library's generic [`Error::Cache`]
+/// variant.
+///
+/// [`Cache`]: super::Cache
+/// [`Error::Cache`]: crate::Error::Cache
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum Error {
+    /// When a snapshot passed to [`Cache::restore`] is not a cache snapshot.
+    ///
+    /// [`Cache::restore`]: super::Cache::restore
+    InvalidSnapshot,
+    /// When a snapshot passed to [`Cache::restore`] was written in a format
+    /// version that this version of the library does not support. The version
+    /// of the snapshot is provided.
+    ///
+    /// [`Cache::restore`]: super::Cache::restore
+    UnsupportedSnapshotVersion(u32),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidSnapshot => f.write_str("Not a cache snapshot"),
+            Self::UnsupportedSnapshotVersion(version) => {
+                write!(f, "Unsupported cache snapshot version {}", version)
+            },
+        }
+    }
+}
+
+impl StdError for Error {}
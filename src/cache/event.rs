@@ -1,7 +1,16 @@
 use std::collections::HashSet;
 
-use super::{Cache, CacheUpdate};
-use crate::model::channel::{Channel, GuildChannel, Message};
+use async_trait::async_trait;
+
+use super::{Cache, CacheBackendExt, CacheChange, CacheUpdate, Change};
+use crate::model::channel::{
+    Channel,
+    GuildChannel,
+    Message,
+    MessageReaction,
+    ReactionType,
+    StageInstance,
+};
 use crate::model::event::{
     ChannelCreateEvent,
     ChannelDeleteEvent,
@@ -17,38 +26,62 @@ use crate::model::event::{
     GuildRoleCreateEvent,
     GuildRoleDeleteEvent,
     GuildRoleUpdateEvent,
+    GuildScheduledEventCreateEvent,
+    GuildScheduledEventDeleteEvent,
+    GuildScheduledEventUpdateEvent,
+    GuildScheduledEventUserAddEvent,
+    GuildScheduledEventUserRemoveEvent,
     GuildStickersUpdateEvent,
     GuildUnavailableEvent,
     GuildUpdateEvent,
     MessageCreateEvent,
+    MessageDeleteBulkEvent,
+    MessageDeleteEvent,
     MessageUpdateEvent,
     PresenceUpdateEvent,
     PresencesReplaceEvent,
+    ReactionAddEvent,
+    ReactionRemoveAllEvent,
+    ReactionRemoveEvent,
     ReadyEvent,
+    StageInstanceCreateEvent,
+    StageInstanceDeleteEvent,
+    StageInstanceUpdateEvent,
     ThreadCreateEvent,
     ThreadDeleteEvent,
+    ThreadListSyncEvent,
+    ThreadMembersUpdateEvent,
     ThreadUpdateEvent,
     UserUpdateEvent,
     VoiceStateUpdateEvent,
 };
-use crate::model::guild::{Guild, Member, Role};
+use crate::model::guild::{Guild, Member, Role, ScheduledEvent};
+use crate::model::id::{ChannelId, GuildId, MessageId, ScheduledEventId, UserId};
 use crate::model::user::{CurrentUser, OnlineStatus};
 use crate::model::voice::VoiceState;
 
+#[async_trait]
 impl CacheUpdate for ChannelCreateEvent {
     type Output = Channel;
 
-    fn update(&mut self, cache: &Cache) -> Option<Self::Output> {
-        match self.channel {
+    async fn update(&mut self, cache: &Cache) -> Option<Self::Output> {
+        if !cache.settings.read().cache_channels {
+            return None;
+        }
+
+        let old_channel = match self.channel {
             Channel::Guild(ref channel) => {
                 let (guild_id, channel_id) = (channel.guild_id, channel.id);
 
                 let old_channel = cache
-                    .guilds
-                    .get_mut(&guild_id)
-                    .and_then(|mut g| g.channels.insert(channel_id, self.channel.clone()));
+                    .backend
+                    .update_guild_with(guild_id, |g| {
+                        g.channels.insert(channel_id, self.channel.clone())
+                    })
+                    .await
+                    .flatten();
 
-                cache.channels.insert(channel_id, channel.clone());
+                cache.backend.insert_channel(channel.clone()).await;
 
                 old_channel
             },
@@ -59,13 +92,13 @@ impl CacheUpdate for ChannelCreateEvent {
 
                 let id = {
                     let user_id = {
-                        cache.update_user_entry(&channel.recipient);
+                        cache.update_user_entry(&channel.recipient).await;
 
                         channel.recipient.id
                     };
 
-                    if let Some(u) = cache.users.get(&user_id) {
-                        channel.recipient = u.clone();
+                    if let Some(u) = cache.backend.user(user_id).await {
+                        channel.recipient = u;
                     }
 
                     channel.id
@@ -77,94 +110,154 @@ impl CacheUpdate for ChannelCreateEvent {
                 let (guild_id, channel_id) = (category.guild_id, category.id);
 
                 let old_channel = cache
-                    .guilds
-                    .get_mut(&guild_id)
-                    .and_then(|mut g| g.channels.insert(channel_id, self.channel.clone()));
+                    .backend
+                    .update_guild_with(guild_id, |g| {
+                        g.channels.insert(channel_id, self.channel.clone())
+                    })
+                    .await
+                    .flatten();
 
                 cache.categories.insert(channel_id, category.clone());
 
                 old_channel
             },
+        };
+
+        if cache.has_subscribers() {
+            cache.notify(CacheChange::Channel(Box::new(Change::new(
+                old_channel.clone(),
+                self.channel.clone(),
+            ))));
         }
+
+        old_channel
     }
 }
 
+#[async_trait]
 impl CacheUpdate for ChannelDeleteEvent {
     type Output = ();
 
-    fn update(&mut self, cache: &Cache) -> Option<()> {
-        match self.channel {
+    async fn update(&mut self, cache: &Cache) -> Option<()> {
+        let removed = match self.channel {
             Channel::Guild(ref channel) => {
                 let (guild_id, channel_id) = (channel.guild_id, channel.id);
 
-                cache.channels.remove(&channel_id);
+                let removed = cache.backend.remove_channel(channel_id).await.map(Channel::Guild);
 
-                cache.guilds.get_mut(&guild_id).map(|mut g| g.channels.remove(&channel_id));
+                cache
+                    .backend
+                    .update_guild_with(guild_id, |g| g.channels.remove(&channel_id))
+                    .await
+                    .flatten()
+                    .or(removed)
             },
             Channel::Category(ref category) => {
                 let (guild_id, channel_id) = (category.guild_id, category.id);
 
-                cache.categories.remove(&channel_id);
+                let removed =
+                    cache.categories.remove(&channel_id).map(|(_, c)| Channel::Category(c));
 
-                cache.guilds.get_mut(&guild_id).map(|mut g| g.channels.remove(&channel_id));
+                cache
+                    .backend
+                    .update_guild_with(guild_id, |g| g.channels.remove(&channel_id))
+                    .await
+                    .flatten()
+                    .or(removed)
             },
             Channel::Private(ref channel) => {
                 let id = { channel.id };
 
-                cache.private_channels.remove(&id);
+                cache.private_channels.remove(&id).map(|(_, c)| Channel::Private(c))
             },
         };
 
         // Remove the cached messages for the channel.
-        cache.messages.remove(&self.channel.id());
+        cache.backend.remove_channel_messages(self.channel.id()).await;
+
+        if let Some(removed) = removed.filter(|_| cache.has_subscribers()) {
+            cache.notify(CacheChange::Channel(Box::new(Change::Remove(removed))));
+        }
 
         None
     }
 }
 
+#[async_trait]
 impl CacheUpdate for ChannelUpdateEvent {
     type Output = ();
 
-    fn update(&mut self, cache: &Cache) -> Option<()> {
-        match self.channel {
+    async fn update(&mut self, cache: &Cache) -> Option<()> {
+        if !cache.settings.read().cache_channels {
+            return None;
+        }
+
+        let old_channel = match self.channel {
             Channel::Guild(ref channel) => {
                 let (guild_id, channel_id) = (channel.guild_id, channel.id);
 
-                cache.channels.insert(channel_id, channel.clone());
+                let old_channel =
+                    cache.backend.insert_channel(channel.clone()).await.map(Channel::Guild);
 
                 cache
-                    .guilds
-                    .get_mut(&guild_id)
-                    .map(|mut g| g.channels.insert(channel_id, self.channel.clone()));
+                    .backend
+                    .update_guild_with(guild_id, |g| {
+                        g.channels.insert(channel_id, self.channel.clone())
+                    })
+                    .await
+                    .flatten()
+                    .or(old_channel)
             },
             Channel::Private(ref channel) => {
-                if let Some(mut c) = cache.private_channels.get_mut(&channel.id) {
-                    c.clone_from(channel);
-                }
+                let mut c = cache.private_channels.get_mut(&channel.id)?;
+                let old_channel = c.clone();
+                c.clone_from(channel);
+
+                Some(Channel::Private(old_channel))
             },
             Channel::Category(ref category) => {
                 let (guild_id, channel_id) = (category.guild_id, category.id);
 
-                cache.categories.insert(channel_id, category.clone());
+                let old_channel =
+                    cache.categories.insert(channel_id, category.clone()).map(Channel::Category);
 
                 cache
-                    .guilds
-                    .get_mut(&guild_id)
-                    .map(|mut g| g.channels.insert(channel_id, self.channel.clone()));
+                    .backend
+                    .update_guild_with(guild_id, |g| {
+                        g.channels.insert(channel_id, self.channel.clone())
+                    })
+                    .await
+                    .flatten()
+                    .or(old_channel)
             },
+        };
+
+        if cache.has_subscribers() {
+            cache.notify(CacheChange::Channel(Box::new(Change::new(
+                old_channel,
+                self.channel.clone(),
+            ))));
         }
 
         None
     }
 }
 
+#[async_trait]
 impl CacheUpdate for ChannelPinsUpdateEvent {
     type Output = ();
 
-    fn update(&mut self, cache: &Cache) -> Option<()> {
-        if let Some(mut channel) = cache.channels.get_mut(&self.channel_id) {
-            channel.last_pin_timestamp = self.last_pin_timestamp;
-
+    async fn update(&mut self, cache: &Cache) -> Option<()> {
+        let last_pin_timestamp = self.last_pin_timestamp;
+
+        if cache
+            .backend
+            .update_channel_with(self.channel_id, |channel| {
+                channel.last_pin_timestamp = last_pin_timestamp;
+            })
+            .await
+            .is_some()
+        {
             return None;
         }
 
@@ -178,23 +271,45 @@ impl CacheUpdate for ChannelPinsUpdateEvent {
     }
 }
 
+#[async_trait]
 impl CacheUpdate for GuildCreateEvent {
     type Output = ();
 
-    fn update(&mut self, cache: &Cache) -> Option<()> {
+    async fn update(&mut self, cache: &Cache) -> Option<()> {
         cache.unavailable_guilds.remove(&self.guild.id);
         let mut guild = self.guild.clone();
+        let settings = cache.settings();
+
+        if !settings.cache_channels {
+            guild.channels.clear();
+            guild.threads.clear();
+        }
+        if !settings.cache_members {
+            guild.members.clear();
+        }
+        if !settings.cache_presences {
+            guild.presences.clear();
+        }
+        if !settings.cache_emojis {
+            guild.emojis.clear();
+        }
+        if !settings.cache_stickers {
+            guild.stickers.clear();
+        }
+        if !settings.cache_voice_states {
+            guild.voice_states.clear();
+        }
 
         for (user_id, member) in &mut guild.members {
-            cache.update_user_entry(&member.user);
-            if let Some(u) = cache.user(user_id) {
+            cache.update_user_entry(&member.user).await;
+            if let Some(u) = cache.user(user_id).await {
                 member.user = u;
             }
         }
 
         for pair in guild.channels.clone() {
             if let Channel::Guild(channel) = pair.1 {
-                cache.channels.insert(pair.0, channel);
+                cache.backend.insert_channel(channel).await;
             }
         }
 
@@ -204,26 +319,56 @@ impl CacheUpdate for GuildCreateEvent {
             }
         }
 
-        cache.guilds.insert(self.guild.id, guild);
+        if settings.cache_guilds {
+            let guild_id = guild.id;
+            let member_ids = guild.members.keys().copied().collect::<Vec<_>>();
+            let presence_ids = guild.presences.keys().copied().collect::<Vec<_>>();
+            let new_guild = cache.has_subscribers().then(|| guild.clone());
+
+            let old_guild = cache.backend.insert_guild(guild).await;
+
+            if let Some(old_guild) = &old_guild {
+                cache.forget_guild(old_guild).await;
+            }
+
+            cache.seen_members(guild_id, member_ids).await;
+            cache.seen_presences(Some(guild_id), presence_ids).await;
+
+            if let Some(new_guild) = new_guild {
+                cache.notify(CacheChange::Guild(Box::new(Change::new(old_guild, new_guild))));
+            }
+        }
 
         None
     }
 }
 
+/// Cleans up after a guild was removed from the cache.
+async fn guild_removed(cache: &Cache, guild: &Guild) {
+    cache.forget_guild(guild).await;
+
+    if cache.has_subscribers() {
+        cache.notify(CacheChange::Guild(Box::new(Change::Remove(guild.clone()))));
+    }
+}
+
+#[async_trait]
 impl CacheUpdate for GuildDeleteEvent {
     type Output = Guild;
 
-    fn update(&mut self, cache: &Cache) -> Option<Self::Output> {
-        match cache.guilds.remove(&self.guild.id) {
+    async fn update(&mut self, cache: &Cache) -> Option<Self::Output> {
+        match cache.backend.remove_guild(self.guild.id).await {
             Some(guild) => {
-                for (channel_id, channel) in &guild.1.channels {
+                guild_removed(cache, &guild).await;
+
+                for (channel_id, channel) in &guild.channels {
                     match channel {
                         Channel::Guild(_) => {
                             // Remove the channel from the cache.
-                            cache.channels.remove(channel_id);
+                            cache.backend.remove_channel(*channel_id).await;
 
                             // Remove the channel's cached messages.
-                            cache.messages.remove(channel_id);
+                            cache.backend.remove_channel_messages(*channel_id).await;
                         },
                         Channel::Category(_) => {
                             // Remove the category from the cache
@@ -233,66 +378,111 @@ impl CacheUpdate for GuildDeleteEvent {
                     }
                 }
 
-                Some(guild.1)
+                Some(guild)
             },
             None => None,
         }
     }
 }
 
+#[async_trait]
 impl CacheUpdate for GuildEmojisUpdateEvent {
     type Output = ();
 
-    fn update(&mut self, cache: &Cache) -> Option<()> {
-        if let Some(mut guild) = cache.guilds.get_mut(&self.guild_id) {
-            guild.emojis.clone_from(&self.emojis);
+    async fn update(&mut self, cache: &Cache) -> Option<()> {
+        if !cache.settings.read().cache_emojis {
+            return None;
         }
 
+        cache
+            .backend
+            .update_guild_with(self.guild_id, |guild| {
+                guild.emojis.clone_from(&self.emojis);
+            })
+            .await;
+
         None
     }
 }
 
+/// Notifies subscribers of a member that was inserted or updated.
+async fn member_changed(cache: &Cache, old: Option<Member>, guild_id: GuildId, user_id: UserId) {
+    if cache.has_subscribers() {
+        if let Some(new) = cache.backend.member(guild_id, user_id).await {
+            cache.notify(CacheChange::Member(Box::new(Change::new(old, new))));
+        }
+    }
+}
+
+#[async_trait]
 impl CacheUpdate for GuildMemberAddEvent {
     type Output = ();
 
-    fn update(&mut self, cache: &Cache) -> Option<()> {
+    async fn update(&mut self, cache: &Cache) -> Option<()> {
         let user_id = self.member.user.id;
-        cache.update_user_entry(&self.member.user);
-        if let Some(u) = cache.user(user_id) {
+        cache.update_user_entry(&self.member.user).await;
+        if let Some(u) = cache.user(user_id).await {
             self.member.user = u;
         }
 
-        if let Some(mut guild) = cache.guilds.get_mut(&self.member.guild_id) {
-            guild.member_count += 1;
-            guild.members.insert(user_id, self.member.clone());
+        let guild_exists = cache
+            .backend
+            .update_guild_with(self.member.guild_id, |guild| {
+                guild.member_count += 1;
+            })
+            .await
+            .is_some();
+
+        if guild_exists && cache.settings.read().cache_members {
+            let old = cache.backend.insert_member(self.member.clone()).await;
+            member_changed(cache, old, self.member.guild_id, user_id).await;
+            cache.seen_members(self.member.guild_id, Some(user_id)).await;
         }
 
         None
     }
 }
 
+#[async_trait]
 impl CacheUpdate for GuildMemberRemoveEvent {
     type Output = Member;
 
-    fn update(&mut self, cache: &Cache) -> Option<Self::Output> {
-        if let Some(mut guild) = cache.guilds.get_mut(&self.guild_id) {
-            guild.member_count -= 1;
-            return guild.members.remove(&self.user.id);
+    async fn update(&mut self, cache: &Cache) -> Option<Self::Output> {
+        cache
+            .backend
+            .update_guild_with(self.guild_id, |guild| {
+                guild.member_count -= 1;
+            })
+            .await?;
+
+        let member = cache.backend.remove_member(self.guild_id, self.user.id).await;
+        cache.forget_member(self.guild_id, self.user.id).await;
+
+        if let Some(member) = member.as_ref().filter(|_| cache.has_subscribers()) {
+            cache.notify(CacheChange::Member(Box::new(Change::Remove(member.clone()))));
         }
 
-        None
+        member
     }
 }
 
+#[async_trait]
 impl CacheUpdate for GuildMemberUpdateEvent {
     type Output = Member;
 
-    fn update(&mut self, cache: &Cache) -> Option<Self::Output> {
-        cache.update_user_entry(&self.user);
+    async fn update(&mut self, cache: &Cache) -> Option<Self::Output> {
+        cache.update_user_entry(&self.user).await;
+
+        if !cache.settings.read().cache_members {
+            return None;
+        }
+
+        cache.backend.read_guild_with(self.guild_id, |_| ()).await?;
 
-        if let Some(mut guild) = cache.guilds.get_mut(&self.guild_id) {
-            let item = if let Some(member) = guild.members.get_mut(&self.user.id) {
-                let item = Some(member.clone());
+        let item = cache
+            .backend
+            .update_member_with(self.guild_id, self.user.id, |member| {
+                let item = member.clone();
 
                 member.joined_at.clone_from(&Some(self.joined_at));
                 member.nick.clone_from(&self.nick);
@@ -306,12 +496,13 @@ impl CacheUpdate for GuildMemberUpdateEvent {
                 member.communication_disabled_until.clone_from(&self.communication_disabled_until);
 
                 item
-            } else {
-                None
-            };
+            })
+            .await;
 
-            if item.is_none() {
-                guild.members.insert(self.user.id, Member {
+        if item.is_none() {
+            cache
+                .backend
+                .insert_member(Member {
                     deaf: false,
                     guild_id: self.guild_id,
                     joined_at: Some(self.joined_at),
@@ -324,164 +515,370 @@ impl CacheUpdate for GuildMemberUpdateEvent {
                     permissions: None,
                     avatar: self.avatar.clone(),
                     communication_disabled_until: self.communication_disabled_until,
-                });
-            }
-
-            item
-        } else {
-            None
+                })
+                .await;
         }
+
+        member_changed(cache, item.clone(), self.guild_id, self.user.id).await;
+        cache.seen_members(self.guild_id, Some(self.user.id)).await;
+
+        item
     }
 }
 
+#[async_trait]
 impl CacheUpdate for GuildMembersChunkEvent {
     type Output = ();
 
-    fn update(&mut self, cache: &Cache) -> Option<()> {
+    async fn update(&mut self, cache: &Cache) -> Option<()> {
         for member in self.members.values() {
-            cache.update_user_entry(&member.user);
+            cache.update_user_entry(&member.user).await;
+        }
+
+        if !cache.settings.read().cache_members {
+            return None;
         }
 
-        if let Some(mut g) = cache.guilds.get_mut(&self.guild_id) {
-            g.members.extend(self.members.clone());
+        for member in self.members.values() {
+            let old = cache.backend.insert_member(member.clone()).await;
+            member_changed(cache, old, self.guild_id, member.user.id).await;
         }
 
+        cache.seen_members(self.guild_id, self.members.keys().copied()).await;
+
         None
     }
 }
 
+#[async_trait]
 impl CacheUpdate for GuildRoleCreateEvent {
     type Output = ();
 
-    fn update(&mut self, cache: &Cache) -> Option<()> {
-        cache
-            .guilds
-            .get_mut(&self.role.guild_id)
-            .map(|mut g| g.roles.insert(self.role.id, self.role.clone()));
+    async fn update(&mut self, cache: &Cache) -> Option<()> {
+        let old = cache.backend.insert_role(self.role.clone()).await;
+
+        if cache.has_subscribers() {
+            if let Some(new) = cache.backend.role(self.role.guild_id, self.role.id).await {
+                cache.notify(CacheChange::Role(Box::new(Change::new(old, new))));
+            }
+        }
 
         None
     }
 }
 
+#[async_trait]
 impl CacheUpdate for GuildRoleDeleteEvent {
     type Output = Role;
 
-    fn update(&mut self, cache: &Cache) -> Option<Self::Output> {
-        cache.guilds.get_mut(&self.guild_id).and_then(|mut g| g.roles.remove(&self.role_id))
+    async fn update(&mut self, cache: &Cache) -> Option<Self::Output> {
+        let role = cache.backend.remove_role(self.guild_id, self.role_id).await;
+
+        if let Some(role) = role.as_ref().filter(|_| cache.has_subscribers()) {
+            cache.notify(CacheChange::Role(Box::new(Change::Remove(role.clone()))));
+        }
+
+        role
     }
 }
 
+#[async_trait]
 impl CacheUpdate for GuildRoleUpdateEvent {
     type Output = Role;
 
-    fn update(&mut self, cache: &Cache) -> Option<Self::Output> {
-        if let Some(mut guild) = cache.guilds.get_mut(&self.role.guild_id) {
-            if let Some(role) = guild.roles.get_mut(&self.role.id) {
-                return Some(std::mem::replace(role, self.role.clone()));
-            }
+    async fn update(&mut self, cache: &Cache) -> Option<Self::Output> {
+        cache.backend.role(self.role.guild_id, self.role.id).await?;
+
+        let old = cache.backend.insert_role(self.role.clone()).await;
+
+        if let Some(old) = old.clone().filter(|_| cache.has_subscribers()) {
+            cache.notify(CacheChange::Role(Box::new(Change::Update {
+                old,
+                new: self.role.clone(),
+            })));
         }
 
+        old
+    }
+}
+
+#[async_trait]
+impl CacheUpdate for GuildScheduledEventCreateEvent {
+    type Output = ();
+
+    async fn update(&mut self, cache: &Cache) -> Option<()> {
+        upsert_scheduled_event(cache, &self.event).await;
+
+        None
+    }
+}
+
+#[async_trait]
+impl CacheUpdate for GuildScheduledEventUpdateEvent {
+    type Output = ScheduledEvent;
+
+    async fn update(&mut self, cache: &Cache) -> Option<Self::Output> {
+        upsert_scheduled_event(cache, &self.event).await
+    }
+}
+
+#[async_trait]
+impl CacheUpdate for GuildScheduledEventDeleteEvent {
+    type Output = ScheduledEvent;
+
+    async fn update(&mut self, cache: &Cache) -> Option<Self::Output> {
+        let event_id = self.event.id;
+
+        cache
+            .backend
+            .update_guild_with(self.event.guild_id, |guild| {
+                let index = guild.scheduled_events.iter().position(|e| e.id == event_id)?;
+
+                Some(guild.scheduled_events.remove(index))
+            })
+            .await
+            .flatten()
+    }
+}
+
+#[async_trait]
+impl CacheUpdate for GuildScheduledEventUserAddEvent {
+    type Output = ();
+
+    async fn update(&mut self, cache: &Cache) -> Option<()> {
+        update_scheduled_event_user_count(cache, self.guild_id, self.scheduled_event_id, |count| {
+            count.saturating_add(1)
+        })
+        .await;
+
+        None
+    }
+}
+
+#[async_trait]
+impl CacheUpdate for GuildScheduledEventUserRemoveEvent {
+    type Output = ();
+
+    async fn update(&mut self, cache: &Cache) -> Option<()> {
+        update_scheduled_event_user_count(cache, self.guild_id, self.scheduled_event_id, |count| {
+            count.saturating_sub(1)
+        })
+        .await;
+
         None
     }
 }
 
+/// Inserts or replaces a scheduled event of a cached guild, returning the
+/// replaced event.
+async fn upsert_scheduled_event(cache: &Cache, event: &ScheduledEvent) -> Option<ScheduledEvent> {
+    cache
+        .backend
+        .update_guild_with(event.guild_id, |guild| {
+            if let Some(i) = guild.scheduled_events.iter().position(|e| e.id == event.id) {
+                Some(std::mem::replace(&mut guild.scheduled_events[i], event.clone()))
+            } else {
+                guild.scheduled_events.push(event.clone());
+                None
+            }
+        })
+        .await
+        .flatten()
+}
+
+/// Updates the number of users subscribed to a cached scheduled event, if it
+/// is known.
+async fn update_scheduled_event_user_count(
+    cache: &Cache,
+    guild_id: GuildId,
+    event_id: ScheduledEventId,
+    f: impl FnOnce(u64) -> u64 + Send,
+) {
+    cache
+        .backend
+        .update_guild_with(guild_id, |guild| {
+            let event = guild.scheduled_events.iter_mut().find(|e| e.id == event_id);
+
+            if let Some(count) = event.and_then(|e| e.user_count.as_mut()) {
+                *count = f(*count);
+            }
+        })
+        .await;
+}
+
+#[async_trait]
 impl CacheUpdate for GuildStickersUpdateEvent {
     type Output = ();
 
-    fn update(&mut self, cache: &Cache) -> Option<()> {
-        if let Some(mut guild) = cache.guilds.get_mut(&self.guild_id) {
-            guild.stickers.clone_from(&self.stickers);
+    async fn update(&mut self, cache: &Cache) -> Option<()> {
+        if !cache.settings.read().cache_stickers {
+            return None;
         }
 
+        cache
+            .backend
+            .update_guild_with(self.guild_id, |guild| {
+                guild.stickers.clone_from(&self.stickers);
+            })
+            .await;
+
         None
     }
 }
 
+#[async_trait]
 impl CacheUpdate for GuildUnavailableEvent {
     type Output = ();
 
-    fn update(&mut self, cache: &Cache) -> Option<()> {
+    async fn update(&mut self, cache: &Cache) -> Option<()> {
         cache.unavailable_guilds.insert(self.guild_id);
-        cache.guilds.remove(&self.guild_id);
+
+        if let Some(guild) = cache.backend.remove_guild(self.guild_id).await {
+            guild_removed(cache, &guild).await;
+        }
 
         None
     }
 }
 
+#[async_trait]
 impl CacheUpdate for GuildUpdateEvent {
     type Output = ();
 
-    fn update(&mut self, cache: &Cache) -> Option<()> {
-        if let Some(mut guild) = cache.guilds.get_mut(&self.guild.id) {
-            guild.afk_channel_id.clone_from(&self.guild.afk_channel_id);
-            guild.afk_timeout = self.guild.afk_timeout;
-            guild.banner.clone_from(&self.guild.banner);
-            guild.discovery_splash.clone_from(&self.guild.discovery_splash);
-            guild.features.clone_from(&self.guild.features);
-            guild.icon.clone_from(&self.guild.icon);
-            guild.name.clone_from(&self.guild.name);
-            guild.owner_id.clone_from(&self.guild.owner_id);
-            guild.roles.clone_from(&self.guild.roles);
-            guild.splash.clone_from(&self.guild.splash);
-            guild.vanity_url_code.clone_from(&self.guild.vanity_url_code);
-            guild.welcome_screen.clone_from(&self.guild.welcome_screen);
-            guild.default_message_notifications = self.guild.default_message_notifications;
-            guild.max_members = self.guild.max_members;
-            guild.max_presences = self.guild.max_presences;
-            guild.max_video_channel_users = self.guild.max_video_channel_users;
-            guild.mfa_level = self.guild.mfa_level;
-            guild.nsfw_level = self.guild.nsfw_level;
-            guild.premium_subscription_count = self.guild.premium_subscription_count;
-            guild.premium_tier = self.guild.premium_tier;
-            guild.public_updates_channel_id = self.guild.public_updates_channel_id;
-            guild.rules_channel_id = self.guild.rules_channel_id;
-            guild.system_channel_flags = self.guild.system_channel_flags;
-            guild.system_channel_id = self.guild.system_channel_id;
-            guild.verification_level = self.guild.verification_level;
-            guild.widget_channel_id = self.guild.widget_channel_id;
-            guild.widget_enabled = self.guild.widget_enabled;
+    async fn update(&mut self, cache: &Cache) -> Option<()> {
+        let old_guild =
+            if cache.has_subscribers() { cache.backend.guild(self.guild.id).await } else { None };
+
+        cache
+            .backend
+            .update_guild_with(self.guild.id, |guild| {
+                guild.afk_channel_id.clone_from(&self.guild.afk_channel_id);
+                guild.afk_timeout = self.guild.afk_timeout;
+                guild.banner.clone_from(&self.guild.banner);
+                guild.discovery_splash.clone_from(&self.guild.discovery_splash);
+                guild.features.clone_from(&self.guild.features);
+                guild.icon.clone_from(&self.guild.icon);
+                guild.name.clone_from(&self.guild.name);
+                guild.owner_id.clone_from(&self.guild.owner_id);
+                guild.roles.clone_from(&self.guild.roles);
+                guild.splash.clone_from(&self.guild.splash);
+                guild.vanity_url_code.clone_from(&self.guild.vanity_url_code);
+                guild.welcome_screen.clone_from(&self.guild.welcome_screen);
+                guild.default_message_notifications = self.guild.default_message_notifications;
+                guild.max_members = self.guild.max_members;
+                guild.max_presences = self.guild.max_presences;
+                guild.max_video_channel_users = self.guild.max_video_channel_users;
+                guild.mfa_level = self.guild.mfa_level;
+                guild.nsfw_level = self.guild.nsfw_level;
+                guild.premium_subscription_count = self.guild.premium_subscription_count;
+                guild.premium_tier = self.guild.premium_tier;
+                guild.public_updates_channel_id = self.guild.public_updates_channel_id;
+                guild.rules_channel_id = self.guild.rules_channel_id;
+                guild.system_channel_flags = self.guild.system_channel_flags;
+                guild.system_channel_id = self.guild.system_channel_id;
+                guild.verification_level = self.guild.verification_level;
+                guild.widget_channel_id = self.guild.widget_channel_id;
+                guild.widget_enabled = self.guild.widget_enabled;
+            })
+            .await;
+
+        if let Some(old) = old_guild {
+            if let Some(new) = cache.backend.guild(self.guild.id).await {
+                cache.notify(CacheChange::Guild(Box::new(Change::Update {
+                    old,
+                    new,
+                })));
+            }
         }
 
         None
     }
 }
 
+#[async_trait]
 impl CacheUpdate for MessageCreateEvent {
     /// The oldest message, if the channel's message cache was already full.
     type Output = Message;
 
-    fn update(&mut self, cache: &Cache) -> Option<Self::Output> {
-        let max = cache.settings().max_messages;
+    async fn update(&mut self, cache: &Cache) -> Option<Self::Output> {
+        let max = {
+            let settings = cache.settings.read();
+
+            if settings.cache_messages {
+                settings.max_messages
+            } else {
+                0
+            }
+        };
 
         if max == 0 {
             return None;
         }
 
-        let messages =
-            cache.messages.entry(self.message.channel_id).or_insert_with(Default::default);
-        let mut queue =
-            cache.message_queue.entry(self.message.channel_id).or_insert_with(Default::default);
+        if !cache.has_subscribers() {
+            return cache.backend.insert_message(self.message.clone(), max).await;
+        }
 
-        let mut removed_msg = None;
+        let old = cache.backend.message(self.message.channel_id, self.message.id).await;
+        let removed = cache.backend.insert_message(self.message.clone(), max).await;
 
-        if messages.len() == max {
-            if let Some(id) = queue.pop_front() {
-                removed_msg = messages.remove(&id);
+        cache.notify(CacheChange::Message(Box::new(Change::new(old, self.message.clone()))));
+
+        if let Some(removed) = &removed {
+            cache.notify(CacheChange::Message(Box::new(Change::Remove(removed.clone()))));
+        }
+
+        removed
+    }
+}
+
+#[async_trait]
+impl CacheUpdate for MessageDeleteBulkEvent {
+    /// The deleted messages that were cached.
+    type Output = Vec<Message>;
+
+    async fn update(&mut self, cache: &Cache) -> Option<Self::Output> {
+        let mut removed = Vec::new();
+
+        for id in &self.ids {
+            if let Some(message) = remove_message(cache, self.channel_id, *id).await {
+                removed.push(message);
             }
         }
 
-        queue.push_back(self.message.id);
-        messages.insert(self.message.id, self.message.clone());
+        (!removed.is_empty()).then(|| removed)
+    }
+}
+
+#[async_trait]
+impl CacheUpdate for MessageDeleteEvent {
+    type Output = Message;
 
-        removed_msg.map(|i| i.1)
+    async fn update(&mut self, cache: &Cache) -> Option<Self::Output> {
+        remove_message(cache, self.channel_id, self.message_id).await
     }
 }
 
+async fn remove_message(
+    cache: &Cache,
+    channel_id: ChannelId,
+    message_id: MessageId,
+) -> Option<Message> {
+    let removed = cache.backend.remove_message(channel_id, message_id).await?;
+
+    if cache.has_subscribers() {
+        cache.notify(CacheChange::Message(Box::new(Change::Remove(removed.clone()))));
+    }
+
+    Some(removed)
+}
+
+#[async_trait]
 impl CacheUpdate for MessageUpdateEvent {
     type Output = Message;
 
     #[rustfmt::skip]
-    fn update(&mut self, cache: &Cache) -> Option<Self::Output> {
+    async fn update(&mut self, cache: &Cache) -> Option<Self::Output> {
         // Destructure, so we get an `unused` warning when we forget to process one of the fields
         // in this method
         #[allow(deprecated)] // yes rust, exhaustive means exhaustive, even the deprecated ones
@@ -493,8 +890,7 @@ impl CacheUpdate for MessageUpdateEvent {
             author: _, timestamp: _,  nonce: _, kind: _, stickers: _,  guild_id: _,
         } = &self;
 
-        let messages = cache.messages.get_mut(channel_id)?;
-        let mut message = messages.get_mut(id)?;
+        let old_message = cache.backend.update_message_with(*channel_id, *id, |message| {
         let old_message = message.clone();
 
         if let Some(x) = attachments { message.attachments = x.clone() }
@@ -512,80 +908,222 @@ impl CacheUpdate for MessageUpdateEvent {
         if let Some(x) = components { message.components = x.clone() }
         if let Some(x) = sticker_items { message.sticker_items = x.clone() }
 
-        Some(old_message)
+        old_message
+        }).await;
+
+        if let Some(old) = old_message.clone().filter(|_| cache.has_subscribers()) {
+            if let Some(new) = cache.backend.message(*channel_id, *id).await {
+                cache.notify(CacheChange::Message(Box::new(Change::Update { old, new })));
+            }
+        }
+
+        old_message
     }
 }
 
+#[async_trait]
 impl CacheUpdate for PresenceUpdateEvent {
     type Output = ();
 
-    fn update(&mut self, cache: &Cache) -> Option<()> {
+    async fn update(&mut self, cache: &Cache) -> Option<()> {
         if let Some(user) = self.presence.user.to_user() {
-            cache.update_user_entry(&user);
+            cache.update_user_entry(&user).await;
         }
 
-        if let Some(user) = cache.user(self.presence.user.id) {
+        if let Some(user) = cache.user(self.presence.user.id).await {
             self.presence.user.update_with_user(user);
         }
 
+        let settings = cache.settings();
+
         if let Some(guild_id) = self.presence.guild_id {
-            if let Some(mut guild) = cache.guilds.get_mut(&guild_id) {
-                // If the member went offline, remove them from the presence list.
-                if self.presence.status == OnlineStatus::Offline {
-                    guild.presences.remove(&self.presence.user.id);
+            let presence = &self.presence;
+
+            if settings.cache_presences {
+                cache
+                    .backend
+                    .update_guild_with(guild_id, |guild| {
+                        // If the member went offline, remove them from the presence list.
+                        if presence.status == OnlineStatus::Offline {
+                            guild.presences.remove(&presence.user.id);
+                        } else {
+                            guild.presences.insert(presence.user.id, presence.clone());
+                        }
+                    })
+                    .await;
+
+                if presence.status == OnlineStatus::Offline {
+                    cache.forget_presence(Some(guild_id), presence.user.id);
                 } else {
-                    guild.presences.insert(self.presence.user.id, self.presence.clone());
+                    cache.seen_presences(Some(guild_id), Some(presence.user.id)).await;
                 }
+            }
 
-                // Create a partial member instance out of the presence update
-                // data.
-                if let Some(user) = self.presence.user.to_user() {
-                    guild.members.entry(self.presence.user.id).or_insert_with(|| Member {
-                        deaf: false,
-                        guild_id,
-                        joined_at: None,
-                        mute: false,
-                        nick: None,
-                        user,
-                        roles: vec![],
-                        pending: false,
-                        premium_since: None,
-                        permissions: None,
-                        avatar: None,
-                        communication_disabled_until: None,
-                    });
+            // Create a partial member instance out of the presence update
+            // data.
+            if let Some(user) = self.presence.user.to_user().filter(|_| settings.cache_members) {
+                if cache.backend.member(guild_id, user.id).await.is_none() {
+                    cache
+                        .backend
+                        .insert_member(Member {
+                            deaf: false,
+                            guild_id,
+                            joined_at: None,
+                            mute: false,
+                            nick: None,
+                            user,
+                            roles: vec![],
+                            pending: false,
+                            premium_since: None,
+                            permissions: None,
+                            avatar: None,
+                            communication_disabled_until: None,
+                        })
+                        .await;
+                    member_changed(cache, None, guild_id, self.presence.user.id).await;
                 }
+
+                cache.seen_members(guild_id, Some(self.presence.user.id)).await;
             }
         } else if self.presence.status == OnlineStatus::Offline {
-            cache.presences.remove(&self.presence.user.id);
-        } else {
-            cache.presences.insert(self.presence.user.id, self.presence.clone());
+            cache.backend.remove_presence(self.presence.user.id).await;
+            cache.forget_presence(None, self.presence.user.id);
+        } else if settings.cache_presences {
+            cache.backend.insert_presence(self.presence.clone()).await;
+            cache.seen_presences(None, Some(self.presence.user.id)).await;
         }
 
         None
     }
 }
 
+#[async_trait]
 impl CacheUpdate for PresencesReplaceEvent {
     type Output = ();
 
-    fn update(&mut self, cache: &Cache) -> Option<()> {
+    async fn update(&mut self, cache: &Cache) -> Option<()> {
+        if !cache.settings.read().cache_presences {
+            return None;
+        }
+
         for presence in &self.presences {
-            cache.presences.insert(presence.user.id, presence.clone());
+            cache.backend.insert_presence(presence.clone()).await;
         }
 
+        cache.seen_presences(None, self.presences.iter().map(|presence| presence.user.id)).await;
+
+        None
+    }
+}
+
+#[async_trait]
+impl CacheUpdate for ReactionAddEvent {
+    type Output = ();
+
+    async fn update(&mut self, cache: &Cache) -> Option<()> {
+        let reaction = &self.reaction;
+        let me = reaction.user_id == Some(cache.current_user_id());
+
+        update_reactions(cache, reaction.channel_id, reaction.message_id, |reactions| {
+            match reactions.iter_mut().find(|r| r.reaction_type == reaction.emoji) {
+                Some(cached) => {
+                    cached.count += 1;
+                    cached.me |= me;
+                },
+                None => reactions.push(MessageReaction {
+                    count: 1,
+                    me,
+                    reaction_type: reaction.emoji.clone(),
+                }),
+            }
+        })
+        .await;
+
+        None
+    }
+}
+
+#[async_trait]
+impl CacheUpdate for ReactionRemoveEvent {
+    type Output = ();
+
+    async fn update(&mut self, cache: &Cache) -> Option<()> {
+        let reaction = &self.reaction;
+        let me = reaction.user_id == Some(cache.current_user_id());
+
+        update_reactions(cache, reaction.channel_id, reaction.message_id, |reactions| {
+            remove_reaction(reactions, &reaction.emoji, me);
+        })
+        .await;
+
         None
     }
 }
 
+#[async_trait]
+impl CacheUpdate for ReactionRemoveAllEvent {
+    type Output = ();
+
+    async fn update(&mut self, cache: &Cache) -> Option<()> {
+        update_reactions(cache, self.channel_id, self.message_id, Vec::clear).await;
+
+        None
+    }
+}
+
+/// Removes one reaction of the given type, dropping the type once it has no
+/// reactions left.
+fn remove_reaction(reactions: &mut Vec<MessageReaction>, emoji: &ReactionType, me: bool) {
+    if let Some(index) = reactions.iter().position(|r| &r.reaction_type == emoji) {
+        let cached = &mut reactions[index];
+        cached.count = cached.count.saturating_sub(1);
+        cached.me &= !me;
+
+        if cached.count == 0 {
+            reactions.remove(index);
+        }
+    }
+}
+
+/// Lends the reactions of a cached message to `f`.
+async fn update_reactions(
+    cache: &Cache,
+    channel_id: ChannelId,
+    message_id: MessageId,
+    f: impl FnOnce(&mut Vec<MessageReaction>) + Send,
+) {
+    let old = cache
+        .backend
+        .update_message_with(channel_id, message_id, |message| {
+            let old = cache.has_subscribers().then(|| message.clone());
+
+            f(&mut message.reactions);
+
+            old
+        })
+        .await;
+
+    if let Some(old) = old.flatten() {
+        if let Some(new) = cache.backend.message(channel_id, message_id).await {
+            cache.notify(CacheChange::Message(Box::new(Change::Update {
+                old,
+                new,
+            })));
+        }
+    }
+}
+
+#[async_trait]
 impl CacheUpdate for ReadyEvent {
     type Output = ();
 
-    fn update(&mut self, cache: &Cache) -> Option<()> {
+    async fn update(&mut self, cache: &Cache) -> Option<()> {
         let mut ready = self.ready.clone();
 
         for unavailable in ready.guilds {
-            cache.guilds.remove(&unavailable.id);
+            if let Some(guild) = cache.backend.remove_guild(unavailable.id).await {
+                guild_removed(cache, &guild).await;
+            }
             cache.unavailable_guilds.insert(unavailable.id);
         }
 
@@ -594,33 +1132,42 @@ impl CacheUpdate for ReadyEvent {
         let ready_guilds_hashset =
             self.ready.guilds.iter().map(|status| status.id).collect::<HashSet<_>>();
         let shard_data = self.ready.shard.unwrap_or([1, 1]);
-        for guild_entry in cache.guilds.iter() {
-            let guild = guild_entry.key();
+        for guild in cache.backend.guild_ids().await {
             // Only handle data for our shard.
             if crate::utils::shard_id(guild.0, shard_data[1]) == shard_data[0]
-                && !ready_guilds_hashset.contains(guild)
+                && !ready_guilds_hashset.contains(&guild)
             {
-                guilds_to_remove.push(*guild);
+                guilds_to_remove.push(guild);
             }
         }
         if !guilds_to_remove.is_empty() {
             for guild in guilds_to_remove {
-                cache.guilds.remove(&guild);
+                if let Some(guild) = cache.backend.remove_guild(guild).await {
+                    guild_removed(cache, &guild).await;
+                }
             }
         }
 
         // `ready.private_channels` will always be empty, and possibly be removed in the future.
         // So don't handle it at all.
 
+        let cache_presences = cache.settings.read().cache_presences;
+
         for (user_id, presence) in &mut ready.presences {
             if let Some(user) = presence.user.to_user() {
-                cache.update_user_entry(&user);
+                cache.update_user_entry(&user).await;
             }
-            if let Some(user) = cache.user(user_id) {
+            if let Some(user) = cache.user(user_id).await {
                 presence.user.update_with_user(user);
             }
 
-            cache.presences.insert(*user_id, presence.clone());
+            if cache_presences {
+                cache.backend.insert_presence(presence.clone()).await;
+            }
+        }
+
+        if cache_presences {
+            cache.seen_presences(None, ready.presences.keys().copied()).await;
         }
 
         *cache.shard_count.write() = ready.shard.map_or(1, |s| s[1]);
@@ -630,71 +1177,245 @@ impl CacheUpdate for ReadyEvent {
     }
 }
 
-impl CacheUpdate for ThreadCreateEvent {
-    type Output = GuildChannel;
+#[async_trait]
+impl CacheUpdate for StageInstanceCreateEvent {
+    type Output = ();
 
-    fn update(&mut self, cache: &Cache) -> Option<Self::Output> {
-        let (guild_id, thread_id) = (self.thread.guild_id, self.thread.id);
+    async fn update(&mut self, cache: &Cache) -> Option<()> {
+        upsert_stage_instance(cache, &self.stage_instance).await;
 
-        cache.guilds.get_mut(&guild_id).and_then(|mut g| {
-            if let Some(i) = g.threads.iter().position(|e| e.id == thread_id) {
-                Some(std::mem::replace(&mut g.threads[i], self.thread.clone()))
+        None
+    }
+}
+
+#[async_trait]
+impl CacheUpdate for StageInstanceUpdateEvent {
+    type Output = StageInstance;
+
+    async fn update(&mut self, cache: &Cache) -> Option<Self::Output> {
+        upsert_stage_instance(cache, &self.stage_instance).await
+    }
+}
+
+#[async_trait]
+impl CacheUpdate for StageInstanceDeleteEvent {
+    type Output = StageInstance;
+
+    async fn update(&mut self, cache: &Cache) -> Option<Self::Output> {
+        let stage_id = self.stage_instance.id;
+
+        cache
+            .backend
+            .update_guild_with(self.stage_instance.guild_id, |guild| {
+                let index = guild.stage_instances.iter().position(|s| s.id == stage_id)?;
+
+                Some(guild.stage_instances.remove(index))
+            })
+            .await
+            .flatten()
+    }
+}
+
+/// Inserts or replaces a stage instance of a cached guild, returning the
+/// replaced instance.
+async fn upsert_stage_instance(cache: &Cache, stage: &StageInstance) -> Option<StageInstance> {
+    cache
+        .backend
+        .update_guild_with(stage.guild_id, |guild| {
+            if let Some(i) = guild.stage_instances.iter().position(|s| s.id == stage.id) {
+                Some(std::mem::replace(&mut guild.stage_instances[i], stage.clone()))
             } else {
-                g.threads.push(self.thread.clone());
+                guild.stage_instances.push(stage.clone());
                 None
             }
         })
-    }
+        .await
+        .flatten()
 }
 
-impl CacheUpdate for ThreadUpdateEvent {
-    type Output = GuildChannel;
-
-    fn update(&mut self, cache: &Cache) -> Option<Self::Output> {
-        let (guild_id, thread_id) = (self.thread.guild_id, self.thread.id);
+/// Inserts or replaces a thread of a cached guild, notifying subscribers, and
+/// returns the replaced thread.
+async fn upsert_thread(cache: &Cache, thread: &GuildChannel) -> Option<GuildChannel> {
+    let thread_id = thread.id;
 
-        cache.guilds.get_mut(&guild_id).and_then(|mut g| {
+    let old = cache
+        .backend
+        .update_guild_with(thread.guild_id, |g| {
             if let Some(i) = g.threads.iter().position(|e| e.id == thread_id) {
-                Some(std::mem::replace(&mut g.threads[i], self.thread.clone()))
+                Some(std::mem::replace(&mut g.threads[i], thread.clone()))
             } else {
-                g.threads.push(self.thread.clone());
+                g.threads.push(thread.clone());
                 None
             }
         })
+        .await?;
+
+    if cache.has_subscribers() {
+        cache.notify(CacheChange::Channel(Box::new(Change::new(
+            old.clone().map(Channel::Guild),
+            Channel::Guild(thread.clone()),
+        ))));
+    }
+
+    old
+}
+
+#[async_trait]
+impl CacheUpdate for ThreadCreateEvent {
+    type Output = GuildChannel;
+
+    async fn update(&mut self, cache: &Cache) -> Option<Self::Output> {
+        if !cache.settings.read().cache_channels {
+            return None;
+        }
+
+        upsert_thread(cache, &self.thread).await
+    }
+}
+
+#[async_trait]
+impl CacheUpdate for ThreadUpdateEvent {
+    type Output = GuildChannel;
+
+    async fn update(&mut self, cache: &Cache) -> Option<Self::Output> {
+        if !cache.settings.read().cache_channels {
+            return None;
+        }
+
+        upsert_thread(cache, &self.thread).await
     }
 }
 
+#[async_trait]
 impl CacheUpdate for ThreadDeleteEvent {
     type Output = GuildChannel;
 
-    fn update(&mut self, cache: &Cache) -> Option<Self::Output> {
+    async fn update(&mut self, cache: &Cache) -> Option<Self::Output> {
         let (guild_id, thread_id) = (self.thread.guild_id, self.thread.id);
 
-        cache.guilds.get_mut(&guild_id).and_then(|mut g| {
-            g.threads.iter().position(|e| e.id == thread_id).map(|i| g.threads.remove(i))
-        })
+        let removed = cache
+            .backend
+            .update_guild_with(guild_id, |g| {
+                g.threads.iter().position(|e| e.id == thread_id).map(|i| g.threads.remove(i))
+            })
+            .await
+            .flatten();
+
+        if let Some(removed) = removed.as_ref().filter(|_| cache.has_subscribers()) {
+            cache.notify(CacheChange::Channel(Box::new(Change::Remove(Channel::Guild(
+                removed.clone(),
+            )))));
+        }
+
+        removed
     }
 }
 
+#[async_trait]
+impl CacheUpdate for ThreadListSyncEvent {
+    type Output = ();
+
+    async fn update(&mut self, cache: &Cache) -> Option<()> {
+        if !cache.settings.read().cache_channels {
+            return None;
+        }
+
+        let mut replaced = cache
+            .backend
+            .update_guild_with(self.guild_id, |guild| {
+                // The threads of the synced parent channels, or of the whole guild
+                // if none are given, are replaced.
+                let (replaced, kept) = std::mem::take(&mut guild.threads).into_iter().partition(
+                    |thread: &GuildChannel| {
+                        self.channels_id.is_empty()
+                            || thread
+                                .parent_id
+                                .map_or(false, |parent| self.channels_id.contains(&parent))
+                    },
+                );
+
+                guild.threads = kept;
+                guild.threads.extend(self.threads.iter().cloned());
+
+                replaced
+            })
+            .await?;
+
+        if cache.has_subscribers() {
+            for thread in &self.threads {
+                let old = replaced
+                    .iter()
+                    .position(|old| old.id == thread.id)
+                    .map(|i| Channel::Guild(replaced.swap_remove(i)));
+
+                cache.notify(CacheChange::Channel(Box::new(Change::new(
+                    old,
+                    Channel::Guild(thread.clone()),
+                ))));
+            }
+
+            for thread in replaced {
+                cache
+                    .notify(CacheChange::Channel(Box::new(Change::Remove(Channel::Guild(thread)))));
+            }
+        }
+
+        None
+    }
+}
+
+#[async_trait]
+impl CacheUpdate for ThreadMembersUpdateEvent {
+    type Output = ();
+
+    async fn update(&mut self, cache: &Cache) -> Option<()> {
+        let (thread_id, member_count) = (self.id, self.member_count);
+
+        cache
+            .backend
+            .update_guild_with(self.guild_id, |guild| {
+                if let Some(thread) = guild.threads.iter_mut().find(|thread| thread.id == thread_id)
+                {
+                    thread.member_count = Some(member_count);
+                }
+            })
+            .await;
+
+        None
+    }
+}
+
+#[async_trait]
 impl CacheUpdate for UserUpdateEvent {
     type Output = CurrentUser;
 
-    fn update(&mut self, cache: &Cache) -> Option<Self::Output> {
+    async fn update(&mut self, cache: &Cache) -> Option<Self::Output> {
         let mut user = cache.user.write();
         Some(std::mem::replace(&mut user, self.current_user.clone()))
     }
 }
 
+#[async_trait]
 impl CacheUpdate for VoiceStateUpdateEvent {
     type Output = VoiceState;
 
-    fn update(&mut self, cache: &Cache) -> Option<VoiceState> {
-        if let Some(guild_id) = self.voice_state.guild_id {
-            if let Some(mut guild) = cache.guilds.get_mut(&guild_id) {
-                if let Some(member) = &self.voice_state.member {
-                    guild.members.insert(member.user.id, member.clone());
-                }
+    async fn update(&mut self, cache: &Cache) -> Option<VoiceState> {
+        let guild_id = self.voice_state.guild_id?;
+        let settings = cache.settings();
+
+        if let Some(member) = self.voice_state.member.as_ref().filter(|_| settings.cache_members) {
+            let old = cache.backend.insert_member(member.clone()).await;
+            member_changed(cache, old, guild_id, member.user.id).await;
+            cache.seen_members(guild_id, Some(member.user.id)).await;
+        }
 
+        if !settings.cache_voice_states {
+            return None;
+        }
+
+        cache
+            .backend
+            .update_guild_with(guild_id, |guild| {
                 if self.voice_state.channel_id.is_some() {
                     // Update or add to the voice state list
                     guild.voice_states.insert(self.voice_state.user_id, self.voice_state.clone())
@@ -702,11 +1423,8 @@ impl CacheUpdate for VoiceStateUpdateEvent {
                     // Remove the user from the voice state list
                     guild.voice_states.remove(&self.voice_state.user_id)
                 }
-            } else {
-                None
-            }
-        } else {
-            None
-        }
+            })
+            .await
+            .flatten()
     }
 }
@@ -0,0 +1,414 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::hash::Hash;
+use std::time::{Duration, Instant};
+
+use super::{Cache, CacheBackendExt, CacheChange, Change};
+use crate::model::prelude::*;
+
+/// How often entries are checked for having outlived their [`ttl`].
+///
+/// [`ttl`]: EvictionPolicy::ttl
+const SWEEP_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Bounds on how many entries of a kind the [`Cache`] keeps, and for how long.
+///
+/// Entries are ordered by when they were last seen, that is when an event
+/// inserting or updating them was last received. Neither bound is set by
+/// default, so that entries are kept until Discord reports their removal.
+///
+/// Expired entries are evicted while events are being processed.
+///
+/// # Examples
+///
+/// Keep at most 1000 members per guild, and none that were not seen for an
+/// hour:
+///
+/// ```rust
+/// use std::time::Duration;
+///
+/// use serenity::cache::{EvictionPolicy, Settings};
+///
+/// let mut settings = Settings::new();
+/// settings
+///     .member_eviction(EvictionPolicy::new().max_entries(1000).ttl(Duration::from_secs(60 * 60)));
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct EvictionPolicy {
+    /// The maximum number of entries. Once exceeded, the least recently seen
+    /// entries are evicted.
+    ///
+    /// Defaults to `None`.
+    pub max_entries: Option<usize>,
+    /// How long an entry is kept after it was last seen.
+    ///
+    /// Defaults to `None`.
+    pub ttl: Option<Duration>,
+}
+
+impl EvictionPolicy {
+    /// Creates a policy that never evicts entries.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum number of entries.
+    ///
+    /// Refer to [`max_entries`] for more information.
+    ///
+    /// [`max_entries`]: #structfield.max_entries
+    #[must_use]
+    pub fn max_entries(mut self, max: usize) -> Self {
+        self.max_entries = Some(max);
+
+        self
+    }
+
+    /// Sets how long an entry is kept after it was last seen.
+    ///
+    /// Refer to [`ttl`] for more information.
+    ///
+    /// [`ttl`]: #structfield.ttl
+    #[must_use]
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+
+        self
+    }
+
+    fn is_active(&self) -> bool {
+        self.max_entries.is_some() || self.ttl.is_some()
+    }
+}
+
+/// The times at which entries were last seen, ordered from least to most
+/// recently seen.
+#[derive(Debug)]
+struct LastSeen<K> {
+    seen: HashMap<K, Instant>,
+    order: BTreeSet<(Instant, K)>,
+}
+
+impl<K> Default for LastSeen<K> {
+    fn default() -> Self {
+        Self {
+            seen: HashMap::new(),
+            order: BTreeSet::new(),
+        }
+    }
+}
+
+impl<K: Copy + Eq + Hash + Ord> LastSeen<K> {
+    fn touch(&mut self, key: K, now: Instant) {
+        if let Some(previous) = self.seen.insert(key, now) {
+            self.order.remove(&(previous, key));
+        }
+
+        self.order.insert((now, key));
+    }
+
+    fn remove(&mut self, key: K) {
+        if let Some(previous) = self.seen.remove(&key) {
+            self.order.remove(&(previous, key));
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.seen.is_empty()
+    }
+
+    /// Removes the entries that exceed the bounds of the policy, returning
+    /// their keys.
+    fn evict(&mut self, policy: EvictionPolicy, now: Instant) -> Vec<K> {
+        let cutoff = policy.ttl.and_then(|ttl| now.checked_sub(ttl));
+        let mut evicted = Vec::new();
+
+        while let Some(&(last_seen, key)) = self.order.iter().next() {
+            let full = policy.max_entries.map_or(false, |max| self.seen.len() > max);
+            let expired = cutoff.map_or(false, |cutoff| last_seen < cutoff);
+
+            if !full && !expired {
+                break;
+            }
+
+            self.order.remove(&(last_seen, key));
+            self.seen.remove(&key);
+            evicted.push(key);
+        }
+
+        evicted
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Policies {
+    users: EvictionPolicy,
+    members: EvictionPolicy,
+    presences: EvictionPolicy,
+}
+
+/// Entries evicted from the trackers, yet to be removed from the cache.
+#[derive(Default)]
+struct Evicted {
+    users: Vec<UserId>,
+    members: Vec<(GuildId, UserId)>,
+    presences: Vec<(Option<GuildId>, UserId)>,
+}
+
+/// Tracks when users, members and presences were last seen, for the
+/// [`EvictionPolicy`]s of the cache.
+#[derive(Debug, Default)]
+pub(crate) struct Eviction {
+    users: LastSeen<UserId>,
+    members: HashMap<GuildId, LastSeen<UserId>>,
+    /// Presences by the guild they belong to, if any.
+    presences: HashMap<Option<GuildId>, LastSeen<UserId>>,
+    last_sweep: Option<Instant>,
+}
+
+impl Eviction {
+    /// Evicts expired entries, at most once per [`SWEEP_INTERVAL`].
+    fn sweep(&mut self, policies: Policies, now: Instant, evicted: &mut Evicted) {
+        if self.last_sweep.map_or(false, |last| now.duration_since(last) < SWEEP_INTERVAL) {
+            return;
+        }
+
+        self.last_sweep = Some(now);
+
+        evicted.users.extend(self.users.evict(policies.users, now));
+
+        for (guild_id, members) in &mut self.members {
+            let guild_id = *guild_id;
+
+            evicted
+                .members
+                .extend(members.evict(policies.members, now).into_iter().map(|id| (guild_id, id)));
+        }
+
+        for (guild_id, presences) in &mut self.presences {
+            let guild_id = *guild_id;
+
+            evicted.presences.extend(
+                presences.evict(policies.presences, now).into_iter().map(|id| (guild_id, id)),
+            );
+        }
+
+        self.members.retain(|_, members| !members.is_empty());
+        self.presences.retain(|_, presences| !presences.is_empty());
+    }
+}
+
+impl Cache {
+    fn eviction_policies(&self) -> Policies {
+        let settings = self.settings.read();
+
+        Policies {
+            users: settings.user_eviction,
+            members: settings.member_eviction,
+            presences: settings.presence_eviction,
+        }
+    }
+
+    /// Runs `f` on the trackers if any eviction policy is set, then removes
+    /// the evicted entries from the cache.
+    async fn track(&self, f: impl FnOnce(&mut Eviction, Policies, Instant, &mut Evicted) + Send) {
+        let policies = self.eviction_policies();
+
+        if !policies.users.is_active()
+            && !policies.members.is_active()
+            && !policies.presences.is_active()
+        {
+            return;
+        }
+
+        let now = Instant::now();
+        let mut evicted = Evicted::default();
+
+        {
+            let mut eviction = self.eviction.lock();
+
+            f(&mut eviction, policies, now, &mut evicted);
+            eviction.sweep(policies, now, &mut evicted);
+        }
+
+        for user_id in evicted.users {
+            self.backend.remove_user(user_id).await;
+        }
+
+        let mut removed_members = Vec::with_capacity(evicted.members.len());
+
+        for (guild_id, user_id) in evicted.members {
+            let member = self.backend.remove_member(guild_id, user_id).await;
+            removed_members.push(user_id);
+
+            if let Some(member) = member.filter(|_| self.has_subscribers()) {
+                self.notify(CacheChange::Member(Box::new(Change::Remove(member))));
+            }
+        }
+
+        for (guild_id, user_id) in evicted.presences {
+            match guild_id {
+                Some(guild_id) => {
+                    self.backend
+                        .update_guild_with(guild_id, |guild| {
+                            guild.presences.remove(&user_id);
+                        })
+                        .await;
+                },
+                None => {
+                    self.backend.remove_presence(user_id).await;
+                },
+            }
+        }
+
+        self.remove_unreferenced_users(removed_members).await;
+    }
+
+    pub(crate) async fn seen_user(&self, user_id: UserId) {
+        self.track(|eviction, policies, now, evicted| {
+            if policies.users.is_active() {
+                eviction.users.touch(user_id, now);
+                evicted.users.extend(eviction.users.evict(policies.users, now));
+            }
+        })
+        .await;
+    }
+
+    pub(crate) async fn seen_members(
+        &self,
+        guild_id: GuildId,
+        user_ids: impl IntoIterator<Item = UserId> + Send,
+    ) {
+        self.track(|eviction, policies, now, evicted| {
+            if policies.members.is_active() {
+                let members = eviction.members.entry(guild_id).or_default();
+
+                for user_id in user_ids {
+                    members.touch(user_id, now);
+                }
+
+                evicted.members.extend(
+                    members.evict(policies.members, now).into_iter().map(|id| (guild_id, id)),
+                );
+            }
+        })
+        .await;
+    }
+
+    pub(crate) async fn seen_presences(
+        &self,
+        guild_id: Option<GuildId>,
+        user_ids: impl IntoIterator<Item = UserId> + Send,
+    ) {
+        self.track(|eviction, policies, now, evicted| {
+            if policies.presences.is_active() {
+                let presences = eviction.presences.entry(guild_id).or_default();
+
+                for user_id in user_ids {
+                    presences.touch(user_id, now);
+                }
+
+                evicted.presences.extend(
+                    presences.evict(policies.presences, now).into_iter().map(|id| (guild_id, id)),
+                );
+            }
+        })
+        .await;
+    }
+
+    /// Stops tracking a member that was removed from the cache, and removes
+    /// the user if it is no longer referenced.
+    pub(crate) async fn forget_member(&self, guild_id: GuildId, user_id: UserId) {
+        if let Some(members) = self.eviction.lock().members.get_mut(&guild_id) {
+            members.remove(user_id);
+        }
+
+        self.remove_unreferenced_users(vec![user_id]).await;
+    }
+
+    /// Stops tracking a presence that was removed from the cache.
+    pub(crate) fn forget_presence(&self, guild_id: Option<GuildId>, user_id: UserId) {
+        if let Some(presences) = self.eviction.lock().presences.get_mut(&guild_id) {
+            presences.remove(user_id);
+        }
+    }
+
+    /// Stops tracking the members and presences of a guild that was removed
+    /// from the cache, and removes its members' users that are no longer
+    /// referenced.
+    pub(crate) async fn forget_guild(&self, guild: &Guild) {
+        {
+            let mut eviction = self.eviction.lock();
+
+            eviction.members.remove(&guild.id);
+            eviction.presences.remove(&Some(guild.id));
+        }
+
+        self.remove_unreferenced_users(guild.members.keys().copied().collect()).await;
+    }
+
+    /// Removes the given users if [`Settings::remove_unreferenced_users`] is
+    /// enabled and they share no cached guild or private channel with the
+    /// current user anymore.
+    ///
+    /// [`Settings::remove_unreferenced_users`]: super::Settings::remove_unreferenced_users
+    pub(crate) async fn remove_unreferenced_users(&self, user_ids: Vec<UserId>) {
+        if user_ids.is_empty() || !self.settings.read().remove_unreferenced_users {
+            return;
+        }
+
+        let mut user_ids = user_ids.into_iter().collect::<HashSet<_>>();
+        user_ids.remove(&self.user.read().id);
+
+        for channel in &self.private_channels {
+            user_ids.remove(&channel.recipient.id);
+        }
+
+        self.backend.retain_non_members(&mut user_ids).await;
+
+        if user_ids.is_empty() {
+            return;
+        }
+
+        {
+            let mut eviction = self.eviction.lock();
+
+            for user_id in &user_ids {
+                eviction.users.remove(*user_id);
+            }
+        }
+
+        for user_id in user_ids {
+            self.backend.remove_user(user_id).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, Instant};
+
+    use super::{EvictionPolicy, LastSeen};
+
+    #[test]
+    fn test_last_seen_eviction() {
+        let now = Instant::now();
+        let mut last_seen = LastSeen::default();
+
+        for key in 0..4_u64 {
+            last_seen.touch(key, now + Duration::from_secs(key));
+        }
+
+        // The least recently seen entries go first.
+        last_seen.touch(0, now + Duration::from_secs(4));
+        let policy = EvictionPolicy::new().max_entries(2);
+        assert_eq!(last_seen.evict(policy, now), vec![1, 2]);
+
+        let policy = EvictionPolicy::new().ttl(Duration::from_secs(1));
+        let later = now + Duration::from_secs(4) + Duration::from_millis(500);
+        assert_eq!(last_seen.evict(policy, later), vec![3]);
+        assert_eq!(last_seen.evict(policy, later), Vec::<u64>::new());
+    }
+}
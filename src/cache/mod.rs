@@ -30,63 +30,82 @@
 //! [`Shard`]: crate::gateway::Shard
 //! [`http`]: crate::http
 
-use std::collections::hash_map::RandomState;
-use std::collections::{HashMap, VecDeque};
-use std::hash::BuildHasher;
+use std::collections::HashMap;
 use std::str::FromStr;
+use std::sync::Arc;
 #[cfg(feature = "temp_cache")]
 use std::time::Duration;
 
-use dashmap::iter::Iter;
-use dashmap::mapref::entry::Entry;
-use dashmap::mapref::multiple::RefMulti;
+use async_trait::async_trait;
 use dashmap::{DashMap, DashSet};
 #[cfg(feature = "temp_cache")]
 use moka::dash::Cache as DashCache;
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use tracing::instrument;
 
 use crate::model::prelude::*;
+mod backend;
 mod cache_update;
+mod changes;
+mod error;
 mod event;
+mod eviction;
+#[cfg(feature = "model")]
+mod permissions;
+mod query;
 mod settings;
+mod snapshot;
+mod stats;
 
+pub(crate) use self::backend::CacheBackendExt;
+pub use self::backend::{CacheBackend, InMemoryCacheBackend};
 pub use self::cache_update::CacheUpdate;
+use self::changes::Subscribers;
+pub use self::changes::{CacheChange, CacheChanges, Change};
+pub use self::error::Error as CacheError;
+use self::eviction::Eviction;
+pub use self::eviction::EvictionPolicy;
+#[cfg(feature = "model")]
+pub use self::permissions::{PermissionSource, PermissionStep, PermissionsExplanation};
+pub use self::query::MemberQuery;
 pub use self::settings::Settings;
+pub use self::stats::CacheStats;
 
-type MessageCache = DashMap<ChannelId, DashMap<MessageId, Message>>;
-
+#[async_trait]
 pub trait FromStrAndCache: Sized {
     type Err;
 
     #[allow(clippy::missing_errors_doc)]
-    fn from_str<CRL>(cache: CRL, s: &str) -> Result<Self, Self::Err>
+    async fn from_str<CRL>(cache: CRL, s: &str) -> Result<Self, Self::Err>
     where
         CRL: AsRef<Cache> + Send + Sync;
 }
 
+#[async_trait]
 pub trait StrExt: Sized {
     #[allow(clippy::missing_errors_doc)]
-    fn parse_cached<CRL, F: FromStrAndCache>(&self, cache: CRL) -> Result<F, F::Err>
+    async fn parse_cached<CRL, F: FromStrAndCache>(&self, cache: CRL) -> Result<F, F::Err>
     where
         CRL: AsRef<Cache> + Send + Sync;
 }
 
+#[async_trait]
 impl StrExt for &str {
     #[allow(clippy::missing_errors_doc)]
-    fn parse_cached<CRL, F: FromStrAndCache>(&self, cache: CRL) -> Result<F, F::Err>
+    async fn parse_cached<CRL, F: FromStrAndCache>(&self, cache: CRL) -> Result<F, F::Err>
     where
         CRL: AsRef<Cache> + Send + Sync,
     {
-        F::from_str(&cache, self)
+        F::from_str(&cache, self).await
     }
 }
 
+#[async_trait]
 impl<F: FromStr> FromStrAndCache for F {
     type Err = F::Err;
 
     #[allow(clippy::missing_errors_doc)]
-    fn from_str<CRL>(_cache: CRL, s: &str) -> Result<Self, Self::Err>
+    async fn from_str<CRL>(_cache: CRL, s: &str) -> Result<Self, Self::Err>
     where
         CRL: AsRef<Cache> + Send + Sync,
     {
@@ -94,19 +113,17 @@ impl<F: FromStr> FromStrAndCache for F {
     }
 }
 
-/// Iterator given to the selector closure in [`Cache::channel_messages_field`].
+/// Iterator given to the selector closure in [`Cache::channel_messages_field`],
+/// yielding the messages from oldest to newest.
 // Wrapper around a specific iterator type to allow swapping out iterators on cache design changes
 #[derive(Clone)]
-pub struct MessageIterator<'a, S: BuildHasher + Clone>(
-    Iter<'a, MessageId, Message, S, DashMap<MessageId, Message, S>>,
-);
+pub struct MessageIterator<'a>(std::slice::Iter<'a, &'a Message>);
 
-impl<'a, S: 'a + BuildHasher + Clone> Iterator for MessageIterator<'a, S> {
-    // type Item = &'a Message;
-    type Item = RefMulti<'a, MessageId, Message, S>;
+impl<'a> Iterator for MessageIterator<'a> {
+    type Item = &'a Message;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.0.next()
+        self.0.next().copied()
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
@@ -126,13 +143,9 @@ impl<'a, S: 'a + BuildHasher + Clone> Iterator for MessageIterator<'a, S> {
 #[derive(Debug)]
 #[non_exhaustive]
 pub struct Cache {
-    /// A map of channels in [`Guild`]s that the current user has received data
-    /// for.
-    ///
-    /// When a [`Event::GuildDelete`] or [`Event::GuildUnavailable`] is
-    /// received and processed by the cache, the relevant channels are also
-    /// removed from this map.
-    pub(crate) channels: DashMap<ChannelId, GuildChannel>,
+    /// The store of guilds, channels, members, roles, users, messages and
+    /// presences.
+    pub(crate) backend: Arc<dyn CacheBackend>,
     /// Cache of channels that have been fetched via to_channel.
     ///
     /// Each value has a maximum TTL of 1 hour.
@@ -140,14 +153,6 @@ pub struct Cache {
     pub(crate) temp_channels: DashCache<ChannelId, GuildChannel>,
     /// A map of channel categories.
     pub(crate) categories: DashMap<ChannelId, ChannelCategory>,
-    /// A map of guilds with full data available. This includes data like
-    /// [`Role`]s and [`Emoji`]s that are not available through the REST API.
-    pub(crate) guilds: DashMap<GuildId, Guild>,
-    pub(crate) messages: MessageCache,
-    /// A map of users' presences. This is updated in real-time. Note that
-    /// status updates are often "eaten" by the gateway, and this should not
-    /// be treated as being entirely 100% accurate.
-    pub(crate) presences: DashMap<UserId, Presence>,
     /// A map of direct message channels that the current user has open with
     /// other users.
     pub(crate) private_channels: DashMap<ChannelId, PrivateChannel>,
@@ -168,27 +173,6 @@ pub struct Cache {
     ///
     /// Refer to the documentation for [`CurrentUser`] for more information.
     pub(crate) user: RwLock<CurrentUser>,
-    /// A map of users that the current user sees.
-    ///
-    /// Users are added to - and updated from - this map via the following
-    /// received events:
-    ///
-    /// - [`GuildMemberAdd`][`GuildMemberAddEvent`]
-    /// - [`GuildMemberRemove`][`GuildMemberRemoveEvent`]
-    /// - [`GuildMembersChunk`][`GuildMembersChunkEvent`]
-    /// - [`PresenceUpdate`][`PresenceUpdateEvent`]
-    /// - [`Ready`][`ReadyEvent`]
-    ///
-    /// Note, however, that users are _not_ removed from the map on removal
-    /// events such as [`GuildMemberRemove`][`GuildMemberRemoveEvent`], as other
-    /// structs such as members or recipients may still exist.
-    pub(crate) users: DashMap<UserId, User>,
-    /// Queue of message IDs for each channel.
-    ///
-    /// This is simply a vecdeque so we can keep track of the order of messages
-    /// inserted into the cache. When a maximum number of messages are in a
-    /// channel's cache, we can pop the front and remove that ID from the cache.
-    pub(crate) message_queue: DashMap<ChannelId, VecDeque<MessageId>>,
     /// Cache of users who have been fetched from `to_user`.
     ///
     /// Each value has a max TTL of 1 hour.
@@ -196,6 +180,11 @@ pub struct Cache {
     pub(crate) temp_users: DashCache<UserId, User>,
     /// The settings for the cache.
     settings: RwLock<Settings>,
+    /// When users, members and presences were last seen, for the eviction
+    /// policies of the settings.
+    eviction: Mutex<Eviction>,
+    /// The subscribers to changes of the cache.
+    subscribers: Subscribers,
 }
 
 impl Cache {
@@ -221,11 +210,22 @@ impl Cache {
     #[instrument]
     pub fn new_with_settings(settings: Settings) -> Self {
         Self {
+            backend: settings
+                .backend
+                .clone()
+                .unwrap_or_else(|| Arc::new(InMemoryCacheBackend::new())),
             settings: RwLock::new(settings),
             ..Default::default()
         }
     }
 
+    /// The store that the cache keeps its data in. Refer to [`CacheBackend`]
+    /// for more information.
+    #[must_use]
+    pub fn backend(&self) -> &Arc<dyn CacheBackend> {
+        &self.backend
+    }
+
     /// Fetches the number of [`Member`]s that have not had data received.
     ///
     /// The important detail to note here is that this is the number of
@@ -260,7 +260,7 @@ impl Cache {
     ///         // seconds.
     ///         tokio::time::sleep(Duration::from_secs(5)).await;
     ///
-    ///         println!("{} unknown members", ctx.cache.unknown_members());
+    ///         println!("{} unknown members", ctx.cache.unknown_members().await);
     ///     }
     /// }
     ///
@@ -273,18 +273,20 @@ impl Cache {
     /// ```
     ///
     /// [`Shard::chunk_guild`]: crate::gateway::Shard::chunk_guild
-    pub fn unknown_members(&self) -> u64 {
+    pub async fn unknown_members(&self) -> u64 {
         let mut total = 0;
 
-        for guild_entry in self.guilds.iter() {
-            let guild = guild_entry.value();
+        self.backend
+            .for_each_guild(&mut |guild| {
+                let members = guild.members.len() as u64;
 
-            let members = guild.members.len() as u64;
+                if guild.member_count > members {
+                    total += guild.member_count - members;
+                }
 
-            if guild.member_count > members {
-                total += guild.member_count - members;
-            }
-        }
+                true
+            })
+            .await;
 
         total
     }
@@ -330,7 +332,7 @@ impl Cache {
     /// #[serenity::async_trait]
     /// impl EventHandler for Handler {
     ///     async fn ready(&self, context: Context, _: Ready) {
-    ///         let guilds = context.cache.guilds().len();
+    ///         let guilds = context.cache.guilds().await.len();
     ///
     ///         println!("Guilds in the Cache: {}", guilds);
     ///     }
@@ -339,9 +341,9 @@ impl Cache {
     ///
     /// [`Context`]: crate::client::Context
     /// [`Shard`]: crate::gateway::Shard
-    pub fn guilds(&self) -> Vec<GuildId> {
+    pub async fn guilds(&self) -> Vec<GuildId> {
         let chain = self.unavailable_guilds.clone().into_iter();
-        self.guilds.iter().map(|i| *i.key()).chain(chain).collect()
+        self.backend.guild_ids().await.into_iter().chain(chain).collect()
     }
 
     /// Retrieves a [`Channel`] from the cache based on the given Id.
@@ -354,13 +356,12 @@ impl Cache {
     /// - [`GuildChannel`]: [`Self::guild_channel`] or [`Self::guild_channels`]
     /// - [`PrivateChannel`]: [`Self::private_channel`] or [`Self::private_channels`]
     #[inline]
-    pub fn channel<C: Into<ChannelId>>(&self, id: C) -> Option<Channel> {
-        self._channel(id.into())
+    pub async fn channel<C: Into<ChannelId>>(&self, id: C) -> Option<Channel> {
+        self._channel(id.into()).await
     }
 
-    fn _channel(&self, id: ChannelId) -> Option<Channel> {
-        if let Some(channel) = self.channels.get(&id) {
-            let channel = channel.clone();
+    async fn _channel(&self, id: ChannelId) -> Option<Channel> {
+        if let Some(channel) = self.backend.channel(id).await {
             return Some(Channel::Guild(channel));
         }
 
@@ -383,22 +384,30 @@ impl Cache {
     /// iterator of a given channel.
     ///
     /// ```rust,no_run
+    /// # async fn run() {
     /// # let cache: serenity::cache::Cache = todo!();
     /// // Find all messages by user ID 8 in channel ID 7
-    /// let messages_by_user = cache.channel_messages_field(7, |msgs| {
-    ///     msgs.filter_map(|m| if m.author.id == 8 { Some(m.clone()) } else { None })
-    ///         .collect::<Vec<_>>()
-    /// });
+    /// let messages_by_user = cache
+    ///     .channel_messages_field(7, |msgs| {
+    ///         msgs.filter_map(|m| if m.author.id == 8 { Some(m.clone()) } else { None })
+    ///             .collect::<Vec<_>>()
+    ///     })
+    ///     .await;
+    /// # }
     /// ```
-    pub fn channel_messages_field<T>(
+    pub async fn channel_messages_field<T>(
         &self,
         channel_id: impl Into<ChannelId>,
-        selector: impl FnOnce(MessageIterator<'_, RandomState>) -> T,
-    ) -> Option<T> {
-        let msg = self.messages.get(&channel_id.into())?;
-        let message_iter = MessageIterator(msg.iter());
-
-        Some(selector(message_iter))
+        selector: impl FnOnce(MessageIterator<'_>) -> T + Send,
+    ) -> Option<T>
+    where
+        T: Send,
+    {
+        self.backend
+            .read_channel_messages_with(channel_id.into(), |messages| {
+                selector(MessageIterator(messages.iter()))
+            })
+            .await
     }
 
     /// Clones an entire guild from the cache based on the given `id`.
@@ -413,19 +422,21 @@ impl Cache {
     /// ```rust,no_run
     /// # use serenity::cache::Cache;
     /// #
+    /// # async fn run() {
     /// # let cache = Cache::default();
     /// // assuming the cache is in scope, e.g. via `Context`
-    /// if let Some(guild) = cache.guild(7) {
+    /// if let Some(guild) = cache.guild(7).await {
     ///     println!("Guild name: {}", guild.name);
     /// }
+    /// # }
     /// ```
     #[inline]
-    pub fn guild<G: Into<GuildId>>(&self, id: G) -> Option<Guild> {
-        self._guild(id.into())
+    pub async fn guild<G: Into<GuildId>>(&self, id: G) -> Option<Guild> {
+        self._guild(id.into()).await
     }
 
-    fn _guild(&self, id: GuildId) -> Option<Guild> {
-        self.guilds.get(&id).map(|i| i.clone())
+    async fn _guild(&self, id: GuildId) -> Option<Guild> {
+        self.backend.guild(id).await
     }
 
     /// This method allows to select a field of the guild instead of
@@ -435,35 +446,39 @@ impl Cache {
     /// ```rust,no_run
     /// # use serenity::cache::Cache;
     /// #
-    /// # fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
     /// # let cache = Cache::default();
     /// // We clone only the `len()` returned `usize` instead of the entire guild or the channels.
-    /// if let Some(channel_len) = cache.guild_field(7, |guild| guild.channels.len()) {
+    /// if let Some(channel_len) = cache.guild_field(7, |guild| guild.channels.len()).await {
     ///     println!("Guild channels count: {}", channel_len);
     /// }
     /// #   Ok(())
     /// # }
     /// ```
     #[inline]
-    pub fn guild_field<Ret, Fun>(&self, id: impl Into<GuildId>, field_selector: Fun) -> Option<Ret>
+    pub async fn guild_field<Ret, Fun>(
+        &self,
+        id: impl Into<GuildId>,
+        field_selector: Fun,
+    ) -> Option<Ret>
     where
-        Fun: FnOnce(&Guild) -> Ret,
+        Ret: Send,
+        Fun: FnOnce(&Guild) -> Ret + Send,
     {
-        self._guild_field(id.into(), field_selector)
+        self._guild_field(id.into(), field_selector).await
     }
 
-    fn _guild_field<Ret, Fun>(&self, id: GuildId, field_accessor: Fun) -> Option<Ret>
+    async fn _guild_field<Ret, Fun>(&self, id: GuildId, field_accessor: Fun) -> Option<Ret>
     where
-        Fun: FnOnce(&Guild) -> Ret,
+        Ret: Send,
+        Fun: FnOnce(&Guild) -> Ret + Send,
     {
-        let guild = self.guilds.get(&id)?;
-
-        Some(field_accessor(&guild))
+        self.backend.read_guild_with(id, field_accessor).await
     }
 
     /// Returns the number of cached guilds.
-    pub fn guild_count(&self) -> usize {
-        self.guilds.len()
+    pub async fn guild_count(&self) -> usize {
+        self.backend.guild_count().await
     }
 
     /// Retrieves a reference to a [`Guild`]'s channel. Unlike [`Self::channel`],
@@ -486,7 +501,7 @@ impl Cache {
     /// #[serenity::async_trait]
     /// impl EventHandler for Handler {
     ///     async fn message(&self, context: Context, message: Message) {
-    ///         let channel = match context.cache.guild_channel(message.channel_id) {
+    ///         let channel = match context.cache.guild_channel(message.channel_id).await {
     ///             Some(channel) => channel,
     ///             None => {
     ///                 let result = message
@@ -515,12 +530,12 @@ impl Cache {
     ///
     /// [`EventHandler::message`]: crate::client::EventHandler::message
     #[inline]
-    pub fn guild_channel<C: Into<ChannelId>>(&self, id: C) -> Option<GuildChannel> {
-        self._guild_channel(id.into())
+    pub async fn guild_channel<C: Into<ChannelId>>(&self, id: C) -> Option<GuildChannel> {
+        self._guild_channel(id.into()).await
     }
 
-    fn _guild_channel(&self, id: ChannelId) -> Option<GuildChannel> {
-        self.channels.get(&id).map(|i| i.clone())
+    async fn _guild_channel(&self, id: ChannelId) -> Option<GuildChannel> {
+        self.backend.channel(id).await
     }
 
     /// This method allows to only clone a field of the guild channel instead of
@@ -530,34 +545,38 @@ impl Cache {
     /// ```rust,no_run
     /// # use serenity::cache::Cache;
     /// #
-    /// # fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
     /// # let cache = Cache::default();
     /// // We clone only the `name` instead of the entire channel.
-    /// if let Some(channel_name) = cache.guild_channel_field(7, |channel| channel.name.clone()) {
+    /// if let Some(channel_name) = cache.guild_channel_field(7, |channel| channel.name.clone()).await {
     ///     println!("Guild channel name: {}", channel_name);
     /// }
     /// #   Ok(())
     /// # }
     /// ```
     #[inline]
-    pub fn guild_channel_field<Ret, Fun>(
+    pub async fn guild_channel_field<Ret, Fun>(
         &self,
         id: impl Into<ChannelId>,
         field_selector: Fun,
     ) -> Option<Ret>
     where
-        Fun: FnOnce(&GuildChannel) -> Ret,
+        Ret: Send,
+        Fun: FnOnce(&GuildChannel) -> Ret + Send,
     {
-        self._guild_channel_field(id.into(), field_selector)
+        self._guild_channel_field(id.into(), field_selector).await
     }
 
-    fn _guild_channel_field<Ret, Fun>(&self, id: ChannelId, field_selector: Fun) -> Option<Ret>
+    async fn _guild_channel_field<Ret, Fun>(
+        &self,
+        id: ChannelId,
+        field_selector: Fun,
+    ) -> Option<Ret>
     where
-        Fun: FnOnce(&GuildChannel) -> Ret,
+        Ret: Send,
+        Fun: FnOnce(&GuildChannel) -> Ret + Send,
     {
-        let channel = self.channels.get(&id)?;
-
-        Some(field_selector(&channel))
+        self.backend.read_channel_with(id, field_selector).await
     }
 
     /// Retrieves a [`Guild`]'s member from the cache based on the guild's and
@@ -579,7 +598,7 @@ impl Cache {
     /// # async fn run(http: Http, cache: Cache, message: Message) {
     /// #
     /// let member = {
-    ///     let channel = match cache.guild_channel(message.channel_id) {
+    ///     let channel = match cache.guild_channel(message.channel_id).await {
     ///         Some(channel) => channel,
     ///         None => {
     ///             if let Err(why) = message.channel_id.say(http, "Error finding channel data").await {
@@ -589,7 +608,7 @@ impl Cache {
     ///         },
     ///     };
     ///
-    ///     match cache.member(channel.guild_id, message.author.id) {
+    ///     match cache.member(channel.guild_id, message.author.id).await {
     ///         Some(member) => member,
     ///         None => {
     ///             if let Err(why) = message.channel_id.say(&http, "Error finding member data").await {
@@ -611,19 +630,16 @@ impl Cache {
     /// [`EventHandler::message`]: crate::client::EventHandler::message
     /// [`members`]: crate::model::guild::Guild::members
     #[inline]
-    pub fn member<G, U>(&self, guild_id: G, user_id: U) -> Option<Member>
+    pub async fn member<G, U>(&self, guild_id: G, user_id: U) -> Option<Member>
     where
         G: Into<GuildId>,
         U: Into<UserId>,
     {
-        self._member(guild_id.into(), user_id.into())
+        self._member(guild_id.into(), user_id.into()).await
     }
 
-    fn _member(&self, guild_id: GuildId, user_id: UserId) -> Option<Member> {
-        match self.guilds.get(&guild_id) {
-            Some(guild) => guild.members.get(&user_id).cloned(),
-            None => None,
-        }
+    async fn _member(&self, guild_id: GuildId, user_id: UserId) -> Option<Member> {
+        self.backend.member(guild_id, user_id).await
     }
 
     /// This method allows to only clone a field of a member instead of
@@ -633,50 +649,49 @@ impl Cache {
     /// ```rust,no_run
     /// # use serenity::cache::Cache;
     /// #
-    /// # fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
     /// # let cache = Cache::default();
     /// // We clone only the `name` instead of the entire channel.
-    /// if let Some(Some(nick)) = cache.member_field(7, 8, |member| member.nick.clone()) {
+    /// if let Some(Some(nick)) = cache.member_field(7, 8, |member| member.nick.clone()).await {
     ///     println!("Member's nick: {}", nick);
     /// }
     /// #   Ok(())
     /// # }
     /// ```
     #[inline]
-    pub fn member_field<Ret, Fun>(
+    pub async fn member_field<Ret, Fun>(
         &self,
         guild_id: impl Into<GuildId>,
         user_id: impl Into<UserId>,
         field_selector: Fun,
     ) -> Option<Ret>
     where
-        Fun: FnOnce(&Member) -> Ret,
+        Ret: Send,
+        Fun: FnOnce(&Member) -> Ret + Send,
     {
-        self._member_field(guild_id.into(), user_id.into(), field_selector)
+        self._member_field(guild_id.into(), user_id.into(), field_selector).await
     }
 
-    fn _member_field<Ret, Fun>(
+    async fn _member_field<Ret, Fun>(
         &self,
         guild_id: GuildId,
         user_id: UserId,
         field_selector: Fun,
     ) -> Option<Ret>
     where
-        Fun: FnOnce(&Member) -> Ret,
+        Ret: Send,
+        Fun: FnOnce(&Member) -> Ret + Send,
     {
-        let guild = self.guilds.get(&guild_id)?;
-        let member = guild.members.get(&user_id)?;
-
-        Some(field_selector(member))
+        self.backend.read_member_with(guild_id, user_id, field_selector).await
     }
 
     #[inline]
-    pub fn guild_roles(&self, guild_id: impl Into<GuildId>) -> Option<HashMap<RoleId, Role>> {
-        self._guild_roles(guild_id.into())
+    pub async fn guild_roles(&self, guild_id: impl Into<GuildId>) -> Option<HashMap<RoleId, Role>> {
+        self._guild_roles(guild_id.into()).await
     }
 
-    fn _guild_roles(&self, guild_id: GuildId) -> Option<HashMap<RoleId, Role>> {
-        self.guilds.get(&guild_id).map(|g| g.roles.clone())
+    async fn _guild_roles(&self, guild_id: GuildId) -> Option<HashMap<RoleId, Role>> {
+        self.backend.roles(guild_id).await
     }
 
     /// This method clones and returns all unavailable guilds.
@@ -687,49 +702,56 @@ impl Cache {
 
     /// This method returns all channels from a guild of with the given `guild_id`.
     #[inline]
-    pub fn guild_channels(
+    pub async fn guild_channels(
         &self,
         guild_id: impl Into<GuildId>,
     ) -> Option<DashMap<ChannelId, GuildChannel>> {
-        self._guild_channels(guild_id.into())
+        self._guild_channels(guild_id.into()).await
     }
 
-    fn _guild_channels(&self, guild_id: GuildId) -> Option<DashMap<ChannelId, GuildChannel>> {
-        self.guilds.get(&guild_id).map(|g| {
-            g.channels
-                .iter()
-                .filter_map(|c| match c.1 {
-                    Channel::Guild(channel) => Some((channel.id, channel.clone())),
-                    _ => None,
-                })
-                .collect()
-        })
+    async fn _guild_channels(&self, guild_id: GuildId) -> Option<DashMap<ChannelId, GuildChannel>> {
+        self.backend
+            .read_guild_with(guild_id, |g| {
+                g.channels
+                    .iter()
+                    .filter_map(|c| match c.1 {
+                        Channel::Guild(channel) => Some((channel.id, channel.clone())),
+                        _ => None,
+                    })
+                    .collect()
+            })
+            .await
     }
 
     /// Returns the number of guild channels in the cache.
-    pub fn guild_channel_count(&self) -> usize {
-        self.channels.len()
+    pub async fn guild_channel_count(&self) -> usize {
+        self.backend.channel_count().await
     }
 
     /// This method returns all categories from a guild of with the given `guild_id`.
     #[inline]
-    pub fn guild_categories(
+    pub async fn guild_categories(
         &self,
         guild_id: impl Into<GuildId>,
     ) -> Option<DashMap<ChannelId, ChannelCategory>> {
-        self._guild_categories(guild_id.into())
+        self._guild_categories(guild_id.into()).await
     }
 
-    fn _guild_categories(&self, guild_id: GuildId) -> Option<DashMap<ChannelId, ChannelCategory>> {
-        self.guilds.get(&guild_id).map(|g| {
-            g.channels
-                .iter()
-                .filter_map(|c| match c.1 {
-                    Channel::Category(category) => Some((category.id, category.clone())),
-                    _ => None,
-                })
-                .collect()
-        })
+    async fn _guild_categories(
+        &self,
+        guild_id: GuildId,
+    ) -> Option<DashMap<ChannelId, ChannelCategory>> {
+        self.backend
+            .read_guild_with(guild_id, |g| {
+                g.channels
+                    .iter()
+                    .filter_map(|c| match c.1 {
+                        Channel::Category(category) => Some((category.id, category.clone())),
+                        _ => None,
+                    })
+                    .collect()
+            })
+            .await
     }
 
     /// Returns the number of shards.
@@ -752,9 +774,9 @@ impl Cache {
     /// # use serenity::cache::Cache;
     /// # use serenity::model::channel::Message;
     /// #
-    /// # fn run(cache: Cache, message: Message) {
+    /// # async fn run(cache: Cache, message: Message) {
     /// #
-    /// match cache.message(message.channel_id, message.id) {
+    /// match cache.message(message.channel_id, message.id).await {
     ///     Some(m) => assert_eq!(message.content, m.content),
     ///     None => println!("No message found in cache."),
     /// };
@@ -763,18 +785,16 @@ impl Cache {
     ///
     /// [`EventHandler::message`]: crate::client::EventHandler::message
     #[inline]
-    pub fn message<C, M>(&self, channel_id: C, message_id: M) -> Option<Message>
+    pub async fn message<C, M>(&self, channel_id: C, message_id: M) -> Option<Message>
     where
         C: Into<ChannelId>,
         M: Into<MessageId>,
     {
-        self._message(channel_id.into(), message_id.into())
+        self._message(channel_id.into(), message_id.into()).await
     }
 
-    fn _message(&self, channel_id: ChannelId, message_id: MessageId) -> Option<Message> {
-        self.messages
-            .get(&channel_id)
-            .and_then(|messages| messages.get(&message_id).map(|i| i.clone()))
+    async fn _message(&self, channel_id: ChannelId, message_id: MessageId) -> Option<Message> {
+        self.backend.message(channel_id, message_id).await
     }
 
     /// Retrieves a [`PrivateChannel`] from the cache's [`Self::private_channels`]
@@ -822,23 +842,70 @@ impl Cache {
     /// ```rust,no_run
     /// # use serenity::cache::Cache;
     /// #
+    /// # async fn run() {
     /// # let cache = Cache::default();
     /// // assuming the cache is in scope, e.g. via `Context`
-    /// if let Some(role) = cache.role(7, 77) {
+    /// if let Some(role) = cache.role(7, 77).await {
     ///     println!("Role with Id 77 is called {}", role.name);
     /// }
+    /// # }
     /// ```
     #[inline]
-    pub fn role<G, R>(&self, guild_id: G, role_id: R) -> Option<Role>
+    pub async fn role<G, R>(&self, guild_id: G, role_id: R) -> Option<Role>
     where
         G: Into<GuildId>,
         R: Into<RoleId>,
     {
-        self._role(guild_id.into(), role_id.into())
+        self._role(guild_id.into(), role_id.into()).await
     }
 
-    fn _role(&self, guild_id: GuildId, role_id: RoleId) -> Option<Role> {
-        self.guilds.get(&guild_id).and_then(|g| g.roles.get(&role_id).cloned())
+    async fn _role(&self, guild_id: GuildId, role_id: RoleId) -> Option<Role> {
+        self.backend.role(guild_id, role_id).await
+    }
+
+    /// Counts the entries in the cache and approximates their memory usage.
+    ///
+    /// This iterates over all guilds, so avoid calling it too often on large
+    /// caches.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serenity::cache::Cache;
+    ///
+    /// # async fn run() {
+    /// let cache = Cache::new();
+    /// let stats = cache.stats().await;
+    ///
+    /// println!("{} users, ~{} bytes", stats.users, stats.approximate_size);
+    /// # }
+    /// ```
+    pub async fn stats(&self) -> CacheStats {
+        let mut stats = CacheStats {
+            guilds: self.backend.guild_count().await,
+            channels: self.backend.channel_count().await
+                + self.categories.len()
+                + self.private_channels.len(),
+            users: self.backend.user_count().await,
+            presences: self.backend.presence_count().await,
+            messages: self.backend.message_count().await,
+            ..CacheStats::default()
+        };
+
+        self.backend
+            .for_each_guild(&mut |guild| {
+                stats.members += guild.members.len();
+                stats.roles += guild.roles.len();
+                stats.emojis += guild.emojis.len();
+                stats.presences += guild.presences.len();
+
+                true
+            })
+            .await;
+
+        stats.compute_approximate_size();
+
+        stats
     }
 
     /// Returns the settings.
@@ -881,41 +948,103 @@ impl Cache {
     /// #
     /// # #[command]
     /// # async fn test(context: &Context) -> CommandResult {
-    /// if let Some(user) = context.cache.user(7) {
+    /// if let Some(user) = context.cache.user(7).await {
     ///     println!("User with Id 7 is currently named {}", user.name);
     /// }
     /// #     Ok(())
     /// # }
     /// ```
     #[inline]
-    pub fn user<U: Into<UserId>>(&self, user_id: U) -> Option<User> {
-        self._user(user_id.into())
+    pub async fn user<U: Into<UserId>>(&self, user_id: U) -> Option<User> {
+        self._user(user_id.into()).await
     }
 
     #[cfg(feature = "temp_cache")]
-    fn _user(&self, user_id: UserId) -> Option<User> {
-        if let Some(user) = self.users.get(&user_id) {
-            Some(user.clone())
-        } else {
-            self.temp_users.get(&user_id)
-        }
+    async fn _user(&self, user_id: UserId) -> Option<User> {
+        self.backend.user(user_id).await.or_else(|| self.temp_users.get(&user_id))
+    }
+
+    #[cfg(not(feature = "temp_cache"))]
+    async fn _user(&self, user_id: UserId) -> Option<User> {
+        self.backend.user(user_id).await
+    }
+
+    /// This method allows to only clone a field of a user instead of the
+    /// entire user by providing a `field_selector`-closure picking what you
+    /// want to clone.
+    ///
+    /// ```rust,no_run
+    /// # async fn run() {
+    /// # let cache: serenity::cache::Cache = todo!();
+    /// if let Some(name) = cache.user_field(7, |user| user.name.clone()).await {
+    ///     println!("User with Id 7 is currently named {}", name);
+    /// }
+    /// # }
+    /// ```
+    #[inline]
+    pub async fn user_field<Ret, Fun>(
+        &self,
+        user_id: impl Into<UserId>,
+        field_selector: Fun,
+    ) -> Option<Ret>
+    where
+        Ret: Send,
+        Fun: FnOnce(&User) -> Ret + Send,
+    {
+        self._user_field(user_id.into(), field_selector).await
+    }
+
+    #[cfg(feature = "temp_cache")]
+    async fn _user_field<Ret, Fun>(&self, user_id: UserId, field_selector: Fun) -> Option<Ret>
+    where
+        Ret: Send,
+        Fun: FnOnce(&User) -> Ret + Send,
+    {
+        let mut field_selector = Some(field_selector);
+
+        self.backend
+            .read_user_with(user_id, |user| field_selector.take().map(|f| f(user)))
+            .await
+            .flatten()
+            .or_else(|| {
+                let user = self.temp_users.get(&user_id)?;
+
+                field_selector.take().map(|f| f(&user))
+            })
     }
 
     #[cfg(not(feature = "temp_cache"))]
-    fn _user(&self, user_id: UserId) -> Option<User> {
-        self.users.get(&user_id).map(|u| u.clone())
+    async fn _user_field<Ret, Fun>(&self, user_id: UserId, field_selector: Fun) -> Option<Ret>
+    where
+        Ret: Send,
+        Fun: FnOnce(&User) -> Ret + Send,
+    {
+        self.backend.read_user_with(user_id, field_selector).await
     }
 
     /// Clones all users and returns them.
+    ///
+    /// To only read a single user, use [`Self::user`] or [`Self::user_field`]
+    /// instead, which do not clone the other users.
     #[inline]
-    pub fn users(&self) -> DashMap<UserId, User> {
-        self.users.clone()
+    pub async fn users(&self) -> DashMap<UserId, User> {
+        let users = DashMap::with_capacity(self.backend.user_count().await);
+
+        self.backend
+            .for_each_user(&mut |user| {
+                users.insert(user.id, user.clone());
+
+                true
+            })
+            .await;
+
+        users
     }
 
     /// Returns the amount of cached users.
     #[inline]
-    pub fn user_count(&self) -> usize {
-        self.users.len()
+    pub async fn user_count(&self) -> usize {
+        self.backend.user_count().await
     }
 
     /// Clones a category matching the `channel_id` and returns it.
@@ -994,18 +1123,14 @@ impl Cache {
     ///
     /// [`CacheUpdate` examples]: CacheUpdate#examples
     #[instrument(skip(self, e))]
-    pub fn update<E: CacheUpdate>(&self, e: &mut E) -> Option<E::Output> {
-        e.update(self)
+    pub async fn update<E: CacheUpdate>(&self, e: &mut E) -> Option<E::Output> {
+        e.update(self).await
     }
 
-    pub(crate) fn update_user_entry(&self, user: &User) {
-        match self.users.entry(user.id) {
-            Entry::Vacant(e) => {
-                e.insert(user.clone());
-            },
-            Entry::Occupied(mut e) => {
-                e.get_mut().clone_from(user);
-            },
+    pub(crate) async fn update_user_entry(&self, user: &User) {
+        if self.settings.read().cache_users {
+            self.backend.insert_user(user.clone()).await;
+            self.seen_user(user.id).await;
         }
     }
 }
@@ -1013,22 +1138,19 @@ impl Cache {
 impl Default for Cache {
     fn default() -> Cache {
         Cache {
-            channels: DashMap::default(),
+            backend: Arc::new(InMemoryCacheBackend::new()),
             #[cfg(feature = "temp_cache")]
             temp_channels: DashCache::builder().time_to_live(Duration::from_secs(60 * 60)).build(),
             categories: DashMap::default(),
-            guilds: DashMap::default(),
-            messages: DashMap::default(),
-            presences: DashMap::default(),
             private_channels: DashMap::with_capacity(128),
             settings: RwLock::new(Settings::default()),
+            eviction: Mutex::new(Eviction::default()),
+            subscribers: Subscribers::default(),
             shard_count: RwLock::new(1),
             unavailable_guilds: DashSet::default(),
             user: RwLock::new(CurrentUser::default()),
-            users: DashMap::default(),
             #[cfg(feature = "temp_cache")]
             temp_users: DashCache::builder().time_to_live(Duration::from_secs(60 * 60)).build(),
-            message_queue: DashMap::default(),
         }
     }
 }
@@ -1037,12 +1159,27 @@ impl Default for Cache {
 mod test {
     use std::collections::HashMap;
 
-    use crate::cache::{Cache, CacheUpdate, Settings};
-    use crate::json::from_number;
+    use futures::{FutureExt, StreamExt};
+
+    use crate::cache::{
+        Cache,
+        CacheBackend,
+        CacheChange,
+        CacheError,
+        CacheUpdate,
+        Change,
+        EvictionPolicy,
+        InMemoryCacheBackend,
+        MemberQuery,
+        PermissionSource,
+        Settings,
+    };
+    use crate::json::{from_number, from_value, json};
     use crate::model::prelude::*;
+    use crate::utils::Colour;
 
-    #[test]
-    fn test_cache_messages() {
+    #[tokio::test]
+    async fn test_cache_messages() {
         let mut settings = Settings::new();
         settings.max_messages(2);
         let cache = Cache::new_with_settings(settings);
@@ -1095,31 +1232,44 @@ mod test {
         };
 
         // Check that the channel cache doesn't exist.
-        assert!(!cache.messages.contains_key(&event.message.channel_id));
+        assert!(cache.backend.channel_messages(event.message.channel_id).await.is_none());
         // Add first message, none because message ID 2 doesn't already exist.
-        assert!(event.update(&cache).is_none());
+        assert!(event.update(&cache).await.is_none());
         // None, it only returns the oldest message if the cache was already full.
-        assert!(event.update(&cache).is_none());
+        assert!(event.update(&cache).await.is_none());
         // Assert there's only 1 message in the channel's message cache.
-        assert_eq!(cache.messages.get(&event.message.channel_id).unwrap().len(), 1);
+        assert_eq!(
+            cache.backend.channel_messages(event.message.channel_id).await.unwrap().len(),
+            1
+        );
 
         // Add a second message, assert that channel message cache length is 2.
         event.message.id = MessageId(4);
-        assert!(event.update(&cache).is_none());
-        assert_eq!(cache.messages.get(&event.message.channel_id).unwrap().len(), 2);
+        assert!(event.update(&cache).await.is_none());
+        assert_eq!(
+            cache.backend.channel_messages(event.message.channel_id).await.unwrap().len(),
+            2
+        );
 
         // Add a third message, the first should now be removed.
         event.message.id = MessageId(5);
-        assert!(event.update(&cache).is_some());
+        assert!(event.update(&cache).await.is_some());
 
         {
-            let channel = cache.messages.get(&event.message.channel_id).unwrap();
+            let channel = cache.backend.channel_messages(event.message.channel_id).await.unwrap();
 
             assert_eq!(channel.len(), 2);
             // Check that the first message is now removed.
-            assert!(!channel.contains_key(&MessageId(3)));
+            assert!(!channel.iter().any(|m| m.id == MessageId(3)));
         }
 
+        let ids = cache
+            .channel_messages_field(event.message.channel_id, |messages| {
+                messages.map(|m| m.id).collect::<Vec<_>>()
+            })
+            .await;
+        assert_eq!(ids, Some(vec![MessageId(4), MessageId(5)]));
+
         let channel = Channel::Guild(GuildChannel {
             id: event.message.channel_id,
             bitrate: None,
@@ -1156,8 +1306,8 @@ mod test {
         let mut delete = ChannelDeleteEvent {
             channel: channel.clone(),
         };
-        assert!(cache.update(&mut delete).is_none());
-        assert!(!cache.messages.contains_key(&delete.channel.id()));
+        assert!(cache.update(&mut delete).await.is_none());
+        assert!(cache.backend.channel_messages(delete.channel.id()).await.is_none());
 
         // Test deletion of a guild channel's message cache when a GuildDeleteEvent
         // is received.
@@ -1210,13 +1360,14 @@ mod test {
                     widget_enabled: Some(false),
                     widget_channel_id: None,
                     stage_instances: vec![],
+                    scheduled_events: vec![],
                     threads: vec![],
                     stickers: HashMap::new(),
                 },
             }
         };
-        assert!(cache.update(&mut guild_create).is_none());
-        assert!(cache.update(&mut event).is_none());
+        assert!(cache.update(&mut guild_create).await.is_none());
+        assert!(cache.update(&mut event).await.is_none());
 
         let mut guild_delete = GuildDeleteEvent {
             guild: UnavailableGuild {
@@ -1227,9 +1378,956 @@ mod test {
 
         // The guild existed in the cache, so the cache's guild is returned by the
         // update.
-        assert!(cache.update(&mut guild_delete).is_some());
+        assert!(cache.update(&mut guild_delete).await.is_some());
 
         // Assert that the channel's message cache no longer exists.
-        assert!(!cache.messages.contains_key(&ChannelId(2)));
+        assert!(cache.backend.channel_messages(ChannelId(2)).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_cache_backend() {
+        let backend = InMemoryCacheBackend::new();
+        let mut user = User::default();
+        user.id = UserId(7);
+        backend.insert_user(user).await;
+
+        let mut settings = Settings::new();
+        settings.backend(backend);
+        let cache = Cache::new_with_settings(settings);
+
+        assert_eq!(cache.user(UserId(7)).await.map(|u| u.id), Some(UserId(7)));
+        assert_eq!(cache.user_field(UserId(7), |u| u.id).await, Some(UserId(7)));
+        assert_eq!(cache.user_count().await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_cache_disabled_resources() {
+        let mut settings = Settings::new();
+        settings.cache_users(false).cache_presences(false);
+        let cache = Cache::new_with_settings(settings);
+
+        let mut event = PresenceUpdateEvent {
+            presence: Presence {
+                activities: vec![],
+                client_status: None,
+                guild_id: None,
+                status: OnlineStatus::Online,
+                user: PresenceUser {
+                    id: UserId(3),
+                    avatar: None,
+                    bot: Some(false),
+                    discriminator: Some(1),
+                    email: None,
+                    mfa_enabled: None,
+                    name: Some("user".to_string()),
+                    verified: None,
+                    public_flags: None,
+                },
+            },
+        };
+        cache.update(&mut event).await;
+
+        assert!(cache.user(UserId(3)).await.is_none());
+        assert!(cache.backend.presence(UserId(3)).await.is_none());
+
+        cache.settings.write().cache_presences = true;
+        cache.update(&mut event).await;
+
+        assert!(cache.user(UserId(3)).await.is_none());
+        assert!(cache.backend.presence(UserId(3)).await.is_some());
+
+        let mut settings = Settings::new();
+        settings.cache_channels(false);
+        let cache = Cache::new_with_settings(settings);
+        let thread = channel(ChannelId(5), Some(ChannelId(4)), ChannelType::PublicThread);
+
+        let mut guild = guild(GuildId(1));
+        guild.threads.push(thread.clone());
+        cache
+            .update(&mut GuildCreateEvent {
+                guild,
+            })
+            .await;
+        cache
+            .update(&mut ThreadCreateEvent {
+                thread: thread.clone(),
+            })
+            .await;
+        cache
+            .update(&mut ThreadUpdateEvent {
+                thread: thread.clone(),
+            })
+            .await;
+        cache
+            .update(&mut ThreadListSyncEvent {
+                guild_id: GuildId(1),
+                channels_id: vec![],
+                threads: vec![thread],
+                members: vec![],
+            })
+            .await;
+
+        assert!(cache.guild(GuildId(1)).await.unwrap().threads.is_empty());
+    }
+
+    fn guild(id: GuildId) -> Guild {
+        Guild {
+            id,
+            afk_channel_id: None,
+            afk_timeout: 0,
+            application_id: None,
+            default_message_notifications: DefaultMessageNotificationLevel::All,
+            emojis: HashMap::new(),
+            explicit_content_filter: ExplicitContentFilter::None,
+            features: vec![],
+            icon: None,
+            joined_at: Timestamp::now(),
+            large: false,
+            member_count: 0,
+            members: HashMap::new(),
+            mfa_level: MfaLevel::None,
+            name: String::new(),
+            owner_id: UserId(100),
+            presences: HashMap::new(),
+            roles: HashMap::new(),
+            splash: None,
+            discovery_splash: None,
+            system_channel_id: None,
+            system_channel_flags: SystemChannelFlags::default(),
+            rules_channel_id: None,
+            public_updates_channel_id: None,
+            verification_level: VerificationLevel::Low,
+            voice_states: HashMap::new(),
+            description: None,
+            premium_tier: PremiumTier::Tier0,
+            channels: HashMap::new(),
+            premium_subscription_count: 0,
+            banner: None,
+            vanity_url_code: None,
+            preferred_locale: "en-US".to_string(),
+            welcome_screen: None,
+            approximate_member_count: None,
+            approximate_presence_count: None,
+            nsfw_level: NsfwLevel::Default,
+            max_video_channel_users: None,
+            max_presences: None,
+            max_members: None,
+            widget_enabled: None,
+            widget_channel_id: None,
+            stage_instances: vec![],
+            scheduled_events: vec![],
+            threads: vec![],
+            stickers: HashMap::new(),
+        }
+    }
+
+    fn member(guild_id: GuildId, user_id: UserId) -> Member {
+        let mut user = User::default();
+        user.id = user_id;
+
+        Member {
+            deaf: false,
+            guild_id,
+            joined_at: None,
+            mute: false,
+            nick: None,
+            roles: vec![],
+            user,
+            pending: false,
+            premium_since: None,
+            permissions: None,
+            avatar: None,
+            communication_disabled_until: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cache_eviction() {
+        let mut settings = Settings::new();
+        settings
+            .member_eviction(EvictionPolicy::new().max_entries(2))
+            .remove_unreferenced_users(true);
+        let cache = Cache::new_with_settings(settings);
+
+        cache
+            .update(&mut GuildCreateEvent {
+                guild: guild(GuildId(1)),
+            })
+            .await;
+
+        for id in 1..=3 {
+            cache
+                .update(&mut GuildMemberAddEvent {
+                    member: member(GuildId(1), UserId(id)),
+                })
+                .await;
+        }
+
+        // The least recently seen member was evicted, along with its user.
+        assert!(cache.member(GuildId(1), UserId(1)).await.is_none());
+        assert!(cache.user(UserId(1)).await.is_none());
+        assert!(cache.member(GuildId(1), UserId(3)).await.is_some());
+
+        let stats = cache.stats().await;
+        assert_eq!((stats.guilds, stats.members, stats.users), (1, 2, 2));
+        assert!(stats.approximate_size > 0);
+
+        cache
+            .update(&mut GuildMemberRemoveEvent {
+                guild_id: GuildId(1),
+                user: member(GuildId(1), UserId(2)).user,
+            })
+            .await;
+        assert!(cache.user(UserId(2)).await.is_none());
+        assert!(cache.user(UserId(3)).await.is_some());
+
+        cache
+            .update(&mut GuildDeleteEvent {
+                guild: UnavailableGuild {
+                    id: GuildId(1),
+                    unavailable: false,
+                },
+            })
+            .await;
+        assert_eq!(cache.stats().await.users, 0);
+    }
+
+    #[tokio::test]
+    async fn test_cache_snapshot() {
+        let mut settings = Settings::new();
+        settings.max_messages(10);
+        let cache = Cache::new_with_settings(settings.clone());
+        let mut guild = guild(GuildId(1));
+        guild.members.insert(UserId(2), member(GuildId(1), UserId(2)));
+        let text = channel(ChannelId(2), None, ChannelType::Text);
+        guild.channels.insert(text.id, Channel::Guild(text));
+        cache
+            .update(&mut GuildCreateEvent {
+                guild,
+            })
+            .await;
+        cache
+            .update(&mut ChannelCreateEvent {
+                channel: from_value(json!({
+                    "id": "5",
+                    "type": 1,
+                    "recipients": [
+                        {"id": "4", "username": "friend", "discriminator": "0001", "avatar": null},
+                    ],
+                }))
+                .unwrap(),
+            })
+            .await;
+        for id in 1..=2 {
+            cache
+                .update(&mut MessageCreateEvent {
+                    message: message(MessageId(id)),
+                })
+                .await;
+        }
+
+        let mut snapshot = Vec::new();
+        cache.snapshot(&mut snapshot).await.unwrap();
+
+        let restored = Cache::new_with_settings(settings);
+        restored.restore(&snapshot[..]).await.unwrap();
+
+        assert_eq!(restored.stats().await, cache.stats().await);
+        assert!(restored.member(GuildId(1), UserId(2)).await.is_some());
+        assert!(restored.user(UserId(2)).await.is_some());
+        assert_eq!(restored.private_channel(ChannelId(5)).unwrap().recipient.name, "friend");
+        assert_eq!(restored.user(UserId(4)).await.unwrap().name, "friend");
+        // The messages keep their order.
+        assert_eq!(
+            restored
+                .channel_messages_field(ChannelId(2), |messages| {
+                    messages.map(|message| message.id).collect::<Vec<_>>()
+                })
+                .await,
+            Some(vec![MessageId(1), MessageId(2)])
+        );
+
+        snapshot[..14].copy_from_slice(b"not-a-snapshot");
+        assert!(matches!(
+            Cache::new().restore(&snapshot[..]).await,
+            Err(crate::Error::Cache(CacheError::InvalidSnapshot))
+        ));
+        assert!(matches!(
+            Cache::new().restore(&b"serenity-cache 0\n{}"[..]).await,
+            Err(crate::Error::Cache(CacheError::UnsupportedSnapshotVersion(0)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_cache_snapshot_eviction() {
+        let cache = Cache::new();
+        let mut guild = guild(GuildId(1));
+        for id in 1..=2 {
+            guild.members.insert(UserId(id), member(GuildId(1), UserId(id)));
+        }
+        cache
+            .update(&mut GuildCreateEvent {
+                guild,
+            })
+            .await;
+
+        let mut snapshot = Vec::new();
+        cache.snapshot(&mut snapshot).await.unwrap();
+
+        let mut settings = Settings::new();
+        settings.member_eviction(EvictionPolicy::new().max_entries(2));
+        let restored = Cache::new_with_settings(settings);
+        restored.restore(&snapshot[..]).await.unwrap();
+        assert_eq!(restored.stats().await.members, 2);
+
+        // The restored members are tracked, and evicted for newer ones.
+        restored
+            .update(&mut GuildMemberAddEvent {
+                member: member(GuildId(1), UserId(3)),
+            })
+            .await;
+        assert_eq!(restored.stats().await.members, 2);
+        assert!(restored.member(GuildId(1), UserId(3)).await.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_cache_subscribe() {
+        let cache = Cache::new();
+        let mut changes = cache.subscribe();
+
+        cache
+            .update(&mut GuildCreateEvent {
+                guild: guild(GuildId(1)),
+            })
+            .await;
+        cache
+            .update(&mut GuildMemberAddEvent {
+                member: member(GuildId(1), UserId(2)),
+            })
+            .await;
+        let mut updated = member(GuildId(1), UserId(2));
+        updated.nick = Some("nick".to_string());
+        cache
+            .update(&mut GuildMemberAddEvent {
+                member: updated,
+            })
+            .await;
+        cache
+            .update(&mut GuildMemberRemoveEvent {
+                guild_id: GuildId(1),
+                user: member(GuildId(1), UserId(2)).user,
+            })
+            .await;
+
+        let mut next = || changes.next().now_or_never().flatten();
+
+        assert!(
+            matches!(next(), Some(CacheChange::Guild(change)) if matches!(*change, Change::Insert(_)))
+        );
+        assert!(
+            matches!(next(), Some(CacheChange::Member(change)) if matches!(*change, Change::Insert(_)))
+        );
+        match next() {
+            Some(CacheChange::Member(change)) => match *change {
+                Change::Update {
+                    old,
+                    new,
+                } => assert_eq!((old.nick, new.nick), (None, Some("nick".to_string()))),
+                change => panic!("unexpected change: {:?}", change),
+            },
+            change => panic!("unexpected change: {:?}", change),
+        }
+        assert!(
+            matches!(next(), Some(CacheChange::Member(change)) if matches!(*change, Change::Remove(_)))
+        );
+        assert!(next().is_none());
+
+        drop(changes);
+        cache
+            .update(&mut GuildDeleteEvent {
+                guild: UnavailableGuild {
+                    id: GuildId(1),
+                    unavailable: false,
+                },
+            })
+            .await;
+        assert!(!cache.has_subscribers());
+    }
+
+    #[tokio::test]
+    async fn test_cache_subscribe_threads() {
+        let cache = Cache::new();
+        cache
+            .update(&mut GuildCreateEvent {
+                guild: guild(GuildId(1)),
+            })
+            .await;
+        let mut changes = cache.subscribe();
+
+        let thread = channel(ChannelId(5), Some(ChannelId(4)), ChannelType::PublicThread);
+        let other = channel(ChannelId(6), Some(ChannelId(4)), ChannelType::PublicThread);
+        let mut renamed = thread.clone();
+        renamed.name = "renamed".to_string();
+
+        cache
+            .update(&mut ThreadCreateEvent {
+                thread: thread.clone(),
+            })
+            .await;
+        cache
+            .update(&mut ThreadUpdateEvent {
+                thread: renamed.clone(),
+            })
+            .await;
+        cache
+            .update(&mut ThreadCreateEvent {
+                thread: other,
+            })
+            .await;
+        cache
+            .update(&mut ThreadListSyncEvent {
+                guild_id: GuildId(1),
+                channels_id: vec![ChannelId(4)],
+                threads: vec![thread],
+                members: vec![],
+            })
+            .await;
+        cache
+            .update(&mut ThreadDeleteEvent {
+                thread: PartialGuildChannel {
+                    id: ChannelId(5),
+                    guild_id: GuildId(1),
+                    kind: ChannelType::PublicThread,
+                    parent_id: ChannelId(4),
+                },
+            })
+            .await;
+
+        let mut next = || match changes.next().now_or_never().flatten() {
+            Some(CacheChange::Channel(change)) => {
+                let id = |channel: &Channel| channel.id().0;
+
+                (change.old_value().map(id), change.new_value().map(id))
+            },
+            change => panic!("unexpected change: {:?}", change),
+        };
+
+        assert_eq!(next(), (None, Some(5)));
+        assert_eq!(next(), (Some(5), Some(5)));
+        assert_eq!(next(), (None, Some(6)));
+        // The synced threads replace those of their parent channels.
+        assert_eq!(next(), (Some(5), Some(5)));
+        assert_eq!(next(), (Some(6), None));
+        assert_eq!(next(), (Some(5), None));
+        assert!(changes.next().now_or_never().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_cache_subscribe_eviction() {
+        let mut settings = Settings::new();
+        settings.member_eviction(EvictionPolicy::new().max_entries(1));
+        let cache = Cache::new_with_settings(settings);
+        cache
+            .update(&mut GuildCreateEvent {
+                guild: guild(GuildId(1)),
+            })
+            .await;
+        let mut changes = cache.subscribe();
+
+        for id in 1..=2 {
+            cache
+                .update(&mut GuildMemberAddEvent {
+                    member: member(GuildId(1), UserId(id)),
+                })
+                .await;
+        }
+
+        let mut next = || match changes.next().now_or_never().flatten() {
+            Some(CacheChange::Member(change)) => {
+                let id = |member: &Member| member.user.id.0;
+
+                (change.old_value().map(id), change.new_value().map(id))
+            },
+            change => panic!("unexpected change: {:?}", change),
+        };
+
+        assert_eq!(next(), (None, Some(1)));
+        assert_eq!(next(), (None, Some(2)));
+        // The first member was evicted to make room for the second.
+        assert_eq!(next(), (Some(1), None));
+    }
+
+    #[tokio::test]
+    async fn test_cache_query_members() {
+        let cache = Cache::new();
+        let mut guild = guild(GuildId(1));
+
+        for id in 1..=4 {
+            let mut member = member(GuildId(1), UserId(id));
+            member.user.name = format!("User{}", id);
+            member.joined_at = Some(Timestamp::from_unix_timestamp(id as i64).unwrap());
+            member.pending = id == 4;
+            guild.members.insert(member.user.id, member);
+        }
+
+        let timed_out = guild.members.get_mut(&UserId(2)).unwrap();
+        timed_out.nick = Some("Ärger".to_string());
+        timed_out.roles.push(RoleId(9));
+        timed_out.communication_disabled_until =
+            Some(Timestamp::from_unix_timestamp(i32::MAX as i64).unwrap());
+
+        cache
+            .update(&mut GuildCreateEvent {
+                guild,
+            })
+            .await;
+
+        let cache = &cache;
+        let ids = |query: MemberQuery| async move {
+            let members = cache.query_members(GuildId(1), &query).await.unwrap();
+            members.into_iter().map(|member| member.user.id.0).collect::<Vec<_>>()
+        };
+
+        assert_eq!(ids(MemberQuery::new()).await, vec![1, 2, 3, 4]);
+        assert_eq!(ids(MemberQuery::new().after(UserId(1)).limit(2)).await, vec![2, 3]);
+        assert_eq!(ids(MemberQuery::new().name_prefix("user")).await, vec![1, 2, 3, 4]);
+        assert_eq!(ids(MemberQuery::new().name_prefix("äR")).await, vec![2]);
+        assert_eq!(ids(MemberQuery::new().role(RoleId(9))).await, vec![2]);
+        assert_eq!(
+            ids(MemberQuery::new().joined_after(Timestamp::from_unix_timestamp(2).unwrap())).await,
+            vec![3, 4]
+        );
+        assert_eq!(ids(MemberQuery::new().pending(false)).await, vec![1, 2, 3]);
+        assert_eq!(ids(MemberQuery::new().timed_out(true)).await, vec![2]);
+        assert_eq!(ids(MemberQuery::new().timed_out(false).pending(false)).await, vec![1, 3]);
+        assert!(cache.query_members(GuildId(2), &MemberQuery::new()).await.is_none());
+    }
+
+    fn channel(id: ChannelId, parent_id: Option<ChannelId>, kind: ChannelType) -> GuildChannel {
+        GuildChannel {
+            id,
+            bitrate: None,
+            parent_id,
+            guild_id: GuildId(1),
+            kind,
+            last_message_id: None,
+            last_pin_timestamp: None,
+            name: String::new(),
+            permission_overwrites: vec![],
+            position: 0,
+            topic: None,
+            user_limit: None,
+            nsfw: false,
+            rate_limit_per_user: None,
+            rtc_region: None,
+            video_quality_mode: None,
+            message_count: None,
+            member_count: None,
+            thread_metadata: None,
+            member: None,
+            default_auto_archive_duration: None,
+            flags: ChannelFlags::empty(),
+            total_message_sent: None,
+            available_tags: Vec::new(),
+            applied_tags: Vec::new(),
+            default_reaction_emoji: None,
+            default_thread_rate_limit_per_user: None,
+            default_sort_order: None,
+        }
+    }
+
+    fn role(id: RoleId, position: i64, permissions: Permissions) -> Role {
+        Role {
+            id,
+            guild_id: GuildId(1),
+            colour: Colour::default(),
+            hoist: false,
+            managed: false,
+            mentionable: false,
+            name: String::new(),
+            permissions,
+            position,
+            tags: RoleTags::default(),
+            icon: None,
+            unicode_emoji: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cache_permissions_in() {
+        let cache = Cache::new();
+        let mut guild = guild(GuildId(1));
+
+        for role in [
+            role(RoleId(1), 0, Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES),
+            role(RoleId(2), 1, Permissions::ATTACH_FILES),
+        ] {
+            guild.roles.insert(role.id, role);
+        }
+
+        let mut member = member(GuildId(1), UserId(2));
+        member.roles.push(RoleId(2));
+        guild.members.insert(UserId(2), member);
+
+        let mut text = channel(ChannelId(4), None, ChannelType::Text);
+        text.permission_overwrites = vec![
+            PermissionOverwrite {
+                allow: Permissions::empty(),
+                deny: Permissions::SEND_MESSAGES,
+                kind: PermissionOverwriteType::Role(RoleId(2)),
+            },
+            PermissionOverwrite {
+                allow: Permissions::ADD_REACTIONS,
+                deny: Permissions::empty(),
+                kind: PermissionOverwriteType::Member(UserId(2)),
+            },
+        ];
+        guild.channels.insert(text.id, Channel::Guild(text));
+        guild.threads.push(channel(ChannelId(5), Some(ChannelId(4)), ChannelType::PublicThread));
+
+        cache
+            .update(&mut GuildCreateEvent {
+                guild,
+            })
+            .await;
+
+        // Threads are subject to the overwrites of their parent.
+        let explanation = cache.permissions_in(ChannelId(5), UserId(2)).await.unwrap();
+        assert_eq!(explanation.permissions, Permissions::VIEW_CHANNEL | Permissions::ADD_REACTIONS);
+        assert_eq!(explanation.overwrites_channel_id, ChannelId(4));
+
+        let source =
+            |permissions| explanation.decided_by(permissions).map(|step| step.source.clone());
+        assert_eq!(source(Permissions::VIEW_CHANNEL), Some(PermissionSource::Role(RoleId(1))));
+        assert_eq!(
+            source(Permissions::SEND_MESSAGES),
+            Some(PermissionSource::RoleOverwrites(vec![RoleId(2)]))
+        );
+        assert_eq!(source(Permissions::ATTACH_FILES), Some(PermissionSource::Implicit));
+        assert_eq!(
+            source(Permissions::ADD_REACTIONS),
+            Some(PermissionSource::MemberOverwrite(UserId(2)))
+        );
+        assert_eq!(source(Permissions::BAN_MEMBERS), None);
+
+        let mut timed_out = cache.member(GuildId(1), UserId(2)).await.unwrap();
+        timed_out.communication_disabled_until =
+            Some(Timestamp::from_unix_timestamp(i32::MAX.into()).unwrap());
+        cache
+            .update(&mut GuildMemberAddEvent {
+                member: timed_out,
+            })
+            .await;
+        let explanation = cache.permissions_in(ChannelId(4), UserId(2)).await.unwrap();
+        assert_eq!(explanation.permissions, Permissions::VIEW_CHANNEL);
+        assert_eq!(
+            explanation.decided_by(Permissions::ADD_REACTIONS).map(|step| &step.source),
+            Some(&PermissionSource::Timeout)
+        );
+
+        let error = cache.permissions_in(ChannelId(4), UserId(3)).await.unwrap_err();
+        assert!(matches!(error, crate::Error::Model(ModelError::MemberNotFound)));
+    }
+
+    #[tokio::test]
+    async fn test_cache_permissions_in_conflicting_overwrites() {
+        let cache = Cache::new();
+        let mut guild = guild(GuildId(1));
+
+        for role in [
+            role(RoleId(1), 0, Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES),
+            role(RoleId(2), 1, Permissions::empty()),
+            role(RoleId(3), 2, Permissions::empty()),
+        ] {
+            guild.roles.insert(role.id, role);
+        }
+
+        let mut member = member(GuildId(1), UserId(2));
+        member.roles.extend([RoleId(3), RoleId(2)]);
+        guild.members.insert(UserId(2), member);
+
+        // The lower role allows what the higher one denies, and the other way
+        // around.
+        let mut text = channel(ChannelId(4), None, ChannelType::Text);
+        text.permission_overwrites = vec![
+            PermissionOverwrite {
+                allow: Permissions::empty(),
+                deny: Permissions::SEND_MESSAGES,
+                kind: PermissionOverwriteType::Role(RoleId(1)),
+            },
+            PermissionOverwrite {
+                allow: Permissions::SEND_MESSAGES,
+                deny: Permissions::ADD_REACTIONS,
+                kind: PermissionOverwriteType::Role(RoleId(2)),
+            },
+            PermissionOverwrite {
+                allow: Permissions::ADD_REACTIONS,
+                deny: Permissions::SEND_MESSAGES,
+                kind: PermissionOverwriteType::Role(RoleId(3)),
+            },
+        ];
+        guild.channels.insert(text.id, Channel::Guild(text));
+
+        cache
+            .update(&mut GuildCreateEvent {
+                guild,
+            })
+            .await;
+
+        // Allows win over denies of other roles, regardless of their order.
+        let explanation = cache.permissions_in(ChannelId(4), UserId(2)).await.unwrap();
+        assert_eq!(
+            explanation.permissions,
+            Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES | Permissions::ADD_REACTIONS
+        );
+
+        let step = explanation.decided_by(Permissions::SEND_MESSAGES).unwrap();
+        assert_eq!(step.source, PermissionSource::RoleOverwrites(vec![RoleId(2), RoleId(3)]));
+        assert_eq!(step.allow, Permissions::SEND_MESSAGES | Permissions::ADD_REACTIONS);
+        assert_eq!(step.deny, Permissions::SEND_MESSAGES | Permissions::ADD_REACTIONS);
+
+        // The overwrite for `@everyone` applies before the combined ones.
+        let sources = explanation.steps.iter().map(|step| &step.source).collect::<Vec<_>>();
+        assert_eq!(sources[sources.len() - 2], &PermissionSource::EveryoneOverwrite);
+    }
+
+    fn message(id: MessageId) -> Message {
+        from_value(json!({
+            "id": id,
+            "channel_id": "2",
+            "guild_id": "1",
+            "author": {"id": "3", "username": "user", "discriminator": "0001", "avatar": null},
+            "content": "",
+            "timestamp": "2022-01-01T00:00:00Z",
+            "edited_timestamp": null,
+            "tts": false,
+            "mention_everyone": false,
+            "mentions": [],
+            "mention_roles": [],
+            "attachments": [],
+            "embeds": [],
+            "pinned": false,
+            "type": 0,
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_cache_message_deletes() {
+        let mut settings = Settings::new();
+        settings.max_messages(10);
+        let cache = Cache::new_with_settings(settings);
+        let mut changes = cache.subscribe();
+
+        for id in 1..=4 {
+            cache
+                .update(&mut MessageCreateEvent {
+                    message: message(MessageId(id)),
+                })
+                .await;
+        }
+
+        let mut delete = MessageDeleteEvent {
+            guild_id: Some(GuildId(1)),
+            channel_id: ChannelId(2),
+            message_id: MessageId(1),
+        };
+        assert_eq!(cache.update(&mut delete).await.map(|m| m.id), Some(MessageId(1)));
+        assert!(cache.update(&mut delete).await.is_none());
+
+        let mut delete_bulk = MessageDeleteBulkEvent {
+            guild_id: Some(GuildId(1)),
+            channel_id: ChannelId(2),
+            ids: vec![MessageId(2), MessageId(3), MessageId(5)],
+        };
+        let removed = cache.update(&mut delete_bulk).await.unwrap();
+        assert_eq!(removed.iter().map(|m| m.id).collect::<Vec<_>>(), vec![
+            MessageId(2),
+            MessageId(3)
+        ]);
+
+        let remaining = cache.backend.channel_messages(ChannelId(2)).await.unwrap();
+        assert_eq!(remaining.iter().map(|m| m.id).collect::<Vec<_>>(), vec![MessageId(4)]);
+
+        let removals = std::iter::from_fn(|| changes.next().now_or_never().flatten())
+            .filter(|change| {
+                matches!(change, CacheChange::Message(change) if matches!(**change, Change::Remove(_)))
+            })
+            .count();
+        assert_eq!(removals, 3);
+    }
+
+    #[tokio::test]
+    async fn test_cache_reactions() {
+        let mut settings = Settings::new();
+        settings.max_messages(10);
+        let cache = Cache::new_with_settings(settings);
+        cache.user.write().id = UserId(9);
+
+        cache
+            .update(&mut MessageCreateEvent {
+                message: message(MessageId(1)),
+            })
+            .await;
+
+        let reaction = |user_id: u64, emoji: &str| -> Reaction {
+            from_value(json!({
+                "user_id": user_id.to_string(),
+                "channel_id": "2",
+                "message_id": "1",
+                "guild_id": "1",
+                "emoji": {"id": null, "name": emoji},
+            }))
+            .unwrap()
+        };
+        let cache = &cache;
+        let reactions =
+            || async move { cache.message(ChannelId(2), MessageId(1)).await.unwrap().reactions };
+
+        for (user_id, emoji) in [(3, "👍"), (9, "👍"), (3, "🎉")] {
+            cache
+                .update(&mut ReactionAddEvent {
+                    reaction: reaction(user_id, emoji),
+                })
+                .await;
+        }
+        let cached = reactions().await;
+        assert_eq!(cached.len(), 2);
+        assert_eq!((cached[0].count, cached[0].me), (2, true));
+        assert_eq!((cached[1].count, cached[1].me), (1, false));
+
+        cache
+            .update(&mut ReactionRemoveEvent {
+                reaction: reaction(9, "👍"),
+            })
+            .await;
+        cache
+            .update(&mut ReactionRemoveEvent {
+                reaction: reaction(3, "🎉"),
+            })
+            .await;
+        let cached = reactions().await;
+        assert_eq!(cached.len(), 1);
+        assert_eq!((cached[0].count, cached[0].me), (1, false));
+
+        cache
+            .update(&mut ReactionRemoveAllEvent {
+                guild_id: Some(GuildId(1)),
+                channel_id: ChannelId(2),
+                message_id: MessageId(1),
+            })
+            .await;
+        assert!(reactions().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_cache_guild_resources() {
+        let cache = Cache::new();
+        let mut guild = guild(GuildId(1));
+        guild.threads.push(channel(ChannelId(5), Some(ChannelId(4)), ChannelType::PublicThread));
+        guild.threads.push(channel(ChannelId(6), Some(ChannelId(7)), ChannelType::PublicThread));
+        cache
+            .update(&mut GuildCreateEvent {
+                guild,
+            })
+            .await;
+
+        let cache = &cache;
+        let thread_ids = || async move {
+            let guild = cache.guild(GuildId(1)).await.unwrap();
+            guild.threads.iter().map(|thread| thread.id.0).collect::<Vec<_>>()
+        };
+
+        // Only the threads of the synced channels are replaced.
+        cache
+            .update(&mut ThreadListSyncEvent {
+                guild_id: GuildId(1),
+                channels_id: vec![ChannelId(4)],
+                threads: vec![channel(ChannelId(8), Some(ChannelId(4)), ChannelType::PublicThread)],
+                members: vec![],
+            })
+            .await;
+        assert_eq!(thread_ids().await, vec![6, 8]);
+
+        cache
+            .update(&mut ThreadMembersUpdateEvent {
+                id: ChannelId(8),
+                guild_id: GuildId(1),
+                member_count: 3,
+                added_members: vec![],
+                removed_members_ids: vec![],
+            })
+            .await;
+        let guild = cache.guild(GuildId(1)).await.unwrap();
+        assert_eq!(guild.threads[1].member_count, Some(3));
+
+        let stage_instance = |topic: &str| -> StageInstance {
+            from_value(json!({
+                "id": "10",
+                "guild_id": "1",
+                "channel_id": "4",
+                "topic": topic,
+                "privacy_level": 2,
+                "discoverable_disabled": false,
+            }))
+            .unwrap()
+        };
+        cache
+            .update(&mut StageInstanceCreateEvent {
+                stage_instance: stage_instance("Hello"),
+            })
+            .await;
+        let old = cache
+            .update(&mut StageInstanceUpdateEvent {
+                stage_instance: stage_instance("World"),
+            })
+            .await;
+        assert_eq!(old.map(|stage| stage.topic), Some("Hello".to_string()));
+        let guild = cache.guild(GuildId(1)).await.unwrap();
+        assert_eq!(guild.stage_instances.len(), 1);
+        assert_eq!(guild.stage_instances[0].topic, "World");
+
+        let removed = cache
+            .update(&mut StageInstanceDeleteEvent {
+                stage_instance: stage_instance("World"),
+            })
+            .await;
+        assert!(removed.is_some());
+        assert!(cache.guild(GuildId(1)).await.unwrap().stage_instances.is_empty());
+
+        let scheduled_event: ScheduledEvent = from_value(json!({
+            "id": "11",
+            "guild_id": "1",
+            "channel_id": "4",
+            "name": "Event",
+            "scheduled_start_time": "2022-01-01T00:00:00Z",
+            "privacy_level": 2,
+            "status": 1,
+            "entity_type": 1,
+            "user_count": 1,
+        }))
+        .unwrap();
+        cache
+            .update(&mut GuildScheduledEventCreateEvent {
+                event: scheduled_event.clone(),
+            })
+            .await;
+        cache
+            .update(&mut GuildScheduledEventUserAddEvent {
+                scheduled_event_id: ScheduledEventId(11),
+                guild_id: GuildId(1),
+                user_id: UserId(2),
+            })
+            .await;
+        let guild = cache.guild(GuildId(1)).await.unwrap();
+        assert_eq!(guild.scheduled_events[0].user_count, Some(2));
+
+        let removed = cache
+            .update(&mut GuildScheduledEventDeleteEvent {
+                event: scheduled_event,
+            })
+            .await;
+        assert_eq!(removed.map(|event| event.id), Some(ScheduledEventId(11)));
+        assert!(cache.guild(GuildId(1)).await.unwrap().scheduled_events.is_empty());
     }
 }
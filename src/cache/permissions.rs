@@ -0,0 +1,307 @@
+use super::{Cache, CacheBackendExt};
+use crate::internal::prelude::*;
+use crate::model::prelude::*;
+
+/// What a [`PermissionStep`] originates from.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum PermissionSource {
+    /// The member owns the guild, and so has all permissions.
+    Owner,
+    /// The base permissions of a role of the member. The `@everyone` role has
+    /// the same Id as the guild.
+    Role(RoleId),
+    /// A role of the member grants [Administrator], and so all permissions.
+    ///
+    /// [Administrator]: Permissions::ADMINISTRATOR
+    Administrator,
+    /// The permission overwrite for `@everyone`.
+    EveryoneOverwrite,
+    /// The permission overwrites for the given roles of the member, from the
+    /// lowest to the highest role, applied together: the permissions that
+    /// any of them allows are granted, even if another one denies them.
+    RoleOverwrites(Vec<RoleId>),
+    /// A permission overwrite for the member.
+    MemberOverwrite(UserId),
+    /// The member is timed out, leaving only the permissions to view the
+    /// channel and read its message history.
+    Timeout,
+    /// Permissions that are implied by others, such as the ability to attach
+    /// files without being able to send messages, or voice permissions in a
+    /// text channel.
+    Implicit,
+}
+
+/// A change to a member's permissions while computing them.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct PermissionStep {
+    /// What the change originates from.
+    pub source: PermissionSource,
+    /// The permissions that were granted.
+    pub allow: Permissions,
+    /// The permissions that were denied.
+    pub deny: Permissions,
+}
+
+/// The permissions of a member in a channel, as returned by
+/// [`Cache::permissions_in`], along with how they were computed.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct PermissionsExplanation {
+    /// The permissions of the member in the channel.
+    pub permissions: Permissions,
+    /// The channel whose permission overwrites were applied. For threads,
+    /// this is their parent channel.
+    pub overwrites_channel_id: ChannelId,
+    /// The changes that led to the [`permissions`], in the order in which
+    /// they were applied. Later steps take precedence over earlier ones.
+    ///
+    /// [`permissions`]: #structfield.permissions
+    pub steps: Vec<PermissionStep>,
+}
+
+impl PermissionsExplanation {
+    /// Returns the step that last granted or denied any of the given
+    /// permissions, and so decided whether the member has them.
+    ///
+    /// Returns `None` if no step touched them, meaning that the member lacks
+    /// them because no role grants them.
+    #[must_use]
+    pub fn decided_by(&self, permissions: Permissions) -> Option<&PermissionStep> {
+        self.steps
+            .iter()
+            .rev()
+            .find(|step| step.allow.intersects(permissions) || step.deny.intersects(permissions))
+    }
+
+    fn push(&mut self, source: PermissionSource, allow: Permissions, deny: Permissions) {
+        self.permissions = (self.permissions & !deny) | allow;
+        self.steps.push(PermissionStep {
+            source,
+            allow,
+            deny,
+        });
+    }
+
+    /// Records the permissions that `f` removes, if any, as being denied by
+    /// `source`.
+    fn remove(&mut self, source: PermissionSource, f: impl FnOnce(Permissions) -> Permissions) {
+        let removed = self.permissions - f(self.permissions);
+
+        if !removed.is_empty() {
+            self.push(source, Permissions::empty(), removed);
+        }
+    }
+}
+
+fn is_thread(channel: &GuildChannel) -> bool {
+    matches!(
+        channel.kind,
+        ChannelType::NewsThread | ChannelType::PublicThread | ChannelType::PrivateThread
+    )
+}
+
+impl Cache {
+    /// Computes the permissions of a member in a guild channel or thread from
+    /// the cache, explaining which role or permission overwrite granted or
+    /// denied each of them.
+    ///
+    /// Threads use the permission overwrites of their parent channel. Members
+    /// that are [timed out] only keep the permissions to view the channel and
+    /// read its message history, unless they own the guild or are
+    /// administrators.
+    ///
+    /// # Examples
+    ///
+    /// Telling a user why they cannot send messages:
+    ///
+    /// ```rust,no_run
+    /// use serenity::cache::Cache;
+    /// use serenity::model::id::{ChannelId, UserId};
+    /// use serenity::model::Permissions;
+    ///
+    /// # async fn run(cache: &Cache) -> serenity::Result<()> {
+    /// let explanation = cache.permissions_in(ChannelId(7), UserId(8)).await?;
+    ///
+    /// if !explanation.permissions.send_messages() {
+    ///     match explanation.decided_by(Permissions::SEND_MESSAGES) {
+    ///         Some(step) => println!("Denied by {:?}", step.source),
+    ///         None => println!("No role grants it"),
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ModelError::ChannelNotFound`] if the channel, or the parent
+    /// of a thread, is not a guild channel in the cache, and a
+    /// [`ModelError::GuildNotFound`] if its guild is not in the cache.
+    ///
+    /// Returns a [`ModelError::MemberNotFound`] if the member is not in the
+    /// cache, and a [`ModelError::RoleNotFound`] if it has a role that is not.
+    ///
+    /// [timed out]: Member::communication_disabled_until
+    pub async fn permissions_in(
+        &self,
+        channel_id: impl Into<ChannelId>,
+        user_id: impl Into<UserId>,
+    ) -> Result<PermissionsExplanation> {
+        self._permissions_in(channel_id.into(), user_id.into()).await
+    }
+
+    async fn _permissions_in(
+        &self,
+        channel_id: ChannelId,
+        user_id: UserId,
+    ) -> Result<PermissionsExplanation> {
+        let channel = match self.backend.channel(channel_id).await {
+            Some(channel) => channel,
+            None => self
+                .backend
+                .find_map_guild(|guild| {
+                    guild.threads.iter().find(|thread| thread.id == channel_id).cloned()
+                })
+                .await
+                .ok_or(Error::Model(ModelError::ChannelNotFound))?,
+        };
+
+        let overwrites_channel = match channel.parent_id {
+            Some(parent_id) if is_thread(&channel) => self
+                .backend
+                .channel(parent_id)
+                .await
+                .ok_or(Error::Model(ModelError::ChannelNotFound))?,
+            _ => channel.clone(),
+        };
+
+        self.backend
+            .read_guild_with(channel.guild_id, |guild| {
+                let member =
+                    guild.members.get(&user_id).ok_or(Error::Model(ModelError::MemberNotFound))?;
+
+                explain_permissions(guild, &channel, &overwrites_channel, member)
+            })
+            .await
+            .ok_or(Error::Model(ModelError::GuildNotFound))?
+    }
+}
+
+fn explain_permissions(
+    guild: &Guild,
+    channel: &GuildChannel,
+    overwrites_channel: &GuildChannel,
+    member: &Member,
+) -> Result<PermissionsExplanation> {
+    let mut explanation = PermissionsExplanation {
+        permissions: Permissions::empty(),
+        overwrites_channel_id: overwrites_channel.id,
+        steps: Vec::new(),
+    };
+
+    let grant_all = |explanation: &mut PermissionsExplanation, source| {
+        explanation.push(source, Permissions::all(), Permissions::empty());
+        explanation.remove(PermissionSource::Implicit, |permissions| {
+            Guild::remove_unnecessary_voice_permissions(channel, permissions)
+        });
+    };
+
+    // The owner has all permissions in all cases.
+    if member.user.id == guild.owner_id {
+        grant_all(&mut explanation, PermissionSource::Owner);
+
+        return Ok(explanation);
+    }
+
+    // The base permissions are those of `@everyone` and the member's roles.
+    let everyone_id = RoleId(guild.id.0);
+
+    for role_id in std::iter::once(&everyone_id).chain(&member.roles) {
+        let role = guild.roles.get(role_id).ok_or(Error::Model(ModelError::RoleNotFound))?;
+
+        explanation.push(PermissionSource::Role(role.id), role.permissions, Permissions::empty());
+    }
+
+    // Administrators have all permissions in any channel.
+    if explanation.permissions.contains(Permissions::ADMINISTRATOR) {
+        grant_all(&mut explanation, PermissionSource::Administrator);
+
+        return Ok(explanation);
+    }
+
+    // The overwrite for `@everyone` applies first, followed by those for the
+    // member's roles, whose denials apply before all of their grants, so that
+    // any role allowing a permission wins. The one for the member goes last.
+    for overwrite in &overwrites_channel.permission_overwrites {
+        if overwrite.kind == PermissionOverwriteType::Role(everyone_id) {
+            explanation.push(PermissionSource::EveryoneOverwrite, overwrite.allow, overwrite.deny);
+        }
+    }
+
+    let mut role_overwrites = overwrites_channel
+        .permission_overwrites
+        .iter()
+        .filter_map(|overwrite| match overwrite.kind {
+            PermissionOverwriteType::Role(role_id)
+                if role_id != everyone_id && member.roles.contains(&role_id) =>
+            {
+                guild.roles.get(&role_id).map(|role| (role.position, role_id, overwrite))
+            },
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    if !role_overwrites.is_empty() {
+        role_overwrites.sort_by_key(|(position, ..)| *position);
+
+        let mut allow = Permissions::empty();
+        let mut deny = Permissions::empty();
+
+        for (_, _, overwrite) in &role_overwrites {
+            allow |= overwrite.allow;
+            deny |= overwrite.deny;
+        }
+
+        let role_ids = role_overwrites.into_iter().map(|(_, role_id, _)| role_id).collect();
+
+        explanation.push(PermissionSource::RoleOverwrites(role_ids), allow, deny);
+    }
+
+    for overwrite in &overwrites_channel.permission_overwrites {
+        if overwrite.kind == PermissionOverwriteType::Member(member.user.id) {
+            explanation.push(
+                PermissionSource::MemberOverwrite(member.user.id),
+                overwrite.allow,
+                overwrite.deny,
+            );
+        }
+    }
+
+    // The default channel is always readable.
+    if channel.id.0 == guild.id.0 && !explanation.permissions.contains(Permissions::VIEW_CHANNEL) {
+        explanation.push(
+            PermissionSource::Implicit,
+            Permissions::VIEW_CHANNEL,
+            Permissions::empty(),
+        );
+    }
+
+    let timed_out =
+        member.communication_disabled_until.map_or(false, |until| until > Timestamp::now());
+
+    if timed_out {
+        explanation.remove(PermissionSource::Timeout, |permissions| {
+            permissions & (Permissions::VIEW_CHANNEL | Permissions::READ_MESSAGE_HISTORY)
+        });
+    }
+
+    explanation.remove(PermissionSource::Implicit, |mut permissions| {
+        Guild::remove_unusable_permissions(&mut permissions);
+
+        permissions
+    });
+
+    Ok(explanation)
+}
@@ -0,0 +1,199 @@
+use super::{Cache, CacheBackendExt};
+use crate::model::prelude::*;
+
+/// Filters over the cached members of a guild, for [`Cache::query_members`].
+///
+/// All filters that are set must match. Matching members are ordered by user
+/// Id, and can be paginated with [`Self::after`] and [`Self::limit`].
+///
+/// # Examples
+///
+/// Listing the first 10 members with a role whose name or nickname starts with
+/// "ser":
+///
+/// ```rust
+/// use serenity::cache::MemberQuery;
+/// use serenity::model::id::RoleId;
+///
+/// let query = MemberQuery::new().role(RoleId(7)).name_prefix("ser").limit(10);
+/// ```
+#[derive(Clone, Debug, Default)]
+#[must_use]
+pub struct MemberQuery {
+    role: Option<RoleId>,
+    /// Lowercased, to be matched case-insensitively.
+    name_prefix: Option<String>,
+    joined_after: Option<Timestamp>,
+    pending: Option<bool>,
+    timed_out: Option<bool>,
+    after: Option<UserId>,
+    limit: Option<usize>,
+}
+
+impl MemberQuery {
+    /// Creates a query matching all members.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only matches members that have the given role.
+    pub fn role(mut self, role_id: impl Into<RoleId>) -> Self {
+        self.role = Some(role_id.into());
+
+        self
+    }
+
+    /// Only matches members whose username or nickname starts with the given
+    /// prefix, ignoring case.
+    pub fn name_prefix(mut self, prefix: impl AsRef<str>) -> Self {
+        self.name_prefix = Some(prefix.as_ref().to_lowercase());
+
+        self
+    }
+
+    /// Only matches members that joined the guild after the given time.
+    pub fn joined_after(mut self, timestamp: impl Into<Timestamp>) -> Self {
+        self.joined_after = Some(timestamp.into());
+
+        self
+    }
+
+    /// Only matches members that have, or have not yet passed the guild's
+    /// membership screening.
+    ///
+    /// Refer to [`Member::pending`] for more information.
+    pub fn pending(mut self, pending: bool) -> Self {
+        self.pending = Some(pending);
+
+        self
+    }
+
+    /// Only matches members that are, or are not currently timed out.
+    ///
+    /// Refer to [`Member::communication_disabled_until`] for more information.
+    pub fn timed_out(mut self, timed_out: bool) -> Self {
+        self.timed_out = Some(timed_out);
+
+        self
+    }
+
+    /// Only returns members with a user Id greater than the given one, which
+    /// is usually the last one of the previous page.
+    pub fn after(mut self, user_id: impl Into<UserId>) -> Self {
+        self.after = Some(user_id.into());
+
+        self
+    }
+
+    /// Returns at most the given number of members.
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+
+        self
+    }
+
+    fn matches(&self, member: &Member, now: Timestamp) -> bool {
+        if self.after.map_or(false, |after| member.user.id <= after) {
+            return false;
+        }
+
+        if let Some(role_id) = self.role {
+            if !member.roles.contains(&role_id) {
+                return false;
+            }
+        }
+
+        if let Some(prefix) = &self.name_prefix {
+            let nick_matches =
+                member.nick.as_ref().map_or(false, |nick| starts_with_ignore_case(nick, prefix));
+
+            if !nick_matches && !starts_with_ignore_case(&member.user.name, prefix) {
+                return false;
+            }
+        }
+
+        if let Some(joined_after) = self.joined_after {
+            if member.joined_at.map_or(true, |joined_at| joined_at <= joined_after) {
+                return false;
+            }
+        }
+
+        if self.pending.map_or(false, |pending| member.pending != pending) {
+            return false;
+        }
+
+        if let Some(timed_out) = self.timed_out {
+            let is_timed_out =
+                member.communication_disabled_until.map_or(false, |until| until > now);
+
+            if is_timed_out != timed_out {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+/// Whether `s` starts with `prefix`, which must be lowercase, ignoring case.
+fn starts_with_ignore_case(s: &str, prefix: &str) -> bool {
+    let mut chars = s.chars().flat_map(char::to_lowercase);
+
+    prefix.chars().all(|c| chars.next() == Some(c))
+}
+
+impl Cache {
+    /// Returns the cached members of a guild that match the `query`, ordered
+    /// by user Id.
+    ///
+    /// Only the matching members are cloned, rather than the whole guild.
+    ///
+    /// Returns `None` if the guild is not in the cache.
+    ///
+    /// # Examples
+    ///
+    /// Paginating over the members that are timed out:
+    ///
+    /// ```rust,no_run
+    /// use serenity::cache::{Cache, MemberQuery};
+    /// use serenity::model::id::GuildId;
+    ///
+    /// # async fn run(cache: &Cache) {
+    /// let mut query = MemberQuery::new().timed_out(true).limit(100);
+    ///
+    /// while let Some(members) = cache.query_members(GuildId(7), &query).await {
+    ///     for member in &members {
+    ///         println!("{} is timed out", member.user.name);
+    ///     }
+    ///
+    ///     match members.last() {
+    ///         Some(last) if members.len() == 100 => query = query.after(last.user.id),
+    ///         _ => break,
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub async fn query_members(
+        &self,
+        guild_id: impl Into<GuildId>,
+        query: &MemberQuery,
+    ) -> Option<Vec<Member>> {
+        let now = Timestamp::now();
+
+        self.backend
+            .read_guild_with(guild_id.into(), |guild| {
+                let mut members = guild
+                    .members
+                    .values()
+                    .filter(|member| query.matches(member, now))
+                    .collect::<Vec<_>>();
+
+                members.sort_unstable_by_key(|member| member.user.id);
+                members.truncate(query.limit.unwrap_or(usize::MAX));
+
+                members.into_iter().cloned().collect()
+            })
+            .await
+    }
+}
@@ -1,3 +1,7 @@
+use std::sync::Arc;
+
+use super::{CacheBackend, EvictionPolicy};
+
 /// Settings for the cache.
 ///
 /// # Examples
@@ -10,13 +14,122 @@
 /// let mut settings = CacheSettings::new();
 /// settings.max_messages(10);
 /// ```
-#[derive(Clone, Debug, Default)]
+///
+/// Only cache guilds, their roles and channels:
+///
+/// ```rust
+/// use serenity::cache::Settings as CacheSettings;
+///
+/// let mut settings = CacheSettings::new();
+/// settings
+///     .cache_members(false)
+///     .cache_presences(false)
+///     .cache_users(false)
+///     .cache_emojis(false)
+///     .cache_stickers(false)
+///     .cache_voice_states(false);
+/// ```
+#[derive(Clone, Debug)]
 #[non_exhaustive]
 pub struct Settings {
     /// The maximum number of messages to store in a channel's message cache.
     ///
     /// Defaults to 0.
     pub max_messages: usize,
+    /// The store that the cache keeps its data in.
+    ///
+    /// Defaults to `None`, keeping the data in memory via an
+    /// [`InMemoryCacheBackend`].
+    ///
+    /// [`InMemoryCacheBackend`]: super::InMemoryCacheBackend
+    pub backend: Option<Arc<dyn CacheBackend>>,
+    /// Whether guilds are cached.
+    ///
+    /// Members, roles, emojis, stickers, voice states and guild presences are
+    /// stored within their guild, so none of them are cached if this is
+    /// disabled.
+    ///
+    /// Defaults to `true`.
+    pub cache_guilds: bool,
+    /// Whether guild channels, categories and private channels are cached.
+    ///
+    /// Defaults to `true`.
+    pub cache_channels: bool,
+    /// Whether guild members are cached.
+    ///
+    /// Defaults to `true`.
+    pub cache_members: bool,
+    /// Whether presences are cached.
+    ///
+    /// Defaults to `true`.
+    pub cache_presences: bool,
+    /// Whether users are cached.
+    ///
+    /// Defaults to `true`.
+    pub cache_users: bool,
+    /// Whether the emojis of guilds are cached.
+    ///
+    /// Defaults to `true`.
+    pub cache_emojis: bool,
+    /// Whether the stickers of guilds are cached.
+    ///
+    /// Defaults to `true`.
+    pub cache_stickers: bool,
+    /// Whether the voice states of guilds are cached.
+    ///
+    /// Defaults to `true`.
+    pub cache_voice_states: bool,
+    /// Whether messages are cached, up to [`max_messages`] per channel.
+    ///
+    /// Defaults to `true`.
+    ///
+    /// [`max_messages`]: #structfield.max_messages
+    pub cache_messages: bool,
+    /// When to evict users from the cache.
+    ///
+    /// Defaults to never evicting them.
+    pub user_eviction: EvictionPolicy,
+    /// When to evict members from the cache. The maximum number of entries
+    /// applies per guild.
+    ///
+    /// Defaults to never evicting them.
+    pub member_eviction: EvictionPolicy,
+    /// When to evict presences from the cache. The maximum number of entries
+    /// applies per guild, and separately to presences not tied to a guild.
+    ///
+    /// Defaults to never evicting them.
+    pub presence_eviction: EvictionPolicy,
+    /// Whether to remove users from the cache once they share no cached guild
+    /// or private channel with the current user anymore, such as after they
+    /// left the last guild or their member was evicted.
+    ///
+    /// Defaults to `false`, keeping users until they are evicted by
+    /// [`user_eviction`].
+    ///
+    /// [`user_eviction`]: #structfield.user_eviction
+    pub remove_unreferenced_users: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            max_messages: 0,
+            backend: None,
+            cache_guilds: true,
+            cache_channels: true,
+            cache_members: true,
+            cache_presences: true,
+            cache_users: true,
+            cache_emojis: true,
+            cache_stickers: true,
+            cache_voice_states: true,
+            cache_messages: true,
+            user_eviction: EvictionPolicy::default(),
+            member_eviction: EvictionPolicy::default(),
+            presence_eviction: EvictionPolicy::default(),
+            remove_unreferenced_users: false,
+        }
+    }
 }
 
 impl Settings {
@@ -48,4 +161,159 @@ impl Settings {
 
         self
     }
+
+    /// Sets the store that the cache keeps its data in.
+    ///
+    /// Refer to [`backend`] for more information.
+    ///
+    /// [`backend`]: #structfield.backend
+    pub fn backend<B: CacheBackend + 'static>(&mut self, backend: B) -> &mut Self {
+        self.backend = Some(Arc::new(backend));
+
+        self
+    }
+
+    /// Sets whether to cache guilds.
+    ///
+    /// Refer to [`cache_guilds`] for more information.
+    ///
+    /// [`cache_guilds`]: #structfield.cache_guilds
+    pub fn cache_guilds(&mut self, enabled: bool) -> &mut Self {
+        self.cache_guilds = enabled;
+
+        self
+    }
+
+    /// Sets whether to cache channels.
+    ///
+    /// Refer to [`cache_channels`] for more information.
+    ///
+    /// [`cache_channels`]: #structfield.cache_channels
+    pub fn cache_channels(&mut self, enabled: bool) -> &mut Self {
+        self.cache_channels = enabled;
+
+        self
+    }
+
+    /// Sets whether to cache guild members.
+    ///
+    /// Refer to [`cache_members`] for more information.
+    ///
+    /// [`cache_members`]: #structfield.cache_members
+    pub fn cache_members(&mut self, enabled: bool) -> &mut Self {
+        self.cache_members = enabled;
+
+        self
+    }
+
+    /// Sets whether to cache presences.
+    ///
+    /// Refer to [`cache_presences`] for more information.
+    ///
+    /// [`cache_presences`]: #structfield.cache_presences
+    pub fn cache_presences(&mut self, enabled: bool) -> &mut Self {
+        self.cache_presences = enabled;
+
+        self
+    }
+
+    /// Sets whether to cache users.
+    ///
+    /// Refer to [`cache_users`] for more information.
+    ///
+    /// [`cache_users`]: #structfield.cache_users
+    pub fn cache_users(&mut self, enabled: bool) -> &mut Self {
+        self.cache_users = enabled;
+
+        self
+    }
+
+    /// Sets whether to cache the emojis of guilds.
+    ///
+    /// Refer to [`cache_emojis`] for more information.
+    ///
+    /// [`cache_emojis`]: #structfield.cache_emojis
+    pub fn cache_emojis(&mut self, enabled: bool) -> &mut Self {
+        self.cache_emojis = enabled;
+
+        self
+    }
+
+    /// Sets whether to cache the stickers of guilds.
+    ///
+    /// Refer to [`cache_stickers`] for more information.
+    ///
+    /// [`cache_stickers`]: #structfield.cache_stickers
+    pub fn cache_stickers(&mut self, enabled: bool) -> &mut Self {
+        self.cache_stickers = enabled;
+
+        self
+    }
+
+    /// Sets whether to cache the voice states of guilds.
+    ///
+    /// Refer to [`cache_voice_states`] for more information.
+    ///
+    /// [`cache_voice_states`]: #structfield.cache_voice_states
+    pub fn cache_voice_states(&mut self, enabled: bool) -> &mut Self {
+        self.cache_voice_states = enabled;
+
+        self
+    }
+
+    /// Sets whether to cache messages.
+    ///
+    /// Refer to [`cache_messages`] for more information.
+    ///
+    /// [`cache_messages`]: #structfield.cache_messages
+    pub fn cache_messages(&mut self, enabled: bool) -> &mut Self {
+        self.cache_messages = enabled;
+
+        self
+    }
+
+    /// Sets when to evict users from the cache.
+    ///
+    /// Refer to [`user_eviction`] for more information.
+    ///
+    /// [`user_eviction`]: #structfield.user_eviction
+    pub fn user_eviction(&mut self, policy: EvictionPolicy) -> &mut Self {
+        self.user_eviction = policy;
+
+        self
+    }
+
+    /// Sets when to evict members from the cache.
+    ///
+    /// Refer to [`member_eviction`] for more information.
+    ///
+    /// [`member_eviction`]: #structfield.member_eviction
+    pub fn member_eviction(&mut self, policy: EvictionPolicy) -> &mut Self {
+        self.member_eviction = policy;
+
+        self
+    }
+
+    /// Sets when to evict presences from the cache.
+    ///
+    /// Refer to [`presence_eviction`] for more information.
+    ///
+    /// [`presence_eviction`]: #structfield.presence_eviction
+    pub fn presence_eviction(&mut self, policy: EvictionPolicy) -> &mut Self {
+        self.presence_eviction = policy;
+
+        self
+    }
+
+    /// Sets whether to remove users that share no cached guild or private
+    /// channel with the current user anymore.
+    ///
+    /// Refer to [`remove_unreferenced_users`] for more information.
+    ///
+    /// [`remove_unreferenced_users`]: #structfield.remove_unreferenced_users
+    pub fn remove_unreferenced_users(&mut self, enabled: bool) -> &mut Self {
+        self.remove_unreferenced_users = enabled;
+
+        self
+    }
 }
@@ -0,0 +1,201 @@
+use std::io::{BufRead, BufReader, Read, Write};
+
+use serde::{Deserialize, Serialize};
+
+use super::{Cache, CacheBackendExt, CacheError};
+use crate::internal::prelude::*;
+use crate::json;
+use crate::model::event::GuildCreateEvent;
+use crate::model::prelude::*;
+
+/// Starts the first line of every snapshot, followed by the format version.
+const MAGIC: &str = "serenity-cache";
+/// The version of the snapshot format, to be incremented whenever the
+/// [`Snapshot`] changes in an incompatible way.
+const VERSION: u32 = 1;
+
+#[derive(Deserialize, Serialize)]
+struct Snapshot {
+    user: CurrentUser,
+    shard_count: u64,
+    guilds: Vec<Guild>,
+    unavailable_guilds: Vec<GuildId>,
+    private_channels: Vec<PrivateChannel>,
+    users: Vec<User>,
+    /// The cached messages of each channel, from oldest to newest.
+    messages: Vec<Vec<Message>>,
+}
+
+impl Cache {
+    /// Writes the guilds, including their channels, roles and members, along
+    /// with the private channels, users and cached messages to `writer`, for
+    /// a later [`Self::restore`].
+    ///
+    /// The snapshot starts with a header line holding the format version,
+    /// followed by the data as compact JSON.
+    ///
+    /// # Examples
+    ///
+    /// Writing a snapshot to a file on shutdown, and restoring it on start:
+    ///
+    /// ```rust,no_run
+    /// use std::fs::File;
+    /// use std::io::{BufReader, BufWriter};
+    ///
+    /// use serenity::cache::Cache;
+    ///
+    /// # async fn run(cache: &Cache) -> serenity::Result<()> {
+    /// cache.snapshot(BufWriter::new(File::create("cache.snapshot")?)).await?;
+    ///
+    /// // After restarting:
+    /// if let Ok(file) = File::open("cache.snapshot") {
+    ///     cache.restore(BufReader::new(file)).await?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::Io`] if writing failed, or an [`Error::Json`] if
+    /// the data could not be serialized.
+    ///
+    /// [`Error::Io`]: crate::Error::Io
+    /// [`Error::Json`]: crate::Error::Json
+    pub async fn snapshot(&self, mut writer: impl Write) -> Result<()> {
+        let mut guilds = Vec::with_capacity(self.backend.guild_count().await);
+        let mut channel_ids = Vec::new();
+
+        self.backend
+            .for_each_guild(&mut |guild| {
+                channel_ids.extend(guild.channels.keys().copied());
+                channel_ids.extend(guild.threads.iter().map(|thread| thread.id));
+                guilds.push(guild.clone());
+
+                true
+            })
+            .await;
+
+        let private_channels =
+            self.private_channels.iter().map(|channel| channel.clone()).collect::<Vec<_>>();
+        channel_ids.extend(private_channels.iter().map(|channel| channel.id));
+
+        let mut users = Vec::with_capacity(self.backend.user_count().await);
+
+        self.backend
+            .for_each_user(&mut |user| {
+                users.push(user.clone());
+
+                true
+            })
+            .await;
+
+        let mut messages = Vec::new();
+
+        for channel_id in channel_ids {
+            if let Some(channel_messages) = self.backend.channel_messages(channel_id).await {
+                if !channel_messages.is_empty() {
+                    messages.push(channel_messages);
+                }
+            }
+        }
+
+        let snapshot = Snapshot {
+            user: self.user.read().clone(),
+            shard_count: *self.shard_count.read(),
+            guilds,
+            unavailable_guilds: self.unavailable_guilds.iter().map(|id| *id).collect(),
+            private_channels,
+            users,
+            messages,
+        };
+
+        writeln!(writer, "{} {}", MAGIC, VERSION)?;
+        writer.write_all(json::to_string(&snapshot)?.as_bytes())?;
+        writer.flush()?;
+
+        Ok(())
+    }
+
+    /// Reads a snapshot written by [`Self::snapshot`] into the cache.
+    ///
+    /// This is meant to be called before the client is started, so that
+    /// shards that resume their sessions start with a warm cache. Shards that
+    /// IDENTIFY anew receive all guilds again, replacing the restored ones.
+    ///
+    /// The [`Settings`] of the cache apply to the restored data as they do to
+    /// data received from Discord, so resources whose caching is disabled are
+    /// skipped, and the eviction policies are applied.
+    ///
+    /// [`Settings`]: super::Settings
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`CacheError::InvalidSnapshot`] if the data is not a
+    /// snapshot, or a [`CacheError::UnsupportedSnapshotVersion`] if it was
+    /// written by an incompatible version of the library.
+    ///
+    /// Returns an [`Error::Io`] if reading failed, or an [`Error::Json`] if
+    /// the data could not be deserialized.
+    ///
+    /// [`Error::Io`]: crate::Error::Io
+    /// [`Error::Json`]: crate::Error::Json
+    pub async fn restore(&self, reader: impl Read) -> Result<()> {
+        let mut reader = BufReader::new(reader);
+        let mut header = String::new();
+        reader.read_line(&mut header)?;
+
+        let version = header
+            .trim_end()
+            .strip_prefix(MAGIC)
+            .and_then(|version| version.strip_prefix(' '))
+            .and_then(|version| version.parse::<u32>().ok())
+            .ok_or(CacheError::InvalidSnapshot)?;
+
+        if version != VERSION {
+            return Err(CacheError::UnsupportedSnapshotVersion(version).into());
+        }
+
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+        let snapshot: Snapshot = json::from_slice(&mut data)?;
+
+        *self.user.write() = snapshot.user;
+        *self.shard_count.write() = snapshot.shard_count;
+
+        for user in &snapshot.users {
+            self.update_user_entry(user).await;
+        }
+
+        // Handled like a received guild, so that its members and presences
+        // are tracked by the eviction policies.
+        for guild in snapshot.guilds {
+            self.update(&mut GuildCreateEvent {
+                guild,
+            })
+            .await;
+        }
+
+        for guild_id in snapshot.unavailable_guilds {
+            if self.backend.read_guild_with(guild_id, |_| ()).await.is_none() {
+                self.unavailable_guilds.insert(guild_id);
+            }
+        }
+
+        let settings = self.settings();
+
+        if settings.cache_channels {
+            for channel in snapshot.private_channels {
+                self.private_channels.insert(channel.id, channel);
+            }
+        }
+
+        if settings.cache_messages && settings.max_messages > 0 {
+            for message in snapshot.messages.into_iter().flatten() {
+                self.backend.insert_message(message, settings.max_messages).await;
+            }
+        }
+
+        Ok(())
+    }
+}
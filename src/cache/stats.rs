@@ -0,0 +1,47 @@
+use std::mem::size_of;
+
+use crate::model::prelude::*;
+
+/// The number of entries in the [`Cache`], as returned by [`Cache::stats`].
+///
+/// [`Cache`]: super::Cache
+/// [`Cache::stats`]: super::Cache::stats
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct CacheStats {
+    /// The number of guilds.
+    pub guilds: usize,
+    /// The number of guild channels, categories and private channels.
+    pub channels: usize,
+    /// The number of members over all guilds.
+    pub members: usize,
+    /// The number of roles over all guilds.
+    pub roles: usize,
+    /// The number of emojis over all guilds.
+    pub emojis: usize,
+    /// The number of users.
+    pub users: usize,
+    /// The number of presences, both of guilds and not tied to a guild.
+    pub presences: usize,
+    /// The number of messages over all channels.
+    pub messages: usize,
+    /// The approximate memory usage of the entries above, in bytes.
+    ///
+    /// This only accounts for the size of the entries themselves, not for
+    /// the data they own on the heap, such as names, so the actual usage is
+    /// higher.
+    pub approximate_size: usize,
+}
+
+impl CacheStats {
+    pub(crate) fn compute_approximate_size(&mut self) {
+        self.approximate_size = self.guilds * size_of::<Guild>()
+            + self.channels * size_of::<GuildChannel>()
+            + self.members * size_of::<Member>()
+            + self.roles * size_of::<Role>()
+            + self.emojis * size_of::<Emoji>()
+            + self.users * size_of::<User>()
+            + self.presences * size_of::<Presence>()
+            + self.messages * size_of::<Message>();
+    }
+}
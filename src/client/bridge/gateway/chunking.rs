@@ -0,0 +1,350 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::channel::mpsc::UnboundedSender as Sender;
+use tokio::time::Instant;
+
+use crate::gateway::ReconnectType;
+use crate::model::event::{Event, GuildMembersChunkEvent};
+use crate::model::guild::Guild;
+use crate::model::id::GuildId;
+
+/// Which guilds are chunked automatically by [`GuildChunking`].
+#[derive(Clone)]
+#[non_exhaustive]
+pub enum ChunkGuilds {
+    /// Chunk all guilds.
+    All,
+    /// Chunk only guilds that are [large], as these are sent without their
+    /// offline members.
+    ///
+    /// [large]: Guild::large
+    Large,
+    /// Chunk the guilds for which the predicate returns `true`.
+    Predicate(Arc<dyn Fn(&Guild) -> bool + Send + Sync>),
+}
+
+impl ChunkGuilds {
+    /// Chunks the guilds for which `predicate` returns `true`.
+    pub fn predicate<F>(predicate: F) -> Self
+    where
+        F: Fn(&Guild) -> bool + Send + Sync + 'static,
+    {
+        Self::Predicate(Arc::new(predicate))
+    }
+
+    fn applies_to(&self, guild: &Guild) -> bool {
+        match self {
+            Self::All => true,
+            Self::Large => guild.large,
+            Self::Predicate(predicate) => predicate(guild),
+        }
+    }
+}
+
+impl fmt::Debug for ChunkGuilds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::All => f.write_str("All"),
+            Self::Large => f.write_str("Large"),
+            Self::Predicate(_) => f.write_str("Predicate(..)"),
+        }
+    }
+}
+
+/// Automatic chunking of guilds, requesting all of their members once they
+/// are received after a shard connects or the bot joins them.
+///
+/// Each shard requests the members of one guild at a time, at most once per
+/// [`Self::interval`], to stay within the gateway's rate limit. Requires the
+/// [`GatewayIntents::GUILD_MEMBERS`] intent.
+///
+/// # Examples
+///
+/// Chunking large guilds, one every two seconds per shard:
+///
+/// ```rust
+/// use std::time::Duration;
+///
+/// use serenity::client::bridge::gateway::{ChunkGuilds, GuildChunking};
+///
+/// let chunking = GuildChunking::new(ChunkGuilds::Large).interval(Duration::from_secs(2));
+/// ```
+///
+/// [`GatewayIntents::GUILD_MEMBERS`]: crate::model::gateway::GatewayIntents::GUILD_MEMBERS
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct GuildChunking {
+    pub(crate) guilds: ChunkGuilds,
+    pub(crate) interval: Duration,
+}
+
+impl GuildChunking {
+    /// Chunks the given guilds, one per second per shard.
+    pub fn new(guilds: ChunkGuilds) -> Self {
+        Self {
+            guilds,
+            interval: Duration::from_secs(1),
+        }
+    }
+
+    /// Sets the minimum time between two chunk requests of a shard.
+    ///
+    /// Defaults to one second.
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+
+        self
+    }
+}
+
+/// A request for the members of a guild, answered with all chunks of its
+/// nonce.
+struct MemberRequest {
+    sender: Sender<GuildMembersChunkEvent>,
+    /// The number of chunks yet to be received, once known.
+    remaining: Option<u32>,
+}
+
+/// The chunking state of a shard runner: the guilds yet to be chunked
+/// automatically, and the pending member requests.
+#[derive(Default)]
+pub(crate) struct Chunker {
+    chunking: Option<GuildChunking>,
+    queue: VecDeque<GuildId>,
+    /// The guilds in the queue, so that each is queued only once.
+    queued: HashSet<GuildId>,
+    last_chunked: Option<Instant>,
+    requests: HashMap<String, MemberRequest>,
+    next_nonce: u64,
+}
+
+impl Chunker {
+    pub(crate) fn new(chunking: Option<GuildChunking>) -> Self {
+        Self {
+            chunking,
+            ..Self::default()
+        }
+    }
+
+    /// Queues guilds to be chunked automatically, and forwards member chunks
+    /// to the requests of their nonces.
+    pub(crate) fn handle_event(&mut self, event: &Event) {
+        match event {
+            Event::GuildCreate(event) => {
+                if let Some(chunking) = &self.chunking {
+                    if chunking.guilds.applies_to(&event.guild) {
+                        self.enqueue(event.guild.id);
+                    }
+                }
+            },
+            Event::GuildMembersChunk(event) => {
+                let nonce = match &event.nonce {
+                    Some(nonce) => nonce,
+                    None => return,
+                };
+
+                if let Some(request) = self.requests.get_mut(nonce) {
+                    let remaining = request.remaining.get_or_insert(event.chunk_count);
+                    *remaining = remaining.saturating_sub(1);
+
+                    let done = *remaining == 0;
+                    let sent = request.sender.unbounded_send(event.clone()).is_ok();
+
+                    if done || !sent {
+                        self.requests.remove(nonce);
+                    }
+                }
+            },
+            _ => {},
+        }
+    }
+
+    /// Queues a guild to be chunked, unless it already is.
+    fn enqueue(&mut self, guild_id: GuildId) {
+        if self.queued.insert(guild_id) {
+            self.queue.push_back(guild_id);
+        }
+    }
+
+    /// Returns the next guild to chunk automatically, if the interval since
+    /// the last one passed.
+    pub(crate) fn next_guild(&mut self) -> Option<GuildId> {
+        let interval = self.chunking.as_ref()?.interval;
+
+        if self.last_chunked.map_or(false, |last| last.elapsed() < interval) {
+            return None;
+        }
+
+        let guild_id = self.queue.pop_front()?;
+        self.queued.remove(&guild_id);
+        self.last_chunked = Some(Instant::now());
+
+        Some(guild_id)
+    }
+
+    /// Registers a member request, returning the nonce to send it with.
+    pub(crate) fn register(
+        &mut self,
+        shard_id: u64,
+        sender: Sender<GuildMembersChunkEvent>,
+    ) -> String {
+        let nonce = format!("{}-{}", shard_id, self.next_nonce);
+        self.next_nonce += 1;
+
+        self.requests.insert(nonce.clone(), MemberRequest {
+            sender,
+            remaining: None,
+        });
+
+        nonce
+    }
+
+    /// Queues a guild that could not be chunked to be chunked first, once
+    /// the interval passed.
+    pub(crate) fn requeue(&mut self, guild_id: GuildId) {
+        if self.queued.insert(guild_id) {
+            self.queue.push_front(guild_id);
+        }
+    }
+
+    /// Drops a member request that could not be sent, failing it.
+    pub(crate) fn cancel(&mut self, nonce: &str) {
+        self.requests.remove(nonce);
+    }
+
+    /// Drops all pending member requests, failing them, as the remaining
+    /// chunks are not received after the shard reconnected.
+    ///
+    /// When the shard re-identifies, the queued guilds are dropped as well,
+    /// as they are received again in the new session. A resumed session keeps
+    /// them, as they are not.
+    pub(crate) fn interrupt(&mut self, reconnect: &ReconnectType) {
+        self.requests.clear();
+
+        if let ReconnectType::Reidentify = reconnect {
+            self.queue.clear();
+            self.queued.clear();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+    use std::time::Duration;
+
+    use futures::channel::mpsc;
+
+    use super::{ChunkGuilds, Chunker, GuildChunking};
+    use crate::gateway::ReconnectType;
+    use crate::model::event::{Event, GuildMembersChunkEvent};
+    use crate::model::id::GuildId;
+
+    fn chunk(nonce: &str, chunk_index: u32) -> Event {
+        Event::GuildMembersChunk(GuildMembersChunkEvent {
+            guild_id: GuildId(1),
+            members: HashMap::new(),
+            chunk_index,
+            chunk_count: 2,
+            nonce: Some(nonce.to_string()),
+        })
+    }
+
+    #[test]
+    fn test_member_requests() {
+        let mut chunker = Chunker::new(None);
+        let (sender, mut receiver) = mpsc::unbounded();
+        let nonce = chunker.register(0, sender);
+
+        chunker.handle_event(&chunk("other", 0));
+        assert!(receiver.try_next().is_err());
+
+        chunker.handle_event(&chunk(&nonce, 0));
+        chunker.handle_event(&chunk(&nonce, 1));
+        assert_eq!(receiver.try_next().unwrap().unwrap().chunk_index, 0);
+        assert_eq!(receiver.try_next().unwrap().unwrap().chunk_index, 1);
+
+        // The channel is closed after the last chunk.
+        assert!(receiver.try_next().unwrap().is_none());
+        assert!(chunker.requests.is_empty());
+    }
+
+    #[test]
+    fn test_chunk_interval() {
+        let chunking = GuildChunking::new(ChunkGuilds::All).interval(Duration::from_secs(60));
+        let mut chunker = Chunker::new(Some(chunking));
+        chunker.enqueue(GuildId(1));
+        chunker.enqueue(GuildId(2));
+
+        assert_eq!(chunker.next_guild(), Some(GuildId(1)));
+        assert_eq!(chunker.next_guild(), None);
+        assert_eq!(chunker.queue.len(), 1);
+    }
+
+    #[test]
+    fn test_interrupt_member_requests() {
+        let mut chunker = Chunker::new(None);
+        let (sender, mut receiver) = mpsc::unbounded();
+        let nonce = chunker.register(0, sender);
+
+        chunker.handle_event(&chunk(&nonce, 0));
+        chunker.interrupt(&ReconnectType::Resume);
+
+        // The received chunk is kept, but no more are awaited.
+        assert_eq!(receiver.try_next().unwrap().unwrap().chunk_index, 0);
+        assert!(receiver.try_next().unwrap().is_none());
+        assert!(chunker.requests.is_empty());
+    }
+
+    #[test]
+    fn test_requeue_guild() {
+        let chunking = GuildChunking::new(ChunkGuilds::All).interval(Duration::ZERO);
+        let mut chunker = Chunker::new(Some(chunking));
+        chunker.enqueue(GuildId(1));
+        chunker.enqueue(GuildId(2));
+
+        let guild_id = chunker.next_guild().unwrap();
+        chunker.requeue(guild_id);
+
+        assert_eq!(chunker.next_guild(), Some(GuildId(1)));
+        assert_eq!(chunker.next_guild(), Some(GuildId(2)));
+        assert_eq!(chunker.next_guild(), None);
+    }
+
+    #[test]
+    fn test_queue_guild_once() {
+        let chunking = GuildChunking::new(ChunkGuilds::All).interval(Duration::ZERO);
+        let mut chunker = Chunker::new(Some(chunking));
+        chunker.enqueue(GuildId(1));
+        chunker.enqueue(GuildId(2));
+        chunker.enqueue(GuildId(1));
+
+        assert_eq!(chunker.next_guild(), Some(GuildId(1)));
+        assert_eq!(chunker.next_guild(), Some(GuildId(2)));
+        assert_eq!(chunker.next_guild(), None);
+
+        // A chunked guild can be queued again.
+        chunker.enqueue(GuildId(1));
+        assert_eq!(chunker.next_guild(), Some(GuildId(1)));
+    }
+
+    #[test]
+    fn test_interrupt_queue() {
+        let chunking = GuildChunking::new(ChunkGuilds::All).interval(Duration::ZERO);
+        let mut chunker = Chunker::new(Some(chunking));
+        chunker.enqueue(GuildId(1));
+
+        // The guilds are not received again when resuming.
+        chunker.interrupt(&ReconnectType::Resume);
+        assert_eq!(chunker.queue.len(), 1);
+
+        chunker.interrupt(&ReconnectType::Reidentify);
+        assert!(chunker.queue.is_empty());
+
+        chunker.enqueue(GuildId(1));
+        assert_eq!(chunker.next_guild(), Some(GuildId(1)));
+    }
+}
@@ -44,6 +44,11 @@
 
 pub mod event;
 
+mod chunking;
+#[cfg(feature = "remote_shard_coordinator")]
+mod remote_shard_coordinator;
+mod session_store;
+mod shard_coordinator;
 mod shard_manager;
 mod shard_manager_monitor;
 mod shard_messenger;
@@ -54,6 +59,12 @@ mod shard_runner_message;
 use std::fmt;
 use std::time::Duration as StdDuration;
 
+pub(crate) use self::chunking::Chunker;
+pub use self::chunking::{ChunkGuilds, GuildChunking};
+#[cfg(feature = "remote_shard_coordinator")]
+pub use self::remote_shard_coordinator::{RemoteShardCoordinator, ShardCoordinatorServer};
+pub use self::session_store::{FileSessionStore, SessionStore};
+pub use self::shard_coordinator::{InMemoryShardCoordinator, ShardCoordinator};
 pub use self::shard_manager::{ShardManager, ShardManagerOptions};
 pub use self::shard_manager_monitor::{ShardManagerError, ShardManagerMonitor};
 pub use self::shard_messenger::ShardMessenger;
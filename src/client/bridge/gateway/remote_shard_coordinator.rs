@@ -0,0 +1,471 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Range;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use parking_lot::Mutex as SyncMutex;
+use serde::{Deserialize, Serialize};
+use tokio::io::{
+    split,
+    AsyncBufReadExt,
+    AsyncRead,
+    AsyncWrite,
+    AsyncWriteExt,
+    BufReader,
+    WriteHalf,
+};
+#[cfg(unix)]
+use tokio::net::UnixListener;
+use tokio::net::{TcpListener, TcpStream, ToSocketAddrs};
+use tokio::sync::{oneshot, Mutex};
+use tokio::task::JoinHandle;
+use tracing::{debug, warn};
+
+use super::shard_coordinator::coordinator_error;
+use super::{InMemoryShardCoordinator, ShardCoordinator, ShardId};
+use crate::client::ClientError;
+use crate::internal::prelude::*;
+use crate::internal::tokio::spawn_named;
+use crate::json;
+
+/// A request of a [`RemoteShardCoordinator`] to a [`ShardCoordinatorServer`].
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+enum CoordinatorRequest {
+    ClaimShards { total_shards: u64, count: u64 },
+    ReleaseShards { shards: Range<u64> },
+    AcquireIdentify { shard_id: u64, max_concurrency: u64 },
+}
+
+/// The response to a [`CoordinatorRequest`], with the claimed shards if
+/// shards were claimed.
+type CoordinatorResponse = StdResult<Option<Range<u64>>, String>;
+
+/// A [`CoordinatorRequest`] sent as a line of JSON, along with an ID that the
+/// response is sent back with.
+///
+/// Requests are answered as soon as they were handled, so that a process
+/// waiting for an identify slot does not hold up its other requests.
+#[derive(Debug, Deserialize, Serialize)]
+struct RequestFrame {
+    id: u64,
+    request: CoordinatorRequest,
+}
+
+/// A [`CoordinatorResponse`] sent as a line of JSON, along with the ID of the
+/// request it answers.
+#[derive(Debug, Deserialize, Serialize)]
+struct ResponseFrame {
+    id: u64,
+    response: CoordinatorResponse,
+}
+
+trait Connection: AsyncRead + AsyncWrite + Send + Unpin {}
+
+impl<T: AsyncRead + AsyncWrite + Send + Unpin> Connection for T {}
+
+/// The requests of a [`RemoteShardCoordinator`] that were not answered yet.
+#[derive(Default)]
+struct PendingRequests {
+    senders: HashMap<u64, oneshot::Sender<CoordinatorResponse>>,
+    disconnected: bool,
+}
+
+/// A [`ShardCoordinator`] delegating to a [`ShardCoordinatorServer`] over TCP
+/// or a Unix socket.
+///
+/// Shards claimed by a process are released by the server when the process
+/// disconnects, including when it crashed. Requests of different shards are
+/// sent over the same connection, without waiting for each other.
+///
+/// # Examples
+///
+/// Running shards 4 out of 16 shards, alongside other processes connected to
+/// the same server:
+///
+/// ```rust,no_run
+/// use serenity::client::bridge::gateway::RemoteShardCoordinator;
+/// use serenity::prelude::*;
+///
+/// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+/// let coordinator = RemoteShardCoordinator::connect_tcp("10.0.0.1:7777").await?;
+///
+/// let mut client =
+///     Client::builder("token", GatewayIntents::default()).shard_coordinator(coordinator).await?;
+///
+/// client.start_coordinated(16, 4).await?;
+/// #     Ok(())
+/// # }
+/// ```
+pub struct RemoteShardCoordinator {
+    writer: Mutex<WriteHalf<Box<dyn Connection>>>,
+    pending: Arc<SyncMutex<PendingRequests>>,
+    next_id: AtomicU64,
+    /// Reads the responses of the server, for as long as the coordinator
+    /// exists.
+    reader: JoinHandle<()>,
+}
+
+impl RemoteShardCoordinator {
+    /// Connects to a [`ShardCoordinatorServer`] listening on a TCP socket.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] if connecting failed.
+    pub async fn connect_tcp(address: impl ToSocketAddrs) -> Result<Self> {
+        Ok(Self::new(TcpStream::connect(address).await?))
+    }
+
+    /// Connects to a [`ShardCoordinatorServer`] listening on a Unix socket.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] if connecting failed.
+    #[cfg(unix)]
+    pub async fn connect_unix(path: impl AsRef<std::path::Path>) -> Result<Self> {
+        Ok(Self::new(tokio::net::UnixStream::connect(path).await?))
+    }
+
+    fn new(connection: impl Connection + 'static) -> Self {
+        let connection: Box<dyn Connection> = Box::new(connection);
+        let (reader, writer) = split(connection);
+        let pending = Arc::new(SyncMutex::new(PendingRequests::default()));
+
+        let reader = {
+            let pending = Arc::clone(&pending);
+
+            spawn_named("shard_coordinator::responses", async move {
+                let mut reader = BufReader::new(reader);
+
+                loop {
+                    match read_line::<_, ResponseFrame>(&mut reader).await {
+                        Ok(Some(frame)) => {
+                            let sender = pending.lock().senders.remove(&frame.id);
+
+                            if let Some(sender) = sender {
+                                drop(sender.send(frame.response));
+                            }
+                        },
+                        Ok(None) => break,
+                        Err(why) => {
+                            warn!("[Shard Coordinator] Error reading response: {:?}", why);
+
+                            break;
+                        },
+                    }
+                }
+
+                // Dropping the senders fails the requests waiting for them.
+                let mut pending = pending.lock();
+                pending.disconnected = true;
+                pending.senders.clear();
+            })
+        };
+
+        Self {
+            writer: Mutex::new(writer),
+            pending,
+            next_id: AtomicU64::new(0),
+            reader,
+        }
+    }
+
+    async fn request(&self, request: CoordinatorRequest) -> Result<Option<Range<u64>>> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let (tx, rx) = oneshot::channel();
+
+        {
+            let mut pending = self.pending.lock();
+
+            if pending.disconnected {
+                return Err(disconnected());
+            }
+
+            pending.senders.insert(id, tx);
+        }
+
+        let frame = RequestFrame {
+            id,
+            request,
+        };
+
+        if let Err(why) = write_line(&mut *self.writer.lock().await, &frame).await {
+            self.pending.lock().senders.remove(&id);
+
+            return Err(why);
+        }
+
+        match rx.await {
+            Ok(response) => response.map_err(coordinator_error),
+            Err(_) => Err(disconnected()),
+        }
+    }
+}
+
+impl Drop for RemoteShardCoordinator {
+    fn drop(&mut self) {
+        // The reader holds the other half of the connection, so it has to be
+        // stopped for the connection to close.
+        self.reader.abort();
+    }
+}
+
+impl fmt::Debug for RemoteShardCoordinator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RemoteShardCoordinator").finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl ShardCoordinator for RemoteShardCoordinator {
+    async fn claim_shards(&self, total_shards: u64, count: u64) -> Result<Range<u64>> {
+        let request = CoordinatorRequest::ClaimShards {
+            total_shards,
+            count,
+        };
+
+        self.request(request)
+            .await?
+            .ok_or_else(|| coordinator_error("The coordinator server claimed no shards"))
+    }
+
+    async fn release_shards(&self, shards: Range<u64>) -> Result<()> {
+        let request = CoordinatorRequest::ReleaseShards {
+            shards,
+        };
+
+        self.request(request).await.map(drop)
+    }
+
+    async fn acquire_identify(&self, shard_id: ShardId, max_concurrency: u64) -> Result<()> {
+        let request = CoordinatorRequest::AcquireIdentify {
+            shard_id: shard_id.0,
+            max_concurrency,
+        };
+
+        self.request(request).await.map(drop)
+    }
+}
+
+/// A server coordinating the shards of the processes connected to it via a
+/// [`RemoteShardCoordinator`].
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use serenity::client::bridge::gateway::ShardCoordinatorServer;
+/// use tokio::net::TcpListener;
+///
+/// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+/// let listener = TcpListener::bind("0.0.0.0:7777").await?;
+///
+/// ShardCoordinatorServer::new().serve_tcp(listener).await?;
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct ShardCoordinatorServer {
+    coordinator: Arc<InMemoryShardCoordinator>,
+}
+
+impl ShardCoordinatorServer {
+    /// Creates a server without any claimed shards.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Serves processes connecting to the TCP listener, until accepting a
+    /// connection fails.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] if accepting a connection failed.
+    pub async fn serve_tcp(self, listener: TcpListener) -> Result<()> {
+        loop {
+            let (stream, _) = listener.accept().await?;
+
+            self.serve(stream);
+        }
+    }
+
+    /// Serves processes connecting to the Unix socket listener, until
+    /// accepting a connection fails.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] if accepting a connection failed.
+    #[cfg(unix)]
+    pub async fn serve_unix(self, listener: UnixListener) -> Result<()> {
+        loop {
+            let (stream, _) = listener.accept().await?;
+
+            self.serve(stream);
+        }
+    }
+
+    fn serve(&self, connection: impl Connection + 'static) {
+        let coordinator = Arc::clone(&self.coordinator);
+
+        spawn_named("shard_coordinator::connection", async move {
+            let mut claimed = Vec::new();
+
+            if let Err(why) = serve_connection(&coordinator, connection, &mut claimed).await {
+                debug!("[Shard Coordinator] Connection closed with error: {:?}", why);
+            }
+
+            // The process disconnected, so it no longer runs its shards.
+            for shards in claimed {
+                drop(coordinator.release_shards(shards).await);
+            }
+        });
+    }
+}
+
+async fn serve_connection<C: Connection + 'static>(
+    coordinator: &Arc<InMemoryShardCoordinator>,
+    connection: C,
+    claimed: &mut Vec<Range<u64>>,
+) -> Result<()> {
+    let (reader, writer) = split(connection);
+    let mut reader = BufReader::new(reader);
+    let writer = Arc::new(Mutex::new(writer));
+
+    while let Some(RequestFrame {
+        id,
+        request,
+    }) = read_line(&mut reader).await?
+    {
+        let response = match request {
+            CoordinatorRequest::ClaimShards {
+                total_shards,
+                count,
+            } => coordinator.claim_shards(total_shards, count).await.map(|shards| {
+                claimed.push(shards.clone());
+
+                Some(shards)
+            }),
+            CoordinatorRequest::ReleaseShards {
+                shards,
+            } => {
+                claimed.retain(|claim| *claim != shards);
+
+                coordinator.release_shards(shards).await.map(|()| None)
+            },
+            // Waiting for an identify slot may take a while, so other requests
+            // are handled in the meantime.
+            CoordinatorRequest::AcquireIdentify {
+                shard_id,
+                max_concurrency,
+            } => {
+                let coordinator = Arc::clone(coordinator);
+                let writer = Arc::clone(&writer);
+
+                spawn_named("shard_coordinator::acquire_identify", async move {
+                    let response = coordinator
+                        .acquire_identify(ShardId(shard_id), max_concurrency)
+                        .await
+                        .map(|()| None);
+
+                    let frame = ResponseFrame {
+                        id,
+                        response: into_response(response),
+                    };
+
+                    if let Err(why) = write_line(&mut *writer.lock().await, &frame).await {
+                        debug!("[Shard Coordinator] Failed to send identify slot: {:?}", why);
+                    }
+                });
+
+                continue;
+            },
+        };
+
+        let frame = ResponseFrame {
+            id,
+            response: into_response(response),
+        };
+
+        write_line(&mut *writer.lock().await, &frame).await?;
+    }
+
+    Ok(())
+}
+
+fn into_response(result: Result<Option<Range<u64>>>) -> CoordinatorResponse {
+    result.map_err(|why| match why {
+        Error::Client(ClientError::Coordinator(message)) => message,
+        why => {
+            warn!("[Shard Coordinator] Error handling request: {:?}", why);
+
+            why.to_string()
+        },
+    })
+}
+
+fn disconnected() -> Error {
+    coordinator_error("The coordinator server disconnected")
+}
+
+async fn write_line<W, T>(writer: &mut W, value: &T) -> Result<()>
+where
+    W: AsyncWrite + Unpin + ?Sized,
+    T: Serialize,
+{
+    let mut line = json::to_string(value)?;
+    line.push('\n');
+
+    writer.write_all(line.as_bytes()).await?;
+    writer.flush().await?;
+
+    Ok(())
+}
+
+/// Reads a line of JSON, returning `None` if the connection was closed.
+async fn read_line<R, T>(reader: &mut R) -> Result<Option<T>>
+where
+    R: AsyncBufReadExt + Unpin + ?Sized,
+    T: for<'de> Deserialize<'de>,
+{
+    let mut line = String::new();
+
+    if reader.read_line(&mut line).await? == 0 {
+        return Ok(None);
+    }
+
+    json::from_str(&mut line).map(Some)
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use tokio::io::duplex;
+    use tokio::time::{timeout, Instant};
+
+    use super::{RemoteShardCoordinator, ShardCoordinatorServer};
+    use crate::client::bridge::gateway::{ShardCoordinator, ShardId};
+
+    #[tokio::test(start_paused = true)]
+    async fn test_requests_do_not_wait_for_identify_slots() {
+        let (client, server) = duplex(1024);
+        ShardCoordinatorServer::new().serve(server);
+        let coordinator = RemoteShardCoordinator::new(client);
+
+        coordinator.acquire_identify(ShardId(0), 1).await.unwrap();
+
+        // The next slot of the bucket is 5 seconds away, which must not hold
+        // up claiming shards.
+        let started = Instant::now();
+        let (identify, claim) = tokio::join!(
+            coordinator.acquire_identify(ShardId(1), 1),
+            timeout(Duration::from_secs(1), coordinator.claim_shards(2, 2)),
+        );
+
+        assert_eq!(claim.unwrap().unwrap(), 0..2);
+        identify.unwrap();
+        assert!(started.elapsed() >= Duration::from_secs(4));
+    }
+}
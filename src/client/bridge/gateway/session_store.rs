@@ -0,0 +1,190 @@
+use std::collections::HashMap;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tokio::fs;
+use tokio::sync::Mutex;
+
+use super::ShardId;
+use crate::gateway::SessionInfo;
+use crate::internal::prelude::*;
+use crate::json;
+
+/// Persists the gateway sessions of shards, so that they can be resumed
+/// instead of IDENTIFYing again after the process restarted.
+///
+/// The [`ShardQueuer`] loads the session of a shard before starting it, and
+/// the [`ShardRunner`] saves the session when it changes, every 30 seconds
+/// while events are received, and when the shard is shut down with a
+/// resumable close code. Sessions that can no longer be resumed are removed.
+///
+/// A session saved a while before the process stopped can still be resumed,
+/// Discord then replays the events that were missed since. Unless the shard
+/// was shut down gracefully, the saved sequence number may be up to 30 seconds
+/// old, so events received in that window are dispatched again after the
+/// restart.
+///
+/// A session is only valid for the shard total it was created with: after
+/// resharding, the session of a shard with the same ID belongs to a different
+/// shard, and resuming it fails. Stores therefore must not load a session that
+/// was saved with a different shard total.
+///
+/// Refer to [`FileSessionStore`] for a store backed by a file.
+///
+/// [`ShardQueuer`]: super::ShardQueuer
+/// [`ShardRunner`]: super::ShardRunner
+#[async_trait]
+pub trait SessionStore: Send + Sync {
+    /// Loads the session of a shard, if one was saved with the same shard
+    /// total.
+    async fn load(&self, shard_id: ShardId, shard_total: u64) -> Result<Option<SessionInfo>>;
+
+    /// Saves the session of a shard, replacing any previously saved one.
+    async fn save(&self, shard_id: ShardId, shard_total: u64, session: &SessionInfo) -> Result<()>;
+
+    /// Removes the session of a shard, if one was saved.
+    async fn remove(&self, shard_id: ShardId) -> Result<()>;
+}
+
+/// A [`SessionStore`] keeping the sessions of all shards in a single JSON file.
+///
+/// The file is created when the first session is saved, and replaced
+/// atomically on every write. Sessions saved with a different shard total than
+/// the one being loaded are dropped.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use serenity::client::bridge::gateway::FileSessionStore;
+/// use serenity::prelude::*;
+///
+/// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = Client::builder("token", GatewayIntents::default())
+///     .session_store(FileSessionStore::new("sessions.json"))
+///     .await?;
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct FileSessionStore {
+    path: PathBuf,
+    /// Serializes read-modify-write cycles of the file.
+    lock: Mutex<()>,
+}
+
+impl FileSessionStore {
+    /// Creates a store using the file at the given path.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            lock: Mutex::new(()),
+        }
+    }
+
+    async fn read(&self) -> Result<HashMap<String, StoredSession>> {
+        match fs::read_to_string(&self.path).await {
+            Ok(mut contents) => json::from_str(&mut contents),
+            Err(why) if why.kind() == ErrorKind::NotFound => Ok(HashMap::new()),
+            Err(why) => Err(why.into()),
+        }
+    }
+
+    async fn write(&self, sessions: &HashMap<String, StoredSession>) -> Result<()> {
+        let temp = self.path.with_extension("tmp");
+
+        fs::write(&temp, json::to_string(sessions)?).await?;
+        fs::rename(&temp, &self.path).await?;
+
+        Ok(())
+    }
+}
+
+/// A session in a [`FileSessionStore`], along with the shard total it was
+/// saved with.
+#[derive(Deserialize, Serialize)]
+struct StoredSession {
+    shard_total: u64,
+    session: SessionInfo,
+}
+
+#[async_trait]
+impl SessionStore for FileSessionStore {
+    async fn load(&self, shard_id: ShardId, shard_total: u64) -> Result<Option<SessionInfo>> {
+        let _lock = self.lock.lock().await;
+
+        let mut sessions = self.read().await?;
+        let stored = match sessions.remove(&shard_id.to_string()) {
+            Some(stored) => stored,
+            None => return Ok(None),
+        };
+
+        if stored.shard_total == shard_total {
+            return Ok(Some(stored.session));
+        }
+
+        self.write(&sessions).await?;
+
+        Ok(None)
+    }
+
+    async fn save(&self, shard_id: ShardId, shard_total: u64, session: &SessionInfo) -> Result<()> {
+        let _lock = self.lock.lock().await;
+
+        let mut sessions = self.read().await?;
+        sessions.insert(shard_id.to_string(), StoredSession {
+            shard_total,
+            session: session.clone(),
+        });
+
+        self.write(&sessions).await
+    }
+
+    async fn remove(&self, shard_id: ShardId) -> Result<()> {
+        let _lock = self.lock.lock().await;
+
+        let mut sessions = self.read().await?;
+
+        if sessions.remove(&shard_id.to_string()).is_some() {
+            self.write(&sessions).await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{FileSessionStore, SessionStore};
+    use crate::client::bridge::gateway::ShardId;
+    use crate::gateway::SessionInfo;
+
+    #[tokio::test]
+    async fn test_file_session_store() {
+        let path =
+            std::env::temp_dir().join(format!("serenity-sessions-{}.json", std::process::id()));
+        let store = FileSessionStore::new(&path);
+        let session = SessionInfo {
+            session_id: "abc".to_string(),
+            seq: 42,
+            resume_gateway_url: Some("wss://resume.example".to_string()),
+        };
+
+        assert_eq!(store.load(ShardId(0), 2).await.unwrap(), None);
+
+        store.save(ShardId(0), 2, &session).await.unwrap();
+        store.save(ShardId(1), 2, &SessionInfo::new("def", 1)).await.unwrap();
+        assert_eq!(store.load(ShardId(0), 2).await.unwrap(), Some(session));
+
+        store.remove(ShardId(0)).await.unwrap();
+        assert_eq!(store.load(ShardId(0), 2).await.unwrap(), None);
+        assert_eq!(store.load(ShardId(1), 2).await.unwrap(), Some(SessionInfo::new("def", 1)));
+
+        // After resharding, the session belongs to a different shard.
+        assert_eq!(store.load(ShardId(1), 4).await.unwrap(), None);
+        assert_eq!(store.load(ShardId(1), 2).await.unwrap(), None);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,175 @@
+use std::collections::HashMap;
+use std::ops::Range;
+
+use async_trait::async_trait;
+use tokio::sync::Mutex;
+use tokio::time::{sleep_until, Duration, Instant};
+
+use super::ShardId;
+use crate::client::ClientError;
+use crate::internal::prelude::*;
+
+/// The interval that IDENTIFYs within the same rate limit bucket must be
+/// spaced apart by.
+const IDENTIFY_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Coordinates the shards of a bot that is scaled across multiple processes.
+///
+/// The [`ShardQueuer`] acquires an identify slot from the coordinator before
+/// starting each shard, and the [`ShardRunner`] before any later IDENTIFY of
+/// the shard, such as after its session was invalidated. This way the
+/// processes together do not IDENTIFY more than `max_concurrency` shards per 5
+/// seconds. [`Client::start_coordinated`]
+/// additionally claims the range of shards that a process runs from the
+/// coordinator, so that no two processes run the same shard.
+///
+/// [`InMemoryShardCoordinator`] coordinates the shards within a single
+/// process, and is used by default. To coordinate multiple processes, run a
+/// [`ShardCoordinatorServer`] and connect each process to it via a
+/// [`RemoteShardCoordinator`], which requires the
+/// `remote_shard_coordinator` feature.
+///
+/// [`ShardCoordinatorServer`]: super::ShardCoordinatorServer
+/// [`RemoteShardCoordinator`]: super::RemoteShardCoordinator
+/// [`ShardQueuer`]: super::ShardQueuer
+/// [`ShardRunner`]: super::ShardRunner
+/// [`Client::start_coordinated`]: crate::Client::start_coordinated
+#[async_trait]
+pub trait ShardCoordinator: Send + Sync {
+    /// Claims `count` consecutive shards out of `total_shards`, which must be
+    /// the same across all processes.
+    ///
+    /// Returns [`ClientError::Coordinator`] if no such range of shards is
+    /// left unclaimed.
+    async fn claim_shards(&self, total_shards: u64, count: u64) -> Result<Range<u64>>;
+
+    /// Releases shards claimed via [`Self::claim_shards`], so that another
+    /// process can run them.
+    async fn release_shards(&self, shards: Range<u64>) -> Result<()>;
+
+    /// Waits until the shard may IDENTIFY, which is 5 seconds after the last
+    /// shard of the same rate limit bucket (`shard_id % max_concurrency`) did.
+    async fn acquire_identify(&self, shard_id: ShardId, max_concurrency: u64) -> Result<()>;
+}
+
+/// A [`ShardCoordinator`] coordinating the shards within a single process.
+///
+/// This is also what a [`ShardCoordinatorServer`] coordinates the shards of
+/// its connected processes with.
+///
+/// [`ShardCoordinatorServer`]: super::ShardCoordinatorServer
+#[derive(Debug, Default)]
+pub struct InMemoryShardCoordinator {
+    state: Mutex<CoordinatorState>,
+}
+
+#[derive(Debug, Default)]
+struct CoordinatorState {
+    total_shards: u64,
+    claimed: Vec<Range<u64>>,
+    /// The instants that the last IDENTIFY of each rate limit bucket was
+    /// scheduled at.
+    identifies: HashMap<u64, Instant>,
+}
+
+impl InMemoryShardCoordinator {
+    /// Creates a coordinator without any claimed shards.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl ShardCoordinator for InMemoryShardCoordinator {
+    async fn claim_shards(&self, total_shards: u64, count: u64) -> Result<Range<u64>> {
+        let mut state = self.state.lock().await;
+
+        if state.claimed.is_empty() {
+            state.total_shards = total_shards;
+        } else if state.total_shards != total_shards {
+            return Err(coordinator_error(format!(
+                "{} total shards were claimed from, not {}",
+                state.total_shards, total_shards
+            )));
+        }
+
+        if count == 0 {
+            return Err(coordinator_error("Can not claim zero shards"));
+        }
+
+        // Claims either start at zero or right after another claim.
+        let start = std::iter::once(0)
+            .chain(state.claimed.iter().map(|claim| claim.end))
+            .filter(|start| {
+                let end = start + count;
+
+                end <= total_shards
+                    && state.claimed.iter().all(|claim| end <= claim.start || claim.end <= *start)
+            })
+            .min()
+            .ok_or_else(|| {
+                coordinator_error(format!(
+                    "No {} consecutive shards out of {} are left unclaimed",
+                    count, total_shards
+                ))
+            })?;
+
+        state.claimed.push(start..start + count);
+
+        Ok(start..start + count)
+    }
+
+    async fn release_shards(&self, shards: Range<u64>) -> Result<()> {
+        self.state.lock().await.claimed.retain(|claim| *claim != shards);
+
+        Ok(())
+    }
+
+    async fn acquire_identify(&self, shard_id: ShardId, max_concurrency: u64) -> Result<()> {
+        let bucket = shard_id.0 % max_concurrency.max(1);
+
+        // Schedule the IDENTIFY while holding the lock, so that concurrent
+        // callers of the same bucket are spaced apart as well.
+        let slot = {
+            let mut state = self.state.lock().await;
+            let now = Instant::now();
+            let slot = match state.identifies.get(&bucket) {
+                Some(last) => now.max(*last + IDENTIFY_INTERVAL),
+                None => now,
+            };
+
+            state.identifies.insert(bucket, slot);
+
+            slot
+        };
+
+        sleep_until(slot).await;
+
+        Ok(())
+    }
+}
+
+pub(super) fn coordinator_error(message: impl Into<String>) -> Error {
+    Error::Client(ClientError::Coordinator(message.into()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{InMemoryShardCoordinator, ShardCoordinator};
+
+    #[tokio::test]
+    async fn test_claim_shards() {
+        let coordinator = InMemoryShardCoordinator::new();
+
+        assert_eq!(coordinator.claim_shards(10, 4).await.unwrap(), 0..4);
+        assert_eq!(coordinator.claim_shards(10, 4).await.unwrap(), 4..8);
+        assert!(coordinator.claim_shards(10, 4).await.is_err());
+        assert!(coordinator.claim_shards(12, 2).await.is_err());
+
+        coordinator.release_shards(0..4).await.unwrap();
+        assert_eq!(coordinator.claim_shards(10, 2).await.unwrap(), 0..2);
+        assert_eq!(coordinator.claim_shards(10, 2).await.unwrap(), 2..4);
+        assert_eq!(coordinator.claim_shards(10, 2).await.unwrap(), 8..10);
+    }
+}
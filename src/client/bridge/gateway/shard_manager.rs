@@ -9,6 +9,10 @@ use tracing::{info, instrument, warn};
 use typemap_rev::TypeMap;
 
 use super::{
+    GuildChunking,
+    InMemoryShardCoordinator,
+    SessionStore,
+    ShardCoordinator,
     ShardId,
     ShardManagerMessage,
     ShardManagerMonitor,
@@ -18,9 +22,10 @@ use super::{
 };
 #[cfg(feature = "voice")]
 use crate::client::bridge::voice::VoiceGatewayManager;
-use crate::client::{EventHandler, RawEventHandler};
+use crate::client::{DispatchTasks, EventHandler, EventMiddleware, RawEventHandler};
 #[cfg(feature = "framework")]
 use crate::framework::Framework;
+use crate::gateway::{GatewayEncoding, TransportCompression};
 use crate::internal::prelude::*;
 use crate::internal::tokio::spawn_named;
 use crate::model::gateway::GatewayIntents;
@@ -52,8 +57,9 @@ use crate::CacheAndHttp;
 /// use std::sync::Arc;
 ///
 /// use serenity::client::bridge::gateway::{ShardManager, ShardManagerOptions};
-/// use serenity::client::{EventHandler, RawEventHandler};
+/// use serenity::client::{DispatchTasks, EventHandler, RawEventHandler};
 /// use serenity::framework::{Framework, StandardFramework};
+/// use serenity::gateway::{GatewayEncoding, TransportCompression};
 /// use serenity::http::Http;
 /// use serenity::model::gateway::GatewayIntents;
 /// use serenity::prelude::*;
@@ -75,8 +81,10 @@ use crate::CacheAndHttp;
 ///
 /// ShardManager::new(ShardManagerOptions {
 ///     data: &data,
-///     event_handler: &Some(event_handler),
-///     raw_event_handler: &None,
+///     event_handlers: &[event_handler],
+///     raw_event_handlers: &[],
+///     middleware: &[],
+///     dispatch_tasks: &Arc::new(DispatchTasks::default()),
 ///     framework: &framework,
 ///     // the shard index to start initiating from
 ///     shard_index: 0,
@@ -89,6 +97,11 @@ use crate::CacheAndHttp;
 ///     ws_url: &gateway_url,
 ///     # cache_and_http: &cache_and_http,
 ///     intents: GatewayIntents::non_privileged(),
+///     compression: TransportCompression::None,
+///     encoding: GatewayEncoding::Json,
+///     session_store: &None,
+///     shard_coordinator: &None,
+///     chunking: &None,
 /// });
 /// #     Ok(())
 /// # }
@@ -126,11 +139,18 @@ impl ShardManager {
 
         let mut shard_queuer = ShardQueuer {
             data: Arc::clone(opt.data),
-            event_handler: opt.event_handler.as_ref().map(Arc::clone),
-            raw_event_handler: opt.raw_event_handler.as_ref().map(Arc::clone),
+            event_handlers: opt.event_handlers.to_vec(),
+            raw_event_handlers: opt.raw_event_handlers.to_vec(),
+            middleware: opt.middleware.to_vec(),
+            dispatch_tasks: Arc::clone(opt.dispatch_tasks),
             #[cfg(feature = "framework")]
             framework: Arc::clone(opt.framework),
-            last_start: None,
+            coordinator: opt
+                .shard_coordinator
+                .clone()
+                .unwrap_or_else(|| Arc::new(InMemoryShardCoordinator::new())),
+            session_start_limit: None,
+            session_start_reset: None,
             manager_tx: thread_tx.clone(),
             queue: VecDeque::new(),
             runners: Arc::clone(&runners),
@@ -140,6 +160,10 @@ impl ShardManager {
             ws_url: Arc::clone(opt.ws_url),
             cache_and_http: Arc::clone(opt.cache_and_http),
             intents: opt.intents,
+            compression: opt.compression,
+            encoding: opt.encoding,
+            session_store: opt.session_store.clone(),
+            chunking: opt.chunking.clone(),
         };
 
         spawn_named("shard_queuer::run", async move {
@@ -301,20 +325,25 @@ impl ShardManager {
     /// over the [`Self::shutdown`] method.
     #[instrument(skip(self))]
     pub async fn shutdown_all(&mut self) {
-        let keys = {
-            let runners = self.runners.lock().await;
+        if self.runners.lock().await.is_empty() {
+            return;
+        }
 
-            if runners.is_empty() {
-                return;
-            }
+        self.shutdown_all_with_code(1000).await;
+    }
 
-            runners.keys().copied().collect::<Vec<_>>()
-        };
+    /// Shuts down all shards with the given close code, then stops the
+    /// [`ShardQueuer`] and the [`ShardManagerMonitor`], even if no shard was
+    /// running.
+    ///
+    /// [`ShardQueuer`]: super::ShardQueuer
+    pub(crate) async fn shutdown_all_with_code(&mut self, code: u16) {
+        let keys = self.runners.lock().await.keys().copied().collect::<Vec<_>>();
 
         info!("Shutting down all shards");
 
         for shard_id in keys {
-            self.shutdown(shard_id, 1000).await;
+            self.shutdown(shard_id, code).await;
         }
 
         drop(self.shard_queuer.unbounded_send(ShardQueuerMessage::Shutdown));
@@ -346,8 +375,10 @@ impl Drop for ShardManager {
 
 pub struct ShardManagerOptions<'a> {
     pub data: &'a Arc<RwLock<TypeMap>>,
-    pub event_handler: &'a Option<Arc<dyn EventHandler>>,
-    pub raw_event_handler: &'a Option<Arc<dyn RawEventHandler>>,
+    pub event_handlers: &'a [Arc<dyn EventHandler>],
+    pub raw_event_handlers: &'a [Arc<dyn RawEventHandler>],
+    pub middleware: &'a [Arc<dyn EventMiddleware>],
+    pub dispatch_tasks: &'a Arc<DispatchTasks>,
     #[cfg(feature = "framework")]
     pub framework: &'a Arc<dyn Framework + Send + Sync>,
     pub shard_index: u64,
@@ -358,4 +389,13 @@ pub struct ShardManagerOptions<'a> {
     pub ws_url: &'a Arc<Mutex<String>>,
     pub cache_and_http: &'a Arc<CacheAndHttp>,
     pub intents: GatewayIntents,
+    pub compression: TransportCompression,
+    pub encoding: GatewayEncoding,
+    pub session_store: &'a Option<Arc<dyn SessionStore>>,
+    /// The coordinator that identify slots are acquired from, or `None` to
+    /// coordinate the shards of only this manager.
+    pub shard_coordinator: &'a Option<Arc<dyn ShardCoordinator>>,
+    /// The guilds that shards chunk automatically, or `None` to not chunk
+    /// guilds automatically.
+    pub chunking: &'a Option<GuildChunking>,
 }
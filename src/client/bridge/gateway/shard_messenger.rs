@@ -1,5 +1,6 @@
 use async_tungstenite::tungstenite::Message;
-use futures::channel::mpsc::{TrySendError, UnboundedSender as Sender};
+use futures::channel::mpsc::{self, TrySendError, UnboundedSender as Sender};
+use futures::StreamExt;
 
 use super::{ChunkGuildFilter, ShardClientMessage, ShardRunnerMessage};
 #[cfg(feature = "collector")]
@@ -10,7 +11,7 @@ use crate::collector::{
     ModalInteractionFilter,
     ReactionFilter,
 };
-use crate::gateway::InterMessage;
+use crate::gateway::{GatewayError, InterMessage};
 use crate::model::prelude::*;
 
 /// A lightweight wrapper around an mpsc sender.
@@ -119,6 +120,72 @@ impl ShardMessenger {
         }));
     }
 
+    /// Requests the members of a [`Guild`], resolving with the members of all
+    /// [`Event::GuildMembersChunk`] events sent in response.
+    ///
+    /// Unlike [`Self::chunk_guild`], the chunks are correlated with the
+    /// request by a nonce that is generated for it.
+    ///
+    /// **Note**: Requesting members with [`ChunkGuildFilter::None`] requires
+    /// the [`GatewayIntents::GUILD_MEMBERS`] intent.
+    ///
+    /// # Examples
+    ///
+    /// Requesting up to 10 members whose username starts with `"ser"`:
+    ///
+    /// ```rust,no_run
+    /// use serenity::client::bridge::gateway::ChunkGuildFilter;
+    /// use serenity::model::id::GuildId;
+    /// # use serenity::client::Context;
+    ///
+    /// # async fn run(ctx: Context) -> serenity::Result<()> {
+    /// let members = ctx
+    ///     .shard
+    ///     .request_members(GuildId(7), Some(10), ChunkGuildFilter::Query("ser".to_string()))
+    ///     .await?;
+    ///
+    /// println!("Found {} members", members.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`GatewayError::MemberRequestInterrupted`] if the shard
+    /// stopped or reconnected before all chunks were received.
+    ///
+    /// [`GatewayIntents::GUILD_MEMBERS`]: crate::model::gateway::GatewayIntents::GUILD_MEMBERS
+    pub async fn request_members(
+        &self,
+        guild_id: impl Into<GuildId>,
+        limit: Option<u16>,
+        filter: ChunkGuildFilter,
+    ) -> crate::Result<Vec<Member>> {
+        let (sender, mut receiver) = mpsc::unbounded();
+
+        self.send_to_shard(ShardRunnerMessage::RequestMembers {
+            guild_id: guild_id.into(),
+            limit,
+            filter,
+            sender,
+        })
+        .map_err(|_| GatewayError::MemberRequestInterrupted)?;
+
+        let mut members = Vec::new();
+        let mut chunk_count = 0;
+
+        while let Some(chunk) = receiver.next().await {
+            members.extend(chunk.members.into_iter().map(|(_, member)| member));
+            chunk_count += 1;
+
+            if chunk_count == chunk.chunk_count {
+                return Ok(members);
+            }
+        }
+
+        Err(GatewayError::MemberRequestInterrupted.into())
+    }
+
     /// Sets the user's current activity, if any.
     ///
     /// Other presence settings are maintained.
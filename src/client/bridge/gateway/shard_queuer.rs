@@ -1,15 +1,19 @@
-use std::collections::{HashMap, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::Arc;
 
 use futures::channel::mpsc::{UnboundedReceiver as Receiver, UnboundedSender as Sender};
-use futures::StreamExt;
+use futures::future::join_all;
+use futures::{FutureExt, StreamExt};
 use tokio::sync::{Mutex, RwLock};
-use tokio::time::{sleep, timeout, Duration, Instant};
+use tokio::time::{sleep_until, Duration, Instant};
 use tracing::{debug, info, instrument, warn};
 use typemap_rev::TypeMap;
 
 use super::{
+    GuildChunking,
+    SessionStore,
     ShardClientMessage,
+    ShardCoordinator,
     ShardId,
     ShardManagerMessage,
     ShardMessenger,
@@ -20,13 +24,13 @@ use super::{
 };
 #[cfg(feature = "voice")]
 use crate::client::bridge::voice::VoiceGatewayManager;
-use crate::client::{EventHandler, RawEventHandler};
+use crate::client::{DispatchTasks, EventHandler, EventMiddleware, RawEventHandler};
 #[cfg(feature = "framework")]
 use crate::framework::Framework;
-use crate::gateway::{ConnectionStage, InterMessage, Shard};
+use crate::gateway::{ConnectionStage, GatewayEncoding, InterMessage, Shard, TransportCompression};
 use crate::internal::prelude::*;
 use crate::internal::tokio::spawn_named;
-use crate::model::gateway::GatewayIntents;
+use crate::model::gateway::{GatewayIntents, SessionStartLimit};
 use crate::CacheAndHttp;
 
 const WAIT_BETWEEN_BOOTS_IN_SECONDS: u64 = 5;
@@ -34,32 +38,59 @@ const WAIT_BETWEEN_BOOTS_IN_SECONDS: u64 = 5;
 /// The shard queuer is a simple loop that runs indefinitely to manage the
 /// startup of shards.
 ///
+/// Shards are started in rate limit buckets of `shard_id % max_concurrency`,
+/// as given by the [`SessionStartLimit`] of the bot. The queued shards of
+/// different buckets are started together, while shards of the same bucket
+/// are started 5 seconds apart, as scheduled by the [`ShardCoordinator`].
+/// When the bot is out of session starts, the queuer waits for the limit to
+/// reset.
+///
 /// A shard queuer instance _should_ be run in its own thread, due to the
-/// blocking nature of the loop itself as well as a 5 second thread sleep
-/// between shard starts.
+/// blocking nature of the loop itself as well as the sleeps between shard
+/// starts.
 pub struct ShardQueuer {
     /// A copy of [`Client::data`] to be given to runners for contextual
     /// dispatching.
     ///
     /// [`Client::data`]: crate::Client::data
     pub data: Arc<RwLock<TypeMap>>,
-    /// A reference to an [`EventHandler`], such as the one given to the
-    /// [`Client`].
+    /// References to the [`EventHandler`]s, such as the ones given to the
+    /// [`Client`], in dispatch order.
+    ///
+    /// [`Client`]: crate::Client
+    pub event_handlers: Vec<Arc<dyn EventHandler>>,
+    /// References to the [`RawEventHandler`]s, such as the ones given to the
+    /// [`Client`], in dispatch order.
+    ///
+    /// [`Client`]: crate::Client
+    pub raw_event_handlers: Vec<Arc<dyn RawEventHandler>>,
+    /// References to the [`EventMiddleware`], such as the ones given to the
+    /// [`Client`], in the order they are run.
     ///
     /// [`Client`]: crate::Client
-    pub event_handler: Option<Arc<dyn EventHandler>>,
-    /// A reference to an [`RawEventHandler`], such as the one given to the
-    /// [`Client`].
+    pub middleware: Vec<Arc<dyn EventMiddleware>>,
+    /// The tasks that events are dispatched to handlers in, such as the ones
+    /// of the [`Client`].
     ///
     /// [`Client`]: crate::Client
-    pub raw_event_handler: Option<Arc<dyn RawEventHandler>>,
+    pub dispatch_tasks: Arc<DispatchTasks>,
     /// A copy of the framework
     #[cfg(feature = "framework")]
     pub framework: Arc<dyn Framework + Send + Sync>,
-    /// The instant that a shard was last started.
+    /// The coordinator that identify slots are acquired from before starting
+    /// shards.
     ///
     /// This is used to determine how long to wait between shard IDENTIFYs.
-    pub last_start: Option<Instant>,
+    pub coordinator: Arc<dyn ShardCoordinator>,
+    /// The session start limit of the bot, fetched before the first shard is
+    /// started and whenever its rate limit period reset.
+    ///
+    /// The remaining session starts are counted down locally as shards are
+    /// started.
+    pub session_start_limit: Option<SessionStartLimit>,
+    /// The instant that the rate limit period of the
+    /// [`Self::session_start_limit`] resets at.
+    pub session_start_reset: Option<Instant>,
     /// A copy of the sender channel to communicate with the
     /// [`ShardManagerMonitor`].
     ///
@@ -80,6 +111,15 @@ pub struct ShardQueuer {
     pub ws_url: Arc<Mutex<String>>,
     pub cache_and_http: Arc<CacheAndHttp>,
     pub intents: GatewayIntents,
+    /// The transport compression that shards negotiate with the gateway.
+    pub compression: TransportCompression,
+    /// The encoding of the payloads that shards exchange with the gateway.
+    pub encoding: GatewayEncoding,
+    /// The store that sessions are loaded from before starting shards, so that
+    /// they can be resumed.
+    pub session_store: Option<Arc<dyn SessionStore>>,
+    /// The guilds that shards chunk automatically, if any.
+    pub chunking: Option<GuildChunking>,
 }
 
 impl ShardQueuer {
@@ -88,96 +128,263 @@ impl ShardQueuer {
     /// This will loop over the internal [`Self::rx`] for [`ShardQueuerMessage`]s,
     /// blocking for messages on what to do.
     ///
-    /// If a [`ShardQueuerMessage::Start`] is received, this will:
+    /// Shards that are told to start via [`ShardQueuerMessage::Start`] are
+    /// queued, along with the ones told to start while the previous shards
+    /// were starting. Then, this will:
+    ///
+    /// 1. Check the session start limit, sleeping until it resets if no
+    /// session starts remain
+    /// 2. Take up to one queued shard of every rate limit bucket off the queue,
+    /// and no more than the remaining session starts
+    /// 3. Concurrently for each of them, acquire an identify slot from the
+    /// [`ShardCoordinator`], sleeping until 5 seconds passed since the last
+    /// shard of the same bucket was started, and start the shard by ID
     ///
-    /// 1. Check how much time has passed since the last shard was started
-    /// 2. If the amount of time is less than the ratelimit, it will sleep until
-    /// that time has passed
-    /// 3. Start the shard by ID
+    /// Shards that failed to start are queued again, and retried after 5
+    /// seconds.
     ///
     /// If a [`ShardQueuerMessage::Shutdown`] is received, this will return and
-    /// the loop will be over.
+    /// the loop will be over. This also happens while waiting for the session
+    /// start limit to reset, with shards told to start meanwhile being queued.
     ///
     /// **Note**: This should be run in its own thread due to the blocking
     /// nature of the loop.
     #[instrument(skip(self))]
     pub async fn run(&mut self) {
-        // The duration to timeout from reads over the Rx channel. This can be
-        // done in a loop, and if the read times out then a shard can be
-        // started if one is presently waiting in the queue.
-        const TIMEOUT: Duration = Duration::from_secs(WAIT_BETWEEN_BOOTS_IN_SECONDS);
-
         loop {
-            match timeout(TIMEOUT, self.rx.next()).await {
-                Ok(Some(ShardQueuerMessage::Shutdown)) => {
-                    debug!("[Shard Queuer] Received to shutdown.");
-                    self.shutdown_runners().await;
+            // Only block for a message if no shard is queued to be started.
+            if self.queue.is_empty() {
+                match self.rx.next().await {
+                    Some(message) => {
+                        if !self.handle_message(message).await {
+                            break;
+                        }
+                    },
+                    None => break,
+                }
+            }
 
-                    break;
-                },
-                Ok(Some(ShardQueuerMessage::ShutdownShard(shard, code))) => {
-                    debug!("[Shard Queuer] Received to shutdown shard {} with {}.", shard.0, code);
-                    self.shutdown(shard, code).await;
-                },
-                Ok(Some(ShardQueuerMessage::Start(id, total))) => {
-                    debug!("[Shard Queuer] Received to start shard {} of {}.", id.0, total.0);
-                    self.checked_start(id.0, total.0).await;
+            // Take in the messages that were sent meanwhile, such as the
+            // starts of all shards when the client starts, so that the shards
+            // are started together.
+            while let Some(message) = self.rx.next().now_or_never() {
+                match message {
+                    Some(message) => {
+                        if !self.handle_message(message).await {
+                            return;
+                        }
+                    },
+                    None => return,
+                }
+            }
+
+            if !self.start_queued().await {
+                break;
+            }
+        }
+    }
+
+    /// Acts on a message, queueing shards that are told to start.
+    ///
+    /// Returns `false` if the queuer was told to shut down.
+    async fn handle_message(&mut self, message: ShardQueuerMessage) -> bool {
+        match message {
+            ShardQueuerMessage::Shutdown => {
+                debug!("[Shard Queuer] Received to shutdown.");
+                self.shutdown_runners().await;
+
+                return false;
+            },
+            ShardQueuerMessage::ShutdownShard(shard, code) => {
+                debug!("[Shard Queuer] Received to shutdown shard {} with {}.", shard.0, code);
+                self.shutdown(shard, code).await;
+            },
+            ShardQueuerMessage::Start(id, total) => {
+                debug!("[Shard Queuer] Queueing start of shard {} of {}.", id.0, total.0);
+                self.queue.push_back((id.0, total.0));
+            },
+        }
+
+        true
+    }
+
+    /// Sleeps until the given instant, while still acting on messages, so that
+    /// a long wait does not delay a shutdown.
+    ///
+    /// Shards that are told to start in the meantime are queued.
+    ///
+    /// Returns `false` if the queuer was told to shut down.
+    async fn wait_until(&mut self, deadline: Instant) -> bool {
+        loop {
+            tokio::select! {
+                () = sleep_until(deadline) => return true,
+                message = self.rx.next() => match message {
+                    Some(message) => {
+                        if !self.handle_message(message).await {
+                            return false;
+                        }
+                    },
+                    None => return false,
                 },
-                Ok(None) => break,
-                Err(_) => {
-                    if let Some((id, total)) = self.queue.pop_front() {
-                        self.checked_start(id, total).await;
+            }
+        }
+    }
+
+    /// Waits until a session start remains.
+    ///
+    /// Returns `false` if the queuer was told to shut down while waiting.
+    #[instrument(skip(self))]
+    async fn check_session_start_limit(&mut self) -> bool {
+        loop {
+            if let (Some(limit), Some(reset)) =
+                (&self.session_start_limit, self.session_start_reset)
+            {
+                if Instant::now() < reset {
+                    if limit.remaining > 0 {
+                        return true;
+                    }
+
+                    warn!(
+                        "[Shard Queuer] Out of session starts, waiting {:?} for the limit to reset",
+                        reset - Instant::now(),
+                    );
+
+                    if !self.wait_until(reset).await {
+                        return false;
+                    }
+                }
+            }
+
+            match self.cache_and_http.http.get_bot_gateway().await {
+                Ok(gateway) => {
+                    let limit = gateway.session_start_limit;
+                    debug!("[Shard Queuer] Session start limit: {:?}", limit);
+
+                    let remaining = limit.remaining;
+
+                    self.session_start_reset =
+                        Some(Instant::now() + Duration::from_millis(limit.reset_after));
+                    self.session_start_limit = Some(limit);
+
+                    if remaining > 0 {
+                        return true;
                     }
                 },
+                Err(why) => {
+                    warn!("[Shard Queuer] Failed to get the session start limit: {:?}", why);
+
+                    return true;
+                },
             }
         }
     }
 
+    /// Starts the next batch of queued shards once the session start limit
+    /// allows it, as described in [`Self::run`].
+    ///
+    /// Returns `false` if the queuer was told to shut down while waiting.
     #[instrument(skip(self))]
-    async fn check_last_start(&mut self) {
-        let instant = match self.last_start {
-            Some(instant) => instant,
-            None => return,
-        };
+    async fn start_queued(&mut self) -> bool {
+        if self.queue.is_empty() {
+            return true;
+        }
+
+        if !self.check_session_start_limit().await {
+            return false;
+        }
 
-        // We must wait 5 seconds between IDENTIFYs to avoid session
-        // invalidations.
-        let duration = Duration::from_secs(WAIT_BETWEEN_BOOTS_IN_SECONDS);
-        let elapsed = instant.elapsed();
+        let max_concurrency = self.max_concurrency();
+        let remaining = self.session_start_limit.as_ref().map(|limit| limit.remaining);
+        let batch = take_batch(&mut self.queue, max_concurrency, remaining);
 
-        if elapsed >= duration {
-            return;
+        let results = join_all(
+            batch.iter().map(|&(id, total)| self.checked_start(id, total, max_concurrency)),
+        )
+        .await;
+
+        let mut failed = false;
+
+        for ((id, total), result) in batch.into_iter().zip(results) {
+            match result {
+                // Shards that resume a saved session do not use up a session
+                // start. Those failing to resume IDENTIFY later on, which is
+                // not counted, but the limit is fetched again once it reset.
+                Ok(identifies) => {
+                    if let (true, Some(limit)) = (identifies, &mut self.session_start_limit) {
+                        limit.remaining = limit.remaining.saturating_sub(1);
+                    }
+                },
+                Err(why) => {
+                    warn!("[Shard Queuer] Err starting shard {}: {:?}", id, why);
+                    info!("[Shard Queuer] Re-queueing start of shard {}", id);
+
+                    self.queue.push_back((id, total));
+                    failed = true;
+                },
+            }
         }
 
-        let to_sleep = duration - elapsed;
+        if failed {
+            let retry_at = Instant::now() + Duration::from_secs(WAIT_BETWEEN_BOOTS_IN_SECONDS);
+
+            return self.wait_until(retry_at).await;
+        }
 
-        sleep(to_sleep).await;
+        true
     }
 
+    /// Starts a shard once an identify slot of its rate limit bucket was
+    /// acquired, returning whether it IDENTIFYs rather than resuming a saved
+    /// session.
     #[instrument(skip(self))]
-    async fn checked_start(&mut self, id: u64, total: u64) {
+    async fn checked_start(&self, id: u64, total: u64, max_concurrency: u64) -> Result<bool> {
         debug!("[Shard Queuer] Checked start for shard {} out of {}", id, total);
-        self.check_last_start().await;
 
-        if let Err(why) = self.start(id, total).await {
-            warn!("[Shard Queuer] Err starting shard {}: {:?}", id, why);
-            info!("[Shard Queuer] Re-queueing start of shard {}", id);
+        // We must wait 5 seconds between IDENTIFYs of the same bucket to avoid
+        // session invalidations.
+        self.coordinator.acquire_identify(ShardId(id), max_concurrency).await?;
 
-            self.queue.push_back((id, total));
-        }
+        self.start(id, total).await
+    }
 
-        self.last_start = Some(Instant::now());
+    fn max_concurrency(&self) -> u64 {
+        self.session_start_limit.as_ref().map_or(1, |limit| limit.max_concurrency)
     }
 
+    /// Starts a shard, returning whether it IDENTIFYs rather than resuming a
+    /// saved session.
     #[instrument(skip(self))]
-    async fn start(&mut self, shard_id: u64, shard_total: u64) -> Result<()> {
+    async fn start(&self, shard_id: u64, shard_total: u64) -> Result<bool> {
         let shard_info = [shard_id, shard_total];
 
-        let mut shard = Shard::new(
+        // The session is loaded before connecting, so that the shard connects
+        // to its resume gateway URL.
+        let session = match &self.session_store {
+            Some(store) => match store.load(ShardId(shard_id), shard_total).await {
+                Ok(session) => session,
+                Err(why) => {
+                    warn!("[Shard Queuer] Failed to load session of shard {}: {:?}", shard_id, why);
+
+                    None
+                },
+            },
+            None => None,
+        };
+
+        let identifies = session.is_none();
+
+        if !identifies {
+            debug!("[Shard Queuer] Resuming session of shard {}", shard_id);
+        }
+
+        let mut shard = Shard::new_with_transport(
             Arc::clone(&self.ws_url),
             &self.cache_and_http.http.token,
             shard_info,
             self.intents,
+            self.compression,
+            self.encoding,
+            session,
         )
         .await?;
 
@@ -185,8 +392,14 @@ impl ShardQueuer {
 
         let mut runner = ShardRunner::new(ShardRunnerOptions {
             data: Arc::clone(&self.data),
-            event_handler: self.event_handler.as_ref().map(Arc::clone),
-            raw_event_handler: self.raw_event_handler.as_ref().map(Arc::clone),
+            event_handlers: self.event_handlers.clone(),
+            raw_event_handlers: self.raw_event_handlers.clone(),
+            middleware: self.middleware.clone(),
+            dispatch_tasks: Arc::clone(&self.dispatch_tasks),
+            session_store: self.session_store.clone(),
+            coordinator: Arc::clone(&self.coordinator),
+            max_concurrency: self.max_concurrency(),
+            chunking: self.chunking.clone(),
             #[cfg(feature = "framework")]
             framework: Arc::clone(&self.framework),
             manager_tx: self.manager_tx.clone(),
@@ -209,7 +422,7 @@ impl ShardQueuer {
 
         self.runners.lock().await.insert(ShardId(shard_id), runner_info);
 
-        Ok(())
+        Ok(identifies)
     }
 
     #[instrument(skip(self))]
@@ -256,3 +469,49 @@ impl ShardQueuer {
         }
     }
 }
+
+/// Takes the shards to start together off the queue: the first queued shard
+/// of every rate limit bucket, and no more than `limit` shards if given.
+fn take_batch(
+    queue: &mut VecDeque<(u64, u64)>,
+    max_concurrency: u64,
+    limit: Option<u64>,
+) -> Vec<(u64, u64)> {
+    let max_concurrency = max_concurrency.max(1);
+    let limit = limit.unwrap_or(u64::MAX);
+    let mut buckets = HashSet::new();
+    let mut batch = Vec::new();
+
+    queue.retain(|&(id, total)| {
+        if (batch.len() as u64) < limit && buckets.insert(id % max_concurrency) {
+            batch.push((id, total));
+
+            false
+        } else {
+            true
+        }
+    });
+
+    batch
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::VecDeque;
+
+    use super::take_batch;
+
+    #[test]
+    fn test_take_batch() {
+        let mut queue = (0..6).map(|id| (id, 6)).collect::<VecDeque<_>>();
+
+        // One shard per bucket.
+        assert_eq!(take_batch(&mut queue, 4, None), vec![(0, 6), (1, 6), (2, 6), (3, 6)]);
+        assert_eq!(queue, vec![(4, 6), (5, 6)]);
+
+        // Limited by the remaining session starts.
+        assert_eq!(take_batch(&mut queue, 4, Some(1)), vec![(4, 6)]);
+        assert_eq!(take_batch(&mut queue, 1, None), vec![(5, 6)]);
+        assert!(queue.is_empty());
+    }
+}
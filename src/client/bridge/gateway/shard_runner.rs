@@ -8,15 +8,27 @@ use futures::channel::mpsc::{self, UnboundedReceiver as Receiver, UnboundedSende
 use futures::{SinkExt, StreamExt};
 use serde::Deserialize;
 use tokio::sync::RwLock;
+use tokio::time::{Duration, Instant};
 use tracing::{debug, error, info, instrument, trace, warn};
 use typemap_rev::TypeMap;
 
 use super::event::{ClientEvent, ShardStageUpdateEvent};
-use super::{ShardClientMessage, ShardId, ShardManagerMessage, ShardRunnerMessage};
+use super::{
+    ChunkGuildFilter,
+    Chunker,
+    GuildChunking,
+    SessionStore,
+    ShardClientMessage,
+    ShardCoordinator,
+    ShardId,
+    ShardManagerMessage,
+    ShardRunnerMessage,
+};
 #[cfg(feature = "voice")]
 use crate::client::bridge::voice::VoiceGatewayManager;
 use crate::client::dispatch::{dispatch, DispatchEvent};
-use crate::client::{EventHandler, RawEventHandler};
+use crate::client::event_handler::{EventHandlers, RawEventHandlers};
+use crate::client::{DispatchTasks, EventHandler, EventMiddleware, RawEventHandler};
 #[cfg(feature = "collector")]
 use crate::collector::{
     ComponentInteractionFilter,
@@ -29,19 +41,36 @@ use crate::collector::{
 };
 #[cfg(feature = "framework")]
 use crate::framework::Framework;
-use crate::gateway::{GatewayError, InterMessage, ReconnectType, Shard, ShardAction};
+use crate::gateway::{GatewayError, InterMessage, ReconnectType, SessionInfo, Shard, ShardAction};
 use crate::internal::prelude::*;
-use crate::internal::ws_impl::{ReceiverExt, SenderExt};
 #[cfg(feature = "collector")]
 use crate::model::application::interaction::Interaction;
 use crate::model::event::{Event, GatewayEvent};
+use crate::model::gateway::GatewayIntents;
 use crate::CacheAndHttp;
 
+/// How often the sequence number of a session is saved to the session store
+/// while events are received.
+const SESSION_SAVE_INTERVAL: Duration = Duration::from_secs(30);
+
 /// A runner for managing a [`Shard`] and its respective WebSocket client.
 pub struct ShardRunner {
     data: Arc<RwLock<TypeMap>>,
     event_handler: Option<Arc<dyn EventHandler>>,
     raw_event_handler: Option<Arc<dyn RawEventHandler>>,
+    middleware: Vec<Arc<dyn EventMiddleware>>,
+    dispatch_tasks: Arc<DispatchTasks>,
+    session_store: Option<Arc<dyn SessionStore>>,
+    /// The session as last saved to the session store, and when.
+    saved_session: (Option<SessionInfo>, Instant),
+    coordinator: Arc<dyn ShardCoordinator>,
+    max_concurrency: u64,
+    /// Whether the identify slot that the [`ShardQueuer`] acquired before
+    /// starting the shard is still unused.
+    ///
+    /// [`ShardQueuer`]: super::ShardQueuer
+    holds_identify_slot: bool,
+    chunker: Chunker,
     #[cfg(feature = "framework")]
     framework: Arc<dyn Framework + Send + Sync>,
     manager_tx: Sender<ShardManagerMessage>,
@@ -70,12 +99,31 @@ impl ShardRunner {
     pub fn new(opt: ShardRunnerOptions) -> Self {
         let (tx, rx) = mpsc::unbounded();
 
+        let mut chunking = opt.chunking;
+
+        if chunking.is_some() && !opt.shard.intents.contains(GatewayIntents::GUILD_MEMBERS) {
+            warn!(
+                "[ShardRunner {:?}] Not chunking guilds without the GUILD_MEMBERS intent",
+                opt.shard.shard_info(),
+            );
+
+            chunking = None;
+        }
+
         Self {
             runner_rx: rx,
             runner_tx: tx,
             data: opt.data,
-            event_handler: opt.event_handler,
-            raw_event_handler: opt.raw_event_handler,
+            event_handler: EventHandlers::combine(opt.event_handlers),
+            raw_event_handler: RawEventHandlers::combine(opt.raw_event_handlers),
+            middleware: opt.middleware,
+            dispatch_tasks: opt.dispatch_tasks,
+            session_store: opt.session_store,
+            saved_session: (None, Instant::now()),
+            coordinator: opt.coordinator,
+            max_concurrency: opt.max_concurrency,
+            holds_identify_slot: true,
+            chunker: Chunker::new(chunking),
             #[cfg(feature = "framework")]
             framework: opt.framework,
             manager_tx: opt.manager_tx,
@@ -167,6 +215,8 @@ impl ShardRunner {
                         match self.shard.reconnection_type() {
                             ReconnectType::Reidentify => return self.request_restart().await,
                             ReconnectType::Resume => {
+                                self.chunker.interrupt(&ReconnectType::Resume);
+
                                 if let Err(why) = self.shard.resume().await {
                                     warn!(
                                         "[ShardRunner {:?}] Resume failed, reidentifying: {:?}",
@@ -189,9 +239,29 @@ impl ShardRunner {
                     self.handle_filters(&event);
                 }
 
+                self.chunker.handle_event(&event);
                 self.dispatch(DispatchEvent::Model(event)).await;
             }
 
+            if let Some(guild_id) = self.chunker.next_guild() {
+                debug!("[ShardRunner {:?}] Chunking guild {}", self.shard.shard_info(), guild_id);
+
+                if let Err(why) =
+                    self.shard.chunk_guild(guild_id, None, ChunkGuildFilter::None, None).await
+                {
+                    warn!(
+                        "[ShardRunner {:?}] Failed to chunk guild {}: {:?}",
+                        self.shard.shard_info(),
+                        guild_id,
+                        why
+                    );
+
+                    self.chunker.requeue(guild_id);
+                }
+            }
+
+            self.save_session(false).await;
+
             if !successful && !self.shard.stage().is_connecting() {
                 return self.request_restart().await;
             }
@@ -261,9 +331,33 @@ impl ShardRunner {
     async fn action(&mut self, action: &ShardAction) -> Result<()> {
         match *action {
             ShardAction::Reconnect(ReconnectType::Reidentify) => self.request_restart().await,
-            ShardAction::Reconnect(ReconnectType::Resume) => self.shard.resume().await,
+            ShardAction::Reconnect(ReconnectType::Resume) => {
+                self.holds_identify_slot = false;
+                self.chunker.interrupt(&ReconnectType::Resume);
+
+                self.shard.resume().await
+            },
+            ShardAction::Resume => {
+                self.holds_identify_slot = false;
+
+                self.shard.send_resume().await
+            },
             ShardAction::Heartbeat => self.shard.heartbeat().await,
-            ShardAction::Identify => self.shard.identify().await,
+            ShardAction::Identify => {
+                // Member chunks of the previous session are not received in
+                // the new one, and its guilds are received again.
+                self.chunker.interrupt(&ReconnectType::Reidentify);
+
+                // Only the first IDENTIFY after starting uses the slot that
+                // the queuer acquired, any other one acquires its own.
+                if !std::mem::take(&mut self.holds_identify_slot) {
+                    let shard_id = ShardId(self.shard.shard_info()[0]);
+
+                    self.coordinator.acquire_identify(shard_id, self.max_concurrency).await?;
+                }
+
+                self.shard.identify().await
+            },
         }
     }
 
@@ -284,6 +378,14 @@ impl ShardRunner {
             return true;
         }
 
+        // Closing with 1000 or 1001 invalidates the session, any other code
+        // keeps it resumable.
+        if matches!(close_code, 1000 | 1001) {
+            self.remove_session().await;
+        } else {
+            self.save_session(true).await;
+        }
+
         // Send a Close Frame to Discord, which allows a bot to "log off"
         drop(
             self.shard
@@ -299,7 +401,7 @@ impl ShardRunner {
         // disconnected from Discord.
         loop {
             match self.shard.client.next().await {
-                Some(Ok(tungstenite::Message::Close(_))) => break,
+                Some(Ok(tungstenite::Message::Close(_))) | None => break,
                 Some(Err(_)) => {
                     warn!(
                         "[ShardRunner {:?}] Received an error awaiting close frame",
@@ -333,6 +435,8 @@ impl ShardRunner {
             &self.data,
             &self.event_handler,
             &self.raw_event_handler,
+            &self.middleware,
+            &self.dispatch_tasks,
             &self.runner_tx,
             self.shard.shard_info()[0],
             Arc::clone(&self.cache_and_http),
@@ -391,6 +495,22 @@ impl ShardRunner {
                 }) => {
                     self.shard.chunk_guild(guild_id, limit, filter, nonce.as_deref()).await.is_ok()
                 },
+                ShardClientMessage::Runner(ShardRunnerMessage::RequestMembers {
+                    guild_id,
+                    limit,
+                    filter,
+                    sender,
+                }) => {
+                    let nonce = self.chunker.register(self.shard.shard_info()[0], sender);
+                    let sent =
+                        self.shard.chunk_guild(guild_id, limit, filter, Some(&nonce)).await.is_ok();
+
+                    if !sent {
+                        self.chunker.cancel(&nonce);
+                    }
+
+                    sent
+                },
                 ShardClientMessage::Runner(ShardRunnerMessage::Close(code, reason)) => {
                     let reason = reason.unwrap_or_default();
                     let close = CloseFrame {
@@ -467,7 +587,7 @@ impl ShardRunner {
             },
             InterMessage::Json(value) => {
                 // Value must be forwarded over the websocket
-                self.shard.client.send_json(&value).await.is_ok()
+                self.shard.send_json(&value).await.is_ok()
             },
         }
     }
@@ -537,13 +657,16 @@ impl ShardRunner {
     /// present event was successful.
     #[instrument(skip(self))]
     async fn recv_event(&mut self) -> Result<(Option<Event>, Option<ShardAction>, bool)> {
-        let gw_event = match self.shard.client.recv_json().await {
+        let gw_event = match self.shard.recv_json().await {
             Ok(Some(value)) => GatewayEvent::deserialize(value).map(Some).map_err(From::from),
             Ok(None) => Ok(None),
             Err(Error::Tungstenite(TungsteniteError::Io(_))) => {
                 debug!("Attempting to auto-reconnect");
 
-                match self.shard.reconnection_type() {
+                let reconnect = self.shard.reconnection_type();
+                self.chunker.interrupt(&reconnect);
+
+                match reconnect {
                     ReconnectType::Reidentify => return Ok((None, None, false)),
                     ReconnectType::Resume => {
                         if let Err(why) = self.shard.resume().await {
@@ -638,6 +761,11 @@ impl ShardRunner {
         self.update_manager();
 
         debug!("[ShardRunner {:?}] Requesting restart", self.shard.shard_info(),);
+
+        // The restarted shard must IDENTIFY, rather than resume a session
+        // that could not be resumed.
+        self.remove_session().await;
+
         let shard_id = ShardId(self.shard.shard_info()[0]);
         let msg = ShardManagerMessage::Restart(shard_id);
 
@@ -653,6 +781,59 @@ impl ShardRunner {
         Ok(())
     }
 
+    /// Saves the shard's session to the session store if it changed or the
+    /// saved sequence number is outdated, or unconditionally if `force` is set.
+    async fn save_session(&mut self, force: bool) {
+        let store = match &self.session_store {
+            Some(store) => store,
+            None => return,
+        };
+        let session = self.shard.session();
+        let (saved, saved_at) = &self.saved_session;
+
+        let changed = match (&session, saved) {
+            (Some(session), Some(saved)) => {
+                session.session_id != saved.session_id
+                    || session.resume_gateway_url != saved.resume_gateway_url
+                    || (session.seq != saved.seq && saved_at.elapsed() >= SESSION_SAVE_INTERVAL)
+            },
+            (None, None) => false,
+            _ => true,
+        };
+
+        if !changed && !force {
+            return;
+        }
+
+        let [shard_id, shard_total] = self.shard.shard_info();
+        let shard_id = ShardId(shard_id);
+        let result = match &session {
+            Some(session) => store.save(shard_id, shard_total, session).await,
+            None => store.remove(shard_id).await,
+        };
+
+        if let Err(why) = result {
+            warn!("[ShardRunner {:?}] Failed to save session: {:?}", self.shard.shard_info(), why);
+        }
+
+        self.saved_session = (session, Instant::now());
+    }
+
+    /// Removes the shard's session from the session store.
+    async fn remove_session(&mut self) {
+        if let Some(store) = &self.session_store {
+            if let Err(why) = store.remove(ShardId(self.shard.shard_info()[0])).await {
+                warn!(
+                    "[ShardRunner {:?}] Failed to remove session: {:?}",
+                    self.shard.shard_info(),
+                    why
+                );
+            }
+
+            self.saved_session = (None, Instant::now());
+        }
+    }
+
     #[instrument(skip(self))]
     fn update_manager(&self) {
         drop(self.manager_tx.unbounded_send(ShardManagerMessage::ShardUpdate {
@@ -666,8 +847,17 @@ impl ShardRunner {
 /// Options to be passed to [`ShardRunner::new`].
 pub struct ShardRunnerOptions {
     pub data: Arc<RwLock<TypeMap>>,
-    pub event_handler: Option<Arc<dyn EventHandler>>,
-    pub raw_event_handler: Option<Arc<dyn RawEventHandler>>,
+    pub event_handlers: Vec<Arc<dyn EventHandler>>,
+    pub raw_event_handlers: Vec<Arc<dyn RawEventHandler>>,
+    pub middleware: Vec<Arc<dyn EventMiddleware>>,
+    pub dispatch_tasks: Arc<DispatchTasks>,
+    pub session_store: Option<Arc<dyn SessionStore>>,
+    /// The coordinator that identify slots are acquired from for IDENTIFYs
+    /// after the first one.
+    pub coordinator: Arc<dyn ShardCoordinator>,
+    /// The `max_concurrency` of the bot's session start limit.
+    pub max_concurrency: u64,
+    pub chunking: Option<GuildChunking>,
     #[cfg(feature = "framework")]
     pub framework: Arc<dyn Framework + Send + Sync>,
     pub manager_tx: Sender<ShardManagerMessage>,
@@ -1,4 +1,5 @@
 use async_tungstenite::tungstenite::Message;
+use futures::channel::mpsc::UnboundedSender as Sender;
 
 #[cfg(feature = "collector")]
 use crate::collector::{
@@ -8,6 +9,7 @@ use crate::collector::{
     ModalInteractionFilter,
     ReactionFilter,
 };
+use crate::model::event::GuildMembersChunkEvent;
 use crate::model::gateway::Activity;
 use crate::model::id::{GuildId, UserId};
 use crate::model::user::OnlineStatus;
@@ -45,6 +47,26 @@ pub enum ShardRunnerMessage {
         /// [`GuildMembersChunkEvent`]: crate::model::event::GuildMembersChunkEvent
         nonce: Option<String>,
     },
+    /// Indicates that the client is to request the members of a guild,
+    /// forwarding all [`GuildMembersChunkEvent`]s sent in response to
+    /// `sender`.
+    ///
+    /// The nonce of the request is generated by the [`ShardRunner`].
+    ///
+    /// [`ShardRunner`]: super::ShardRunner
+    RequestMembers {
+        /// The Id of the [`Guild`] to request the members of.
+        ///
+        /// [`Guild`]: crate::model::guild::Guild
+        guild_id: GuildId,
+        /// The maximum number of members to receive.
+        limit: Option<u16>,
+        /// A filter to apply to the returned members.
+        filter: ChunkGuildFilter,
+        /// The channel to send the chunks to. It is closed once the last one
+        /// was sent.
+        sender: Sender<GuildMembersChunkEvent>,
+    },
     /// Indicates that the client is to close with the given status code and
     /// reason.
     ///
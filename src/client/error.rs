@@ -18,6 +18,11 @@ pub enum Error {
     /// When all shards that the client is responsible for have shutdown with an
     /// error.
     Shutdown,
+    /// When a [`ShardCoordinator`] failed, such as when not enough shards were
+    /// left to claim.
+    ///
+    /// [`ShardCoordinator`]: super::bridge::gateway::ShardCoordinator
+    Coordinator(String),
 }
 
 impl fmt::Display for Error {
@@ -25,6 +30,7 @@ impl fmt::Display for Error {
         match self {
             Self::ShardBootFailure => f.write_str("Failed to (re-)boot a shard"),
             Self::Shutdown => f.write_str("The clients shards shutdown"),
+            Self::Coordinator(message) => write!(f, "Shard coordinator error: {}", message),
         }
     }
 }
@@ -1,8 +1,10 @@
 use std::collections::HashMap;
+use std::sync::Arc;
 
 use async_trait::async_trait;
 
 use super::context::Context;
+use super::shutdown::ShutdownReason;
 use crate::client::bridge::gateway::event::*;
 use crate::http::ratelimiting::RatelimitInfo;
 use crate::json::Value;
@@ -319,9 +321,25 @@ pub trait EventHandler: Send + Sync {
     /// Provides the message's data.
     async fn message(&self, _ctx: Context, _new_message: Message) {}
 
+    /// Dispatched when a message is deleted.
+    ///
+    /// Provides the guild's id, the channel's id, the message's id and the
+    /// message if it was cached.
+    #[cfg(feature = "cache")]
+    async fn message_delete(
+        &self,
+        _ctx: Context,
+        _channel_id: ChannelId,
+        _deleted_message_id: MessageId,
+        _guild_id: Option<GuildId>,
+        _deleted_message_if_available: Option<Message>,
+    ) {
+    }
+
     /// Dispatched when a message is deleted.
     ///
     /// Provides the guild's id, the channel's id and the message's id.
+    #[cfg(not(feature = "cache"))]
     async fn message_delete(
         &self,
         _ctx: Context,
@@ -331,9 +349,25 @@ pub trait EventHandler: Send + Sync {
     ) {
     }
 
+    /// Dispatched when multiple messages were deleted at once.
+    ///
+    /// Provides the guild's id, channel's id, the deleted messages' ids and
+    /// those of the messages that were cached.
+    #[cfg(feature = "cache")]
+    async fn message_delete_bulk(
+        &self,
+        _ctx: Context,
+        _channel_id: ChannelId,
+        _multiple_deleted_messages_ids: Vec<MessageId>,
+        _guild_id: Option<GuildId>,
+        _deleted_messages_if_available: Vec<Message>,
+    ) {
+    }
+
     /// Dispatched when multiple messages were deleted at once.
     ///
     /// Provides the guild's id, channel's id and the deleted messages' ids.
+    #[cfg(not(feature = "cache"))]
     async fn message_delete_bulk(
         &self,
         _ctx: Context,
@@ -578,6 +612,15 @@ pub trait EventHandler: Send + Sync {
 
     /// Dispatched when an HTTP rate limit is hit
     async fn ratelimit(&self, _data: RatelimitInfo) {}
+
+    /// Dispatched once when a graceful shutdown of the [`Client`] starts, before
+    /// the shards are closed.
+    ///
+    /// See [`ShutdownHandle::shutdown`] for the steps of a shutdown.
+    ///
+    /// [`Client`]: super::Client
+    /// [`ShutdownHandle::shutdown`]: super::ShutdownHandle::shutdown
+    async fn shutdown(&self, _reason: ShutdownReason) {}
 }
 
 /// This core trait for handling raw events
@@ -586,3 +629,218 @@ pub trait RawEventHandler: Send + Sync {
     /// Dispatched when any event occurs
     async fn raw_event(&self, _ctx: Context, _ev: Event) {}
 }
+
+/// A hook that sees every gateway event before the [`EventHandler`]s,
+/// [`RawEventHandler`]s and the framework do.
+///
+/// Middleware is registered via [`ClientBuilder::middleware`] and run in
+/// registration order. Each middleware may inspect or modify the event, or
+/// drop it so that neither the remaining middleware nor any handler receives
+/// it.
+///
+/// The cache is updated after the middleware chain ran, with the possibly
+/// modified event. Dropped events still update the cache, so that it stays
+/// consistent with Discord's state.
+///
+/// # Examples
+///
+/// Ignoring messages from other bots:
+///
+/// ```rust,no_run
+/// use serenity::async_trait;
+/// use serenity::client::EventMiddleware;
+/// use serenity::model::event::Event;
+/// use serenity::prelude::*;
+///
+/// struct IgnoreBots;
+///
+/// #[async_trait]
+/// impl EventMiddleware for IgnoreBots {
+///     async fn process(&self, _ctx: &Context, event: &mut Event) -> bool {
+///         match event {
+///             Event::MessageCreate(event) => !event.message.author.bot,
+///             _ => true,
+///         }
+///     }
+/// }
+///
+/// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = Client::builder("token", GatewayIntents::default()).middleware(IgnoreBots).await?;
+/// #     Ok(())
+/// # }
+/// ```
+///
+/// [`ClientBuilder::middleware`]: super::ClientBuilder::middleware
+#[async_trait]
+pub trait EventMiddleware: Send + Sync {
+    /// Processes an event, returning whether it should be dispatched further.
+    async fn process(&self, ctx: &Context, event: &mut Event) -> bool;
+}
+
+/// Dispatches events to multiple [`EventHandler`]s, in order.
+pub(crate) struct EventHandlers(Vec<Arc<dyn EventHandler>>);
+
+impl EventHandlers {
+    /// Combines the handlers into one, or returns `None` if there are none.
+    pub(crate) fn combine(
+        mut handlers: Vec<Arc<dyn EventHandler>>,
+    ) -> Option<Arc<dyn EventHandler>> {
+        match handlers.len() {
+            0 => None,
+            1 => handlers.pop(),
+            _ => Some(Arc::new(Self(handlers))),
+        }
+    }
+}
+
+macro_rules! dispatch_in_order {
+    ($($(#[$attr:meta])* fn $name:ident(&self $(, $arg:ident: $ty:ty)*);)*) => {
+        #[async_trait]
+        impl EventHandler for EventHandlers {
+            $(
+                $(#[$attr])*
+                async fn $name(&self $(, $arg: $ty)*) {
+                    for handler in &self.0 {
+                        handler.$name($(Clone::clone(&$arg)),*).await;
+                    }
+                }
+            )*
+        }
+    };
+}
+
+dispatch_in_order! {
+    fn application_command_permissions_update(&self, ctx: Context, permission: CommandPermission);
+    fn auto_moderation_rule_create(&self, ctx: Context, rule: Rule);
+    fn auto_moderation_rule_update(&self, ctx: Context, rule: Rule);
+    fn auto_moderation_rule_delete(&self, ctx: Context, rule: Rule);
+    fn auto_moderation_action_execution(&self, ctx: Context, execution: ActionExecution);
+    #[cfg(feature = "cache")]
+    fn cache_ready(&self, ctx: Context, guilds: Vec<GuildId>);
+    fn channel_create(&self, ctx: Context, channel: &GuildChannel);
+    fn category_create(&self, ctx: Context, category: &ChannelCategory);
+    fn category_delete(&self, ctx: Context, category: &ChannelCategory);
+    fn channel_delete(&self, ctx: Context, channel: &GuildChannel);
+    fn channel_pins_update(&self, ctx: Context, pin: ChannelPinsUpdateEvent);
+    #[cfg(feature = "cache")]
+    fn channel_update(&self, ctx: Context, old: Option<Channel>, new: Channel);
+    #[cfg(not(feature = "cache"))]
+    fn channel_update(&self, ctx: Context, new_data: Channel);
+    fn guild_ban_addition(&self, ctx: Context, guild_id: GuildId, banned_user: User);
+    fn guild_ban_removal(&self, ctx: Context, guild_id: GuildId, unbanned_user: User);
+    #[cfg(feature = "cache")]
+    fn guild_create(&self, ctx: Context, guild: Guild, is_new: bool);
+    #[cfg(not(feature = "cache"))]
+    fn guild_create(&self, ctx: Context, guild: Guild);
+    #[cfg(feature = "cache")]
+    fn guild_delete(&self, ctx: Context, incomplete: UnavailableGuild, full: Option<Guild>);
+    #[cfg(not(feature = "cache"))]
+    fn guild_delete(&self, ctx: Context, incomplete: UnavailableGuild);
+    fn guild_emojis_update(&self, ctx: Context, guild_id: GuildId, current_state: HashMap<EmojiId, Emoji>);
+    fn guild_integrations_update(&self, ctx: Context, guild_id: GuildId);
+    fn guild_member_addition(&self, ctx: Context, new_member: Member);
+    #[cfg(feature = "cache")]
+    fn guild_member_removal(&self, ctx: Context, guild_id: GuildId, user: User, member_data_if_available: Option<Member>);
+    #[cfg(not(feature = "cache"))]
+    fn guild_member_removal(&self, ctx: Context, guild_id: GuildId, kicked: User);
+    #[cfg(feature = "cache")]
+    fn guild_member_update(&self, ctx: Context, old_if_available: Option<Member>, new: Member);
+    #[cfg(not(feature = "cache"))]
+    fn guild_member_update(&self, ctx: Context, new: GuildMemberUpdateEvent);
+    fn guild_members_chunk(&self, ctx: Context, chunk: GuildMembersChunkEvent);
+    fn guild_role_create(&self, ctx: Context, new: Role);
+    #[cfg(feature = "cache")]
+    fn guild_role_delete(&self, ctx: Context, guild_id: GuildId, removed_role_id: RoleId, removed_role_data_if_available: Option<Role>);
+    #[cfg(not(feature = "cache"))]
+    fn guild_role_delete(&self, ctx: Context, guild_id: GuildId, removed_role_id: RoleId);
+    #[cfg(feature = "cache")]
+    fn guild_role_update(&self, ctx: Context, old_data_if_available: Option<Role>, new: Role);
+    #[cfg(not(feature = "cache"))]
+    fn guild_role_update(&self, ctx: Context, new_data: Role);
+    fn guild_stickers_update(&self, ctx: Context, guild_id: GuildId, current_state: HashMap<StickerId, Sticker>);
+    fn guild_unavailable(&self, ctx: Context, guild_id: GuildId);
+    #[cfg(feature = "cache")]
+    fn guild_update(&self, ctx: Context, old_data_if_available: Option<Guild>, new_but_incomplete: PartialGuild);
+    #[cfg(not(feature = "cache"))]
+    fn guild_update(&self, ctx: Context, new_but_incomplete_data: PartialGuild);
+    fn invite_create(&self, ctx: Context, data: InviteCreateEvent);
+    fn invite_delete(&self, ctx: Context, data: InviteDeleteEvent);
+    fn message(&self, ctx: Context, new_message: Message);
+    #[cfg(feature = "cache")]
+    fn message_delete(&self, ctx: Context, channel_id: ChannelId, deleted_message_id: MessageId, guild_id: Option<GuildId>, deleted_message_if_available: Option<Message>);
+    #[cfg(not(feature = "cache"))]
+    fn message_delete(&self, ctx: Context, channel_id: ChannelId, deleted_message_id: MessageId, guild_id: Option<GuildId>);
+    #[cfg(feature = "cache")]
+    fn message_delete_bulk(&self, ctx: Context, channel_id: ChannelId, multiple_deleted_messages_ids: Vec<MessageId>, guild_id: Option<GuildId>, deleted_messages_if_available: Vec<Message>);
+    #[cfg(not(feature = "cache"))]
+    fn message_delete_bulk(&self, ctx: Context, channel_id: ChannelId, multiple_deleted_messages_ids: Vec<MessageId>, guild_id: Option<GuildId>);
+    #[cfg(feature = "cache")]
+    fn message_update(&self, ctx: Context, old_if_available: Option<Message>, new: Option<Message>, event: MessageUpdateEvent);
+    #[cfg(not(feature = "cache"))]
+    fn message_update(&self, ctx: Context, new_data: MessageUpdateEvent);
+    fn reaction_add(&self, ctx: Context, add_reaction: Reaction);
+    fn reaction_remove(&self, ctx: Context, removed_reaction: Reaction);
+    fn reaction_remove_all(&self, ctx: Context, channel_id: ChannelId, removed_from_message_id: MessageId);
+    fn presence_replace(&self, ctx: Context, presences: Vec<Presence>);
+    fn presence_update(&self, ctx: Context, new_data: Presence);
+    fn ready(&self, ctx: Context, data_about_bot: Ready);
+    fn resume(&self, ctx: Context, resumed: ResumedEvent);
+    fn shard_stage_update(&self, ctx: Context, event: ShardStageUpdateEvent);
+    fn typing_start(&self, ctx: Context, event: TypingStartEvent);
+    fn unknown(&self, ctx: Context, name: String, raw: Value);
+    #[cfg(feature = "cache")]
+    fn user_update(&self, ctx: Context, old_data: CurrentUser, new: CurrentUser);
+    #[cfg(not(feature = "cache"))]
+    fn user_update(&self, ctx: Context, new_data: CurrentUser);
+    fn voice_server_update(&self, ctx: Context, event: VoiceServerUpdateEvent);
+    #[cfg(feature = "cache")]
+    fn voice_state_update(&self, ctx: Context, old: Option<VoiceState>, new: VoiceState);
+    #[cfg(not(feature = "cache"))]
+    fn voice_state_update(&self, ctx: Context, new: VoiceState);
+    fn webhook_update(&self, ctx: Context, guild_id: GuildId, belongs_to_channel_id: ChannelId);
+    fn interaction_create(&self, ctx: Context, interaction: Interaction);
+    fn integration_create(&self, ctx: Context, integration: Integration);
+    fn integration_update(&self, ctx: Context, integration: Integration);
+    fn integration_delete(&self, ctx: Context, integration_id: IntegrationId, guild_id: GuildId, application_id: Option<ApplicationId>);
+    fn stage_instance_create(&self, ctx: Context, stage_instance: StageInstance);
+    fn stage_instance_update(&self, ctx: Context, stage_instance: StageInstance);
+    fn stage_instance_delete(&self, ctx: Context, stage_instance: StageInstance);
+    fn thread_create(&self, ctx: Context, thread: GuildChannel);
+    fn thread_update(&self, ctx: Context, thread: GuildChannel);
+    fn thread_delete(&self, ctx: Context, thread: PartialGuildChannel);
+    fn thread_list_sync(&self, ctx: Context, thread_list_sync: ThreadListSyncEvent);
+    fn thread_member_update(&self, ctx: Context, thread_member: ThreadMember);
+    fn thread_members_update(&self, ctx: Context, thread_members_update: ThreadMembersUpdateEvent);
+    fn guild_scheduled_event_create(&self, ctx: Context, event: ScheduledEvent);
+    fn guild_scheduled_event_update(&self, ctx: Context, event: ScheduledEvent);
+    fn guild_scheduled_event_delete(&self, ctx: Context, event: ScheduledEvent);
+    fn guild_scheduled_event_user_add(&self, ctx: Context, subscribed: GuildScheduledEventUserAddEvent);
+    fn guild_scheduled_event_user_remove(&self, ctx: Context, unsubscribed: GuildScheduledEventUserRemoveEvent);
+    fn ratelimit(&self, data: RatelimitInfo);
+    fn shutdown(&self, reason: ShutdownReason);
+}
+
+/// Dispatches events to multiple [`RawEventHandler`]s, in order.
+pub(crate) struct RawEventHandlers(Vec<Arc<dyn RawEventHandler>>);
+
+impl RawEventHandlers {
+    /// Combines the handlers into one, or returns `None` if there are none.
+    pub(crate) fn combine(
+        mut handlers: Vec<Arc<dyn RawEventHandler>>,
+    ) -> Option<Arc<dyn RawEventHandler>> {
+        match handlers.len() {
+            0 => None,
+            1 => handlers.pop(),
+            _ => Some(Arc::new(Self(handlers))),
+        }
+    }
+}
+
+#[async_trait]
+impl RawEventHandler for RawEventHandlers {
+    async fn raw_event(&self, ctx: Context, ev: Event) {
+        for handler in &self.0 {
+            handler.raw_event(ctx.clone(), ev.clone()).await;
+        }
+    }
+}
@@ -24,19 +24,27 @@ mod dispatch;
 mod error;
 #[cfg(feature = "gateway")]
 mod event_handler;
+#[cfg(feature = "gateway")]
+mod shutdown;
 
 use std::future::Future;
 use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context as FutContext, Poll};
+#[cfg(feature = "signal")]
+use std::time::Duration;
 
 use futures::future::BoxFuture;
 use tokio::sync::{Mutex, RwLock};
-use tracing::{debug, error, info, instrument};
+use tracing::{debug, error, info, instrument, warn};
 use typemap_rev::{TypeMap, TypeMapKey};
 
 #[cfg(feature = "gateway")]
 use self::bridge::gateway::{
+    GuildChunking,
+    InMemoryShardCoordinator,
+    SessionStore,
+    ShardCoordinator,
     ShardManager,
     ShardManagerError,
     ShardManagerMonitor,
@@ -47,9 +55,13 @@ use self::bridge::voice::VoiceGatewayManager;
 pub use self::context::Context;
 pub use self::error::Error as ClientError;
 #[cfg(feature = "gateway")]
-pub use self::event_handler::{EventHandler, RawEventHandler};
+use self::event_handler::EventHandlers;
+#[cfg(feature = "gateway")]
+pub use self::event_handler::{EventHandler, EventMiddleware, RawEventHandler};
+#[cfg(feature = "gateway")]
+pub use self::shutdown::{DispatchTasks, ShutdownHandle, ShutdownReason};
 #[cfg(feature = "gateway")]
-use super::gateway::GatewayError;
+use super::gateway::{GatewayEncoding, GatewayError, TransportCompression};
 #[cfg(feature = "cache")]
 pub use crate::cache::Cache;
 #[cfg(feature = "cache")]
@@ -58,6 +70,8 @@ use crate::cache::Settings as CacheSettings;
 use crate::framework::Framework;
 use crate::http::Http;
 use crate::internal::prelude::*;
+#[cfg(feature = "signal")]
+use crate::internal::tokio::spawn_named;
 #[cfg(feature = "gateway")]
 use crate::model::gateway::GatewayIntents;
 use crate::model::id::ApplicationId;
@@ -73,14 +87,23 @@ pub struct ClientBuilder {
     http: Option<Http>,
     fut: Option<BoxFuture<'static, Result<Client>>>,
     intents: GatewayIntents,
+    gateway_url: Option<String>,
+    compression: TransportCompression,
+    encoding: GatewayEncoding,
     #[cfg(feature = "cache")]
     cache_settings: Option<CacheSettings>,
     #[cfg(feature = "framework")]
     framework: Option<Arc<dyn Framework + Send + Sync + 'static>>,
     #[cfg(feature = "voice")]
     voice_manager: Option<Arc<dyn VoiceGatewayManager + Send + Sync + 'static>>,
-    event_handler: Option<Arc<dyn EventHandler>>,
-    raw_event_handler: Option<Arc<dyn RawEventHandler>>,
+    event_handlers: Vec<Arc<dyn EventHandler>>,
+    raw_event_handlers: Vec<Arc<dyn RawEventHandler>>,
+    middleware: Vec<Arc<dyn EventMiddleware>>,
+    #[cfg(feature = "signal")]
+    shutdown_on_signal: Option<Duration>,
+    session_store: Option<Arc<dyn SessionStore>>,
+    shard_coordinator: Option<Arc<dyn ShardCoordinator>>,
+    chunking: Option<GuildChunking>,
 }
 
 #[cfg(feature = "gateway")]
@@ -91,14 +114,23 @@ impl ClientBuilder {
             http: Some(http),
             fut: None,
             intents,
+            gateway_url: None,
+            compression: TransportCompression::None,
+            encoding: GatewayEncoding::Json,
             #[cfg(feature = "cache")]
             cache_settings: Some(CacheSettings::new()),
             #[cfg(feature = "framework")]
             framework: None,
             #[cfg(feature = "voice")]
             voice_manager: None,
-            event_handler: None,
-            raw_event_handler: None,
+            event_handlers: Vec::new(),
+            raw_event_handlers: Vec::new(),
+            middleware: Vec::new(),
+            #[cfg(feature = "signal")]
+            shutdown_on_signal: None,
+            session_store: None,
+            shard_coordinator: None,
+            chunking: None,
         }
     }
 
@@ -310,40 +342,204 @@ impl ClientBuilder {
         self.intents
     }
 
-    /// Sets an event handler with multiple methods for each possible event.
+    /// Sets the URL of the gateway that shards will connect to, e.g.
+    /// `ws://127.0.0.1:3000`.
+    ///
+    /// By default, the URL is retrieved via [`Http::get_gateway`] when the
+    /// builder is awaited. Setting this is mainly useful when running against
+    /// a local stand-in for Discord or a gateway proxy.
+    pub fn gateway_url(mut self, gateway_url: impl Into<String>) -> Self {
+        self.gateway_url = Some(gateway_url.into());
+
+        self
+    }
+
+    /// Gets the gateway URL, if set. See [`Self::gateway_url`] for more info.
+    pub fn get_gateway_url(&self) -> Option<&str> {
+        self.gateway_url.as_deref()
+    }
+
+    /// Sets the transport compression that shards negotiate with the gateway.
+    ///
+    /// Enabling [`TransportCompression::ZlibStream`] greatly reduces the
+    /// bandwidth used by the gateway connections, at the cost of some CPU time
+    /// spent decompressing payloads. Defaults to [`TransportCompression::None`].
+    pub fn transport_compression(mut self, compression: TransportCompression) -> Self {
+        self.compression = compression;
+
+        self
+    }
+
+    /// Gets the transport compression. See [`Self::transport_compression`] for
+    /// more info.
+    pub fn get_transport_compression(&self) -> TransportCompression {
+        self.compression
+    }
+
+    /// Sets the encoding of the payloads that shards exchange with the
+    /// gateway.
+    ///
+    /// [`GatewayEncoding::Etf`] payloads are smaller and cheaper to decode
+    /// than JSON ones. Defaults to [`GatewayEncoding::Json`].
+    pub fn gateway_encoding(mut self, encoding: GatewayEncoding) -> Self {
+        self.encoding = encoding;
+
+        self
+    }
+
+    /// Gets the gateway encoding. See [`Self::gateway_encoding`] for more
+    /// info.
+    pub fn get_gateway_encoding(&self) -> GatewayEncoding {
+        self.encoding
+    }
+
+    /// Gracefully shuts the client down when the process receives Ctrl-C, or
+    /// SIGTERM on Unix, giving running event handlers up to `timeout` to
+    /// finish.
+    ///
+    /// The signals are only listened for while [`Client::start`] or one of
+    /// its variants is running. Refer to [`ShutdownHandle::shutdown`] for the
+    /// steps of a graceful shutdown.
+    ///
+    /// **Note**: This requires the `signal` feature.
+    #[cfg(feature = "signal")]
+    pub fn shutdown_on_signal(mut self, timeout: Duration) -> Self {
+        self.shutdown_on_signal = Some(timeout);
+
+        self
+    }
+
+    /// Gets the shutdown timeout used when receiving a signal, if set. See
+    /// [`Self::shutdown_on_signal`] for more info.
+    #[cfg(feature = "signal")]
+    pub fn get_shutdown_on_signal(&self) -> Option<Duration> {
+        self.shutdown_on_signal
+    }
+
+    /// Sets the store that the gateway sessions of shards are persisted in,
+    /// so that they are resumed rather than IDENTIFYing again after the
+    /// process restarted.
+    ///
+    /// Refer to [`SessionStore`] for more info.
+    pub fn session_store<S: SessionStore + 'static>(mut self, session_store: S) -> Self {
+        self.session_store = Some(Arc::new(session_store));
+
+        self
+    }
+
+    /// Gets the session store, if already initialized. See
+    /// [`Self::session_store`] for more info.
+    pub fn get_session_store(&self) -> Option<Arc<dyn SessionStore>> {
+        self.session_store.clone()
+    }
+
+    /// Sets the coordinator that shards are claimed from and acquire identify
+    /// slots from, to coordinate them with other processes of the bot.
+    ///
+    /// Defaults to an [`InMemoryShardCoordinator`], which only coordinates
+    /// the shards of this client. Refer to [`ShardCoordinator`] for more info.
+    pub fn shard_coordinator<C: ShardCoordinator + 'static>(mut self, coordinator: C) -> Self {
+        self.shard_coordinator = Some(Arc::new(coordinator));
+
+        self
+    }
+
+    /// Gets the shard coordinator, if already initialized. See
+    /// [`Self::shard_coordinator`] for more info.
+    pub fn get_shard_coordinator(&self) -> Option<Arc<dyn ShardCoordinator>> {
+        self.shard_coordinator.clone()
+    }
+
+    /// Sets the guilds that shards request all members of automatically,
+    /// once they are received.
+    ///
+    /// Requires the [`GatewayIntents::GUILD_MEMBERS`] intent. Refer to
+    /// [`GuildChunking`] for more info.
+    pub fn chunk_guilds(mut self, chunking: GuildChunking) -> Self {
+        self.chunking = Some(chunking);
+
+        self
+    }
+
+    /// Gets the automatic chunking of guilds, if set. See
+    /// [`Self::chunk_guilds`] for more info.
+    pub fn get_chunk_guilds(&self) -> Option<&GuildChunking> {
+        self.chunking.as_ref()
+    }
+
+    /// Adds an event handler with multiple methods for each possible event.
+    ///
+    /// Any number of event handlers may be added. Each event is dispatched to
+    /// them in the order they were added, with the next handler only being
+    /// called once the previous one returned.
     pub fn event_handler<H: EventHandler + 'static>(mut self, event_handler: H) -> Self {
-        self.event_handler = Some(Arc::new(event_handler));
+        self.event_handlers.push(Arc::new(event_handler));
 
         self
     }
 
-    /// Sets an event handler with multiple methods for each possible event. Passed by Arc.
+    /// Adds an event handler with multiple methods for each possible event. Passed by Arc.
+    /// See [`Self::event_handler`] for more info.
     pub fn event_handler_arc<H: EventHandler + 'static>(
         mut self,
         event_handler_arc: Arc<H>,
     ) -> Self {
-        self.event_handler = Some(event_handler_arc);
+        self.event_handlers.push(event_handler_arc);
 
         self
     }
 
-    /// Gets the event handler, if already initialized. See [`Self::event_handler`] for more info.
+    /// Gets the event handlers added so far, in dispatch order. See [`Self::event_handler`] for
+    /// more info.
+    pub fn get_event_handlers(&self) -> &[Arc<dyn EventHandler>] {
+        &self.event_handlers
+    }
+
+    /// Gets the first event handler, if any was added. See [`Self::event_handler`] for more info.
+    #[deprecated(note = "use `get_event_handlers`")]
     pub fn get_event_handler(&self) -> Option<Arc<dyn EventHandler>> {
-        self.event_handler.clone()
+        self.event_handlers.first().cloned()
     }
 
-    /// Sets an event handler with a single method where all received gateway
+    /// Adds an event handler with a single method where all received gateway
     /// events will be dispatched.
+    ///
+    /// Like with [`Self::event_handler`], any number of raw event handlers may
+    /// be added, and are dispatched to in the order they were added.
     pub fn raw_event_handler<H: RawEventHandler + 'static>(mut self, raw_event_handler: H) -> Self {
-        self.raw_event_handler = Some(Arc::new(raw_event_handler));
+        self.raw_event_handlers.push(Arc::new(raw_event_handler));
 
         self
     }
 
-    /// Gets the raw event handler, if already initialized. See [`Self::raw_event_handler`] for more
-    /// info.
+    /// Gets the raw event handlers added so far, in dispatch order. See
+    /// [`Self::raw_event_handler`] for more info.
+    pub fn get_raw_event_handlers(&self) -> &[Arc<dyn RawEventHandler>] {
+        &self.raw_event_handlers
+    }
+
+    /// Gets the first raw event handler, if any was added. See [`Self::raw_event_handler`] for
+    /// more info.
+    #[deprecated(note = "use `get_raw_event_handlers`")]
     pub fn get_raw_event_handler(&self) -> Option<Arc<dyn RawEventHandler>> {
-        self.raw_event_handler.clone()
+        self.raw_event_handlers.first().cloned()
+    }
+
+    /// Adds a middleware that every gateway event passes through before it is
+    /// dispatched to the event handlers and the framework.
+    ///
+    /// Middleware is run in the order it was added. Refer to
+    /// [`EventMiddleware`] for more info.
+    pub fn middleware<M: EventMiddleware + 'static>(mut self, middleware: M) -> Self {
+        self.middleware.push(Arc::new(middleware));
+
+        self
+    }
+
+    /// Gets the middleware added so far, in the order it is run. See
+    /// [`Self::middleware`] for more info.
+    pub fn get_middleware(&self) -> &[Arc<dyn EventMiddleware>] {
+        &self.middleware
     }
 }
 
@@ -360,9 +556,22 @@ impl Future for ClientBuilder {
             let framework = self.framework.take()
                 .expect("The `framework`-feature is enabled (it's on by default), but no framework was provided.\n\
                 If you don't want to use the command framework, disable default features and specify all features you want to use.");
-            let event_handler = self.event_handler.take();
-            let raw_event_handler = self.raw_event_handler.take();
+            let event_handlers = std::mem::take(&mut self.event_handlers);
+            let raw_event_handlers = std::mem::take(&mut self.raw_event_handlers);
+            let middleware = std::mem::take(&mut self.middleware);
+            let event_handler = EventHandlers::combine(event_handlers.clone());
+            #[cfg(feature = "signal")]
+            let shutdown_on_signal = self.shutdown_on_signal;
+            let session_store = self.session_store.take();
+            let chunking = self.chunking.take();
+            let shard_coordinator = self
+                .shard_coordinator
+                .take()
+                .unwrap_or_else(|| Arc::new(InMemoryShardCoordinator::new()));
             let intents = self.intents;
+            let gateway_url = self.gateway_url.take();
+            let compression = self.compression;
+            let encoding = self.encoding;
 
             let mut http = self.http.take().unwrap();
             if let Some(event_handler) = event_handler.clone() {
@@ -383,19 +592,26 @@ impl Future for ClientBuilder {
             });
 
             self.fut = Some(Box::pin(async move {
-                let ws_url = Arc::new(Mutex::new(match http.get_gateway().await {
-                    Ok(response) => response.url,
-                    Err(err) => {
-                        tracing::warn!("HTTP request to get gateway URL failed: {}", err);
-                        "wss://gateway.discord.gg".to_string()
+                let ws_url = Arc::new(Mutex::new(match gateway_url {
+                    Some(url) => url,
+                    None => match http.get_gateway().await {
+                        Ok(response) => response.url,
+                        Err(err) => {
+                            tracing::warn!("HTTP request to get gateway URL failed: {}", err);
+                            "wss://gateway.discord.gg".to_string()
+                        },
                     },
                 }));
 
+                let dispatch_tasks = Arc::new(DispatchTasks::default());
+
                 let (shard_manager, shard_manager_worker) = {
                     ShardManager::new(ShardManagerOptions {
                         data: &data,
-                        event_handler: &event_handler,
-                        raw_event_handler: &raw_event_handler,
+                        event_handlers: &event_handlers,
+                        raw_event_handlers: &raw_event_handlers,
+                        middleware: &middleware,
+                        dispatch_tasks: &dispatch_tasks,
                         #[cfg(feature = "framework")]
                         framework: &framework,
                         shard_index: 0,
@@ -406,14 +622,30 @@ impl Future for ClientBuilder {
                         ws_url: &ws_url,
                         cache_and_http: &cache_and_http,
                         intents,
+                        compression,
+                        encoding,
+                        session_store: &session_store,
+                        shard_coordinator: &Some(Arc::clone(&shard_coordinator)),
+                        chunking: &chunking,
                     })
                     .await
                 };
 
+                let shutdown = ShutdownHandle::new(
+                    Arc::clone(&shard_manager),
+                    dispatch_tasks,
+                    Arc::clone(&http),
+                    event_handler,
+                );
+
                 Ok(Client {
                     data,
                     shard_manager,
                     shard_manager_worker,
+                    shutdown,
+                    #[cfg(feature = "signal")]
+                    shutdown_on_signal,
+                    shard_coordinator,
                     #[cfg(feature = "voice")]
                     voice_manager,
                     ws_url,
@@ -648,6 +880,10 @@ pub struct Client {
     /// ```
     pub shard_manager: Arc<Mutex<ShardManager>>,
     shard_manager_worker: ShardManagerMonitor,
+    shutdown: ShutdownHandle,
+    #[cfg(feature = "signal")]
+    shutdown_on_signal: Option<Duration>,
+    shard_coordinator: Arc<dyn ShardCoordinator>,
     /// The voice manager for the client.
     ///
     /// This is an ergonomic structure for interfacing over shards' voice
@@ -671,6 +907,15 @@ impl Client {
         ClientBuilder::new(token, intents)
     }
 
+    /// Returns a handle to gracefully shut the client down, which may be
+    /// cloned and sent to other tasks.
+    ///
+    /// Refer to [`ShutdownHandle`] for an example.
+    #[must_use]
+    pub fn shutdown_handle(&self) -> ShutdownHandle {
+        self.shutdown.clone()
+    }
+
     /// Establish the connection and start listening for events.
     ///
     /// This will start receiving events in a loop and start dispatching the
@@ -937,6 +1182,39 @@ impl Client {
         self.start_connection([range[0], range[1], total_shards]).await
     }
 
+    /// Claims a range of shards from the [`ShardCoordinator`], establishes
+    /// their connections and starts listening for events.
+    ///
+    /// This is used to run a bot across multiple processes, each connected to
+    /// the same [`ShardCoordinatorServer`] via a [`RemoteShardCoordinator`],
+    /// without assigning each process its shard range by hand. The claimed
+    /// shards are released once the connections end.
+    ///
+    /// Refer to [`RemoteShardCoordinator`] for an example.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ClientError::Coordinator`] if `count` shards could not be
+    /// claimed, or a [`ClientError::Shutdown`] when all shards have shutdown
+    /// due to an error.
+    ///
+    /// [`ShardCoordinatorServer`]: bridge::gateway::ShardCoordinatorServer
+    /// [`RemoteShardCoordinator`]: bridge::gateway::RemoteShardCoordinator
+    #[instrument(skip(self))]
+    pub async fn start_coordinated(&mut self, total_shards: u64, count: u64) -> Result<()> {
+        let shards = self.shard_coordinator.claim_shards(total_shards, count).await?;
+
+        info!("Claimed shards {:?} out of {}", shards, total_shards);
+
+        let result = self.start_connection([shards.start, shards.end - 1, total_shards]).await;
+
+        if let Err(why) = self.shard_coordinator.release_shards(shards).await {
+            warn!("Failed to release claimed shards: {:?}", why);
+        }
+
+        result
+    }
+
     /// Shard data layout is:
     /// 0: first shard number to initialize
     /// 1: shard number to initialize up to and including
@@ -976,7 +1254,28 @@ impl Client {
             }
         }
 
-        if let Err(why) = self.shard_manager_worker.run().await {
+        #[cfg(feature = "signal")]
+        let signal_task = self.shutdown_on_signal.map(|timeout| {
+            let shutdown = self.shutdown.clone();
+
+            spawn_named("client::shutdown_on_signal", async move {
+                shutdown::signal().await;
+                shutdown.shutdown_with_reason(ShutdownReason::Signal, timeout).await;
+            })
+        });
+
+        let result = self.shard_manager_worker.run().await;
+
+        if self.shutdown.is_shutting_down() {
+            self.shutdown.wait_finished().await;
+        }
+
+        #[cfg(feature = "signal")]
+        if let Some(signal_task) = signal_task {
+            signal_task.abort();
+        }
+
+        if let Err(why) = result {
             let err = match why {
                 ShardManagerError::DisallowedGatewayIntents => {
                     GatewayError::DisallowedGatewayIntents
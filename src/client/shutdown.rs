@@ -0,0 +1,285 @@
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::{Mutex, Notify};
+use tokio::time::{timeout_at, Instant};
+use tracing::{info, warn};
+
+use super::bridge::gateway::ShardManager;
+use super::EventHandler;
+use crate::http::Http;
+use crate::internal::tokio::spawn_named;
+
+/// The close code that shards are closed with on a graceful shutdown.
+///
+/// Unlike 1000 and 1001, this code does not invalidate the session, so that the
+/// bot may RESUME it after restarting.
+const RESUMABLE_CLOSE_CODE: u16 = 4000;
+
+/// Why the [`Client`] is shutting down, as reported to
+/// [`EventHandler::shutdown`].
+///
+/// [`Client`]: super::Client
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum ShutdownReason {
+    /// [`ShutdownHandle::shutdown`] was called.
+    Requested,
+    /// The process received Ctrl-C, or SIGTERM on Unix. See
+    /// `ClientBuilder::shutdown_on_signal`, which requires the `signal`
+    /// feature.
+    Signal,
+}
+
+/// Keeps count of the tasks that events are dispatched to handlers in, so that
+/// a graceful shutdown can wait for them to finish.
+#[derive(Debug, Default)]
+pub struct DispatchTasks {
+    running: AtomicUsize,
+    idle: Notify,
+}
+
+impl DispatchTasks {
+    /// The number of dispatch tasks that have not finished yet.
+    #[must_use]
+    pub fn running(&self) -> usize {
+        self.running.load(Ordering::Acquire)
+    }
+
+    pub(crate) fn spawn<F>(self: &Arc<Self>, name: &str, future: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        self.running.fetch_add(1, Ordering::AcqRel);
+        let guard = RunningGuard(Arc::clone(self));
+
+        spawn_named(name, async move {
+            let _guard = guard;
+
+            future.await;
+        });
+    }
+
+    /// Waits until no dispatch task is running.
+    pub(crate) async fn wait_idle(&self) {
+        loop {
+            let notified = self.idle.notified();
+
+            if self.running() == 0 {
+                return;
+            }
+
+            notified.await;
+        }
+    }
+}
+
+/// Marks a dispatch task as finished when dropped, including when the task
+/// panicked or was aborted.
+struct RunningGuard(Arc<DispatchTasks>);
+
+impl Drop for RunningGuard {
+    fn drop(&mut self) {
+        if self.0.running.fetch_sub(1, Ordering::AcqRel) == 1 {
+            self.0.idle.notify_waiters();
+        }
+    }
+}
+
+struct ShutdownState {
+    shard_manager: Arc<Mutex<ShardManager>>,
+    dispatch_tasks: Arc<DispatchTasks>,
+    http: Arc<Http>,
+    event_handler: Option<Arc<dyn EventHandler>>,
+    started: AtomicBool,
+    finished: AtomicBool,
+    drained: AtomicBool,
+    /// Notified once the shutdown finished.
+    notify: Notify,
+}
+
+/// A cloneable handle to gracefully shut down a [`Client`], obtained via
+/// [`Client::shutdown_handle`].
+///
+/// # Examples
+///
+/// Shutting down after one minute of operation, giving running event handlers
+/// up to ten seconds to finish:
+///
+/// ```rust,no_run
+/// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+/// use std::time::Duration;
+///
+/// use serenity::prelude::*;
+///
+/// struct Handler;
+///
+/// impl EventHandler for Handler {}
+///
+/// let token = std::env::var("DISCORD_TOKEN")?;
+/// let mut client =
+///     Client::builder(&token, GatewayIntents::default()).event_handler(Handler).await?;
+///
+/// let shutdown = client.shutdown_handle();
+///
+/// tokio::spawn(async move {
+///     tokio::time::sleep(Duration::from_secs(60)).await;
+///
+///     shutdown.shutdown(Duration::from_secs(10)).await;
+/// });
+///
+/// client.start().await?;
+/// #     Ok(())
+/// # }
+/// ```
+///
+/// [`Client`]: super::Client
+/// [`Client::shutdown_handle`]: super::Client::shutdown_handle
+#[derive(Clone)]
+pub struct ShutdownHandle {
+    state: Arc<ShutdownState>,
+}
+
+impl ShutdownHandle {
+    pub(crate) fn new(
+        shard_manager: Arc<Mutex<ShardManager>>,
+        dispatch_tasks: Arc<DispatchTasks>,
+        http: Arc<Http>,
+        event_handler: Option<Arc<dyn EventHandler>>,
+    ) -> Self {
+        Self {
+            state: Arc::new(ShutdownState {
+                shard_manager,
+                dispatch_tasks,
+                http,
+                event_handler,
+                started: AtomicBool::new(false),
+                finished: AtomicBool::new(false),
+                drained: AtomicBool::new(false),
+                notify: Notify::new(),
+            }),
+        }
+    }
+
+    /// Gracefully shuts the client down.
+    ///
+    /// This:
+    ///
+    /// 1. calls [`EventHandler::shutdown`] with [`ShutdownReason::Requested`];
+    /// 2. closes all shards with a close code that keeps their sessions
+    ///    resumable, and shuts down the shard manager, so that no more events
+    ///    are dispatched;
+    /// 3. waits for event handlers that are still running to finish;
+    /// 4. waits for pending HTTP requests made through the client's [`Http`]
+    ///    to finish, including those made by tasks that a handler spawned.
+    ///
+    /// [`Client::start`] and its variants return once this finished.
+    ///
+    /// Returns `false` if the event handlers or HTTP requests did not finish
+    /// within `timeout`. They are left running in that case.
+    ///
+    /// Calling this again while a shutdown is in progress waits for that
+    /// shutdown to finish.
+    ///
+    /// [`Client::start`]: super::Client::start
+    pub async fn shutdown(&self, timeout: Duration) -> bool {
+        self.shutdown_with_reason(ShutdownReason::Requested, timeout).await
+    }
+
+    pub(crate) async fn shutdown_with_reason(
+        &self,
+        reason: ShutdownReason,
+        timeout: Duration,
+    ) -> bool {
+        let state = &self.state;
+        let deadline = Instant::now() + timeout;
+
+        if state.started.swap(true, Ordering::AcqRel) {
+            return self.wait_finished().await;
+        }
+
+        info!("Gracefully shutting down: {:?}", reason);
+
+        if let Some(event_handler) = &state.event_handler {
+            if timeout_at(deadline, event_handler.shutdown(reason)).await.is_err() {
+                warn!("EventHandler::shutdown did not finish in time");
+            }
+        }
+
+        state.shard_manager.lock().await.shutdown_all_with_code(RESUMABLE_CLOSE_CODE).await;
+
+        let mut drained = timeout_at(deadline, state.dispatch_tasks.wait_idle()).await.is_ok();
+
+        if !drained {
+            warn!(
+                "{} event handler(s) still running after the shutdown timeout",
+                state.dispatch_tasks.running()
+            );
+        }
+
+        if timeout_at(deadline, state.http.wait_idle()).await.is_err() {
+            warn!(
+                "{} HTTP request(s) still in flight after the shutdown timeout",
+                state.http.in_flight_requests()
+            );
+
+            drained = false;
+        }
+
+        state.drained.store(drained, Ordering::Release);
+        state.finished.store(true, Ordering::Release);
+        state.notify.notify_waiters();
+
+        drained
+    }
+
+    /// Waits for the shutdown to finish, returning whether the event handlers
+    /// and HTTP requests finished in time.
+    pub(crate) async fn wait_finished(&self) -> bool {
+        let state = &self.state;
+
+        loop {
+            let notified = state.notify.notified();
+
+            if state.finished.load(Ordering::Acquire) {
+                return state.drained.load(Ordering::Acquire);
+            }
+
+            notified.await;
+        }
+    }
+
+    /// Whether a shutdown was started.
+    #[must_use]
+    pub fn is_shutting_down(&self) -> bool {
+        self.state.started.load(Ordering::Acquire)
+    }
+}
+
+/// Waits for Ctrl-C, or SIGTERM on Unix.
+#[cfg(feature = "signal")]
+pub(crate) async fn signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        match signal(SignalKind::terminate()) {
+            Ok(mut terminate) => {
+                tokio::select! {
+                    _ = tokio::signal::ctrl_c() => {},
+                    _ = terminate.recv() => {},
+                }
+
+                return;
+            },
+            Err(why) => warn!("Failed to listen for SIGTERM: {:?}", why),
+        }
+    }
+
+    if let Err(why) = tokio::signal::ctrl_c().await {
+        warn!("Failed to listen for Ctrl-C: {:?}", why);
+        futures::future::pending::<()>().await;
+    }
+}
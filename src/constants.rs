@@ -13,6 +13,10 @@ pub const STICKER_MAX_COUNT: usize = 3;
 /// the REST API.
 pub const GATEWAY_VERSION: u8 = 10;
 
+/// The default base URL of Discord's REST API. Paths of all routes are
+/// relative to this URL.
+pub const API_BASE_URL: &str = "https://discord.com/api/v10";
+
 /// The large threshold to send on identify.
 pub const LARGE_THRESHOLD: u8 = 250;
 
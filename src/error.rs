@@ -9,6 +9,8 @@ use reqwest::{header::InvalidHeaderValue, Error as ReqwestError};
 use serde_json::Error as JsonError;
 use tracing::instrument;
 
+#[cfg(feature = "cache")]
+use crate::cache::CacheError;
 #[cfg(feature = "client")]
 use crate::client::ClientError;
 #[cfg(feature = "collector")]
@@ -17,6 +19,8 @@ use crate::collector::CollectorError;
 use crate::gateway::GatewayError;
 #[cfg(feature = "http")]
 use crate::http::HttpError;
+#[cfg(feature = "interactions_endpoint")]
+use crate::interactions_endpoint::InteractionsEndpointError;
 use crate::internal::prelude::*;
 use crate::model::ModelError;
 
@@ -76,6 +80,11 @@ pub enum Error {
     Other(&'static str),
     /// An error from the [`url`] crate.
     Url(String),
+    /// A [cache] error.
+    ///
+    /// [cache]: crate::cache
+    #[cfg(feature = "cache")]
+    Cache(CacheError),
     /// A [client] error.
     ///
     /// [client]: crate::client
@@ -96,6 +105,11 @@ pub enum Error {
     /// [`http`]: crate::http
     #[cfg(feature = "http")]
     Http(Box<HttpError>),
+    /// An error from the [`interactions_endpoint`] module.
+    ///
+    /// [`interactions_endpoint`]: crate::interactions_endpoint
+    #[cfg(feature = "interactions_endpoint")]
+    InteractionsEndpoint(InteractionsEndpointError),
     /// An error from the `tungstenite` crate.
     #[cfg(feature = "gateway")]
     Tungstenite(TungsteniteError),
@@ -108,6 +122,13 @@ impl From<simd_json::Error> for Error {
     }
 }
 
+#[cfg(feature = "cache")]
+impl From<CacheError> for Error {
+    fn from(e: CacheError) -> Error {
+        Error::Cache(e)
+    }
+}
+
 impl From<FormatError> for Error {
     fn from(e: FormatError) -> Error {
         Error::Format(e)
@@ -153,6 +174,13 @@ impl From<HttpError> for Error {
     }
 }
 
+#[cfg(feature = "interactions_endpoint")]
+impl From<InteractionsEndpointError> for Error {
+    fn from(e: InteractionsEndpointError) -> Error {
+        Error::InteractionsEndpoint(e)
+    }
+}
+
 #[cfg(feature = "http")]
 impl From<InvalidHeaderValue> for Error {
     fn from(e: InvalidHeaderValue) -> Error {
@@ -180,6 +208,8 @@ impl fmt::Display for Error {
             Self::Url(msg) => f.write_str(msg),
             #[cfg(feature = "simd-json")]
             Error::SimdJson(inner) => fmt::Display::fmt(&inner, f),
+            #[cfg(feature = "cache")]
+            Self::Cache(inner) => fmt::Display::fmt(&inner, f),
             #[cfg(feature = "client")]
             Self::Client(inner) => fmt::Display::fmt(&inner, f),
             #[cfg(feature = "collector")]
@@ -188,6 +218,8 @@ impl fmt::Display for Error {
             Self::Gateway(inner) => fmt::Display::fmt(&inner, f),
             #[cfg(feature = "http")]
             Self::Http(inner) => fmt::Display::fmt(&inner, f),
+            #[cfg(feature = "interactions_endpoint")]
+            Self::InteractionsEndpoint(inner) => fmt::Display::fmt(&inner, f),
             #[cfg(feature = "gateway")]
             Self::Tungstenite(inner) => fmt::Display::fmt(&inner, f),
         }
@@ -202,6 +234,8 @@ impl StdError for Error {
             Self::Io(inner) => Some(inner),
             Self::Json(inner) => Some(inner),
             Self::Model(inner) => Some(inner),
+            #[cfg(feature = "cache")]
+            Self::Cache(inner) => Some(inner),
             #[cfg(feature = "client")]
             Self::Client(inner) => Some(inner),
             #[cfg(feature = "collector")]
@@ -210,6 +244,8 @@ impl StdError for Error {
             Self::Gateway(inner) => Some(inner),
             #[cfg(feature = "http")]
             Self::Http(inner) => Some(inner),
+            #[cfg(feature = "interactions_endpoint")]
+            Self::InteractionsEndpoint(inner) => Some(inner),
             #[cfg(feature = "gateway")]
             Self::Tungstenite(inner) => Some(inner),
             _ => None,
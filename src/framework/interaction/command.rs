@@ -0,0 +1,601 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use futures::future::BoxFuture;
+
+use super::CommandOptions;
+use crate::builder::{CreateApplicationCommand, CreateApplicationCommandOption};
+use crate::client::Context;
+use crate::framework::standard::{Check, CommandResult, OnlyIn, Reason};
+use crate::json::NULL;
+use crate::model::application::command::{CommandOptionType, CommandType};
+use crate::model::application::interaction::application_command::{
+    ApplicationCommandInteraction,
+    CommandData,
+    CommandDataOption,
+};
+use crate::model::application::interaction::autocomplete::AutocompleteInteraction;
+use crate::model::application::interaction::message_component::MessageComponentInteraction;
+use crate::model::application::interaction::MessageInteraction;
+use crate::model::channel::{Message, MessageType};
+use crate::model::guild::PartialMember;
+use crate::model::Permissions;
+
+/// The handler of a slash command, receiving the options passed to the
+/// invoked (sub)command.
+pub type SlashCommandFn = for<'fut> fn(
+    &'fut Context,
+    &'fut ApplicationCommandInteraction,
+    &'fut [CommandDataOption],
+) -> BoxFuture<'fut, CommandResult>;
+/// The handler of the autocomplete interactions of a slash command, receiving
+/// the options typed so far, one of which is [focused].
+///
+/// [focused]: CommandDataOption::focused
+pub type AutocompleteFn = for<'fut> fn(
+    &'fut Context,
+    &'fut AutocompleteInteraction,
+    &'fut [CommandDataOption],
+) -> BoxFuture<'fut, CommandResult>;
+/// The handler of message component interactions.
+pub type ComponentFn = for<'fut> fn(
+    &'fut Context,
+    &'fut MessageComponentInteraction,
+) -> BoxFuture<'fut, CommandResult>;
+/// A check deciding whether a slash command may be invoked.
+pub type InteractionCheckFn = for<'fut> fn(
+    &'fut Context,
+    &'fut ApplicationCommandInteraction,
+) -> BoxFuture<'fut, Result<(), Reason>>;
+
+/// A check of a [`SlashCommand`], taking either the interaction, or the
+/// message standing in for it.
+#[derive(Clone, Copy)]
+pub(crate) enum SlashCommandCheck {
+    Interaction(&'static str, InteractionCheckFn),
+    Standard(&'static Check),
+}
+
+impl SlashCommandCheck {
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            Self::Interaction(name, _) => name,
+            Self::Standard(check) => check.name,
+        }
+    }
+}
+
+/// An application command registered with an [`InteractionFramework`], along
+/// with its subcommands and the handlers invoking them.
+///
+/// The command can be a subcommand group, if its subcommands have
+/// subcommands themselves, or a subcommand of its parent, if it has no
+/// subcommand. Only subcommands without subcommands are invoked, so only they
+/// need a handler and options.
+///
+/// Checks, permissions and restrictions apply to the command and all of its
+/// subcommands, while the [bucket] of the innermost command that has one is
+/// used.
+///
+/// [`InteractionFramework`]: super::InteractionFramework
+/// [bucket]: Self::bucket
+#[derive(Clone)]
+pub struct SlashCommand {
+    pub(crate) name: String,
+    description: String,
+    kind: CommandType,
+    name_localizations: HashMap<String, String>,
+    description_localizations: HashMap<String, String>,
+    default_member_permissions: Option<Permissions>,
+    dm_permission: Option<bool>,
+    options: Vec<CreateApplicationCommandOption>,
+    pub(crate) subcommands: Vec<SlashCommand>,
+    pub(crate) handler: Option<SlashCommandFn>,
+    pub(crate) autocomplete: Option<AutocompleteFn>,
+    pub(crate) checks: Vec<SlashCommandCheck>,
+    pub(crate) bucket: Option<String>,
+    pub(crate) only_in: OnlyIn,
+    pub(crate) owners_only: bool,
+    pub(crate) required_permissions: Permissions,
+}
+
+impl Default for SlashCommand {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            description: String::new(),
+            kind: CommandType::ChatInput,
+            name_localizations: HashMap::new(),
+            description_localizations: HashMap::new(),
+            default_member_permissions: None,
+            dm_permission: None,
+            options: Vec::new(),
+            subcommands: Vec::new(),
+            handler: None,
+            autocomplete: None,
+            checks: Vec::new(),
+            bucket: None,
+            only_in: OnlyIn::None,
+            owners_only: false,
+            required_permissions: Permissions::empty(),
+        }
+    }
+}
+
+impl SlashCommand {
+    /// Sets the name of the command.
+    ///
+    /// **Note**: Must be between 1 and 32 lowercase characters, matching `r"^[\w-]{1,32}$"`.
+    pub fn name<D: ToString>(&mut self, name: D) -> &mut Self {
+        self.name = name.to_string();
+        self
+    }
+
+    /// Specifies a localized name of the command.
+    pub fn name_localized<D: ToString, E: ToString>(&mut self, locale: E, name: D) -> &mut Self {
+        self.name_localizations.insert(locale.to_string(), name.to_string());
+        self
+    }
+
+    /// Sets the description of the command.
+    ///
+    /// **Note**: Must be between 1 and 100 characters long. It is not sent
+    /// for user and message commands.
+    pub fn description<D: ToString>(&mut self, description: D) -> &mut Self {
+        self.description = description.to_string();
+        self
+    }
+
+    /// Specifies a localized description of the command.
+    pub fn description_localized<D: ToString, E: ToString>(
+        &mut self,
+        locale: E,
+        description: D,
+    ) -> &mut Self {
+        self.description_localizations.insert(locale.to_string(), description.to_string());
+        self
+    }
+
+    /// Sets the type of the command.
+    ///
+    /// **Note**: This defaults to [`CommandType::ChatInput`], and only
+    /// applies to top-level commands.
+    pub fn kind(&mut self, kind: CommandType) -> &mut Self {
+        self.kind = kind;
+        self
+    }
+
+    /// Specifies the default permissions required to execute the command.
+    ///
+    /// **Note**: This is enforced by Discord, and only applies to top-level
+    /// commands. Use [`Self::required_permissions`] for permissions enforced
+    /// by the framework.
+    pub fn default_member_permissions(&mut self, permissions: Permissions) -> &mut Self {
+        self.default_member_permissions = Some(permissions);
+        self
+    }
+
+    /// Specifies if the command is available in DMs.
+    ///
+    /// **Note**: This only applies to top-level global commands.
+    pub fn dm_permission(&mut self, enabled: bool) -> &mut Self {
+        self.dm_permission = Some(enabled);
+        self
+    }
+
+    /// Creates an option of the command.
+    ///
+    /// **Note**: Only commands without subcommands can have options.
+    pub fn create_option<F>(&mut self, f: F) -> &mut Self
+    where
+        F: FnOnce(&mut CreateApplicationCommandOption) -> &mut CreateApplicationCommandOption,
+    {
+        let mut option = CreateApplicationCommandOption::default();
+        f(&mut option);
+        self.add_option(option)
+    }
+
+    /// Adds an option to the command.
+    ///
+    /// **Note**: Only commands without subcommands can have options.
+    pub fn add_option(&mut self, option: CreateApplicationCommandOption) -> &mut Self {
+        self.options.push(option);
+        self
+    }
+
+    /// Adds the options created by a [`CommandOptions`] implementation, such
+    /// as one derived with the [`CommandOptions`] macro.
+    ///
+    /// **Note**: Only commands without subcommands can have options.
+    ///
+    /// [`CommandOptions`]: macro@super::CommandOptions
+    pub fn options_from<T: CommandOptions>(&mut self) -> &mut Self {
+        self.options.extend(T::create_options());
+        self
+    }
+
+    /// Creates a subcommand of the command.
+    ///
+    /// **Note**: Discord only allows two levels of nesting: a command may
+    /// contain subcommand groups, which may only contain subcommands.
+    pub fn create_subcommand<F>(&mut self, f: F) -> &mut Self
+    where
+        F: FnOnce(&mut SlashCommand) -> &mut SlashCommand,
+    {
+        let mut subcommand = SlashCommand::default();
+        f(&mut subcommand);
+        self.add_subcommand(subcommand)
+    }
+
+    /// Adds a subcommand to the command.
+    pub fn add_subcommand(&mut self, subcommand: SlashCommand) -> &mut Self {
+        self.subcommands.push(subcommand);
+        self
+    }
+
+    /// Sets the function invoked when the command is used.
+    pub fn handler(&mut self, handler: SlashCommandFn) -> &mut Self {
+        self.handler = Some(handler);
+        self
+    }
+
+    /// Sets the function invoked for the autocomplete interactions of the
+    /// command's options.
+    pub fn autocomplete(&mut self, autocomplete: AutocompleteFn) -> &mut Self {
+        self.autocomplete = Some(autocomplete);
+        self
+    }
+
+    /// Adds a check that must pass for the command to be invoked. The name is
+    /// reported in [`DispatchError::CheckFailed`].
+    ///
+    /// [`DispatchError::CheckFailed`]: crate::framework::standard::DispatchError::CheckFailed
+    pub fn check(&mut self, name: &'static str, check: InteractionCheckFn) -> &mut Self {
+        self.checks.push(SlashCommandCheck::Interaction(name, check));
+        self
+    }
+
+    /// Adds a check of the [`StandardFramework`], such as one defined with the
+    /// [`check`] macro, that must pass for the command to be invoked.
+    ///
+    /// The check is given a message standing in for the interaction, with the
+    /// Id, author, member, channel and guild of the interaction, and the
+    /// invoked command as content, such as `/config roles add`, along with
+    /// empty arguments and default command options. As the message does not
+    /// exist, the check should not reply or react to it.
+    ///
+    /// [`StandardFramework`]: crate::framework::StandardFramework
+    /// [`check`]: crate::framework::standard::macros::check
+    pub fn standard_check(&mut self, check: &'static Check) -> &mut Self {
+        self.checks.push(SlashCommandCheck::Standard(check));
+        self
+    }
+
+    /// Sets the name of the [bucket] limiting how often the command can be
+    /// invoked.
+    ///
+    /// [bucket]: super::InteractionFramework::bucket
+    pub fn bucket<D: ToString>(&mut self, bucket: D) -> &mut Self {
+        self.bucket = Some(bucket.to_string());
+        self
+    }
+
+    /// Sets where the command can be invoked.
+    pub fn only_in(&mut self, only_in: OnlyIn) -> &mut Self {
+        self.only_in = only_in;
+        self
+    }
+
+    /// Sets whether only the [owners] can invoke the command.
+    ///
+    /// [owners]: super::InteractionFramework::owners
+    pub fn owners_only(&mut self, owners_only: bool) -> &mut Self {
+        self.owners_only = owners_only;
+        self
+    }
+
+    /// Sets the permissions the invoking member must have in guilds, checked
+    /// by the framework.
+    pub fn required_permissions(&mut self, permissions: Permissions) -> &mut Self {
+        self.required_permissions = permissions;
+        self
+    }
+
+    /// Builds the definition of the command to register it with Discord.
+    #[must_use]
+    pub fn create_application_command(&self) -> CreateApplicationCommand {
+        let mut command = CreateApplicationCommand::default();
+        command.name(&self.name).kind(self.kind);
+
+        if self.kind == CommandType::ChatInput {
+            command.description(&self.description);
+        }
+
+        for (locale, name) in &self.name_localizations {
+            command.name_localized(locale, name);
+        }
+
+        for (locale, description) in &self.description_localizations {
+            command.description_localized(locale, description);
+        }
+
+        if let Some(permissions) = self.default_member_permissions {
+            command.default_member_permissions(permissions);
+        }
+
+        if let Some(enabled) = self.dm_permission {
+            command.dm_permission(enabled);
+        }
+
+        for option in self.create_options() {
+            command.add_option(option);
+        }
+
+        command
+    }
+
+    /// The options of the command's definition: either its subcommands, or
+    /// its own options.
+    fn create_options(&self) -> Vec<CreateApplicationCommandOption> {
+        if self.subcommands.is_empty() {
+            return self.options.clone();
+        }
+
+        self.subcommands
+            .iter()
+            .map(|subcommand| {
+                let mut option = CreateApplicationCommandOption::default();
+                option.name(&subcommand.name).description(&subcommand.description).kind(
+                    if subcommand.subcommands.is_empty() {
+                        CommandOptionType::SubCommand
+                    } else {
+                        CommandOptionType::SubCommandGroup
+                    },
+                );
+
+                for (locale, name) in &subcommand.name_localizations {
+                    option.name_localized(locale, name);
+                }
+
+                for (locale, description) in &subcommand.description_localizations {
+                    option.description_localized(locale, description);
+                }
+
+                for sub_option in subcommand.create_options() {
+                    option.add_sub_option(sub_option);
+                }
+
+                option
+            })
+            .collect()
+    }
+}
+
+impl fmt::Debug for SlashCommand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SlashCommand")
+            .field("name", &self.name)
+            .field("description", &self.description)
+            .field("kind", &self.kind)
+            .field("subcommands", &self.subcommands)
+            .field("checks", &self.checks.iter().map(SlashCommandCheck::name).collect::<Vec<_>>())
+            .field("bucket", &self.bucket)
+            .field("only_in", &self.only_in)
+            .field("owners_only", &self.owners_only)
+            .field("required_permissions", &self.required_permissions)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Builds the message standing in for the invocation of a slash command, for
+/// the checks and buckets of the [`StandardFramework`], which take a message.
+///
+/// [`StandardFramework`]: crate::framework::StandardFramework
+pub(crate) fn invocation_message(
+    interaction: &ApplicationCommandInteraction,
+    name: &str,
+) -> Message {
+    let member = interaction.member.as_ref().map(|member| PartialMember {
+        deaf: member.deaf,
+        joined_at: member.joined_at,
+        mute: member.mute,
+        nick: member.nick.clone(),
+        roles: member.roles.clone(),
+        pending: member.pending,
+        premium_since: member.premium_since,
+        guild_id: interaction.guild_id,
+        user: None,
+        permissions: member.permissions,
+    });
+
+    Message {
+        id: interaction.id.0.into(),
+        channel_id: interaction.channel_id,
+        author: interaction.user.clone(),
+        content: format!("/{}", name),
+        timestamp: interaction.id.created_at(),
+        edited_timestamp: None,
+        tts: false,
+        mention_everyone: false,
+        mentions: Vec::new(),
+        mention_roles: Vec::new(),
+        mention_channels: Vec::new(),
+        attachments: Vec::new(),
+        embeds: Vec::new(),
+        reactions: Vec::new(),
+        nonce: NULL,
+        pinned: false,
+        webhook_id: None,
+        kind: MessageType::ChatInputCommand,
+        activity: None,
+        application: None,
+        application_id: Some(interaction.application_id),
+        message_reference: None,
+        flags: None,
+        referenced_message: None,
+        interaction: Some(MessageInteraction {
+            id: interaction.id,
+            kind: interaction.kind,
+            name: name.to_string(),
+            user: interaction.user.clone(),
+        }),
+        thread: None,
+        components: Vec::new(),
+        sticker_items: Vec::new(),
+        guild_id: interaction.guild_id,
+        member,
+    }
+}
+
+/// Finds the command invoked with the given data, returning the commands from
+/// the top-level command to the invoked one, and the options passed to it.
+pub(crate) fn resolve<'a>(
+    commands: &'a [SlashCommand],
+    data: &'a CommandData,
+) -> Option<(Vec<&'a SlashCommand>, &'a [CommandDataOption])> {
+    let mut command =
+        commands.iter().find(|command| command.name == data.name && command.kind == data.kind)?;
+    let mut options = data.options.as_slice();
+    let mut path = vec![command];
+
+    while !command.subcommands.is_empty() {
+        let option = options.iter().find(|option| {
+            matches!(
+                option.kind,
+                CommandOptionType::SubCommand | CommandOptionType::SubCommandGroup
+            )
+        })?;
+
+        command = command.subcommands.iter().find(|subcommand| subcommand.name == option.name)?;
+        options = &option.options;
+        path.push(command);
+    }
+
+    Some((path, options))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{invocation_message, resolve, SlashCommand};
+    use crate::json::{from_value, json, Value};
+    use crate::model::application::command::CommandOptionType;
+    use crate::model::application::interaction::application_command::{
+        ApplicationCommandInteraction,
+        CommandData,
+    };
+    use crate::model::channel::MessageType;
+    use crate::model::id::{ChannelId, GuildId, RoleId, UserId};
+
+    fn config() -> SlashCommand {
+        let mut command = SlashCommand::default();
+        command.name("config").description("Configures the bot").create_subcommand(|group| {
+            group
+                .name("roles")
+                .description("Configures roles")
+                .name_localized("de", "rollen")
+                .create_subcommand(|subcommand| {
+                    subcommand.name("add").description("Adds a role").create_option(|option| {
+                        option
+                            .name("role")
+                            .description("The role")
+                            .kind(CommandOptionType::Role)
+                            .required(true)
+                    })
+                })
+        });
+
+        command
+    }
+
+    #[test]
+    fn test_create_application_command() {
+        let command = config().create_application_command();
+        let options = command.0["options"].clone();
+
+        assert_eq!(command.0["name"], "config");
+        assert_eq!(command.0["type"], 1);
+        assert_eq!(
+            options,
+            json!([{
+                "name": "roles",
+                "description": "Configures roles",
+                "type": 2,
+                "name_localizations": {"de": "rollen"},
+                "options": [{
+                    "name": "add",
+                    "description": "Adds a role",
+                    "type": 1,
+                    "options": [{
+                        "name": "role",
+                        "description": "The role",
+                        "type": 8,
+                        "required": true,
+                    }],
+                }],
+            }])
+        );
+    }
+
+    #[test]
+    fn test_resolve() {
+        let commands = vec![config()];
+        let data: CommandData = from_value(json!({
+            "id": "1",
+            "name": "config",
+            "type": 1,
+            "options": [{
+                "name": "roles",
+                "type": 2,
+                "options": [{
+                    "name": "add",
+                    "type": 1,
+                    "options": [{"name": "role", "type": 8, "value": "5"}],
+                }],
+            }],
+        }))
+        .unwrap();
+
+        let (path, options) = resolve(&commands, &data).unwrap();
+        let names = path.iter().map(|command| command.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(names, ["config", "roles", "add"]);
+        assert_eq!(options.len(), 1);
+        assert_eq!(options[0].value, Some(Value::from("5")));
+
+        let data: CommandData =
+            from_value(json!({"id": "1", "name": "config", "type": 1, "options": []})).unwrap();
+        assert!(resolve(&commands, &data).is_none());
+    }
+
+    #[test]
+    fn test_invocation_message() {
+        let interaction: ApplicationCommandInteraction = from_value(json!({
+            "id": "10",
+            "application_id": "1",
+            "type": 2,
+            "data": {"id": "5", "name": "config", "type": 1},
+            "guild_id": "2",
+            "channel_id": "3",
+            "member": {
+                "user": {"id": "4", "username": "user", "discriminator": "0001", "avatar": null},
+                "roles": ["6"],
+                "joined_at": "2022-01-01T00:00:00Z",
+                "deaf": false,
+                "mute": false,
+                "permissions": "0",
+            },
+            "token": "token",
+            "version": 1,
+            "locale": "en-US",
+        }))
+        .unwrap();
+
+        let message = invocation_message(&interaction, "config roles add");
+        assert_eq!(message.content, "/config roles add");
+        assert_eq!(message.kind, MessageType::ChatInputCommand);
+        assert_eq!(message.author.id, UserId(4));
+        assert_eq!(message.channel_id, ChannelId(3));
+        assert_eq!(message.guild_id, Some(GuildId(2)));
+        assert_eq!(message.member.unwrap().roles, [RoleId(6)]);
+        assert_eq!(message.interaction.unwrap().name, "config roles add");
+    }
+}
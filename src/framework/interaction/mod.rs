@@ -0,0 +1,497 @@
+//! A framework routing interactions to handler functions, alongside the
+//! [`StandardFramework`] for text commands.
+//!
+//! An [`InteractionFramework`] holds [`SlashCommand`]s, whose subcommands and
+//! subcommand groups are routed to the handler of the invoked subcommand, as
+//! well as handlers for their autocomplete interactions, and handlers for
+//! message components, routed by their custom Id.
+//!
+//! Before a slash command is invoked, the framework enforces its checks,
+//! [buckets] and restrictions the same way the [`StandardFramework`] does,
+//! reporting failures as a [`DispatchError`] to the [`on_dispatch_error`]
+//! hook. Checks of the [`StandardFramework`] can be reused with
+//! [`SlashCommand::standard_check`].
+//!
+//! The options of a command can be declared as a struct deriving
+//! [`CommandOptions`], which creates their definitions and parses the options
+//! given to the command into the struct.
+//!
+//! The framework is an [`EventHandler`], which can be added to a [`Client`]
+//! along with other handlers. It can also generate the definitions of its
+//! commands, to register them with Discord through a [`CommandRegistry`].
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use serenity::framework::interaction::InteractionFramework;
+//! use serenity::framework::standard::CommandResult;
+//! use serenity::model::application::interaction::application_command::{
+//!     ApplicationCommandInteraction,
+//!     CommandDataOption,
+//! };
+//! use serenity::prelude::*;
+//!
+//! async fn ping(
+//!     ctx: &Context,
+//!     interaction: &ApplicationCommandInteraction,
+//!     _: &[CommandDataOption],
+//! ) -> CommandResult {
+//!     interaction
+//!         .create_interaction_response(&ctx.http, |response| {
+//!             response.interaction_response_data(|data| data.content("Pong!"))
+//!         })
+//!         .await?;
+//!
+//!     Ok(())
+//! }
+//!
+//! # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+//! let framework = InteractionFramework::new().bucket("ping", |b| b.delay(5)).command(|command| {
+//!     command
+//!         .name("ping")
+//!         .description("Checks the latency")
+//!         .bucket("ping")
+//!         .handler(|ctx, interaction, options| Box::pin(ping(ctx, interaction, options)))
+//! });
+//!
+//! let mut registry = framework.command_registry();
+//! let token = std::env::var("DISCORD_TOKEN")?;
+//! let mut client =
+//!     Client::builder(&token, GatewayIntents::empty()).event_handler(framework).await?;
+//!
+//! registry.sync_global(&client.cache_and_http.http).await?;
+//! client.start().await?;
+//! #     Ok(())
+//! # }
+//! ```
+//!
+//! [`StandardFramework`]: super::StandardFramework
+//! [buckets]: InteractionFramework::bucket
+//! [`on_dispatch_error`]: InteractionFramework::on_dispatch_error
+//! [`EventHandler`]: crate::client::EventHandler
+//! [`Client`]: crate::Client
+//! [`CommandRegistry`]: crate::model::application::command_sync::CommandRegistry
+
+mod command;
+mod options;
+
+use std::collections::{HashMap, HashSet};
+
+use async_trait::async_trait;
+pub use command_attr::CommandOptions;
+use futures::future::BoxFuture;
+use tokio::sync::Mutex;
+use tokio::time::sleep;
+use tracing::{instrument, warn};
+
+use self::command::SlashCommandCheck;
+pub use self::command::{
+    AutocompleteFn,
+    ComponentFn,
+    InteractionCheckFn,
+    SlashCommand,
+    SlashCommandFn,
+};
+pub use self::options::{CommandOptions, OptionError, OptionValue};
+use crate::builder::CreateApplicationCommand;
+use crate::client::{Context, EventHandler};
+use crate::framework::standard::buckets::{Bucket, RateLimitAction, RateLimitInfo, RevertBucket};
+use crate::framework::standard::{
+    Args,
+    BucketBuilder,
+    CommandOptions as StandardOptions,
+    CommandResult,
+    DispatchError,
+    OnlyIn,
+};
+use crate::model::application::command_sync::CommandRegistry;
+use crate::model::application::interaction::application_command::ApplicationCommandInteraction;
+use crate::model::application::interaction::autocomplete::AutocompleteInteraction;
+use crate::model::application::interaction::message_component::MessageComponentInteraction;
+use crate::model::application::interaction::Interaction;
+use crate::model::channel::Message;
+use crate::model::id::UserId;
+use crate::model::Permissions;
+
+type DispatchHook = for<'fut> fn(
+    &'fut Context,
+    &'fut ApplicationCommandInteraction,
+    DispatchError,
+    &'fut str,
+) -> BoxFuture<'fut, ()>;
+type BeforeHook = for<'fut> fn(
+    &'fut Context,
+    &'fut ApplicationCommandInteraction,
+    &'fut str,
+) -> BoxFuture<'fut, bool>;
+type AfterHook = for<'fut> fn(
+    &'fut Context,
+    &'fut ApplicationCommandInteraction,
+    &'fut str,
+    CommandResult,
+) -> BoxFuture<'fut, ()>;
+type UnrecognisedHook = for<'fut> fn(&'fut Context, &'fut Interaction) -> BoxFuture<'fut, ()>;
+
+/// A utility for routing interactions to handler functions.
+///
+/// Refer to the [module-level documentation] for more information.
+///
+/// [module-level documentation]: self
+#[derive(Default)]
+pub struct InteractionFramework {
+    commands: Vec<SlashCommand>,
+    components: Vec<(String, ComponentFn)>,
+    buckets: Mutex<HashMap<String, Bucket>>,
+    owners: HashSet<UserId>,
+    before: Option<BeforeHook>,
+    after: Option<AfterHook>,
+    dispatch: Option<DispatchHook>,
+    unrecognised: Option<UnrecognisedHook>,
+}
+
+impl InteractionFramework {
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a slash command.
+    #[must_use]
+    pub fn command<F>(mut self, f: F) -> Self
+    where
+        F: FnOnce(&mut SlashCommand) -> &mut SlashCommand,
+    {
+        let mut command = SlashCommand::default();
+        f(&mut command);
+        self.commands.push(command);
+
+        self
+    }
+
+    /// Registers the handler of the message components whose custom Id is
+    /// `custom_id`, or starts with `custom_id` followed by a `:`, which allows
+    /// passing data to the handler, such as `"delete:1234"`.
+    ///
+    /// The handler with the longest matching custom Id is invoked.
+    #[must_use]
+    pub fn component(mut self, custom_id: impl Into<String>, handler: ComponentFn) -> Self {
+        self.components.push((custom_id.into(), handler));
+
+        self
+    }
+
+    /// Defines a bucket, which commands can use with [`SlashCommand::bucket`]
+    /// to limit how often they are invoked.
+    ///
+    /// The [`check`] of the bucket is given the same message standing in for
+    /// the interaction as the [standard checks] of the command.
+    ///
+    /// **Note**: The [`delay_action`] of the bucket is not supported, as it
+    /// would act on that message, which does not exist. Setting one logs a
+    /// warning, and it is never run.
+    ///
+    /// [`check`]: BucketBuilder::check
+    /// [standard checks]: SlashCommand::standard_check
+    /// [`delay_action`]: BucketBuilder::delay_action
+    #[must_use]
+    pub fn bucket<F>(mut self, name: &str, f: F) -> Self
+    where
+        F: FnOnce(&mut BucketBuilder) -> &mut BucketBuilder,
+    {
+        let mut builder = BucketBuilder::default();
+        f(&mut builder);
+
+        if builder.delay_action.take().is_some() {
+            warn!("The delay action of the bucket {} is not run for interactions", name);
+        }
+
+        self.buckets.get_mut().insert(name.to_string(), builder.construct());
+
+        self
+    }
+
+    /// Sets the users who can invoke [owners-only] commands.
+    ///
+    /// [owners-only]: SlashCommand::owners_only
+    #[must_use]
+    pub fn owners(mut self, owners: HashSet<UserId>) -> Self {
+        self.owners = owners;
+
+        self
+    }
+
+    /// Specify the function that's called in case a slash command wasn't
+    /// invoked for one reason or another.
+    #[must_use]
+    pub fn on_dispatch_error(mut self, f: DispatchHook) -> Self {
+        self.dispatch = Some(f);
+
+        self
+    }
+
+    /// Specify the function to be called prior to every slash command's
+    /// invocation. If that function returns `false`, the command is not
+    /// invoked.
+    #[must_use]
+    pub fn before(mut self, f: BeforeHook) -> Self {
+        self.before = Some(f);
+
+        self
+    }
+
+    /// Specify the function to be called after every slash command's
+    /// invocation, with the result of the command.
+    #[must_use]
+    pub fn after(mut self, f: AfterHook) -> Self {
+        self.after = Some(f);
+
+        self
+    }
+
+    /// Specify the function to be called for command and component
+    /// interactions that have no handler.
+    #[must_use]
+    pub fn unrecognised_interaction(mut self, f: UnrecognisedHook) -> Self {
+        self.unrecognised = Some(f);
+
+        self
+    }
+
+    /// Builds the definitions of the registered commands.
+    #[must_use]
+    pub fn create_application_commands(&self) -> Vec<CreateApplicationCommand> {
+        self.commands.iter().map(SlashCommand::create_application_command).collect()
+    }
+
+    /// Creates a [`CommandRegistry`] declaring the registered commands, to
+    /// register them with Discord.
+    #[must_use]
+    pub fn command_registry(&self) -> CommandRegistry {
+        let mut registry = CommandRegistry::new();
+
+        for command in self.create_application_commands() {
+            registry.add_command(command);
+        }
+
+        registry
+    }
+
+    /// Routes an interaction to its handler.
+    #[instrument(skip(self, ctx))]
+    pub async fn dispatch(&self, ctx: Context, interaction: Interaction) {
+        let handled = match &interaction {
+            Interaction::ApplicationCommand(command) => self.dispatch_command(&ctx, command).await,
+            Interaction::Autocomplete(autocomplete) => {
+                self.dispatch_autocomplete(&ctx, autocomplete).await
+            },
+            Interaction::MessageComponent(component) => {
+                self.dispatch_component(&ctx, component).await
+            },
+            _ => true,
+        };
+
+        if !handled {
+            if let Some(unrecognised) = self.unrecognised {
+                unrecognised(&ctx, &interaction).await;
+            }
+        }
+    }
+
+    /// Invokes the handler of a slash command, returning whether it has one.
+    async fn dispatch_command(
+        &self,
+        ctx: &Context,
+        interaction: &ApplicationCommandInteraction,
+    ) -> bool {
+        let (path, options) = match command::resolve(&self.commands, &interaction.data) {
+            Some(resolved) => resolved,
+            None => return false,
+        };
+
+        let command = path[path.len() - 1];
+        let handler = match command.handler {
+            Some(handler) => handler,
+            None => return false,
+        };
+
+        let name = path.iter().map(|command| command.name.as_str()).collect::<Vec<_>>().join(" ");
+        let message = command::invocation_message(interaction, &name);
+
+        if let Some(error) = self.should_fail(ctx, interaction, &message, &path).await {
+            if let Some(dispatch) = self.dispatch {
+                dispatch(ctx, interaction, error, &name).await;
+            }
+
+            return true;
+        }
+
+        if let Some(before) = self.before {
+            if !before(ctx, interaction, &name).await {
+                return true;
+            }
+        }
+
+        let result = handler(ctx, interaction, options).await;
+
+        // Check if the command wants to revert the bucket by giving back a ticket.
+        if matches!(result, Err(ref e) if e.is::<RevertBucket>()) {
+            let mut buckets = self.buckets.lock().await;
+
+            if let Some(bucket) = bucket_name(&path).and_then(|name| buckets.get_mut(name)) {
+                bucket.give(ctx, &message).await;
+            }
+        }
+
+        if let Some(after) = self.after {
+            after(ctx, interaction, &name, result).await;
+        }
+
+        true
+    }
+
+    /// Invokes the autocomplete handler of a slash command, returning whether
+    /// it has one.
+    async fn dispatch_autocomplete(
+        &self,
+        ctx: &Context,
+        interaction: &AutocompleteInteraction,
+    ) -> bool {
+        let (path, options) = match command::resolve(&self.commands, &interaction.data) {
+            Some(resolved) => resolved,
+            None => return false,
+        };
+
+        let autocomplete = match path[path.len() - 1].autocomplete {
+            Some(autocomplete) => autocomplete,
+            None => return false,
+        };
+
+        if let Err(why) = autocomplete(ctx, interaction, options).await {
+            warn!("Autocomplete handler of {} failed: {:?}", interaction.data.name, why);
+        }
+
+        true
+    }
+
+    /// Invokes the handler of a message component, returning whether it has
+    /// one.
+    async fn dispatch_component(
+        &self,
+        ctx: &Context,
+        interaction: &MessageComponentInteraction,
+    ) -> bool {
+        let custom_id = &interaction.data.custom_id;
+        let handler = self
+            .components
+            .iter()
+            .filter(|(id, _)| {
+                custom_id == id
+                    || (custom_id.starts_with(id.as_str())
+                        && custom_id[id.len()..].starts_with(':'))
+            })
+            .max_by_key(|(id, _)| id.len());
+
+        let (id, handler) = match handler {
+            Some(handler) => handler,
+            None => return false,
+        };
+
+        if let Err(why) = handler(ctx, interaction).await {
+            warn!("Component handler of {} failed: {:?}", id, why);
+        }
+
+        true
+    }
+
+    async fn should_fail(
+        &self,
+        ctx: &Context,
+        interaction: &ApplicationCommandInteraction,
+        message: &Message,
+        path: &[&SlashCommand],
+    ) -> Option<DispatchError> {
+        if path.iter().any(|command| command.owners_only)
+            && !self.owners.contains(&interaction.user.id)
+        {
+            return Some(DispatchError::OnlyForOwners);
+        }
+
+        // The innermost restriction applies.
+        match path.iter().rev().map(|command| command.only_in).find(|&only| only != OnlyIn::None) {
+            Some(OnlyIn::Guild) if interaction.guild_id.is_none() => {
+                return Some(DispatchError::OnlyForGuilds);
+            },
+            Some(OnlyIn::Dm) if interaction.guild_id.is_some() => {
+                return Some(DispatchError::OnlyForDM);
+            },
+            _ => {},
+        }
+
+        let required = path.iter().fold(Permissions::empty(), |required, command| {
+            required | command.required_permissions
+        });
+
+        if let Some(member) = &interaction.member {
+            let permissions = member.permissions.unwrap_or_else(Permissions::empty);
+
+            if !permissions.contains(required) && !permissions.administrator() {
+                return Some(DispatchError::LackingPermissions(required - permissions));
+            }
+        }
+
+        for command in path {
+            for check in &command.checks {
+                let result = match check {
+                    SlashCommandCheck::Interaction(_, check) => check(ctx, interaction).await,
+                    SlashCommandCheck::Standard(check) => {
+                        let mut args = Args::new("", &[]);
+
+                        (check.function)(ctx, message, &mut args, &StandardOptions::default()).await
+                    },
+                };
+
+                if let Err(reason) = result {
+                    return Some(DispatchError::CheckFailed(check.name(), reason));
+                }
+            }
+        }
+
+        // Try passing the command's bucket, delaying and trying again if the
+        // bucket awaits ratelimits.
+        loop {
+            let duration = {
+                let mut buckets = self.buckets.lock().await;
+
+                let info = match bucket_name(path).and_then(|name| buckets.get_mut(name)) {
+                    Some(bucket) => bucket.take(ctx, message).await,
+                    None => None,
+                };
+
+                match info {
+                    Some(RateLimitInfo {
+                        action: RateLimitAction::Delayed,
+                        rate_limit,
+                        ..
+                    }) => rate_limit,
+                    Some(info) => return Some(DispatchError::Ratelimited(info)),
+                    None => return None,
+                }
+            };
+
+            sleep(duration).await;
+        }
+    }
+}
+
+#[async_trait]
+impl EventHandler for InteractionFramework {
+    async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
+        self.dispatch(ctx, interaction).await;
+    }
+}
+
+/// The name of the bucket of the innermost command that has one.
+fn bucket_name<'a>(path: &[&'a SlashCommand]) -> Option<&'a str> {
+    path.iter().rev().find_map(|command| command.bucket.as_deref())
+}
@@ -0,0 +1,275 @@
+use std::error::Error as StdError;
+use std::fmt;
+
+use crate::builder::CreateApplicationCommandOption;
+use crate::model::application::command::CommandOptionType;
+use crate::model::application::interaction::application_command::{
+    CommandData,
+    CommandDataOption,
+    CommandDataOptionValue,
+};
+use crate::model::channel::{Attachment, PartialChannel};
+use crate::model::guild::{PartialMember, Role};
+use crate::model::id::{AttachmentId, ChannelId, RoleId, UserId};
+use crate::model::user::User;
+
+/// The options of a slash command, parsed into a type.
+///
+/// This is usually implemented with the [`CommandOptions`] derive macro,
+/// which creates an option for each field of a struct, and parses the
+/// options given to a command into the fields, using their [`OptionValue`]
+/// implementation.
+///
+/// [`CommandOptions`]: macro@crate::framework::interaction::CommandOptions
+pub trait CommandOptions: Sized {
+    /// Creates the definitions of the options, to be added to a command with
+    /// [`SlashCommand::options_from`] or
+    /// [`CreateApplicationCommand::set_options`].
+    ///
+    /// [`SlashCommand::options_from`]: super::SlashCommand::options_from
+    /// [`CreateApplicationCommand::set_options`]: crate::builder::CreateApplicationCommand::set_options
+    fn create_options() -> Vec<CreateApplicationCommandOption>;
+
+    /// Parses the options given to the invoked command or subcommand, as
+    /// passed to a [`SlashCommandFn`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`OptionError`] if an option is missing or not of the
+    /// expected type.
+    ///
+    /// [`SlashCommandFn`]: super::SlashCommandFn
+    fn from_options(options: &[CommandDataOption]) -> Result<Self, OptionError>;
+
+    /// Parses the options of the invoked command, or of the invoked
+    /// subcommand if the command has any.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`OptionError`] if an option is missing or not of the
+    /// expected type.
+    fn from_command_data(data: &CommandData) -> Result<Self, OptionError> {
+        let mut options = &data.options[..];
+
+        while let [option] = options {
+            match option.kind {
+                CommandOptionType::SubCommand | CommandOptionType::SubCommandGroup => {
+                    options = &option.options;
+                },
+                _ => break,
+            }
+        }
+
+        Self::from_options(options)
+    }
+}
+
+/// A type that the value of a command option can be parsed into.
+///
+/// This is implemented for the types of values Discord resolves options to,
+/// and for [`Option`]s of them, which make the option optional.
+pub trait OptionValue: Sized {
+    /// The type of the option.
+    const KIND: CommandOptionType;
+
+    /// Whether the option is required.
+    const REQUIRED: bool = true;
+
+    /// Parses the option with the given name, if it was given.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OptionError::Missing`] if the option is required but was
+    /// not given, and [`OptionError::Mistyped`] if its value is not of the
+    /// expected type.
+    fn from_option(
+        option: Option<&CommandDataOption>,
+        name: &'static str,
+    ) -> Result<Self, OptionError>;
+}
+
+impl<T: OptionValue> OptionValue for Option<T> {
+    const KIND: CommandOptionType = T::KIND;
+    const REQUIRED: bool = false;
+
+    fn from_option(
+        option: Option<&CommandDataOption>,
+        name: &'static str,
+    ) -> Result<Self, OptionError> {
+        match option {
+            Some(option) => T::from_option(Some(option), name).map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+macro_rules! option_value {
+    ($($kind:ident => $ty:ty, $pat:pat => $value:expr;)*) => {
+        $(
+            impl OptionValue for $ty {
+                const KIND: CommandOptionType = CommandOptionType::$kind;
+
+                fn from_option(
+                    option: Option<&CommandDataOption>,
+                    name: &'static str,
+                ) -> Result<Self, OptionError> {
+                    let option = option.ok_or(OptionError::Missing(name))?;
+
+                    match &option.resolved {
+                        Some($pat) => Ok($value),
+                        _ => Err(OptionError::Mistyped {
+                            name,
+                            expected: Self::KIND,
+                        }),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+option_value! {
+    String => String, CommandDataOptionValue::String(value) => value.clone();
+    Integer => i64, CommandDataOptionValue::Integer(value) => *value;
+    Boolean => bool, CommandDataOptionValue::Boolean(value) => *value;
+    Number => f64, CommandDataOptionValue::Number(value) => *value;
+    User => User, CommandDataOptionValue::User(user, _) => user.clone();
+    User => UserId, CommandDataOptionValue::User(user, _) => user.id;
+    User => PartialMember, CommandDataOptionValue::User(_, Some(member)) => member.clone();
+    Channel => PartialChannel, CommandDataOptionValue::Channel(channel) => channel.clone();
+    Channel => ChannelId, CommandDataOptionValue::Channel(channel) => channel.id;
+    Role => Role, CommandDataOptionValue::Role(role) => role.clone();
+    Role => RoleId, CommandDataOptionValue::Role(role) => role.id;
+    Attachment => Attachment, CommandDataOptionValue::Attachment(attachment) => attachment.clone();
+    Attachment => AttachmentId, CommandDataOptionValue::Attachment(attachment) => attachment.id;
+}
+
+/// An error from parsing the options of a command.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum OptionError {
+    /// A required option, with the given name, was not given.
+    Missing(&'static str),
+    /// The value of an option was not of the expected type.
+    ///
+    /// This also happens when Discord did not resolve the value, such as a
+    /// member for a user given outside of a guild.
+    Mistyped { name: &'static str, expected: CommandOptionType },
+}
+
+impl fmt::Display for OptionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing(name) => write!(f, "Required option `{}` is missing", name),
+            Self::Mistyped {
+                name,
+                expected,
+            } => write!(f, "Option `{}` is not of type {:?}", name, expected),
+        }
+    }
+}
+
+impl StdError for OptionError {}
+
+#[cfg(test)]
+mod test {
+    use super::{CommandOptions, OptionError, OptionValue};
+    use crate::builder::CreateApplicationCommandOption;
+    use crate::json::{from_value, json, Value};
+    use crate::model::application::command::CommandOptionType;
+    use crate::model::application::interaction::application_command::{
+        CommandData,
+        CommandDataOption,
+    };
+    use crate::model::id::RoleId;
+
+    #[derive(Debug, PartialEq)]
+    struct AddRole {
+        role: RoleId,
+        reason: Option<String>,
+    }
+
+    impl CommandOptions for AddRole {
+        fn create_options() -> Vec<CreateApplicationCommandOption> {
+            Vec::new()
+        }
+
+        fn from_options(options: &[CommandDataOption]) -> Result<Self, OptionError> {
+            let find = |name| options.iter().find(|option| option.name == name);
+
+            Ok(Self {
+                role: OptionValue::from_option(find("role"), "role")?,
+                reason: OptionValue::from_option(find("reason"), "reason")?,
+            })
+        }
+    }
+
+    fn data(options: Value) -> CommandData {
+        from_value(json!({
+            "id": "1",
+            "name": "config",
+            "type": 1,
+            "options": [{
+                "name": "roles",
+                "type": 2,
+                "options": [{"name": "add", "type": 1, "options": options}],
+            }],
+            "resolved": {
+                "roles": {
+                    "5": {
+                        "id": "5",
+                        "guild_id": "2",
+                        "name": "moderator",
+                        "color": 0,
+                        "hoist": false,
+                        "position": 1,
+                        "permissions": "0",
+                        "managed": false,
+                        "mentionable": false,
+                    },
+                },
+            },
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_from_command_data() {
+        let options = AddRole::from_command_data(&data(json!([
+            {"name": "role", "type": 8, "value": "5"},
+            {"name": "reason", "type": 3, "value": "trusted"},
+        ])));
+        assert_eq!(
+            options,
+            Ok(AddRole {
+                role: RoleId(5),
+                reason: Some("trusted".to_string()),
+            })
+        );
+
+        let options = AddRole::from_command_data(&data(json!([
+            {"name": "role", "type": 8, "value": "5"},
+        ])));
+        assert_eq!(
+            options,
+            Ok(AddRole {
+                role: RoleId(5),
+                reason: None,
+            })
+        );
+
+        let options = AddRole::from_command_data(&data(json!([])));
+        assert_eq!(options, Err(OptionError::Missing("role")));
+
+        let options = AddRole::from_command_data(&data(json!([
+            {"name": "role", "type": 3, "value": "moderator"},
+        ])));
+        assert_eq!(
+            options,
+            Err(OptionError::Mistyped {
+                name: "role",
+                expected: CommandOptionType::Role,
+            })
+        );
+    }
+}
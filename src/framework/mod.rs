@@ -80,6 +80,8 @@
 //!
 //! [`ClientBuilder::framework`]: crate::client::ClientBuilder::framework
 
+#[cfg(feature = "interaction_framework")]
+pub mod interaction;
 #[cfg(feature = "standard_framework")]
 pub mod standard;
 
@@ -190,18 +190,22 @@ pub enum CustomisedHelpData<'a> {
 /// Checks whether a user is member of required roles
 /// and given the required permissions.
 #[cfg(feature = "cache")]
-pub fn has_all_requirements(cache: impl AsRef<Cache>, cmd: &CommandOptions, msg: &Message) -> bool {
+pub async fn has_all_requirements(
+    cache: impl AsRef<Cache>,
+    cmd: &CommandOptions,
+    msg: &Message,
+) -> bool {
     let cache = cache.as_ref();
 
     if let Some(guild_id) = msg.guild_id {
-        if let Some(member) = cache.member(guild_id, msg.author.id) {
-            if let Ok(permissions) = member.permissions(cache) {
+        if let Some(member) = cache.member(guild_id, msg.author.id).await {
+            if let Ok(permissions) = member.permissions(cache).await {
                 return if cmd.allowed_roles.is_empty() {
-                    permissions.administrator() || has_correct_permissions(cache, &cmd, msg)
-                } else if let Some(roles) = cache.guild_roles(guild_id) {
+                    permissions.administrator() || has_correct_permissions(cache, &cmd, msg).await
+                } else if let Some(roles) = cache.guild_roles(guild_id).await {
                     permissions.administrator()
                         || (has_correct_roles(&cmd, &roles, &member)
-                            && has_correct_permissions(cache, &cmd, msg))
+                            && has_correct_permissions(cache, &cmd, msg).await)
                 } else {
                     warn!("Failed to find the guild and its roles.");
 
@@ -225,10 +229,10 @@ fn starts_with_whole_word(search_on: &str, word: &str) -> bool {
 
 // Decides how a listed help entry shall be displayed.
 #[cfg(all(feature = "cache", feature = "http"))]
-fn check_common_behaviour(
+async fn check_common_behaviour(
     cache: impl AsRef<Cache>,
     msg: &Message,
-    options: &impl CommonOptions,
+    options: &(impl CommonOptions + Sync),
     owners: &HashSet<UserId, impl std::hash::BuildHasher + Send + Sync>,
     help_options: &HelpOptions,
 ) -> HelpBehaviour {
@@ -250,7 +254,7 @@ fn check_common_behaviour(
         return HelpBehaviour::Nothing;
     }
 
-    if !has_correct_permissions(&cache, options, msg) {
+    if !has_correct_permissions(&cache, options, msg).await {
         return help_options.lacking_permissions;
     }
 
@@ -263,6 +267,7 @@ fn check_common_behaviour(
 
         HelpBehaviour::Nothing
     })
+    .await
     .unwrap_or(HelpBehaviour::Nothing)
 }
 
@@ -275,7 +280,7 @@ async fn check_command_behaviour(
     owners: &HashSet<UserId, impl std::hash::BuildHasher + Send + Sync>,
     help_options: &HelpOptions,
 ) -> HelpBehaviour {
-    let behaviour = check_common_behaviour(ctx, msg, &options, owners, help_options);
+    let behaviour = check_common_behaviour(ctx, msg, &options, owners, help_options).await;
 
     if behaviour == HelpBehaviour::Nothing
         && (!options.owner_privilege || !owners.contains(&msg.author.id))
@@ -454,7 +459,7 @@ fn nested_group_command_search<'rec, 'a: 'rec>(
         for group in groups {
             let group = *group;
             let group_behaviour =
-                check_common_behaviour(ctx, msg, &group.options, owners, help_options);
+                check_common_behaviour(ctx, msg, &group.options, owners, help_options).await;
 
             match &group_behaviour {
                 HelpBehaviour::Nothing => (),
@@ -616,7 +621,7 @@ async fn fill_eligible_commands<'a>(
         } else {
             std::cmp::max(
                 *highest_formatter,
-                check_common_behaviour(ctx, msg, &group.options, owners, help_options),
+                check_common_behaviour(ctx, msg, &group.options, owners, help_options).await,
             )
         }
     };
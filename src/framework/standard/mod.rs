@@ -252,14 +252,14 @@ impl StandardFramework {
 
         #[cfg(feature = "cache")]
         {
-            if let Some(Channel::Guild(channel)) = msg.channel_id.to_channel_cached(ctx) {
+            if let Some(Channel::Guild(channel)) = msg.channel_id.to_channel_cached(ctx).await {
                 let guild_id = channel.guild_id;
 
                 if self.config.blocked_guilds.contains(&guild_id) {
                     return Some(DispatchError::BlockedGuild);
                 }
 
-                let owner_id_option = ctx.cache.guild_field(guild_id, |guild| guild.owner_id);
+                let owner_id_option = ctx.cache.guild_field(guild_id, |guild| guild.owner_id).await;
 
                 if let Some(owner_id) = owner_id_option {
                     if self.config.blocked_users.contains(&owner_id) {
@@ -852,9 +852,9 @@ impl CommonOptions for &CommandOptions {
 }
 
 #[cfg(feature = "cache")]
-pub(crate) fn has_correct_permissions(
+pub(crate) async fn has_correct_permissions(
     cache: impl AsRef<Cache>,
-    options: &impl CommonOptions,
+    options: &(impl CommonOptions + Sync),
     message: &Message,
 ) -> bool {
     if options.required_permissions().is_empty() {
@@ -886,6 +886,7 @@ pub(crate) fn has_correct_permissions(
                     },
                 }
             })
+            .await
             .unwrap_or(false)
     }
 }
@@ -26,14 +26,15 @@ use uwl::Stream;
 // the author does possess them. To avoid defaulting to permissions of everyone, we fetch
 // the member from HTTP if it is missing in the guild's members list.
 #[cfg(feature = "cache")]
-fn permissions_in(
+async fn permissions_in(
     ctx: &Context,
     guild_id: GuildId,
     channel_id: ChannelId,
     member: &Member,
     roles: &HashMap<RoleId, Role>,
 ) -> Permissions {
-    if ctx.cache.guild_field(guild_id, |guild| member.user.id == guild.owner_id) == Some(true) {
+    if ctx.cache.guild_field(guild_id, |guild| member.user.id == guild.owner_id).await == Some(true)
+    {
         return Permissions::all();
     }
 
@@ -60,7 +61,7 @@ fn permissions_in(
     }
 
     if let Some(Some(Channel::Guild(channel))) =
-        ctx.cache.guild_field(guild_id, |guild| guild.channels.get(&channel_id).cloned())
+        ctx.cache.guild_field(guild_id, |guild| guild.channels.get(&channel_id).cloned()).await
     {
         let mut data = Vec::with_capacity(member.roles.len());
 
@@ -232,6 +233,7 @@ async fn check_discrepancy(
             let member = match ctx
                 .cache
                 .guild_field(guild_id, |guild| guild.members.get(&msg.author.id).cloned())
+                .await
             {
                 Some(Some(member)) => member,
                 // Member not found.
@@ -242,9 +244,12 @@ async fn check_discrepancy(
                 // Guild not found.
                 None => return Ok(()),
             };
-            #[allow(clippy::unwrap_used)] // Allowing unwrap because should always return Some()
-            let roles = ctx.cache.guild_field(guild_id, |guild| guild.roles.clone()).unwrap();
-            let perms = permissions_in(ctx, guild_id, msg.channel_id, &member, &roles);
+            let roles = match ctx.cache.guild_roles(guild_id).await {
+                Some(roles) => roles,
+                // Guild removed in the meantime.
+                None => return Ok(()),
+            };
+            let perms = permissions_in(ctx, guild_id, msg.channel_id, &member, &roles).await;
 
             if !(perms.contains(*options.required_permissions())
                 || options.owner_privilege() && config.owners.contains(&msg.author.id))
@@ -53,6 +53,14 @@ pub enum Error {
     /// If an connection has been established but privileged gateway intents
     /// were provided without enabling them prior.
     DisallowedGatewayIntents,
+    /// When a payload received with [`GatewayEncoding::Etf`] could not be
+    /// decoded, with the reason why.
+    ///
+    /// [`GatewayEncoding::Etf`]: super::GatewayEncoding::Etf
+    InvalidEtf(&'static str),
+    /// When a shard stopped or reconnected before all chunks of a member
+    /// request were received, or it could not be sent.
+    MemberRequestInterrupted,
 }
 
 impl fmt::Display for Error {
@@ -74,6 +82,8 @@ impl fmt::Display for Error {
             Self::DisallowedGatewayIntents => {
                 f.write_str("Disallowed gateway intents were provided")
             },
+            Self::InvalidEtf(reason) => write!(f, "Invalid ETF payload: {}", reason),
+            Self::MemberRequestInterrupted => f.write_str("Member request was interrupted"),
         }
     }
 }
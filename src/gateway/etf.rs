@@ -0,0 +1,486 @@
+//! An encoder and decoder for the [External Term Format][docs] (ETF), which the
+//! gateway speaks when connected to with `encoding=etf`.
+//!
+//! Payloads are converted from and to the same [`Value`]s as JSON payloads, so
+//! that the rest of the library is oblivious to the encoding in use. Atoms are
+//! decoded as strings, except for `nil`, `true` and `false`, and tuples are
+//! decoded as arrays.
+//!
+//! [docs]: https://www.erlang.org/doc/apps/erts/erl_ext_dist.html
+
+use std::convert::TryFrom;
+use std::io::Read;
+
+use flate2::read::ZlibDecoder;
+use serde::ser::Serialize;
+
+use super::GatewayError;
+use crate::internal::prelude::*;
+#[cfg(feature = "simd-json")]
+use crate::json::prelude::{ValueAccess, ValueTrait};
+use crate::json::{self, JsonMap};
+
+const FORMAT_VERSION: u8 = 131;
+
+const NEW_FLOAT_EXT: u8 = 70;
+const COMPRESSED: u8 = 80;
+const SMALL_INTEGER_EXT: u8 = 97;
+const INTEGER_EXT: u8 = 98;
+const ATOM_EXT: u8 = 100;
+const SMALL_TUPLE_EXT: u8 = 104;
+const LARGE_TUPLE_EXT: u8 = 105;
+const NIL_EXT: u8 = 106;
+const STRING_EXT: u8 = 107;
+const LIST_EXT: u8 = 108;
+const BINARY_EXT: u8 = 109;
+const SMALL_BIG_EXT: u8 = 110;
+const LARGE_BIG_EXT: u8 = 111;
+const SMALL_ATOM_EXT: u8 = 115;
+const MAP_EXT: u8 = 116;
+const ATOM_UTF8_EXT: u8 = 118;
+const SMALL_ATOM_UTF8_EXT: u8 = 119;
+
+/// How deeply terms may be nested in a decoded payload, the same as the
+/// recursion limit of `serde_json`, so that a malicious payload can not
+/// overflow the stack.
+const MAX_DEPTH: usize = 128;
+
+/// Decodes an ETF payload.
+///
+/// # Errors
+///
+/// Returns [`GatewayError::InvalidEtf`] if the payload is malformed or uses a
+/// term that has no JSON equivalent, such as a pid.
+pub(crate) fn from_slice(bytes: &[u8]) -> Result<Value> {
+    let mut decoder = Decoder {
+        bytes,
+        depth: 0,
+    };
+
+    if decoder.u8()? != FORMAT_VERSION {
+        return Err(invalid("unsupported format version"));
+    }
+
+    let value = if decoder.bytes.first() == Some(&COMPRESSED) {
+        decoder.u8()?;
+        // The uncompressed size, which is not needed.
+        decoder.u32()?;
+
+        let mut decompressed = Vec::new();
+        ZlibDecoder::new(decoder.bytes)
+            .read_to_end(&mut decompressed)
+            .map_err(|_| invalid("invalid compressed term"))?;
+
+        let mut decoder = Decoder {
+            bytes: &decompressed,
+            depth: 0,
+        };
+
+        decoder.term()?
+    } else {
+        decoder.term()?
+    };
+
+    Ok(value)
+}
+
+/// Encodes a value as an ETF payload.
+///
+/// Strings are encoded as binaries, `null` as the `nil` atom and objects as
+/// maps with binary keys, as the gateway expects.
+///
+/// # Errors
+///
+/// Returns [`Error::Json`] if the value could not be serialized.
+pub(crate) fn to_vec<T: Serialize>(value: &T) -> Result<Vec<u8>> {
+    let value = json::to_value(value)?;
+    let mut bytes = vec![FORMAT_VERSION];
+    encode(&mut bytes, &value);
+
+    Ok(bytes)
+}
+
+fn invalid(reason: &'static str) -> Error {
+    Error::Gateway(GatewayError::InvalidEtf(reason))
+}
+
+struct Decoder<'a> {
+    bytes: &'a [u8],
+    /// How many terms the one being decoded is nested in.
+    depth: usize,
+}
+
+impl<'a> Decoder<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        if self.bytes.len() < len {
+            return Err(invalid("unexpected end of payload"));
+        }
+
+        let (taken, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+
+        Ok(taken)
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16> {
+        let bytes = self.take(2)?;
+
+        Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        let bytes = self.take(4)?;
+
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn string(&mut self, len: usize) -> Result<String> {
+        let bytes = self.take(len)?;
+
+        String::from_utf8(bytes.to_vec()).map_err(|_| invalid("invalid UTF-8 in string"))
+    }
+
+    fn term(&mut self) -> Result<Value> {
+        if self.depth == MAX_DEPTH {
+            return Err(invalid("terms are nested too deeply"));
+        }
+
+        self.depth += 1;
+        let term = self.nested_term();
+        self.depth -= 1;
+
+        term
+    }
+
+    fn nested_term(&mut self) -> Result<Value> {
+        match self.u8()? {
+            NEW_FLOAT_EXT => {
+                let bytes = self.take(8)?;
+                let mut float = [0; 8];
+                float.copy_from_slice(bytes);
+
+                Ok(Value::from(f64::from_be_bytes(float)))
+            },
+            SMALL_INTEGER_EXT => Ok(Value::from(u64::from(self.u8()?))),
+            INTEGER_EXT => Ok(Value::from(i64::from(self.u32()? as i32))),
+            ATOM_EXT | ATOM_UTF8_EXT => {
+                let len = self.u16()? as usize;
+
+                self.atom(len)
+            },
+            SMALL_ATOM_EXT | SMALL_ATOM_UTF8_EXT => {
+                let len = self.u8()? as usize;
+
+                self.atom(len)
+            },
+            SMALL_TUPLE_EXT => {
+                let len = self.u8()? as usize;
+
+                self.terms(len)
+            },
+            LARGE_TUPLE_EXT => {
+                let len = self.u32()? as usize;
+
+                self.terms(len)
+            },
+            NIL_EXT => Ok(Value::from(Vec::<Value>::new())),
+            // A list of bytes, which Erlang uses for lists of small integers.
+            STRING_EXT => {
+                let len = self.u16()? as usize;
+                let bytes = self.take(len)?;
+
+                Ok(Value::from(
+                    bytes.iter().map(|b| Value::from(u64::from(*b))).collect::<Vec<_>>(),
+                ))
+            },
+            LIST_EXT => {
+                let len = self.u32()? as usize;
+                let mut list = self.list(len)?;
+
+                // Proper lists end with an empty list, which is not an element.
+                if self.bytes.first() == Some(&NIL_EXT) {
+                    self.u8()?;
+                } else {
+                    list.push(self.term()?);
+                }
+
+                Ok(Value::from(list))
+            },
+            BINARY_EXT => {
+                let len = self.u32()? as usize;
+
+                Ok(Value::from(self.string(len)?))
+            },
+            SMALL_BIG_EXT => {
+                let len = self.u8()? as usize;
+
+                self.big(len)
+            },
+            LARGE_BIG_EXT => {
+                let len = self.u32()? as usize;
+
+                self.big(len)
+            },
+            MAP_EXT => {
+                let len = self.u32()? as usize;
+                let mut map = JsonMap::new();
+
+                for _ in 0..len {
+                    // Keys are usually atoms or binaries, but may be any term.
+                    let key = match self.term()? {
+                        Value::String(key) => key,
+                        key => json::to_string(&key)?,
+                    };
+
+                    map.insert(key, self.term()?);
+                }
+
+                Ok(Value::from(map))
+            },
+            _ => Err(invalid("unsupported term")),
+        }
+    }
+
+    fn list(&mut self, len: usize) -> Result<Vec<Value>> {
+        // The length is untrusted, so don't preallocate more than the payload
+        // could possibly hold.
+        let mut list = Vec::with_capacity(len.min(self.bytes.len()));
+
+        for _ in 0..len {
+            list.push(self.term()?);
+        }
+
+        Ok(list)
+    }
+
+    fn terms(&mut self, len: usize) -> Result<Value> {
+        self.list(len).map(Value::from)
+    }
+
+    fn atom(&mut self, len: usize) -> Result<Value> {
+        let atom = self.string(len)?;
+
+        Ok(match atom.as_str() {
+            "nil" | "null" => json::NULL,
+            "true" => Value::from(true),
+            "false" => Value::from(false),
+            _ => Value::from(atom),
+        })
+    }
+
+    fn big(&mut self, len: usize) -> Result<Value> {
+        let negative = self.u8()? != 0;
+        let digits = self.take(len)?;
+
+        if digits.iter().skip(8).any(|digit| *digit != 0) {
+            return Err(invalid("integer does not fit in 64 bits"));
+        }
+
+        let magnitude =
+            digits.iter().take(8).rev().fold(0_u64, |acc, digit| (acc << 8) | u64::from(*digit));
+
+        if !negative {
+            return Ok(Value::from(magnitude));
+        }
+
+        match i64::try_from(magnitude) {
+            Ok(magnitude) => Ok(Value::from(-magnitude)),
+            Err(_) if magnitude == i64::MIN.unsigned_abs() => Ok(Value::from(i64::MIN)),
+            Err(_) => Err(invalid("integer does not fit in 64 bits")),
+        }
+    }
+}
+
+fn encode_atom(bytes: &mut Vec<u8>, atom: &str) {
+    bytes.push(SMALL_ATOM_UTF8_EXT);
+    bytes.push(atom.len() as u8);
+    bytes.extend_from_slice(atom.as_bytes());
+}
+
+fn encode_binary(bytes: &mut Vec<u8>, string: &str) {
+    bytes.push(BINARY_EXT);
+    bytes.extend_from_slice(&(string.len() as u32).to_be_bytes());
+    bytes.extend_from_slice(string.as_bytes());
+}
+
+// Values are inspected through their accessors, which both `serde_json` and
+// `simd-json` values have.
+fn encode(bytes: &mut Vec<u8>, value: &Value) {
+    if value.is_null() {
+        encode_atom(bytes, "nil");
+    } else if let Some(boolean) = value.as_bool() {
+        encode_atom(bytes, if boolean { "true" } else { "false" });
+    } else if let Some(string) = value.as_str() {
+        encode_binary(bytes, string);
+    } else if let Some(array) = value.as_array() {
+        if array.is_empty() {
+            bytes.push(NIL_EXT);
+
+            return;
+        }
+
+        bytes.push(LIST_EXT);
+        bytes.extend_from_slice(&(array.len() as u32).to_be_bytes());
+
+        for element in array {
+            encode(bytes, element);
+        }
+
+        bytes.push(NIL_EXT);
+    } else if let Some(map) = value.as_object() {
+        bytes.push(MAP_EXT);
+        bytes.extend_from_slice(&(map.len() as u32).to_be_bytes());
+
+        for (key, value) in map {
+            encode_binary(bytes, key);
+            encode(bytes, value);
+        }
+    } else if let Some(small) = value.as_u64().and_then(|n| u8::try_from(n).ok()) {
+        bytes.push(SMALL_INTEGER_EXT);
+        bytes.push(small);
+    } else if let Some(int) = value.as_i64().and_then(|n| i32::try_from(n).ok()) {
+        bytes.push(INTEGER_EXT);
+        bytes.extend_from_slice(&int.to_be_bytes());
+    } else if let Some(int) = value.as_i64() {
+        encode_big(bytes, int < 0, int.unsigned_abs());
+    } else if let Some(int) = value.as_u64() {
+        encode_big(bytes, false, int);
+    } else {
+        bytes.push(NEW_FLOAT_EXT);
+        bytes.extend_from_slice(&value.as_f64().unwrap_or_default().to_be_bytes());
+    }
+}
+
+fn encode_big(bytes: &mut Vec<u8>, negative: bool, magnitude: u64) {
+    let digits = magnitude.to_le_bytes();
+    let len = digits.iter().rposition(|digit| *digit != 0).map_or(0, |i| i + 1);
+
+    bytes.push(SMALL_BIG_EXT);
+    bytes.push(len as u8);
+    bytes.push(u8::from(negative));
+    bytes.extend_from_slice(&digits[..len]);
+}
+
+#[cfg(test)]
+mod test {
+    use serde::Deserialize;
+
+    use super::{from_slice, to_vec, MAX_DEPTH};
+    use crate::json::{self, json};
+    use crate::model::event::{Event, GatewayEvent};
+
+    // A HELLO as sent by the gateway, with atom keys and a `nil` sequence.
+    const HELLO: &[u8] = &[
+        131, 116, 0, 0, 0, 4, 100, 0, 1, 116, 100, 0, 3, 110, 105, 108, 100, 0, 1, 115, 100, 0, 3,
+        110, 105, 108, 100, 0, 2, 111, 112, 97, 10, 100, 0, 1, 100, 116, 0, 0, 0, 2, 100, 0, 18,
+        104, 101, 97, 114, 116, 98, 101, 97, 116, 95, 105, 110, 116, 101, 114, 118, 97, 108, 98, 0,
+        0, 161, 34, 100, 0, 6, 95, 116, 114, 97, 99, 101, 108, 0, 0, 0, 1, 109, 0, 0, 0, 40, 91,
+        34, 103, 97, 116, 101, 119, 97, 121, 45, 112, 114, 100, 45, 109, 97, 105, 110, 45, 56, 53,
+        56, 100, 34, 44, 123, 34, 109, 105, 99, 114, 111, 115, 34, 58, 48, 46, 48, 125, 93, 106,
+    ];
+
+    // A MESSAGE_DELETE dispatch, with snowflakes encoded as big integers.
+    const MESSAGE_DELETE: &[u8] = &[
+        131, 116, 0, 0, 0, 4, 100, 0, 1, 116, 100, 0, 14, 77, 69, 83, 83, 65, 71, 69, 95, 68, 69,
+        76, 69, 84, 69, 100, 0, 1, 115, 97, 5, 100, 0, 2, 111, 112, 97, 0, 100, 0, 1, 100, 116, 0,
+        0, 0, 3, 100, 0, 2, 105, 100, 110, 8, 0, 21, 16, 174, 167, 232, 114, 114, 14, 100, 0, 10,
+        99, 104, 97, 110, 110, 101, 108, 95, 105, 100, 110, 8, 0, 1, 0, 64, 76, 3, 182, 76, 5, 100,
+        0, 8, 103, 117, 105, 108, 100, 95, 105, 100, 110, 8, 0, 11, 0, 130, 49, 3, 182, 76, 5,
+    ];
+
+    #[test]
+    fn test_decode_hello() {
+        let value = from_slice(HELLO).unwrap();
+
+        assert_eq!(
+            value,
+            json!({
+                "t": null,
+                "s": null,
+                "op": 10,
+                "d": {
+                    "heartbeat_interval": 41250,
+                    "_trace": ["[\"gateway-prd-main-858d\",{\"micros\":0.0}]"],
+                },
+            })
+        );
+        assert!(matches!(GatewayEvent::deserialize(value).unwrap(), GatewayEvent::Hello(41250)));
+    }
+
+    #[test]
+    fn test_decode_dispatch_matches_json() {
+        let mut payload = json::to_string(&json!({
+            "t": "MESSAGE_DELETE",
+            "s": 5,
+            "op": 0,
+            "d": {
+                "id": "1041020807447187477",
+                "channel_id": "381880193700069377",
+                "guild_id": "381880193251409931",
+            },
+        }))
+        .unwrap();
+        let from_json: GatewayEvent = json::from_str(&mut payload).unwrap();
+        let from_etf = GatewayEvent::deserialize(from_slice(MESSAGE_DELETE).unwrap()).unwrap();
+
+        assert!(matches!(from_etf, GatewayEvent::Dispatch(5, Event::MessageDelete(_))));
+        assert_eq!(json::to_string(&from_etf).unwrap(), json::to_string(&from_json).unwrap());
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        for bytes in [HELLO, MESSAGE_DELETE] {
+            let value = from_slice(bytes).unwrap();
+
+            assert_eq!(from_slice(&to_vec(&value).unwrap()).unwrap(), value);
+        }
+
+        let value = json!({
+            "op": 2,
+            "d": {
+                "token": "token",
+                "large_threshold": 250,
+                "shard": [0, 1],
+                "presence": {
+                    "since": null,
+                    "activities": [],
+                    "afk": false,
+                },
+                "snowflake": 1041020807447187477_u64,
+                "negative": -1_041_020_807_i64,
+                "float": 1.5,
+                "unicode": "Gr\u{fc}\u{df}e \u{1f44b}",
+            },
+        });
+
+        assert_eq!(from_slice(&to_vec(&value).unwrap()).unwrap(), value);
+    }
+
+    #[test]
+    fn test_decode_invalid() {
+        assert!(from_slice(&[]).is_err());
+        assert!(from_slice(&[130, 97, 1]).is_err());
+        assert!(from_slice(&HELLO[..HELLO.len() - 1]).is_err());
+        assert!(from_slice(&[131, 103]).is_err());
+    }
+
+    #[test]
+    fn test_decode_depth_limit() {
+        // Single element tuples nested in each other, around an integer.
+        let nested = |depth: usize| {
+            let mut bytes = vec![131];
+            bytes.extend([104, 1].repeat(depth));
+            bytes.extend([97, 0]);
+
+            bytes
+        };
+
+        assert!(from_slice(&nested(MAX_DEPTH - 1)).is_ok());
+        assert!(from_slice(&nested(MAX_DEPTH)).is_err());
+        assert!(from_slice(&nested(1_000_000)).is_err());
+    }
+}
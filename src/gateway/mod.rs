@@ -47,11 +47,14 @@
 //! [docs]: https://discordapp.com/developers/docs/topics/gateway#sharding
 
 mod error;
+pub(crate) mod etf;
 mod shard;
 mod ws_client_ext;
 
 use std::fmt;
 
+use serde::{Deserialize, Serialize};
+
 pub use self::error::Error as GatewayError;
 pub use self::shard::Shard;
 pub use self::ws_client_ext::WebSocketGatewayClientExt;
@@ -142,6 +145,67 @@ impl fmt::Display for ConnectionStage {
     }
 }
 
+/// The transport compression that a [`Shard`] negotiates with the gateway.
+///
+/// With [`Self::ZlibStream`], all payloads received over a connection are part
+/// of a single zlib stream, which considerably reduces the bandwidth used by
+/// bots in many guilds at the cost of some CPU time.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum TransportCompression {
+    /// Payloads are received uncompressed.
+    None,
+    /// Payloads are received compressed with `zlib-stream`, using one zlib
+    /// context per connection.
+    ZlibStream,
+}
+
+impl Default for TransportCompression {
+    fn default() -> Self {
+        Self::None
+    }
+}
+
+impl TransportCompression {
+    /// The value of the `compress` query parameter of the gateway URL, if
+    /// any.
+    pub(crate) fn query_value(self) -> Option<&'static str> {
+        match self {
+            Self::None => None,
+            Self::ZlibStream => Some("zlib-stream"),
+        }
+    }
+}
+
+/// The encoding of the payloads that a [`Shard`] exchanges with the gateway.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum GatewayEncoding {
+    /// Payloads are encoded as JSON.
+    Json,
+    /// Payloads are encoded in the [External Term Format][docs], which is
+    /// more compact and cheaper to decode than JSON.
+    ///
+    /// [docs]: https://www.erlang.org/doc/apps/erts/erl_ext_dist.html
+    Etf,
+}
+
+impl Default for GatewayEncoding {
+    fn default() -> Self {
+        Self::Json
+    }
+}
+
+impl GatewayEncoding {
+    /// The value of the `encoding` query parameter of the gateway URL.
+    pub(crate) fn query_value(self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Etf => "etf",
+        }
+    }
+}
+
 /// A message to be passed around within the library.
 ///
 /// As a user you usually don't need to worry about this, but when working with
@@ -165,6 +229,37 @@ pub enum ShardAction {
     Heartbeat,
     Identify,
     Reconnect(ReconnectType),
+    /// Indicator that a RESUME should be sent over the current connection,
+    /// such as for a restored session.
+    Resume,
+}
+
+/// The state of a gateway session needed to RESUME it, for example after a
+/// restart of the process.
+///
+/// Refer to [`SessionStore`] for how sessions are persisted.
+///
+/// [`SessionStore`]: crate::client::bridge::gateway::SessionStore
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct SessionInfo {
+    /// The ID of the session, as received in the READY payload.
+    pub session_id: String,
+    /// The sequence number of the last dispatch received in the session.
+    pub seq: u64,
+    /// The URL to connect to when resuming, as received in the READY payload.
+    pub resume_gateway_url: Option<String>,
+}
+
+impl SessionInfo {
+    /// Creates the state of a session, without a resume gateway URL.
+    pub fn new(session_id: impl Into<String>, seq: u64) -> Self {
+        Self {
+            session_id: session_id.into(),
+            seq,
+            resume_gateway_url: None,
+        }
+    }
 }
 
 /// The type of reconnection that should be performed.
@@ -10,17 +10,21 @@ use url::Url;
 use super::{
     ConnectionStage,
     CurrentPresence,
+    GatewayEncoding,
     GatewayError,
     ReconnectType,
+    SessionInfo,
     ShardAction,
+    TransportCompression,
     WebSocketGatewayClientExt,
     WsStream,
 };
 use crate::client::bridge::gateway::ChunkGuildFilter;
 use crate::constants::{self, close_codes};
+use crate::gateway::ws_client_ext::EncodedStream;
 use crate::http::Http;
 use crate::internal::prelude::*;
-use crate::internal::ws_impl::create_client;
+use crate::internal::ws_impl::{create_client, Inflater, ReceiverExt, SenderExt};
 use crate::model::event::{Event, GatewayEvent};
 use crate::model::gateway::{Activity, GatewayIntents};
 use crate::model::id::GuildId;
@@ -60,6 +64,11 @@ use crate::model::user::OnlineStatus;
 /// [module docs]: crate::gateway#sharding
 pub struct Shard {
     pub client: WsStream,
+    compression: TransportCompression,
+    encoding: GatewayEncoding,
+    /// The zlib context of the current connection, if `zlib-stream` transport
+    /// compression is used.
+    inflater: Option<Inflater>,
     current_presence: CurrentPresence,
     /// A tuple of:
     ///
@@ -79,6 +88,8 @@ pub struct Shard {
     last_heartbeat_acknowledged: bool,
     seq: u64,
     session_id: Option<String>,
+    /// The URL to connect to when resuming the session.
+    resume_gateway_url: Option<String>,
     shard_info: [u64; 2],
     stage: ConnectionStage,
     /// Instant of when the shard was started.
@@ -132,19 +143,63 @@ impl Shard {
         shard_info: [u64; 2],
         intents: GatewayIntents,
     ) -> Result<Shard> {
-        let url = ws_url.lock().await.clone();
-        let client = connect(&url).await?;
+        Self::new_with_transport(
+            ws_url,
+            token,
+            shard_info,
+            intents,
+            TransportCompression::None,
+            GatewayEncoding::Json,
+            None,
+        )
+        .await
+    }
+
+    /// Instantiates a new instance of a Shard that negotiates the given
+    /// transport compression and payload encoding with the gateway.
+    ///
+    /// If a `session` is given, such as one persisted before the process
+    /// restarted, the shard connects to its resume gateway URL if it has one,
+    /// and upon receiving a HELLO, RESUMEs the session instead of IDENTIFYing.
+    /// If the session can not be resumed, Discord invalidates it and the shard
+    /// falls back to IDENTIFYing.
+    ///
+    /// Refer to [`Self::new`] for more information.
+    ///
+    /// # Errors
+    ///
+    /// On Error, will return either [`Error::Gateway`], [`Error::Tungstenite`]
+    /// or a Rustls/native TLS error.
+    pub async fn new_with_transport(
+        ws_url: Arc<Mutex<String>>,
+        token: &str,
+        shard_info: [u64; 2],
+        intents: GatewayIntents,
+        compression: TransportCompression,
+        encoding: GatewayEncoding,
+        session: Option<SessionInfo>,
+    ) -> Result<Shard> {
+        let (session_id, seq, resume_gateway_url) = match session {
+            Some(session) => (Some(session.session_id), session.seq, session.resume_gateway_url),
+            None => (None, 0, None),
+        };
+        let url = match &resume_gateway_url {
+            Some(url) => url.clone(),
+            None => ws_url.lock().await.clone(),
+        };
+        let client = connect(&url, compression, encoding).await?;
 
         let current_presence = (None, OnlineStatus::Online);
         let heartbeat_instants = (None, None);
         let heartbeat_interval = None;
         let last_heartbeat_acknowledged = true;
-        let seq = 0;
         let stage = ConnectionStage::Handshake;
-        let session_id = None;
 
         Ok(Shard {
             client,
+            compression,
+            encoding,
+            inflater: new_inflater(compression),
             current_presence,
             heartbeat_instants,
             heartbeat_interval,
@@ -155,6 +210,7 @@ impl Shard {
             started: Instant::now(),
             token: token.to_string(),
             session_id,
+            resume_gateway_url,
             shard_info,
             ws_url,
             intents,
@@ -168,6 +224,18 @@ impl Shard {
         self.http = Some(http);
     }
 
+    /// Retrieves the transport compression negotiated with the gateway.
+    #[inline]
+    pub fn compression(&self) -> TransportCompression {
+        self.compression
+    }
+
+    /// Retrieves the encoding of the payloads exchanged with the gateway.
+    #[inline]
+    pub fn encoding(&self) -> GatewayEncoding {
+        self.encoding
+    }
+
     /// Retrieves the current presence of the shard.
     #[inline]
     pub fn current_presence(&self) -> &CurrentPresence {
@@ -206,7 +274,10 @@ impl Shard {
     /// a heartbeat.
     #[instrument(skip(self))]
     pub async fn heartbeat(&mut self) -> Result<()> {
-        match self.client.send_heartbeat(&self.shard_info, Some(self.seq)).await {
+        match EncodedStream::new(&mut self.client, self.encoding)
+            .send_heartbeat(&self.shard_info, Some(self.seq))
+            .await
+        {
             Ok(()) => {
                 self.heartbeat_instants.0 = Some(Instant::now());
                 self.last_heartbeat_acknowledged = false;
@@ -250,6 +321,16 @@ impl Shard {
         self.session_id.as_ref()
     }
 
+    /// Returns the state needed to resume the current session, if there is
+    /// one.
+    pub fn session(&self) -> Option<SessionInfo> {
+        self.session_id.as_ref().map(|session_id| SessionInfo {
+            session_id: session_id.clone(),
+            seq: self.seq,
+            resume_gateway_url: self.resume_gateway_url.clone(),
+        })
+    }
+
     #[inline]
     #[instrument(skip(self))]
     pub fn set_activity(&mut self, activity: Option<Activity>) {
@@ -329,6 +410,7 @@ impl Shard {
                 debug!("[Shard {:?}] Received Ready", self.shard_info);
 
                 self.session_id = Some(ready.ready.session_id.clone());
+                self.resume_gateway_url.clone_from(&ready.ready.resume_gateway_url);
                 self.stage = ConnectionStage::Connected;
 
                 if let Some(ref http) = self.http {
@@ -430,6 +512,7 @@ impl Shard {
                 info!("[Shard {:?}] Invalid session.", self.shard_info);
 
                 self.session_id = None;
+                self.resume_gateway_url = None;
             },
             Some(close_codes::INVALID_GATEWAY_INTENTS) => {
                 error!("[Shard {:?}] Invalid gateway intents have been provided.", self.shard_info);
@@ -516,7 +599,13 @@ impl Shard {
                 }
 
                 Ok(Some(if self.stage == ConnectionStage::Handshake {
-                    ShardAction::Identify
+                    if self.session_id.is_some() {
+                        // The session was restored, resume it over this
+                        // connection instead of opening another one.
+                        ShardAction::Resume
+                    } else {
+                        ShardAction::Identify
+                    }
                 } else {
                     debug!("[Shard {:?}] Received late Hello; autoreconnecting", self.shard_info);
 
@@ -722,7 +811,9 @@ impl Shard {
     ) -> Result<()> {
         debug!("[Shard {:?}] Requesting member chunks", self.shard_info);
 
-        self.client.send_chunk_guild(guild_id, &self.shard_info, limit, filter, nonce).await
+        EncodedStream::new(&mut self.client, self.encoding)
+            .send_chunk_guild(guild_id, &self.shard_info, limit, filter, nonce)
+            .await
     }
 
     /// Sets the shard as going into identifying stage, which sets:
@@ -731,7 +822,9 @@ impl Shard {
     /// - the `stage` to [`ConnectionStage::Identifying`]
     #[instrument(skip(self))]
     pub async fn identify(&mut self) -> Result<()> {
-        self.client.send_identify(&self.shard_info, &self.token, self.intents).await?;
+        EncodedStream::new(&mut self.client, self.encoding)
+            .send_identify(&self.shard_info, &self.token, self.intents)
+            .await?;
 
         self.heartbeat_instants.0 = Some(Instant::now());
         self.stage = ConnectionStage::Identifying;
@@ -757,19 +850,50 @@ impl Shard {
         // accurate when a Hello is received.
         self.stage = ConnectionStage::Connecting;
         self.started = Instant::now();
-        let url = &self.ws_url.lock().await.clone();
-        let client = connect(url).await?;
+        let url = match &self.resume_gateway_url {
+            Some(url) => url.clone(),
+            None => self.ws_url.lock().await.clone(),
+        };
+        let client = connect(&url, self.compression, self.encoding).await?;
+        // The zlib stream starts anew with every connection.
+        self.inflater = new_inflater(self.compression);
         self.stage = ConnectionStage::Handshake;
 
         Ok(client)
     }
 
+    /// Receives the next payload from the gateway, decompressing it if
+    /// transport compression is used.
+    ///
+    /// Returns `Ok(None)` if no complete payload was received within a short
+    /// timeout.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Tungstenite`] if the connection errored, or
+    /// [`GatewayError::Closed`] if it was closed.
+    #[instrument(skip(self))]
+    pub async fn recv_json(&mut self) -> Result<Option<Value>> {
+        self.client.recv_json(self.inflater.as_mut(), self.encoding).await
+    }
+
+    /// Sends a payload to the gateway in the encoding in use.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Tungstenite`] if the payload could not be sent.
+    #[instrument(skip(self))]
+    pub async fn send_json(&mut self, value: &Value) -> Result<()> {
+        self.client.send_encoded(value, self.encoding).await
+    }
+
     #[instrument(skip(self))]
     pub async fn reset(&mut self) {
         self.heartbeat_instants = (Some(Instant::now()), None);
         self.heartbeat_interval = None;
         self.last_heartbeat_acknowledged = true;
         self.session_id = None;
+        self.resume_gateway_url = None;
         self.stage = ConnectionStage::Disconnected;
         self.seq = 0;
     }
@@ -779,11 +903,25 @@ impl Shard {
         debug!("[Shard {:?}] Attempting to resume", self.shard_info);
 
         self.client = self.initialize().await?;
+
+        self.send_resume().await
+    }
+
+    /// Sends a RESUME for the current session over the current connection,
+    /// setting the `stage` to [`ConnectionStage::Resuming`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GatewayError::NoSessionId`] if there is no session to resume.
+    #[instrument(skip(self))]
+    pub async fn send_resume(&mut self) -> Result<()> {
         self.stage = ConnectionStage::Resuming;
 
         match self.session_id.as_ref() {
             Some(session_id) => {
-                self.client.send_resume(&self.shard_info, session_id, self.seq, &self.token).await
+                EncodedStream::new(&mut self.client, self.encoding)
+                    .send_resume(&self.shard_info, session_id, self.seq, &self.token)
+                    .await
             },
             None => Err(Error::Gateway(GatewayError::NoSessionId)),
         }
@@ -801,17 +939,39 @@ impl Shard {
 
     #[instrument(skip(self))]
     pub async fn update_presence(&mut self) -> Result<()> {
-        self.client.send_presence_update(&self.shard_info, &self.current_presence).await
+        EncodedStream::new(&mut self.client, self.encoding)
+            .send_presence_update(&self.shard_info, &self.current_presence)
+            .await
     }
 }
 
-async fn connect(base_url: &str) -> Result<WsStream> {
-    let url =
-        Url::parse(&format!("{}?v={}", base_url, constants::GATEWAY_VERSION)).map_err(|why| {
-            warn!("Error building gateway URL with base `{}`: {:?}", base_url, why);
+fn new_inflater(compression: TransportCompression) -> Option<Inflater> {
+    match compression {
+        TransportCompression::None => None,
+        TransportCompression::ZlibStream => Some(Inflater::new()),
+    }
+}
 
-            Error::Gateway(GatewayError::BuildingUrl)
-        })?;
+async fn connect(
+    base_url: &str,
+    compression: TransportCompression,
+    encoding: GatewayEncoding,
+) -> Result<WsStream> {
+    let mut url = Url::parse(&format!(
+        "{}?v={}&encoding={}",
+        base_url,
+        constants::GATEWAY_VERSION,
+        encoding.query_value()
+    ))
+    .map_err(|why| {
+        warn!("Error building gateway URL with base `{}`: {:?}", base_url, why);
+
+        Error::Gateway(GatewayError::BuildingUrl)
+    })?;
+
+    if let Some(compress) = compression.query_value() {
+        url.query_pairs_mut().append_pair("compress", compress);
+    }
 
     create_client(url).await
 }
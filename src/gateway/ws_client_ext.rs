@@ -5,7 +5,7 @@ use tracing::{debug, instrument, trace};
 
 use crate::client::bridge::gateway::ChunkGuildFilter;
 use crate::constants::OpCode;
-use crate::gateway::{CurrentPresence, WsStream};
+use crate::gateway::{CurrentPresence, GatewayEncoding, WsStream};
 use crate::internal::prelude::*;
 use crate::internal::ws_impl::SenderExt;
 use crate::json::json;
@@ -49,6 +49,74 @@ pub trait WebSocketGatewayClientExt {
 
 #[async_trait]
 impl WebSocketGatewayClientExt for WsStream {
+    async fn send_chunk_guild(
+        &mut self,
+        guild_id: GuildId,
+        shard_info: &[u64; 2],
+        limit: Option<u16>,
+        filter: ChunkGuildFilter,
+        nonce: Option<&str>,
+    ) -> Result<()> {
+        EncodedStream::json(self).send_chunk_guild(guild_id, shard_info, limit, filter, nonce).await
+    }
+
+    async fn send_heartbeat(&mut self, shard_info: &[u64; 2], seq: Option<u64>) -> Result<()> {
+        EncodedStream::json(self).send_heartbeat(shard_info, seq).await
+    }
+
+    async fn send_identify(
+        &mut self,
+        shard_info: &[u64; 2],
+        token: &str,
+        intents: GatewayIntents,
+    ) -> Result<()> {
+        EncodedStream::json(self).send_identify(shard_info, token, intents).await
+    }
+
+    async fn send_presence_update(
+        &mut self,
+        shard_info: &[u64; 2],
+        current_presence: &CurrentPresence,
+    ) -> Result<()> {
+        EncodedStream::json(self).send_presence_update(shard_info, current_presence).await
+    }
+
+    async fn send_resume(
+        &mut self,
+        shard_info: &[u64; 2],
+        session_id: &str,
+        seq: u64,
+        token: &str,
+    ) -> Result<()> {
+        EncodedStream::json(self).send_resume(shard_info, session_id, seq, token).await
+    }
+}
+
+/// A websocket stream that payloads are sent over in the given encoding.
+pub(crate) struct EncodedStream<'a> {
+    stream: &'a mut WsStream,
+    encoding: GatewayEncoding,
+}
+
+impl<'a> EncodedStream<'a> {
+    pub(crate) fn new(stream: &'a mut WsStream, encoding: GatewayEncoding) -> Self {
+        Self {
+            stream,
+            encoding,
+        }
+    }
+
+    fn json(stream: &'a mut WsStream) -> Self {
+        Self::new(stream, GatewayEncoding::Json)
+    }
+
+    async fn send(&mut self, value: &Value) -> Result<()> {
+        self.stream.send_encoded(value, self.encoding).await
+    }
+}
+
+#[async_trait]
+impl WebSocketGatewayClientExt for EncodedStream<'_> {
     #[instrument(skip(self))]
     async fn send_chunk_guild(
         &mut self,
@@ -78,14 +146,14 @@ impl WebSocketGatewayClientExt for WsStream {
             },
         };
 
-        self.send_json(&payload).await.map_err(From::from)
+        self.send(&payload).await.map_err(From::from)
     }
 
     #[instrument(skip(self))]
     async fn send_heartbeat(&mut self, shard_info: &[u64; 2], seq: Option<u64>) -> Result<()> {
         trace!("[Shard {:?}] Sending heartbeat d: {:?}", shard_info, seq);
 
-        self.send_json(&json!({
+        self.send(&json!({
             "d": seq,
             "op": OpCode::Heartbeat.num(),
         }))
@@ -102,7 +170,7 @@ impl WebSocketGatewayClientExt for WsStream {
     ) -> Result<()> {
         debug!("[Shard {:?}] Identifying", shard_info);
 
-        self.send_json(&json!({
+        self.send(&json!({
             "op": OpCode::Identify.num(),
             "d": {
                 "token": token,
@@ -155,7 +223,7 @@ impl WebSocketGatewayClientExt for WsStream {
 
         debug!("[Shard {:?}] Sending presence update", shard_info);
 
-        self.send_json(&json!({
+        self.send(&json!({
             "op": OpCode::StatusUpdate.num(),
             "d": {
                 "afk": false,
@@ -181,7 +249,7 @@ impl WebSocketGatewayClientExt for WsStream {
     ) -> Result<()> {
         debug!("[Shard {:?}] Sending resume; seq: {}", shard_info, seq);
 
-        self.send_json(&json!({
+        self.send(&json!({
             "op": OpCode::Resume.num(),
             "d": {
                 "session_id": session_id,
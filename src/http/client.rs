@@ -4,21 +4,31 @@ use std::borrow::Cow;
 use std::convert::TryFrom;
 use std::fmt;
 use std::str::FromStr;
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
 
 use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
 use reqwest::header::{HeaderMap as Headers, HeaderValue, CONTENT_TYPE};
 use reqwest::{Client, ClientBuilder, Response as ReqwestResponse, StatusCode, Url};
 use serde::de::DeserializeOwned;
+use serde::Serialize;
+use tokio::sync::Notify;
 use tracing::{debug, instrument, trace};
 
 use super::multipart::Multipart;
+use super::payload::GetGuildPruneCount;
 use super::ratelimiting::{RatelimitedRequest, Ratelimiter};
 use super::request::Request;
 use super::routing::RouteInfo;
 use super::typing::Typing;
-use super::{AttachmentType, GuildPagination, HttpError, UserPagination};
+use super::{
+    AttachmentType,
+    GuildPagination,
+    HttpError,
+    RequestOptions,
+    RetryPolicy,
+    UserPagination,
+};
 use crate::internal::prelude::*;
 use crate::json::prelude::*;
 use crate::model::application::command::{Command, CommandPermission};
@@ -51,7 +61,10 @@ pub struct HttpBuilder {
     ratelimiter_disabled: bool,
     token: String,
     proxy: Option<Url>,
+    base_url: Option<String>,
     application_id: Option<u64>,
+    retry_policy: Option<RetryPolicy>,
+    request_options: RequestOptions,
 }
 
 impl HttpBuilder {
@@ -64,7 +77,10 @@ impl HttpBuilder {
             ratelimiter_disabled: false,
             token: parse_token(token),
             proxy: None,
+            base_url: None,
             application_id: None,
+            retry_policy: None,
+            request_options: RequestOptions::default(),
         }
     }
 
@@ -118,6 +134,32 @@ impl HttpBuilder {
         self
     }
 
+    /// Sets the policy for retrying requests that failed transiently, such as
+    /// with a server error or a timeout. If one isn't provided, the
+    /// ratelimiter's is used, which is [`RetryPolicy::default`] unless set
+    /// otherwise.
+    ///
+    /// Retries are performed by the ratelimiter, and so are disabled along
+    /// with it by [`Self::ratelimiter_disabled`].
+    #[must_use]
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(retry_policy);
+
+        self
+    }
+
+    /// Sets the default options of all requests, such as their timeout. These
+    /// are overridden by the options of a [`RequestOptions::scope`].
+    ///
+    /// **Note**: Failing on ratelimits requires the ratelimiter, and so is
+    /// ignored if it is disabled by [`Self::ratelimiter_disabled`].
+    #[must_use]
+    pub fn request_options(mut self, request_options: RequestOptions) -> Self {
+        self.request_options = request_options;
+
+        self
+    }
+
     /// Sets the proxy that Discord HTTP API requests will be passed to. This is
     /// mainly intended for something like [`twilight-http-proxy`] where
     /// multiple processes can make API requests while sharing a single
@@ -141,6 +183,29 @@ impl HttpBuilder {
         Ok(self)
     }
 
+    /// Sets the base URL that all Discord HTTP API requests are made against,
+    /// including the API version, e.g. `http://127.0.0.1:3000/api/v10`.
+    ///
+    /// This is mainly intended for pointing the client at a local stand-in for
+    /// Discord, such as in integration tests. Unlike [`Self::proxy`], which
+    /// only replaces the host, the whole prefix is replaced. If both are set,
+    /// the base URL takes precedence.
+    ///
+    /// This does not affect CDN URLs, such as those returned by
+    /// [`User::avatar_url`] or [`Guild::icon_url`]. Those are built by model
+    /// methods that have no access to an `Http` client, so they always point
+    /// at `https://cdn.discordapp.com`; nothing is fetched from them by the
+    /// library itself.
+    ///
+    /// Defaults to [`constants::API_BASE_URL`].
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Result<Self> {
+        let base_url = base_url.into();
+        Url::parse(&base_url).map_err(HttpError::Url)?;
+        self.base_url = Some(base_url.trim_end_matches('/').to_string());
+
+        Ok(self)
+    }
+
     /// Use the given configuration to build the `Http` client.
     #[must_use]
     pub fn build(self) -> Http {
@@ -153,20 +218,81 @@ impl HttpBuilder {
             builder.build().expect("Cannot build reqwest::Client")
         });
 
-        let ratelimiter = self.ratelimiter.unwrap_or_else(|| {
+        let proxy = self.proxy;
+        let base_url = self.base_url.unwrap_or_else(|| match &proxy {
+            Some(proxy) => {
+                constants::API_BASE_URL.replacen("https://discord.com/", proxy.as_str(), 1)
+            },
+            None => constants::API_BASE_URL.to_string(),
+        });
+
+        let mut ratelimiter = self.ratelimiter.unwrap_or_else(|| {
             let client = client.clone();
             Ratelimiter::new(client, token.to_string())
         });
+        ratelimiter.set_base_url(base_url.clone());
+
+        if let Some(retry_policy) = self.retry_policy {
+            ratelimiter.set_retry_policy(retry_policy);
+        }
 
         let ratelimiter_disabled = self.ratelimiter_disabled;
+        let request_options = self.request_options;
 
         Http {
             client,
             ratelimiter,
             ratelimiter_disabled,
-            proxy: self.proxy,
+            proxy,
+            base_url,
             token,
             application_id,
+            request_options,
+            in_flight: InFlightRequests::default(),
+        }
+    }
+}
+
+/// Keeps count of the requests that are in flight, so that a graceful shutdown
+/// can wait for them to finish.
+#[derive(Default)]
+struct InFlightRequests {
+    count: AtomicUsize,
+    idle: Notify,
+}
+
+impl InFlightRequests {
+    fn count(&self) -> usize {
+        self.count.load(Ordering::Acquire)
+    }
+
+    fn start(&self) -> InFlightGuard<'_> {
+        self.count.fetch_add(1, Ordering::AcqRel);
+
+        InFlightGuard(self)
+    }
+
+    async fn wait_idle(&self) {
+        loop {
+            let notified = self.idle.notified();
+
+            if self.count() == 0 {
+                return;
+            }
+
+            notified.await;
+        }
+    }
+}
+
+/// Marks a request as finished when dropped, including when its future was
+/// cancelled.
+struct InFlightGuard<'a>(&'a InFlightRequests);
+
+impl Drop for InFlightGuard<'_> {
+    fn drop(&mut self) {
+        if self.0.count.fetch_sub(1, Ordering::AcqRel) == 1 {
+            self.0.idle.notify_waiters();
         }
     }
 }
@@ -196,8 +322,11 @@ pub struct Http {
     pub ratelimiter: Ratelimiter,
     pub ratelimiter_disabled: bool,
     pub proxy: Option<Url>,
+    base_url: String,
     pub token: String,
     application_id: AtomicU64,
+    request_options: RequestOptions,
+    in_flight: InFlightRequests,
 }
 
 impl fmt::Debug for Http {
@@ -207,6 +336,9 @@ impl fmt::Debug for Http {
             .field("ratelimiter", &self.ratelimiter)
             .field("ratelimiter_disabled", &self.ratelimiter_disabled)
             .field("proxy", &self.proxy)
+            .field("base_url", &self.base_url)
+            .field("request_options", &self.request_options)
+            .field("in_flight", &self.in_flight.count())
             .finish()
     }
 }
@@ -226,8 +358,11 @@ impl Http {
             ratelimiter: Ratelimiter::new(client2, token.to_string()),
             ratelimiter_disabled: false,
             proxy: None,
+            base_url: constants::API_BASE_URL.to_string(),
             token,
             application_id: AtomicU64::new(0),
+            request_options: RequestOptions::default(),
+            in_flight: InFlightRequests::default(),
         }
     }
 
@@ -258,6 +393,13 @@ impl Http {
         self.application_id.store(application_id, Ordering::Relaxed);
     }
 
+    /// The base URL that all requests are made against. See
+    /// [`HttpBuilder::base_url`] for more info.
+    #[must_use]
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
     /// Adds a [`User`] to a [`Guild`] with a valid OAuth2 access token.
     ///
     /// Returns the created [`Member`] object, or nothing if the user is already a member of the guild.
@@ -265,7 +407,7 @@ impl Http {
         &self,
         guild_id: u64,
         user_id: u64,
-        map: &JsonMap,
+        map: &impl Serialize,
     ) -> Result<Option<Member>> {
         let body = to_vec(map)?;
 
@@ -373,7 +515,7 @@ impl Http {
     pub async fn create_channel(
         &self,
         guild_id: u64,
-        map: &JsonMap,
+        map: &impl Serialize,
         audit_log_reason: Option<&str>,
     ) -> Result<GuildChannel> {
         let body = to_vec(map)?;
@@ -390,7 +532,7 @@ impl Http {
     }
 
     /// Creates a stage instance.
-    pub async fn create_stage_instance(&self, map: &Value) -> Result<StageInstance> {
+    pub async fn create_stage_instance(&self, map: &impl Serialize) -> Result<StageInstance> {
         self.fire(Request {
             body: Some(to_string(map)?.as_bytes()),
             multipart: None,
@@ -406,7 +548,7 @@ impl Http {
         &self,
         channel_id: u64,
         message_id: u64,
-        map: &JsonMap,
+        map: &impl Serialize,
     ) -> Result<GuildChannel> {
         let body = to_vec(map)?;
 
@@ -426,7 +568,7 @@ impl Http {
     pub async fn create_private_thread(
         &self,
         channel_id: u64,
-        map: &JsonMap,
+        map: &impl Serialize,
     ) -> Result<GuildChannel> {
         let body = to_vec(map)?;
 
@@ -452,7 +594,7 @@ impl Http {
     pub async fn create_emoji(
         &self,
         guild_id: u64,
-        map: &Value,
+        map: &impl Serialize,
         audit_log_reason: Option<&str>,
     ) -> Result<Emoji> {
         self.fire(Request {
@@ -472,7 +614,7 @@ impl Http {
     pub async fn create_followup_message(
         &self,
         interaction_token: &str,
-        map: &Value,
+        map: &impl Serialize,
     ) -> Result<Message> {
         self.fire(Request {
             body: Some(to_string(map)?.as_bytes()),
@@ -492,14 +634,14 @@ impl Http {
     pub async fn create_followup_message_with_files(
         &self,
         interaction_token: &str,
-        map: &Value,
+        map: &impl Serialize,
         files: impl IntoIterator<Item = AttachmentType<'_>>,
     ) -> Result<Message> {
         self.fire(Request {
             body: None,
             multipart: Some(Multipart {
                 files: files.into_iter().map(Into::into).collect(),
-                payload_json: Some(map.clone()),
+                payload_json: Some(to_value(map)?),
                 fields: vec![],
             }),
             headers: None,
@@ -522,7 +664,7 @@ impl Http {
     /// application will overwrite the old command.
     ///
     /// [docs]: https://discord.com/developers/docs/interactions/slash-commands#create-global-application-command
-    pub async fn create_global_application_command(&self, map: &Value) -> Result<Command> {
+    pub async fn create_global_application_command(&self, map: &impl Serialize) -> Result<Command> {
         self.fire(Request {
             body: Some(to_string(map)?.as_bytes()),
             multipart: None,
@@ -535,7 +677,10 @@ impl Http {
     }
 
     /// Creates new global application commands.
-    pub async fn create_global_application_commands(&self, map: &Value) -> Result<Vec<Command>> {
+    pub async fn create_global_application_commands(
+        &self,
+        map: &impl Serialize,
+    ) -> Result<Vec<Command>> {
         self.fire(Request {
             body: Some(to_string(map)?.as_bytes()),
             multipart: None,
@@ -551,7 +696,7 @@ impl Http {
     pub async fn create_guild_application_commands(
         &self,
         guild_id: u64,
-        map: &Value,
+        map: &impl Serialize,
     ) -> Result<Vec<Command>> {
         self.fire(Request {
             body: Some(to_string(map)?.as_bytes()),
@@ -598,7 +743,7 @@ impl Http {
     /// [documentation on this endpoint]:
     /// https://discord.com/developers/docs/resources/guild#create-guild
     /// [whitelist]: https://discord.com/developers/docs/resources/guild#create-guild
-    pub async fn create_guild(&self, map: &Value) -> Result<PartialGuild> {
+    pub async fn create_guild(&self, map: &impl Serialize) -> Result<PartialGuild> {
         self.fire(Request {
             body: Some(to_string(map)?.as_bytes()),
             multipart: None,
@@ -618,7 +763,7 @@ impl Http {
     pub async fn create_guild_application_command(
         &self,
         guild_id: u64,
-        map: &Value,
+        map: &impl Serialize,
     ) -> Result<Command> {
         self.fire(Request {
             body: Some(to_string(map)?.as_bytes()),
@@ -644,7 +789,7 @@ impl Http {
         &self,
         guild_id: u64,
         integration_id: u64,
-        map: &Value,
+        map: &impl Serialize,
         audit_log_reason: Option<&str>,
     ) -> Result<()> {
         self.wind(204, Request {
@@ -669,7 +814,7 @@ impl Http {
         &self,
         interaction_id: u64,
         interaction_token: &str,
-        map: &Value,
+        map: &impl Serialize,
     ) -> Result<()> {
         self.wind(204, Request {
             body: Some(to_string(map)?.as_bytes()),
@@ -693,7 +838,7 @@ impl Http {
         &self,
         interaction_id: u64,
         interaction_token: &str,
-        map: &Value,
+        map: &impl Serialize,
         files: impl IntoIterator<Item = AttachmentType<'_>>,
     ) -> Result<()> {
         self.wind(204, Request {
@@ -725,7 +870,7 @@ impl Http {
     pub async fn create_invite(
         &self,
         channel_id: u64,
-        map: &JsonMap,
+        map: &impl Serialize,
         audit_log_reason: Option<&str>,
     ) -> Result<RichInvite> {
         let body = to_vec(map)?;
@@ -746,7 +891,7 @@ impl Http {
         &self,
         channel_id: u64,
         target_id: u64,
-        map: &Value,
+        map: &impl Serialize,
     ) -> Result<()> {
         let body = to_vec(map)?;
 
@@ -763,7 +908,7 @@ impl Http {
     }
 
     /// Creates a private channel with a user.
-    pub async fn create_private_channel(&self, map: &Value) -> Result<PrivateChannel> {
+    pub async fn create_private_channel(&self, map: &impl Serialize) -> Result<PrivateChannel> {
         let body = to_vec(map)?;
 
         self.fire(Request {
@@ -799,7 +944,7 @@ impl Http {
     pub async fn create_role(
         &self,
         guild_id: u64,
-        map: &JsonMap,
+        map: &impl Serialize,
         audit_log_reason: Option<&str>,
     ) -> Result<Role> {
         let body = to_vec(map)?;
@@ -833,7 +978,7 @@ impl Http {
     pub async fn create_scheduled_event(
         &self,
         guild_id: u64,
-        map: &JsonMap,
+        map: &impl Serialize,
         audit_log_reason: Option<&str>,
     ) -> Result<ScheduledEvent> {
         let body = to_vec(map)?;
@@ -919,7 +1064,7 @@ impl Http {
     pub async fn create_webhook(
         &self,
         channel_id: u64,
-        map: &Value,
+        map: &impl Serialize,
         audit_log_reason: Option<&str>,
     ) -> Result<Webhook> {
         let body = to_vec(map)?;
@@ -1083,7 +1228,7 @@ impl Http {
     }
 
     /// Deletes a bunch of messages, only works for bots.
-    pub async fn delete_messages(&self, channel_id: u64, map: &Value) -> Result<()> {
+    pub async fn delete_messages(&self, channel_id: u64, map: &impl Serialize) -> Result<()> {
         self.wind(204, Request {
             body: Some(to_string(map)?.as_bytes()),
             multipart: None,
@@ -1328,7 +1473,7 @@ impl Http {
     pub async fn edit_channel(
         &self,
         channel_id: u64,
-        map: &JsonMap,
+        map: &impl Serialize,
         audit_log_reason: Option<&str>,
     ) -> Result<GuildChannel> {
         let body = to_vec(map)?;
@@ -1345,7 +1490,11 @@ impl Http {
     }
 
     /// Edits a stage instance.
-    pub async fn edit_stage_instance(&self, channel_id: u64, map: &Value) -> Result<StageInstance> {
+    pub async fn edit_stage_instance(
+        &self,
+        channel_id: u64,
+        map: &impl Serialize,
+    ) -> Result<StageInstance> {
         self.fire(Request {
             body: Some(to_string(map)?.as_bytes()),
             multipart: None,
@@ -1362,7 +1511,7 @@ impl Http {
         &self,
         guild_id: u64,
         emoji_id: u64,
-        map: &Value,
+        map: &impl Serialize,
         audit_log_reason: Option<&str>,
     ) -> Result<Emoji> {
         let body = to_vec(map)?;
@@ -1388,7 +1537,7 @@ impl Http {
         &self,
         interaction_token: &str,
         message_id: u64,
-        map: &Value,
+        map: &impl Serialize,
     ) -> Result<Message> {
         self.fire(Request {
             body: Some(to_string(map)?.as_bytes()),
@@ -1412,14 +1561,14 @@ impl Http {
         &self,
         interaction_token: &str,
         message_id: u64,
-        map: &Value,
+        map: &impl Serialize,
         new_attachments: impl IntoIterator<Item = AttachmentType<'_>>,
     ) -> Result<Message> {
         self.fire(Request {
             body: None,
             multipart: Some(Multipart {
                 files: new_attachments.into_iter().map(Into::into).collect(),
-                payload_json: Some(map.clone()),
+                payload_json: Some(to_value(map)?),
                 fields: vec![],
             }),
             headers: None,
@@ -1465,7 +1614,7 @@ impl Http {
     pub async fn edit_global_application_command(
         &self,
         command_id: u64,
-        map: &Value,
+        map: &impl Serialize,
     ) -> Result<Command> {
         self.fire(Request {
             body: Some(to_string(map)?.as_bytes()),
@@ -1483,7 +1632,7 @@ impl Http {
     pub async fn edit_guild(
         &self,
         guild_id: u64,
-        map: &JsonMap,
+        map: &impl Serialize,
         audit_log_reason: Option<&str>,
     ) -> Result<PartialGuild> {
         let body = to_vec(map)?;
@@ -1510,7 +1659,7 @@ impl Http {
         &self,
         guild_id: u64,
         command_id: u64,
-        map: &Value,
+        map: &impl Serialize,
     ) -> Result<Command> {
         self.fire(Request {
             body: Some(to_string(map)?.as_bytes()),
@@ -1536,7 +1685,7 @@ impl Http {
         &self,
         guild_id: u64,
         command_id: u64,
-        map: &Value,
+        map: &impl Serialize,
     ) -> Result<CommandPermission> {
         self.fire(Request {
             body: Some(to_string(map)?.as_bytes()),
@@ -1561,7 +1710,7 @@ impl Http {
     pub async fn edit_guild_application_commands_permissions(
         &self,
         guild_id: u64,
-        map: &Value,
+        map: &impl Serialize,
     ) -> Result<Vec<CommandPermission>> {
         self.fire(Request {
             body: Some(to_string(map)?.as_bytes()),
@@ -1576,7 +1725,11 @@ impl Http {
     }
 
     /// Edits the positions of a guild's channels.
-    pub async fn edit_guild_channel_positions(&self, guild_id: u64, value: &Value) -> Result<()> {
+    pub async fn edit_guild_channel_positions(
+        &self,
+        guild_id: u64,
+        value: &impl Serialize,
+    ) -> Result<()> {
         let body = to_vec(value)?;
 
         self.wind(204, Request {
@@ -1591,7 +1744,11 @@ impl Http {
     }
 
     /// Edits a [`Guild`]'s widget.
-    pub async fn edit_guild_widget(&self, guild_id: u64, map: &Value) -> Result<GuildWidget> {
+    pub async fn edit_guild_widget(
+        &self,
+        guild_id: u64,
+        map: &impl Serialize,
+    ) -> Result<GuildWidget> {
         let body = to_vec(map)?;
 
         self.fire(Request {
@@ -1609,7 +1766,7 @@ impl Http {
     pub async fn edit_guild_welcome_screen(
         &self,
         guild_id: u64,
-        map: &Value,
+        map: &impl Serialize,
     ) -> Result<GuildWelcomeScreen> {
         let body = to_vec(map)?;
 
@@ -1629,7 +1786,7 @@ impl Http {
         &self,
         guild_id: u64,
         user_id: u64,
-        map: &JsonMap,
+        map: &impl Serialize,
         audit_log_reason: Option<&str>,
     ) -> Result<Member> {
         let body = to_vec(map)?;
@@ -1662,7 +1819,7 @@ impl Http {
         &self,
         channel_id: u64,
         message_id: u64,
-        map: &Value,
+        map: &impl Serialize,
     ) -> Result<Message> {
         let body = to_vec(map)?;
 
@@ -1685,14 +1842,14 @@ impl Http {
         &self,
         channel_id: u64,
         message_id: u64,
-        map: &Value,
+        map: &impl Serialize,
         new_attachments: impl IntoIterator<Item = AttachmentType<'_>>,
     ) -> Result<Message> {
         self.fire(Request {
             body: None,
             multipart: Some(Multipart {
                 files: new_attachments.into_iter().map(Into::into).collect(),
-                payload_json: Some(map.clone()),
+                payload_json: Some(to_value(map)?),
                 fields: vec![],
             }),
             headers: None,
@@ -1721,7 +1878,7 @@ impl Http {
     }
 
     /// Edits the current member for the provided [`Guild`] via its Id.
-    pub async fn edit_member_me(&self, guild_id: u64, map: &JsonMap) -> Result<Member> {
+    pub async fn edit_member_me(&self, guild_id: u64, map: &impl Serialize) -> Result<Member> {
         let body = to_vec(map)?;
 
         self.fire(Request {
@@ -1798,7 +1955,7 @@ impl Http {
     pub async fn edit_original_interaction_response(
         &self,
         interaction_token: &str,
-        map: &Value,
+        map: &impl Serialize,
     ) -> Result<Message> {
         self.fire(Request {
             body: Some(to_string(map)?.as_bytes()),
@@ -1813,7 +1970,7 @@ impl Http {
     }
 
     /// Edits the current user's profile settings.
-    pub async fn edit_profile(&self, map: &JsonMap) -> Result<CurrentUser> {
+    pub async fn edit_profile(&self, map: &impl Serialize) -> Result<CurrentUser> {
         let body = to_vec(map)?;
 
         let request = self
@@ -1833,7 +1990,7 @@ impl Http {
         &self,
         guild_id: u64,
         role_id: u64,
-        map: &JsonMap,
+        map: &impl Serialize,
         audit_log_reason: Option<&str>,
     ) -> Result<Role> {
         let body = to_vec(&map)?;
@@ -1904,7 +2061,7 @@ impl Http {
         &self,
         guild_id: u64,
         event_id: u64,
-        map: &JsonMap,
+        map: &impl Serialize,
         audit_log_reason: Option<&str>,
     ) -> Result<ScheduledEvent> {
         let body = to_vec(map)?;
@@ -1929,7 +2086,7 @@ impl Http {
         &self,
         guild_id: u64,
         sticker_id: u64,
-        map: &JsonMap,
+        map: &impl Serialize,
         audit_log_reason: Option<&str>,
     ) -> Result<Sticker> {
         let body = to_vec(&map)?;
@@ -1955,7 +2112,7 @@ impl Http {
     }
 
     /// Edits a thread channel in the [`GuildChannel`] given its Id.
-    pub async fn edit_thread(&self, channel_id: u64, map: &JsonMap) -> Result<GuildChannel> {
+    pub async fn edit_thread(&self, channel_id: u64, map: &impl Serialize) -> Result<GuildChannel> {
         let body = to_vec(map)?;
 
         self.fire(Request {
@@ -2002,7 +2159,12 @@ impl Http {
     /// #     Ok(())
     /// # }
     /// ```
-    pub async fn edit_voice_state(&self, guild_id: u64, user_id: u64, map: &JsonMap) -> Result<()> {
+    pub async fn edit_voice_state(
+        &self,
+        guild_id: u64,
+        user_id: u64,
+        map: &impl Serialize,
+    ) -> Result<()> {
         let body = to_vec(map)?;
 
         self.wind(204, Request {
@@ -2052,7 +2214,7 @@ impl Http {
     /// #     Ok(())
     /// # }
     /// ```
-    pub async fn edit_voice_state_me(&self, guild_id: u64, map: &JsonMap) -> Result<()> {
+    pub async fn edit_voice_state_me(&self, guild_id: u64, map: &impl Serialize) -> Result<()> {
         let body = to_vec(map)?;
 
         self.wind(204, Request {
@@ -2103,7 +2265,7 @@ impl Http {
     pub async fn edit_webhook(
         &self,
         webhook_id: u64,
-        map: &Value,
+        map: &impl Serialize,
         audit_log_reason: Option<&str>,
     ) -> Result<Webhook> {
         self.fire(Request {
@@ -2146,7 +2308,7 @@ impl Http {
         &self,
         webhook_id: u64,
         token: &str,
-        map: &JsonMap,
+        map: &impl Serialize,
     ) -> Result<Webhook> {
         let body = to_vec(map)?;
 
@@ -2215,7 +2377,7 @@ impl Http {
         webhook_id: u64,
         token: &str,
         wait: bool,
-        map: &JsonMap,
+        map: &impl Serialize,
     ) -> Result<Option<Message>> {
         let body = to_vec(map)?;
 
@@ -2255,7 +2417,7 @@ impl Http {
         token: &str,
         wait: bool,
         files: It,
-        map: &JsonMap,
+        map: &impl Serialize,
     ) -> Result<Option<Message>>
     where
         T: Into<AttachmentType<'a>>,
@@ -2303,7 +2465,7 @@ impl Http {
         webhook_id: u64,
         token: &str,
         message_id: u64,
-        map: &JsonMap,
+        map: &impl Serialize,
     ) -> Result<Message> {
         let body = to_vec(map)?;
 
@@ -2434,7 +2596,7 @@ impl Http {
     /// Creates an auto moderation rule in a guild.
     ///
     /// This method requires `MANAGE_GUILD` permissions.
-    pub async fn create_automod_rule(&self, guild_id: u64, map: &JsonMap) -> Result<Rule> {
+    pub async fn create_automod_rule(&self, guild_id: u64, map: &impl Serialize) -> Result<Rule> {
         let body = to_vec(&map)?;
 
         self.fire(Request {
@@ -2455,7 +2617,7 @@ impl Http {
         &self,
         guild_id: u64,
         rule_id: u64,
-        map: &JsonMap,
+        map: &impl Serialize,
     ) -> Result<Rule> {
         let body = to_vec(&map)?;
 
@@ -2791,6 +2953,22 @@ impl Http {
             headers: None,
             route: RouteInfo::GetGlobalApplicationCommands {
                 application_id: self.try_application_id()?,
+                with_localizations: false,
+            },
+        })
+        .await
+    }
+
+    /// Fetches all of the global commands for your application, including
+    /// the localizations of their names and descriptions.
+    pub async fn get_global_application_commands_with_localizations(&self) -> Result<Vec<Command>> {
+        self.fire(Request {
+            body: None,
+            multipart: None,
+            headers: None,
+            route: RouteInfo::GetGlobalApplicationCommands {
+                application_id: self.try_application_id()?,
+                with_localizations: true,
             },
         })
         .await
@@ -2845,6 +3023,26 @@ impl Http {
             route: RouteInfo::GetGuildApplicationCommands {
                 application_id: self.try_application_id()?,
                 guild_id,
+                with_localizations: false,
+            },
+        })
+        .await
+    }
+
+    /// Fetches all of the guild commands for your application for a specific
+    /// guild, including the localizations of their names and descriptions.
+    pub async fn get_guild_application_commands_with_localizations(
+        &self,
+        guild_id: u64,
+    ) -> Result<Vec<Command>> {
+        self.fire(Request {
+            body: None,
+            multipart: None,
+            headers: None,
+            route: RouteInfo::GetGuildApplicationCommands {
+                application_id: self.try_application_id()?,
+                guild_id,
+                with_localizations: true,
             },
         })
         .await
@@ -3037,21 +3235,17 @@ impl Http {
     }
 
     /// Gets the amount of users that can be pruned.
-    pub async fn get_guild_prune_count(&self, guild_id: u64, map: &Value) -> Result<GuildPrune> {
-        // Note for 0.6.x: turn this into a function parameter.
-        #[derive(Deserialize)]
-        struct GetGuildPruneCountRequest {
-            days: u64,
-        }
-
-        let req = from_value::<GetGuildPruneCountRequest>(map.clone())?;
-
+    pub async fn get_guild_prune_count(
+        &self,
+        guild_id: u64,
+        query: &GetGuildPruneCount,
+    ) -> Result<GuildPrune> {
         self.fire(Request {
             body: None,
             multipart: None,
             headers: None,
             route: RouteInfo::GetGuildPruneCount {
-                days: req.days,
+                days: query.days,
                 guild_id,
             },
         })
@@ -3726,7 +3920,7 @@ impl Http {
         &self,
         channel_id: u64,
         files: It,
-        map: &JsonMap,
+        map: &impl Serialize,
     ) -> Result<Message>
     where
         T: Into<AttachmentType<'a>>,
@@ -3747,7 +3941,7 @@ impl Http {
     }
 
     /// Sends a message to a channel.
-    pub async fn send_message(&self, channel_id: u64, map: &Value) -> Result<Message> {
+    pub async fn send_message(&self, channel_id: u64, map: &impl Serialize) -> Result<Message> {
         let body = to_vec(map)?;
 
         self.fire(Request {
@@ -4005,6 +4199,9 @@ impl Http {
     /// Returns the raw reqwest Response. Use [`Self::fire`] to deserialize the response
     /// into some type.
     ///
+    /// The request is bounded by the [`RequestOptions`] of the enclosing
+    /// [`RequestOptions::scope`], falling back to the client's defaults.
+    ///
     /// # Examples
     ///
     /// Send a body of bytes over the [`RouteInfo::CreateMessage`] endpoint:
@@ -4036,14 +4233,22 @@ impl Http {
     /// # }
     /// ```
     #[instrument]
-    pub async fn request(&self, mut req: Request<'_>) -> Result<ReqwestResponse> {
-        let response = if self.ratelimiter_disabled {
-            let request =
-                req.build(&self.client, &self.token, self.proxy.as_ref()).await?.build()?;
-            self.client.execute(request).await?
-        } else {
-            let ratelimiting_req = RatelimitedRequest::from(req);
-            self.ratelimiter.perform(ratelimiting_req).await?
+    pub async fn request(&self, req: Request<'_>) -> Result<ReqwestResponse> {
+        let _guard = self.in_flight.start();
+
+        let options = match RequestOptions::scoped() {
+            Some(scoped) => scoped.or(self.request_options),
+            None => self.request_options,
+        };
+
+        // Scope the resolved options for the ratelimiter to see them.
+        let response = options.scope(self.perform(req));
+        let response = match options.timeout {
+            Some(timeout) => match tokio::time::timeout(timeout, response).await {
+                Ok(response) => response?,
+                Err(_) => return Err(Error::Http(Box::new(HttpError::Timeout(timeout)))),
+            },
+            None => response.await?,
         };
 
         if response.status().is_success() {
@@ -4053,6 +4258,34 @@ impl Http {
         }
     }
 
+    /// The number of requests made through [`Self::request`] that have not
+    /// finished yet, including requests waiting on a ratelimit.
+    #[must_use]
+    pub fn in_flight_requests(&self) -> usize {
+        self.in_flight.count()
+    }
+
+    /// Waits until no request made through [`Self::request`] is in flight.
+    ///
+    /// Requests started while waiting are waited for as well.
+    pub async fn wait_idle(&self) {
+        self.in_flight.wait_idle().await;
+    }
+
+    /// Performs a request through the ratelimiter, unless it is disabled.
+    async fn perform(&self, mut req: Request<'_>) -> Result<ReqwestResponse> {
+        if self.ratelimiter_disabled {
+            let request = req
+                .build_with_base_url(&self.client, &self.token, &self.base_url)
+                .await?
+                .build()?;
+
+            Ok(self.client.execute(request).await?)
+        } else {
+            self.ratelimiter.perform(RatelimitedRequest::from(req)).await
+        }
+    }
+
     /// Performs a request and then verifies that the response status code is equal
     /// to the expected value.
     ///
@@ -1,11 +1,13 @@
 use std::error::Error as StdError;
 use std::fmt;
+use std::time::Duration;
 
 use reqwest::header::InvalidHeaderValue;
 use reqwest::{Error as ReqwestError, Response, StatusCode, Url};
 use url::ParseError as UrlError;
 
 use crate::http::utils::deserialize_errors;
+use crate::http::LightMethod;
 
 #[derive(Clone, Deserialize, Serialize, PartialEq, Eq, Debug)]
 #[non_exhaustive]
@@ -53,6 +55,37 @@ impl ErrorResponse {
     }
 }
 
+/// A request that failed instead of waiting for a ratelimit, as set with
+/// [`RequestOptions::fail_on_ratelimit`].
+///
+/// [`RequestOptions::fail_on_ratelimit`]: super::RequestOptions::fail_on_ratelimit
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct RatelimitedError {
+    /// How long the request would have waited before being performed.
+    pub retry_after: Duration,
+    /// The method of the request.
+    pub method: LightMethod,
+    /// The path of the request.
+    pub path: String,
+    /// Whether the global ratelimit was hit, rather than the route's.
+    pub global: bool,
+}
+
+impl fmt::Display for RatelimitedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Ratelimited on {:?} {} for {}ms",
+            self.method,
+            self.path,
+            self.retry_after.as_millis()
+        )
+    }
+}
+
+impl StdError for RatelimitedError {}
+
 #[derive(Debug)]
 #[non_exhaustive]
 pub enum Error {
@@ -78,6 +111,10 @@ pub enum Error {
     InvalidPort,
     /// When an application id was expected but missing.
     ApplicationIdMissing,
+    /// When a request was ratelimited and set to fail instead of waiting.
+    Ratelimited(RatelimitedError),
+    /// When a request did not complete within its timeout.
+    Timeout(Duration),
 }
 
 impl Error {
@@ -172,6 +209,10 @@ impl fmt::Display for Error {
             Self::InvalidScheme => f.write_str("Invalid Url scheme."),
             Self::InvalidPort => f.write_str("Invalid port."),
             Self::ApplicationIdMissing => f.write_str("Application id was expected but missing."),
+            Self::Ratelimited(inner) => fmt::Display::fmt(inner, f),
+            Self::Timeout(timeout) => {
+                write!(f, "Request did not complete within {}ms.", timeout.as_millis())
+            },
         }
     }
 }
@@ -14,7 +14,10 @@
 //! The former require a [`Client`] to have logged in, while the latter may be
 //! made regardless of any other usage of the library.
 //!
-//! If a request spuriously fails, it will be retried once.
+//! Requests that fail transiently, such as with a server error, are retried
+//! according to the [`RetryPolicy`] set with [`HttpBuilder::retry_policy`].
+//! How long requests may take, and whether they wait for ratelimits, is set
+//! with [`RequestOptions`].
 //!
 //! Note that you may want to perform requests through a [model]s'
 //! instance methods where possible, as they each offer different
@@ -26,8 +29,11 @@
 pub mod client;
 pub mod error;
 pub mod multipart;
+mod options;
+pub mod payload;
 pub mod ratelimiting;
 pub mod request;
+mod retry;
 pub mod routing;
 pub mod typing;
 mod utils;
@@ -39,7 +45,9 @@ pub use reqwest::StatusCode;
 
 pub use self::client::*;
 pub use self::error::Error as HttpError;
+pub use self::options::RequestOptions;
 use self::request::Request;
+pub use self::retry::RetryPolicy;
 pub use self::typing::*;
 #[cfg(feature = "cache")]
 use crate::cache::Cache;
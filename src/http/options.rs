@@ -0,0 +1,152 @@
+use std::future::Future;
+use std::time::Duration;
+
+tokio::task_local! {
+    static SCOPED_OPTIONS: RequestOptions;
+}
+
+/// Options bounding how long a request made by [`Http`] may take.
+///
+/// Options apply to the requests made within a [`Self::scope`], which allows
+/// overriding them for a single call, such as [`ChannelId::say`]. Each field
+/// that is not set by the innermost scope falls back to the outer scopes,
+/// and then to the client's defaults, set with
+/// [`HttpBuilder::request_options`].
+///
+/// By default, requests have no timeout and wait for ratelimits to reset.
+///
+/// # Examples
+///
+/// Failing fast instead of waiting for a ratelimit while replying to a
+/// command, and giving up after 3 seconds:
+///
+/// ```rust,no_run
+/// # use serenity::http::Http;
+/// # use serenity::model::id::ChannelId;
+/// #
+/// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+/// #     let http = Http::new("token");
+/// #     let channel_id = ChannelId(7);
+/// use std::time::Duration;
+///
+/// use serenity::http::{HttpError, RequestOptions};
+/// use serenity::Error;
+///
+/// let options = RequestOptions::new().timeout(Duration::from_secs(3)).fail_on_ratelimit(true);
+///
+/// match options.scope(channel_id.say(&http, "Pong!")).await {
+///     Err(Error::Http(why)) if matches!(*why, HttpError::Ratelimited(_)) => {
+///         println!("Too busy to reply: {}", why);
+///     },
+///     result => {
+///         result?;
+///     },
+/// }
+/// #     Ok(())
+/// # }
+/// ```
+///
+/// [`Http`]: super::Http
+/// [`ChannelId::say`]: crate::model::id::ChannelId::say
+/// [`HttpBuilder::request_options`]: super::HttpBuilder::request_options
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[must_use]
+pub struct RequestOptions {
+    pub(super) timeout: Option<Duration>,
+    pub(super) fail_on_ratelimit: Option<bool>,
+}
+
+impl RequestOptions {
+    /// Creates options that leave every field to the less specific options.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum time a request may take until its response is
+    /// received, including the time spent waiting for ratelimits and before
+    /// retries. Requests that take longer fail with [`HttpError::Timeout`].
+    ///
+    /// [`HttpError::Timeout`]: super::HttpError::Timeout
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+
+        self
+    }
+
+    /// Sets whether a request fails with [`HttpError::Ratelimited`] instead of
+    /// waiting when its route is ratelimited, or when Discord responds that it
+    /// is.
+    ///
+    /// [`HttpError::Ratelimited`]: super::HttpError::Ratelimited
+    pub fn fail_on_ratelimit(mut self, fail_on_ratelimit: bool) -> Self {
+        self.fail_on_ratelimit = Some(fail_on_ratelimit);
+
+        self
+    }
+
+    /// Runs the future with these options applied to every request it makes,
+    /// including those made through high-level methods such as
+    /// [`ChannelId::say`].
+    ///
+    /// Scopes can be nested, in which case the options of the inner scope
+    /// take precedence over those of the outer one.
+    ///
+    /// **Note**: The options only apply to requests made by the future itself,
+    /// not by tasks it spawns.
+    ///
+    /// [`ChannelId::say`]: crate::model::id::ChannelId::say
+    pub async fn scope<F: Future>(self, future: F) -> F::Output {
+        let options = match Self::scoped() {
+            Some(outer) => self.or(outer),
+            None => self,
+        };
+
+        SCOPED_OPTIONS.scope(options, future).await
+    }
+
+    /// The options of the innermost scope the current task is in, if any.
+    pub(super) fn scoped() -> Option<Self> {
+        SCOPED_OPTIONS.try_with(|options| *options).ok()
+    }
+
+    /// Fills the fields that are not set with those of the given options.
+    pub(super) fn or(self, other: Self) -> Self {
+        Self {
+            timeout: self.timeout.or(other.timeout),
+            fail_on_ratelimit: self.fail_on_ratelimit.or(other.fail_on_ratelimit),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::RequestOptions;
+
+    #[test]
+    fn test_or() {
+        let request = RequestOptions::new().fail_on_ratelimit(true);
+        let defaults =
+            RequestOptions::new().timeout(Duration::from_secs(5)).fail_on_ratelimit(false);
+
+        let options = request.or(defaults);
+        assert_eq!(options.timeout, Some(Duration::from_secs(5)));
+        assert_eq!(options.fail_on_ratelimit, Some(true));
+    }
+
+    #[tokio::test]
+    async fn test_scope() {
+        assert_eq!(RequestOptions::scoped(), None);
+
+        let outer = RequestOptions::new().timeout(Duration::from_secs(5));
+        let inner = RequestOptions::new().fail_on_ratelimit(true);
+
+        let options = outer.scope(inner.scope(async { RequestOptions::scoped() })).await;
+        assert_eq!(options, Some(inner.or(outer)));
+
+        let options = outer.scope(async { RequestOptions::scoped() }).await;
+        assert_eq!(options, Some(outer));
+    }
+}
@@ -0,0 +1,323 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use super::{AllowedMentions, Embed};
+use crate::json::Value;
+use crate::model::application::command::{CommandOptionType, CommandPermissionType, CommandType};
+use crate::model::application::component::ActionRow;
+use crate::model::application::interaction::MessageFlags;
+use crate::model::prelude::*;
+
+/// The body of [`Http::create_interaction_response`], or the `payload_json`
+/// of [`Http::create_interaction_response_with_files`].
+///
+/// [Discord docs](https://discord.com/developers/docs/interactions/receiving-and-responding#interaction-response-object).
+///
+/// [`Http::create_interaction_response`]: crate::http::Http::create_interaction_response
+/// [`Http::create_interaction_response_with_files`]: crate::http::Http::create_interaction_response_with_files
+#[derive(Clone, Debug, Serialize)]
+pub struct InteractionResponse {
+    /// The type of the response.
+    #[serde(rename = "type")]
+    pub kind: InteractionResponseType,
+    /// The data of the response, which depends on its type.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<InteractionResponseData>,
+}
+
+/// The data of an [`InteractionResponse`].
+#[derive(Clone, Debug, Serialize)]
+#[serde(untagged)]
+pub enum InteractionResponseData {
+    /// A message, for [`InteractionResponseType::ChannelMessageWithSource`]
+    /// and [`InteractionResponseType::UpdateMessage`], or the flags of the
+    /// deferred responses.
+    Message(InteractionMessage),
+    /// The choices of an [`InteractionResponseType::Autocomplete`].
+    Autocomplete(AutocompleteChoices),
+    /// A popup form, for [`InteractionResponseType::Modal`].
+    Modal(Modal),
+}
+
+/// A message sent in response to an interaction.
+///
+/// [Discord docs](https://discord.com/developers/docs/interactions/receiving-and-responding#interaction-response-object-messages).
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct InteractionMessage {
+    /// Whether the message is read aloud with text-to-speech.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tts: Option<bool>,
+    /// The content of the message, of up to 2000 characters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    /// The embeds of the message, of up to 10.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub embeds: Option<Vec<Embed>>,
+    /// Which mentions of the message notify the mentioned users.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allowed_mentions: Option<AllowedMentions>,
+    /// The flags of the message, such as [`MessageFlags::EPHEMERAL`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flags: Option<MessageFlags>,
+    /// The components of the message, of up to 5 action rows.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub components: Option<Vec<ActionRow>>,
+}
+
+/// The choices of an autocomplete response.
+///
+/// [Discord docs](https://discord.com/developers/docs/interactions/receiving-and-responding#interaction-response-object-autocomplete).
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct AutocompleteChoices {
+    /// The suggested choices, of up to 25.
+    pub choices: Vec<CommandOptionChoice>,
+}
+
+/// A popup form shown in response to an interaction.
+///
+/// [Discord docs](https://discord.com/developers/docs/interactions/receiving-and-responding#interaction-response-object-modal).
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct Modal {
+    /// An identifier defined by the developer for the modal.
+    pub custom_id: String,
+    /// The title of the modal, of up to 45 characters.
+    pub title: String,
+    /// The components of the modal, of 1 to 5 action rows.
+    pub components: Vec<Value>,
+}
+
+/// A choice of a command option, or of an autocomplete response.
+///
+/// [Discord docs](https://discord.com/developers/docs/interactions/application-commands#application-command-object-application-command-option-choice-structure).
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct CommandOptionChoice {
+    /// The name of the choice, of 1 to 100 characters.
+    pub name: String,
+    /// The name of the choice in other locales.
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub name_localizations: HashMap<String, String>,
+    /// The value of the choice: a string, an integer or a number, according
+    /// to the type of the option.
+    pub value: Value,
+}
+
+/// The body of [`Http::create_global_application_command`] and the other
+/// endpoints creating and editing application commands, which take one or a
+/// list of them.
+///
+/// [Discord docs](https://discord.com/developers/docs/interactions/application-commands#create-global-application-command-json-params).
+///
+/// [`Http::create_global_application_command`]: crate::http::Http::create_global_application_command
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct CreateCommand {
+    /// The name of the command, of 1 to 32 characters.
+    pub name: String,
+    /// The name of the command in other locales.
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub name_localizations: HashMap<String, String>,
+    /// The description of the command, of 1 to 100 characters. Must be empty
+    /// for user and message commands.
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub description: String,
+    /// The description of the command in other locales.
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub description_localizations: HashMap<String, String>,
+    /// The type of the command, defaulting to a slash command.
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub kind: Option<CommandType>,
+    /// The options of the command, of up to 25.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub options: Vec<CommandOption>,
+    /// The permissions a member needs to use the command, unless overridden
+    /// in the guild.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_member_permissions: Option<Permissions>,
+    /// Whether a global command can be used in direct messages.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dm_permission: Option<bool>,
+}
+
+/// An option of a [`CreateCommand`].
+///
+/// [Discord docs](https://discord.com/developers/docs/interactions/application-commands#application-command-object-application-command-option-structure).
+#[derive(Clone, Debug, Serialize)]
+pub struct CommandOption {
+    /// The type of the option.
+    #[serde(rename = "type")]
+    pub kind: CommandOptionType,
+    /// The name of the option, of 1 to 32 characters.
+    pub name: String,
+    /// The name of the option in other locales.
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub name_localizations: HashMap<String, String>,
+    /// The description of the option, of 1 to 100 characters.
+    pub description: String,
+    /// The description of the option in other locales.
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub description_localizations: HashMap<String, String>,
+    /// Whether the option must be given.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required: Option<bool>,
+    /// The only values that can be given, of up to 25.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub choices: Vec<CommandOptionChoice>,
+    /// The options of a subcommand or subcommand group.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub options: Vec<CommandOption>,
+    /// The types of channels that can be given.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub channel_types: Vec<ChannelType>,
+    /// The minimum value of an integer or number option.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_value: Option<Value>,
+    /// The maximum value of an integer or number option.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_value: Option<Value>,
+    /// The minimum length of a string option.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_length: Option<u16>,
+    /// The maximum length of a string option.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<u16>,
+    /// Whether the values of the option are suggested with autocomplete
+    /// interactions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub autocomplete: Option<bool>,
+}
+
+impl CommandOption {
+    /// Creates an optional option with the given required fields.
+    #[must_use]
+    pub fn new(
+        kind: CommandOptionType,
+        name: impl Into<String>,
+        description: impl Into<String>,
+    ) -> Self {
+        Self {
+            kind,
+            name: name.into(),
+            name_localizations: HashMap::new(),
+            description: description.into(),
+            description_localizations: HashMap::new(),
+            required: None,
+            choices: Vec::new(),
+            options: Vec::new(),
+            channel_types: Vec::new(),
+            min_value: None,
+            max_value: None,
+            min_length: None,
+            max_length: None,
+            autocomplete: None,
+        }
+    }
+}
+
+/// A permission of a role, user or channel to use a command.
+///
+/// [Discord docs](https://discord.com/developers/docs/interactions/application-commands#application-command-permissions-object-application-command-permissions-structure).
+#[derive(Clone, Debug, Serialize)]
+pub struct CommandPermission {
+    /// The Id of the role, user or channel.
+    pub id: CommandPermissionId,
+    /// What the Id refers to.
+    #[serde(rename = "type")]
+    pub kind: CommandPermissionType,
+    /// Whether the command can be used.
+    pub permission: bool,
+}
+
+/// The body of [`Http::edit_guild_application_command_permissions`].
+///
+/// [Discord docs](https://discord.com/developers/docs/interactions/application-commands#edit-application-command-permissions-json-params).
+///
+/// [`Http::edit_guild_application_command_permissions`]: crate::http::Http::edit_guild_application_command_permissions
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct EditCommandPermissions {
+    /// The permissions of the command, replacing the current ones.
+    pub permissions: Vec<CommandPermission>,
+}
+
+/// The permissions of a command, for
+/// [`Http::edit_guild_application_commands_permissions`], which takes a list
+/// of them.
+///
+/// [`Http::edit_guild_application_commands_permissions`]: crate::http::Http::edit_guild_application_commands_permissions
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct GuildCommandPermissions {
+    /// The Id of the command.
+    pub id: CommandId,
+    /// The permissions of the command, replacing the current ones.
+    pub permissions: Vec<CommandPermission>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        CommandOption,
+        CommandOptionChoice,
+        CreateCommand,
+        InteractionMessage,
+        InteractionResponse,
+        InteractionResponseData,
+    };
+    use crate::json::{json, to_value};
+    use crate::model::application::command::CommandOptionType;
+    use crate::model::application::interaction::MessageFlags;
+    use crate::model::prelude::*;
+
+    #[test]
+    fn test_interaction_response() {
+        let payload = InteractionResponse {
+            kind: InteractionResponseType::ChannelMessageWithSource,
+            data: Some(InteractionResponseData::Message(InteractionMessage {
+                content: Some("Pong!".to_string()),
+                flags: Some(MessageFlags::EPHEMERAL),
+                ..Default::default()
+            })),
+        };
+
+        assert_eq!(
+            to_value(&payload).unwrap(),
+            json!({
+                "type": 4,
+                "data": {"content": "Pong!", "flags": 64},
+            })
+        );
+    }
+
+    #[test]
+    fn test_create_command() {
+        let payload = CreateCommand {
+            name: "roll".to_string(),
+            description: "Rolls a die".to_string(),
+            options: vec![CommandOption {
+                required: Some(true),
+                choices: vec![CommandOptionChoice {
+                    name: "Six".to_string(),
+                    value: json!(6),
+                    ..Default::default()
+                }],
+                ..CommandOption::new(CommandOptionType::Integer, "sides", "The number of sides")
+            }],
+            default_member_permissions: Some(Permissions::SEND_MESSAGES),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            to_value(&payload).unwrap(),
+            json!({
+                "name": "roll",
+                "description": "Rolls a die",
+                "options": [{
+                    "type": 4,
+                    "name": "sides",
+                    "description": "The number of sides",
+                    "required": true,
+                    "choices": [{"name": "Six", "value": 6}],
+                }],
+                "default_member_permissions": "2048",
+            })
+        );
+    }
+}
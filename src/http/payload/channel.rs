@@ -0,0 +1,316 @@
+use serde::Serialize;
+
+use crate::model::prelude::*;
+
+/// The body of [`Http::create_channel`].
+///
+/// [Discord docs](https://discord.com/developers/docs/resources/guild#create-guild-channel-json-params).
+///
+/// [`Http::create_channel`]: crate::http::Http::create_channel
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct CreateChannel {
+    /// The name of the channel, of 1 to 100 characters.
+    pub name: String,
+    /// The type of the channel, defaulting to a text channel.
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub kind: Option<ChannelType>,
+    /// The topic of the channel, of 0 to 1024 characters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub topic: Option<String>,
+    /// The bitrate of a voice channel, in bits per second.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bitrate: Option<u32>,
+    /// The maximum number of users in a voice channel, where `0` is
+    /// unlimited.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_limit: Option<u32>,
+    /// The number of seconds a user has to wait between sending messages.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rate_limit_per_user: Option<u64>,
+    /// The sorting position of the channel.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub position: Option<u32>,
+    /// The permission overwrites of the channel.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub permission_overwrites: Vec<PermissionOverwrite>,
+    /// The category to create the channel in.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<ChannelId>,
+    /// Whether the channel is age-restricted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nsfw: Option<bool>,
+}
+
+/// The body of [`Http::edit_channel`].
+///
+/// [Discord docs](https://discord.com/developers/docs/resources/channel#modify-channel-json-params-guild-channel).
+///
+/// [`Http::edit_channel`]: crate::http::Http::edit_channel
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct EditChannel {
+    /// The name of the channel, of 1 to 100 characters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// The type of the channel. Only conversions between text and news
+    /// channels are supported.
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub kind: Option<ChannelType>,
+    /// The sorting position of the channel.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub position: Option<u32>,
+    /// The topic of the channel, of 0 to 1024 characters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub topic: Option<Option<String>>,
+    /// Whether the channel is age-restricted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nsfw: Option<bool>,
+    /// The number of seconds a user has to wait between sending messages.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rate_limit_per_user: Option<u64>,
+    /// The bitrate of a voice channel, in bits per second.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bitrate: Option<u32>,
+    /// The maximum number of users in a voice channel, where `0` is
+    /// unlimited.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_limit: Option<u32>,
+    /// The permission overwrites of the channel, replacing the current ones.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub permission_overwrites: Option<Vec<PermissionOverwrite>>,
+    /// The category of the channel.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<Option<ChannelId>>,
+    /// The voice region of a voice channel, where `None` is automatic.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rtc_region: Option<Option<String>>,
+    /// The video quality of a voice channel.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub video_quality_mode: Option<VideoQualityMode>,
+}
+
+/// A new position of a channel, for [`Http::edit_guild_channel_positions`],
+/// which takes a list of them.
+///
+/// [Discord docs](https://discord.com/developers/docs/resources/guild#modify-guild-channel-positions-json-params).
+///
+/// [`Http::edit_guild_channel_positions`]: crate::http::Http::edit_guild_channel_positions
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct ChannelPosition {
+    /// The Id of the channel.
+    pub id: ChannelId,
+    /// The sorting position of the channel.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub position: Option<u64>,
+    /// Whether to sync the permission overwrites with the new category.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lock_permissions: Option<bool>,
+    /// The new category of the channel.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<Option<ChannelId>>,
+}
+
+/// The body of [`Http::create_public_thread`] and
+/// [`Http::create_private_thread`].
+///
+/// [Discord docs](https://discord.com/developers/docs/resources/channel#start-thread-without-message-json-params).
+///
+/// [`Http::create_public_thread`]: crate::http::Http::create_public_thread
+/// [`Http::create_private_thread`]: crate::http::Http::create_private_thread
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct CreateThread {
+    /// The name of the thread, of 1 to 100 characters.
+    pub name: String,
+    /// The number of minutes of inactivity after which the thread is
+    /// archived: 60, 1440, 4320 or 10080.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto_archive_duration: Option<u16>,
+    /// The type of the thread, when not created from a message.
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub kind: Option<ChannelType>,
+    /// Whether non-moderators can add other non-moderators to a private
+    /// thread.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub invitable: Option<bool>,
+    /// The number of seconds a user has to wait between sending messages.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rate_limit_per_user: Option<u64>,
+}
+
+/// The body of [`Http::edit_thread`].
+///
+/// [Discord docs](https://discord.com/developers/docs/resources/channel#modify-channel-json-params-thread).
+///
+/// [`Http::edit_thread`]: crate::http::Http::edit_thread
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct EditThread {
+    /// The name of the thread, of 1 to 100 characters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Whether the thread is archived.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archived: Option<bool>,
+    /// The number of minutes of inactivity after which the thread is
+    /// archived: 60, 1440, 4320 or 10080.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto_archive_duration: Option<u16>,
+    /// Whether the thread is locked, so that only moderators can unarchive
+    /// it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locked: Option<bool>,
+    /// Whether non-moderators can add other non-moderators to a private
+    /// thread.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub invitable: Option<bool>,
+    /// The number of seconds a user has to wait between sending messages.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rate_limit_per_user: Option<u64>,
+}
+
+/// The body of [`Http::create_invite`].
+///
+/// [Discord docs](https://discord.com/developers/docs/resources/channel#create-channel-invite-json-params).
+///
+/// [`Http::create_invite`]: crate::http::Http::create_invite
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct CreateInvite {
+    /// The number of seconds until the invite expires, where `0` is never.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_age: Option<u64>,
+    /// The maximum number of uses, where `0` is unlimited.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_uses: Option<u64>,
+    /// Whether the invite only grants temporary membership.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temporary: Option<bool>,
+    /// Whether to always create a new invite, rather than reusing a similar
+    /// one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unique: Option<bool>,
+    /// The type of target of an invite to a voice channel.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_type: Option<InviteTargetType>,
+    /// The user whose stream to show, for [`InviteTargetType::Stream`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_user_id: Option<UserId>,
+    /// The embedded application to open, for
+    /// [`InviteTargetType::EmmbeddedApplication`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_application_id: Option<ApplicationId>,
+}
+
+/// The body of [`Http::delete_messages`].
+///
+/// [Discord docs](https://discord.com/developers/docs/resources/channel#bulk-delete-messages-json-params).
+///
+/// [`Http::delete_messages`]: crate::http::Http::delete_messages
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct DeleteMessages {
+    /// The messages to delete, from 2 to 100 of them.
+    pub messages: Vec<MessageId>,
+}
+
+/// The body of [`Http::create_stage_instance`].
+///
+/// [Discord docs](https://discord.com/developers/docs/resources/stage-instance#create-stage-instance-json-params).
+///
+/// [`Http::create_stage_instance`]: crate::http::Http::create_stage_instance
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct CreateStageInstance {
+    /// The stage channel to start the instance in.
+    pub channel_id: ChannelId,
+    /// The topic of the stage instance, of 1 to 120 characters.
+    pub topic: String,
+}
+
+/// The body of [`Http::edit_stage_instance`].
+///
+/// [Discord docs](https://discord.com/developers/docs/resources/stage-instance#modify-stage-instance-json-params).
+///
+/// [`Http::edit_stage_instance`]: crate::http::Http::edit_stage_instance
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct EditStageInstance {
+    /// The topic of the stage instance, of 1 to 120 characters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub topic: Option<String>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ChannelPosition, CreateChannel, EditChannel};
+    use crate::json::{json, to_value};
+    use crate::model::prelude::*;
+
+    #[test]
+    fn test_create_channel() {
+        let payload = CreateChannel {
+            name: "general".to_string(),
+            kind: Some(ChannelType::Voice),
+            permission_overwrites: vec![PermissionOverwrite {
+                allow: Permissions::CONNECT,
+                deny: Permissions::empty(),
+                kind: PermissionOverwriteType::Role(RoleId(3)),
+            }],
+            parent_id: Some(ChannelId(7)),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            to_value(&payload).unwrap(),
+            json!({
+                "name": "general",
+                "type": 2,
+                "permission_overwrites": [{
+                    "allow": "1048576",
+                    "deny": "0",
+                    "id": "3",
+                    "type": 0,
+                }],
+                "parent_id": "7",
+            })
+        );
+    }
+
+    #[test]
+    fn test_edit_channel_clears_fields() {
+        let payload = EditChannel {
+            name: Some("general".to_string()),
+            topic: Some(None),
+            parent_id: Some(None),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            to_value(&payload).unwrap(),
+            json!({
+                "name": "general",
+                "topic": null,
+                "parent_id": null,
+            })
+        );
+    }
+
+    #[test]
+    fn test_channel_positions() {
+        let payload = vec![
+            ChannelPosition {
+                id: ChannelId(1),
+                position: Some(0),
+                ..Default::default()
+            },
+            ChannelPosition {
+                id: ChannelId(2),
+                lock_permissions: Some(true),
+                parent_id: Some(Some(ChannelId(3))),
+                ..Default::default()
+            },
+        ];
+
+        assert_eq!(
+            to_value(&payload).unwrap(),
+            json!([
+                {"id": "1", "position": 0},
+                {"id": "2", "lock_permissions": true, "parent_id": "3"},
+            ])
+        );
+    }
+}
@@ -0,0 +1,591 @@
+use serde::Serialize;
+
+use crate::model::guild::automod::{Action, EventType, Trigger};
+use crate::model::prelude::*;
+
+/// The body of [`Http::create_guild`].
+///
+/// [Discord docs](https://discord.com/developers/docs/resources/guild#create-guild-json-params).
+///
+/// [`Http::create_guild`]: crate::http::Http::create_guild
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct CreateGuild {
+    /// The name of the guild, of 2 to 100 characters.
+    pub name: String,
+    /// The icon of the guild, as a data URI such as those returned by
+    /// [`utils::read_image`].
+    ///
+    /// [`utils::read_image`]: crate::utils::read_image
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+}
+
+/// The body of [`Http::edit_guild`].
+///
+/// Images are data URIs such as those returned by [`utils::read_image`].
+///
+/// [Discord docs](https://discord.com/developers/docs/resources/guild#modify-guild-json-params).
+///
+/// [`Http::edit_guild`]: crate::http::Http::edit_guild
+/// [`utils::read_image`]: crate::utils::read_image
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct EditGuild {
+    /// The name of the guild, of 2 to 100 characters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// The verification level required to send messages.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verification_level: Option<VerificationLevel>,
+    /// The default notification level of members.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_message_notifications: Option<DefaultMessageNotificationLevel>,
+    /// Whose messages are scanned for explicit content.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explicit_content_filter: Option<ExplicitContentFilter>,
+    /// The channel that idle members are moved to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub afk_channel_id: Option<Option<ChannelId>>,
+    /// The number of seconds after which members are considered idle.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub afk_timeout: Option<u64>,
+    /// The icon of the guild.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon: Option<Option<String>>,
+    /// The user to transfer ownership of the guild to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner_id: Option<UserId>,
+    /// The invite splash of the guild.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub splash: Option<Option<String>>,
+    /// The discovery splash of the guild.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub discovery_splash: Option<Option<String>>,
+    /// The banner of the guild.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub banner: Option<Option<String>>,
+    /// The channel that system messages are sent to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub system_channel_id: Option<Option<ChannelId>>,
+    /// The system messages that are not sent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub system_channel_flags: Option<SystemChannelFlags>,
+    /// The channel showing the rules of a community guild.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rules_channel_id: Option<Option<ChannelId>>,
+    /// The channel that notices from Discord are sent to, in a community
+    /// guild.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub public_updates_channel_id: Option<Option<ChannelId>>,
+    /// The locale of a community guild.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preferred_locale: Option<Option<String>>,
+    /// The features of the guild, replacing the current ones.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub features: Option<Vec<String>>,
+    /// The description of the guild.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<Option<String>>,
+    /// Whether the progress bar of server boosts is shown.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub premium_progress_bar_enabled: Option<bool>,
+}
+
+/// The body of [`Http::create_role`] and [`Http::edit_role`].
+///
+/// [Discord docs](https://discord.com/developers/docs/resources/guild#modify-guild-role-json-params).
+///
+/// [`Http::create_role`]: crate::http::Http::create_role
+/// [`Http::edit_role`]: crate::http::Http::edit_role
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct EditRole {
+    /// The name of the role.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// The permissions granted by the role.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub permissions: Option<Permissions>,
+    /// The colour of the role, as an RGB value.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<u32>,
+    /// Whether the members of the role are shown separately in the member
+    /// list.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hoist: Option<bool>,
+    /// The icon of the role, as a data URI such as those returned by
+    /// [`utils::read_image`].
+    ///
+    /// [`utils::read_image`]: crate::utils::read_image
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon: Option<Option<String>>,
+    /// The unicode emoji shown as the icon of the role.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unicode_emoji: Option<Option<String>>,
+    /// Whether the role can be mentioned by everyone.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mentionable: Option<bool>,
+}
+
+/// The body of [`Http::add_guild_member`].
+///
+/// [Discord docs](https://discord.com/developers/docs/resources/guild#add-guild-member-json-params).
+///
+/// [`Http::add_guild_member`]: crate::http::Http::add_guild_member
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct AddGuildMember {
+    /// An OAuth2 access token of the user, granted with the `guilds.join`
+    /// scope.
+    pub access_token: String,
+    /// The nickname of the member.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nick: Option<String>,
+    /// The roles given to the member.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub roles: Vec<RoleId>,
+    /// Whether the member is muted in voice channels.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mute: Option<bool>,
+    /// Whether the member is deafened in voice channels.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deaf: Option<bool>,
+}
+
+/// The body of [`Http::edit_member`].
+///
+/// [Discord docs](https://discord.com/developers/docs/resources/guild#modify-guild-member-json-params).
+///
+/// [`Http::edit_member`]: crate::http::Http::edit_member
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct EditMember {
+    /// The nickname of the member.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nick: Option<Option<String>>,
+    /// The roles of the member, replacing the current ones.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub roles: Option<Vec<RoleId>>,
+    /// Whether the member is muted in voice channels.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mute: Option<bool>,
+    /// Whether the member is deafened in voice channels.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deaf: Option<bool>,
+    /// The voice channel to move the member to, or `None` to disconnect
+    /// them.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel_id: Option<Option<ChannelId>>,
+    /// When the timeout of the member ends, or `None` to remove it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub communication_disabled_until: Option<Option<Timestamp>>,
+}
+
+/// The body of [`Http::edit_member_me`].
+///
+/// [Discord docs](https://discord.com/developers/docs/resources/guild#modify-current-member-json-params).
+///
+/// [`Http::edit_member_me`]: crate::http::Http::edit_member_me
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct EditCurrentMember {
+    /// The nickname of the current user.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nick: Option<Option<String>>,
+}
+
+/// The body of [`Http::create_emoji`].
+///
+/// [Discord docs](https://discord.com/developers/docs/resources/emoji#create-guild-emoji-json-params).
+///
+/// [`Http::create_emoji`]: crate::http::Http::create_emoji
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct CreateEmoji {
+    /// The name of the emoji.
+    pub name: String,
+    /// The image of the emoji, as a data URI such as those returned by
+    /// [`utils::read_image`].
+    ///
+    /// [`utils::read_image`]: crate::utils::read_image
+    pub image: String,
+    /// The roles allowed to use the emoji, where none allows everyone.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub roles: Vec<RoleId>,
+}
+
+/// The body of [`Http::edit_emoji`].
+///
+/// [Discord docs](https://discord.com/developers/docs/resources/emoji#modify-guild-emoji-json-params).
+///
+/// [`Http::edit_emoji`]: crate::http::Http::edit_emoji
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct EditEmoji {
+    /// The name of the emoji.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// The roles allowed to use the emoji, where none allows everyone.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub roles: Option<Vec<RoleId>>,
+}
+
+/// The body of [`Http::edit_sticker`].
+///
+/// [Discord docs](https://discord.com/developers/docs/resources/sticker#modify-guild-sticker-json-params).
+///
+/// [`Http::edit_sticker`]: crate::http::Http::edit_sticker
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct EditSticker {
+    /// The name of the sticker, of 2 to 30 characters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// The description of the sticker, of 2 to 100 characters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<Option<String>>,
+    /// Comma-separated keywords for autocompletion, of up to 200 characters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<String>,
+}
+
+/// The body of [`Http::edit_guild_widget`].
+///
+/// [Discord docs](https://discord.com/developers/docs/resources/guild#modify-guild-widget).
+///
+/// [`Http::edit_guild_widget`]: crate::http::Http::edit_guild_widget
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct EditGuildWidget {
+    /// Whether the widget is enabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    /// The channel that the widget invites to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel_id: Option<Option<ChannelId>>,
+}
+
+/// The body of [`Http::edit_guild_welcome_screen`].
+///
+/// [Discord docs](https://discord.com/developers/docs/resources/guild#modify-guild-welcome-screen-json-params).
+///
+/// [`Http::edit_guild_welcome_screen`]: crate::http::Http::edit_guild_welcome_screen
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct EditGuildWelcomeScreen {
+    /// Whether the welcome screen is enabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    /// The channels shown in the welcome screen, of up to 5, replacing the
+    /// current ones.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub welcome_channels: Option<Vec<WelcomeChannel>>,
+    /// The description of the guild shown in the welcome screen.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<Option<String>>,
+}
+
+/// A channel shown in the welcome screen, for [`EditGuildWelcomeScreen`].
+///
+/// [Discord docs](https://discord.com/developers/docs/resources/guild#welcome-screen-object-welcome-screen-channel-structure).
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct WelcomeChannel {
+    /// The Id of the channel.
+    pub channel_id: ChannelId,
+    /// The description shown for the channel.
+    pub description: String,
+    /// The Id of the custom emoji shown for the channel.
+    pub emoji_id: Option<EmojiId>,
+    /// The name of the custom emoji, or the unicode emoji, shown for the
+    /// channel.
+    pub emoji_name: Option<String>,
+}
+
+/// The query of [`Http::get_guild_prune_count`].
+///
+/// [Discord docs](https://discord.com/developers/docs/resources/guild#get-guild-prune-count-query-string-params).
+///
+/// [`Http::get_guild_prune_count`]: crate::http::Http::get_guild_prune_count
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct GetGuildPruneCount {
+    /// The number of days of inactivity to count members for.
+    pub days: u64,
+}
+
+/// The body of [`Http::create_guild_integration`].
+///
+/// [`Http::create_guild_integration`]: crate::http::Http::create_guild_integration
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct CreateGuildIntegration {
+    /// The Id of the integration.
+    pub id: IntegrationId,
+    /// The type of the integration, such as `"twitch"` or `"youtube"`.
+    #[serde(rename = "type")]
+    pub kind: String,
+}
+
+/// The body of [`Http::edit_voice_state`] and [`Http::edit_voice_state_me`].
+///
+/// [Discord docs](https://discord.com/developers/docs/resources/guild#modify-current-user-voice-state-json-params).
+///
+/// [`Http::edit_voice_state`]: crate::http::Http::edit_voice_state
+/// [`Http::edit_voice_state_me`]: crate::http::Http::edit_voice_state_me
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct EditVoiceState {
+    /// The stage channel that the user is in.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel_id: Option<ChannelId>,
+    /// Whether the user is suppressed, being unable to speak.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suppress: Option<bool>,
+    /// When the current user requested to speak, or `None` to withdraw the
+    /// request. Only valid for the current user.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_to_speak_timestamp: Option<Option<Timestamp>>,
+}
+
+/// The body of [`Http::create_scheduled_event`].
+///
+/// [Discord docs](https://discord.com/developers/docs/resources/guild-scheduled-event#create-guild-scheduled-event-json-params).
+///
+/// [`Http::create_scheduled_event`]: crate::http::Http::create_scheduled_event
+#[derive(Clone, Debug, Serialize)]
+pub struct CreateScheduledEvent {
+    /// The name of the event, of 1 to 100 characters.
+    pub name: String,
+    /// The type of the event.
+    pub entity_type: ScheduledEventType,
+    /// The privacy level of the event, where `2` is only visible to the
+    /// guild members and the only level currently supported.
+    pub privacy_level: u8,
+    /// When the event starts.
+    pub scheduled_start_time: Timestamp,
+    /// When the event ends. Required for external events.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scheduled_end_time: Option<Timestamp>,
+    /// The stage or voice channel of the event. Required for events that are
+    /// not external.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel_id: Option<ChannelId>,
+    /// The location of an external event.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entity_metadata: Option<ScheduledEventMetadata>,
+    /// The description of the event, of 1 to 1000 characters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// The cover image of the event, as a data URI such as those returned by
+    /// [`utils::read_image`].
+    ///
+    /// [`utils::read_image`]: crate::utils::read_image
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+}
+
+impl CreateScheduledEvent {
+    /// Creates an event that is only visible to the guild members, with the
+    /// given required fields.
+    #[must_use]
+    pub fn new(
+        name: impl Into<String>,
+        entity_type: ScheduledEventType,
+        scheduled_start_time: Timestamp,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            entity_type,
+            privacy_level: 2,
+            scheduled_start_time,
+            scheduled_end_time: None,
+            channel_id: None,
+            entity_metadata: None,
+            description: None,
+            image: None,
+        }
+    }
+}
+
+/// The body of [`Http::edit_scheduled_event`].
+///
+/// When changing the [`entity_type`] to [`ScheduledEventType::External`], the
+/// [`channel_id`] must be cleared, and the [`entity_metadata`] and
+/// [`scheduled_end_time`] set.
+///
+/// [Discord docs](https://discord.com/developers/docs/resources/guild-scheduled-event#modify-guild-scheduled-event-json-params).
+///
+/// [`Http::edit_scheduled_event`]: crate::http::Http::edit_scheduled_event
+/// [`entity_type`]: #structfield.entity_type
+/// [`channel_id`]: #structfield.channel_id
+/// [`entity_metadata`]: #structfield.entity_metadata
+/// [`scheduled_end_time`]: #structfield.scheduled_end_time
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct EditScheduledEvent {
+    /// The name of the event, of 1 to 100 characters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// The type of the event.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entity_type: Option<ScheduledEventType>,
+    /// The status of the event, to start, end or cancel it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<ScheduledEventStatus>,
+    /// When the event starts.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scheduled_start_time: Option<Timestamp>,
+    /// When the event ends.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scheduled_end_time: Option<Timestamp>,
+    /// The stage or voice channel of the event.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel_id: Option<Option<ChannelId>>,
+    /// The location of an external event.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entity_metadata: Option<Option<ScheduledEventMetadata>>,
+    /// The description of the event, of 1 to 1000 characters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<Option<String>>,
+    /// The cover image of the event, as a data URI such as those returned by
+    /// [`utils::read_image`].
+    ///
+    /// [`utils::read_image`]: crate::utils::read_image
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+}
+
+/// The body of [`Http::create_automod_rule`].
+///
+/// [Discord docs](https://discord.com/developers/docs/resources/auto-moderation#create-auto-moderation-rule-json-params).
+///
+/// [`Http::create_automod_rule`]: crate::http::Http::create_automod_rule
+#[derive(Clone, Debug, Serialize)]
+pub struct CreateAutoModRule {
+    /// The name of the rule.
+    pub name: String,
+    /// The event in which the rule is checked.
+    pub event_type: EventType,
+    /// What triggers the rule, along with its metadata.
+    #[serde(flatten)]
+    pub trigger: Trigger,
+    /// The actions executed when the rule is triggered.
+    pub actions: Vec<Action>,
+    /// Whether the rule is enabled, defaulting to `false`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    /// The roles that are not affected by the rule, of up to 20.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub exempt_roles: Vec<RoleId>,
+    /// The channels that are not affected by the rule, of up to 50.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub exempt_channels: Vec<ChannelId>,
+}
+
+impl CreateAutoModRule {
+    /// Creates a rule checking sent messages, with the given required
+    /// fields.
+    #[must_use]
+    pub fn new(name: impl Into<String>, trigger: Trigger, actions: Vec<Action>) -> Self {
+        Self {
+            name: name.into(),
+            event_type: EventType::MessageSend,
+            trigger,
+            actions,
+            enabled: None,
+            exempt_roles: Vec::new(),
+            exempt_channels: Vec::new(),
+        }
+    }
+}
+
+/// The body of [`Http::edit_automod_rule`].
+///
+/// [Discord docs](https://discord.com/developers/docs/resources/auto-moderation#modify-auto-moderation-rule-json-params).
+///
+/// [`Http::edit_automod_rule`]: crate::http::Http::edit_automod_rule
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct EditAutoModRule {
+    /// The name of the rule.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// The event in which the rule is checked.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event_type: Option<EventType>,
+    /// What triggers the rule, along with its metadata.
+    #[serde(flatten)]
+    pub trigger: Option<Trigger>,
+    /// The actions executed when the rule is triggered.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actions: Option<Vec<Action>>,
+    /// Whether the rule is enabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    /// The roles that are not affected by the rule, of up to 20.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exempt_roles: Option<Vec<RoleId>>,
+    /// The channels that are not affected by the rule, of up to 50.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exempt_channels: Option<Vec<ChannelId>>,
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::{CreateAutoModRule, EditMember, WelcomeChannel};
+    use crate::json::{json, to_value};
+    use crate::model::guild::automod::{Action, Trigger};
+    use crate::model::prelude::*;
+
+    #[test]
+    fn test_edit_member() {
+        let payload = EditMember {
+            nick: Some(None),
+            roles: Some(vec![RoleId(1), RoleId(2)]),
+            communication_disabled_until: Some(Some(Timestamp::from_unix_timestamp(0).unwrap())),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            to_value(&payload).unwrap(),
+            json!({
+                "nick": null,
+                "roles": ["1", "2"],
+                "communication_disabled_until": "1970-01-01T00:00:00Z",
+            })
+        );
+    }
+
+    #[test]
+    fn test_welcome_channel() {
+        let payload = WelcomeChannel {
+            channel_id: ChannelId(1),
+            description: "Rules".to_string(),
+            emoji_name: Some("📜".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            to_value(&payload).unwrap(),
+            json!({
+                "channel_id": "1",
+                "description": "Rules",
+                "emoji_id": null,
+                "emoji_name": "📜",
+            })
+        );
+    }
+
+    #[test]
+    fn test_create_automod_rule() {
+        let payload = CreateAutoModRule {
+            exempt_roles: vec![RoleId(3)],
+            ..CreateAutoModRule::new("No links", Trigger::Keyword(vec!["http".to_string()]), vec![
+                Action::BlockMessage,
+                Action::Timeout(Duration::from_secs(60)),
+            ])
+        };
+
+        assert_eq!(
+            to_value(&payload).unwrap(),
+            json!({
+                "name": "No links",
+                "event_type": 1,
+                "trigger_type": 1,
+                "trigger_metadata": {"keyword_filter": ["http"]},
+                "actions": [
+                    {"type": 1},
+                    {"type": 3, "metadata": {"duration_seconds": 60}},
+                ],
+                "exempt_roles": ["3"],
+            })
+        );
+    }
+}
@@ -0,0 +1,356 @@
+use serde::Serialize;
+
+use crate::model::application::component::ActionRow;
+use crate::model::prelude::*;
+
+/// An embed of a message.
+///
+/// [Discord docs](https://discord.com/developers/docs/resources/channel#embed-object).
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct Embed {
+    /// The title of the embed, of up to 256 characters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// The description of the embed, of up to 4096 characters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// The URL that the title links to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// The timestamp shown in the footer.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<Timestamp>,
+    /// The colour of the left-hand side of the embed, as an RGB value.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<u32>,
+    /// The footer of the embed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub footer: Option<EmbedFooter>,
+    /// The image of the embed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image: Option<EmbedMedia>,
+    /// The thumbnail of the embed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thumbnail: Option<EmbedMedia>,
+    /// The author of the embed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<EmbedAuthor>,
+    /// The fields of the embed, of up to 25.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<EmbedField>,
+}
+
+/// The footer of an [`Embed`].
+///
+/// [Discord docs](https://discord.com/developers/docs/resources/channel#embed-object-embed-footer-structure).
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct EmbedFooter {
+    /// The text of the footer, of up to 2048 characters.
+    pub text: String,
+    /// The URL of the icon of the footer.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon_url: Option<String>,
+}
+
+/// The image or thumbnail of an [`Embed`].
+///
+/// [Discord docs](https://discord.com/developers/docs/resources/channel#embed-object-embed-image-structure).
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct EmbedMedia {
+    /// The URL of the image, which may be `attachment://<filename>` to refer
+    /// to an uploaded file.
+    pub url: String,
+}
+
+/// The author of an [`Embed`].
+///
+/// [Discord docs](https://discord.com/developers/docs/resources/channel#embed-object-embed-author-structure).
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct EmbedAuthor {
+    /// The name of the author, of up to 256 characters.
+    pub name: String,
+    /// The URL that the name links to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// The URL of the icon of the author.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon_url: Option<String>,
+}
+
+/// A field of an [`Embed`].
+///
+/// [Discord docs](https://discord.com/developers/docs/resources/channel#embed-object-embed-field-structure).
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct EmbedField {
+    /// The name of the field, of up to 256 characters.
+    pub name: String,
+    /// The value of the field, of up to 1024 characters.
+    pub value: String,
+    /// Whether the field is shown next to other inline fields.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inline: Option<bool>,
+}
+
+/// A type of mention that is parsed from the content of a message, for
+/// [`AllowedMentions`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MentionType {
+    /// The `@everyone` and `@here` mentions.
+    Everyone,
+    /// Mentions of users.
+    Users,
+    /// Mentions of roles.
+    Roles,
+}
+
+/// Which mentions of a message notify the mentioned users.
+///
+/// The default value allows no mentions at all.
+///
+/// [Discord docs](https://discord.com/developers/docs/resources/channel#allowed-mentions-object).
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct AllowedMentions {
+    /// The types of mentions parsed from the content.
+    pub parse: Vec<MentionType>,
+    /// The users that may be mentioned, when [`MentionType::Users`] is not
+    /// parsed.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub users: Vec<UserId>,
+    /// The roles that may be mentioned, when [`MentionType::Roles`] is not
+    /// parsed.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub roles: Vec<RoleId>,
+    /// Whether the author of the replied message is mentioned.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replied_user: Option<bool>,
+}
+
+/// The body of [`Http::send_message`], or the `payload_json` of
+/// [`Http::send_files`].
+///
+/// [Discord docs](https://discord.com/developers/docs/resources/channel#create-message-jsonform-params).
+///
+/// [`Http::send_message`]: crate::http::Http::send_message
+/// [`Http::send_files`]: crate::http::Http::send_files
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct CreateMessage {
+    /// The content of the message, of up to 2000 characters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    /// Whether the message is read aloud with text-to-speech.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tts: Option<bool>,
+    /// The embeds of the message, of up to 10.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub embeds: Vec<Embed>,
+    /// Which mentions of the message notify the mentioned users.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allowed_mentions: Option<AllowedMentions>,
+    /// The message replied to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_reference: Option<MessageReference>,
+    /// The components of the message, of up to 5 action rows.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub components: Vec<ActionRow>,
+    /// The guild stickers sent with the message, of up to 3.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub sticker_ids: Vec<StickerId>,
+    /// The flags of the message. Only [`MessageFlags::SUPPRESS_EMBEDS`] can
+    /// be set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flags: Option<MessageFlags>,
+}
+
+/// The body of [`Http::edit_message`], or the `payload_json` of
+/// [`Http::edit_message_and_attachments`].
+///
+/// [Discord docs](https://discord.com/developers/docs/resources/channel#edit-message-jsonform-params).
+///
+/// [`Http::edit_message`]: crate::http::Http::edit_message
+/// [`Http::edit_message_and_attachments`]: crate::http::Http::edit_message_and_attachments
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct EditMessage {
+    /// The content of the message, of up to 2000 characters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<Option<String>>,
+    /// The embeds of the message, of up to 10, replacing the current ones.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub embeds: Option<Vec<Embed>>,
+    /// The flags of the message. Only [`MessageFlags::SUPPRESS_EMBEDS`] can
+    /// be set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flags: Option<MessageFlags>,
+    /// Which mentions of the message notify the mentioned users.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allowed_mentions: Option<AllowedMentions>,
+    /// The components of the message, replacing the current ones.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub components: Option<Vec<ActionRow>>,
+}
+
+/// The body of [`Http::create_webhook`].
+///
+/// [Discord docs](https://discord.com/developers/docs/resources/webhook#create-webhook-json-params).
+///
+/// [`Http::create_webhook`]: crate::http::Http::create_webhook
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct CreateWebhook {
+    /// The name of the webhook, of 1 to 80 characters.
+    pub name: String,
+    /// The avatar of the webhook, as a data URI such as those returned by
+    /// [`utils::read_image`].
+    ///
+    /// [`utils::read_image`]: crate::utils::read_image
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avatar: Option<String>,
+}
+
+/// The body of [`Http::edit_webhook`] and [`Http::edit_webhook_with_token`].
+///
+/// [Discord docs](https://discord.com/developers/docs/resources/webhook#modify-webhook-json-params).
+///
+/// [`Http::edit_webhook`]: crate::http::Http::edit_webhook
+/// [`Http::edit_webhook_with_token`]: crate::http::Http::edit_webhook_with_token
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct EditWebhook {
+    /// The name of the webhook, of 1 to 80 characters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// The avatar of the webhook, as a data URI such as those returned by
+    /// [`utils::read_image`].
+    ///
+    /// [`utils::read_image`]: crate::utils::read_image
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avatar: Option<Option<String>>,
+    /// The channel to move the webhook to. Not allowed when editing with a
+    /// token.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel_id: Option<ChannelId>,
+}
+
+/// The body of [`Http::execute_webhook`] and
+/// [`Http::create_followup_message`].
+///
+/// [Discord docs](https://discord.com/developers/docs/resources/webhook#execute-webhook-jsonform-params).
+///
+/// [`Http::execute_webhook`]: crate::http::Http::execute_webhook
+/// [`Http::create_followup_message`]: crate::http::Http::create_followup_message
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct ExecuteWebhook {
+    /// The content of the message, of up to 2000 characters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    /// The username shown instead of the name of the webhook.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    /// The URL of the avatar shown instead of the one of the webhook.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avatar_url: Option<String>,
+    /// Whether the message is read aloud with text-to-speech.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tts: Option<bool>,
+    /// The embeds of the message, of up to 10.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub embeds: Vec<Embed>,
+    /// Which mentions of the message notify the mentioned users.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allowed_mentions: Option<AllowedMentions>,
+    /// The components of the message, of up to 5 action rows.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub components: Vec<ActionRow>,
+    /// The flags of the message. Only [`MessageFlags::SUPPRESS_EMBEDS`] can
+    /// be set, along with [`MessageFlags::EPHEMERAL`] for followups.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flags: Option<MessageFlags>,
+}
+
+/// The body of [`Http::edit_webhook_message`],
+/// [`Http::edit_followup_message`] and
+/// [`Http::edit_original_interaction_response`].
+///
+/// [Discord docs](https://discord.com/developers/docs/resources/webhook#edit-webhook-message-jsonform-params).
+///
+/// [`Http::edit_webhook_message`]: crate::http::Http::edit_webhook_message
+/// [`Http::edit_followup_message`]: crate::http::Http::edit_followup_message
+/// [`Http::edit_original_interaction_response`]: crate::http::Http::edit_original_interaction_response
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct EditWebhookMessage {
+    /// The content of the message, of up to 2000 characters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<Option<String>>,
+    /// The embeds of the message, of up to 10, replacing the current ones.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub embeds: Option<Vec<Embed>>,
+    /// Which mentions of the message notify the mentioned users.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allowed_mentions: Option<AllowedMentions>,
+    /// The components of the message, replacing the current ones.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub components: Option<Vec<ActionRow>>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::{AllowedMentions, CreateMessage, EditMessage, Embed, EmbedField, MentionType};
+    use crate::json::{json, to_value};
+    use crate::model::prelude::*;
+
+    #[test]
+    fn test_create_message() {
+        let payload = CreateMessage {
+            content: Some("Hello".to_string()),
+            embeds: vec![Embed {
+                title: Some("Title".to_string()),
+                color: Some(0xff0000),
+                fields: vec![EmbedField {
+                    name: "Name".to_string(),
+                    value: "Value".to_string(),
+                    inline: Some(true),
+                }],
+                ..Default::default()
+            }],
+            allowed_mentions: Some(AllowedMentions {
+                parse: vec![MentionType::Users],
+                ..Default::default()
+            }),
+            message_reference: Some((ChannelId(1), MessageId(2)).into()),
+            flags: Some(MessageFlags::SUPPRESS_EMBEDS),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            to_value(&payload).unwrap(),
+            json!({
+                "content": "Hello",
+                "embeds": [{
+                    "title": "Title",
+                    "color": 0xff0000,
+                    "fields": [{"name": "Name", "value": "Value", "inline": true}],
+                }],
+                "allowed_mentions": {"parse": ["users"]},
+                "message_reference": {"message_id": "2", "channel_id": "1", "guild_id": null},
+                "flags": 4,
+            })
+        );
+    }
+
+    #[test]
+    fn test_edit_message() {
+        let payload = EditMessage {
+            content: Some(None),
+            embeds: Some(Vec::new()),
+            allowed_mentions: Some(AllowedMentions::default()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            to_value(&payload).unwrap(),
+            json!({
+                "content": null,
+                "embeds": [],
+                "allowed_mentions": {"parse": []},
+            })
+        );
+    }
+}
@@ -0,0 +1,47 @@
+//! Typed request bodies for the endpoints of [`Http`].
+//!
+//! Each payload serializes to the exact JSON body of its endpoint, and can be
+//! passed to the [`Http`] methods in place of the builders in
+//! [`crate::builder`]. Unlike the builders, the shape of a payload is checked
+//! at compile time, and can be inspected without performing a request.
+//!
+//! Fields that are `None` are left out of the body. For endpoints that edit
+//! a resource, fields of type `Option<Option<T>>` are cleared by setting them
+//! to `Some(None)`, which is sent as `null`.
+//!
+//! # Examples
+//!
+//! Creating a text channel under a category:
+//!
+//! ```rust,no_run
+//! use serenity::http::payload::CreateChannel;
+//! use serenity::http::Http;
+//! use serenity::model::channel::ChannelType;
+//! use serenity::model::id::ChannelId;
+//!
+//! # async fn run(http: &Http) -> serenity::Result<()> {
+//! let payload = CreateChannel {
+//!     name: "general".to_string(),
+//!     kind: Some(ChannelType::Text),
+//!     parent_id: Some(ChannelId(7)),
+//!     ..Default::default()
+//! };
+//!
+//! http.create_channel(8, &payload, None).await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`Http`]: super::Http
+
+mod application;
+mod channel;
+mod guild;
+mod message;
+mod user;
+
+pub use self::application::*;
+pub use self::channel::*;
+pub use self::guild::*;
+pub use self::message::*;
+pub use self::user::*;
@@ -0,0 +1,32 @@
+use serde::Serialize;
+
+use crate::model::prelude::*;
+
+/// The body of [`Http::edit_profile`].
+///
+/// [Discord docs](https://discord.com/developers/docs/resources/user#modify-current-user-json-params).
+///
+/// [`Http::edit_profile`]: crate::http::Http::edit_profile
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct EditProfile {
+    /// The username of the current user.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    /// The avatar of the current user, as a data URI such as those returned
+    /// by [`utils::read_image`].
+    ///
+    /// [`utils::read_image`]: crate::utils::read_image
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avatar: Option<Option<String>>,
+}
+
+/// The body of [`Http::create_private_channel`].
+///
+/// [Discord docs](https://discord.com/developers/docs/resources/user#create-dm-json-params).
+///
+/// [`Http::create_private_channel`]: crate::http::Http::create_private_channel
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct CreatePrivateChannel {
+    /// The user to open a private channel with.
+    pub recipient_id: UserId,
+}
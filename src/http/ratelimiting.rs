@@ -51,9 +51,11 @@ use tokio::sync::{Mutex, RwLock};
 use tokio::time::{sleep, Duration};
 use tracing::{debug, instrument};
 
+use super::error::RatelimitedError;
 pub use super::routing::Route;
 use super::routing::RouteInfo;
-use super::{HttpError, LightMethod, Request};
+use super::{HttpError, LightMethod, Request, RequestOptions, RetryPolicy};
+use crate::constants;
 use crate::internal::prelude::*;
 
 /// Passed to the [`Ratelimiter::set_ratelimit_callback`] callback. If using Client, that callback
@@ -66,6 +68,12 @@ pub struct RatelimitInfo {
     pub method: LightMethod,
     pub path: String,
     pub global: bool,
+    /// The number of times the request was retried after a transient failure,
+    /// as configured by the [`RetryPolicy`].
+    pub retries: u32,
+    /// Whether the request is delayed to be retried after a transient
+    /// failure, such as a server error, rather than because of a ratelimit.
+    pub transient: bool,
 }
 
 /// Ratelimiter for requests to the Discord API.
@@ -77,6 +85,8 @@ pub struct RatelimitInfo {
 ///
 /// When no tickets are available for some time, then the thread sleeps until
 /// that time passes. The mechanism is known as "pre-emptive ratelimiting".
+/// Requests set to [fail on ratelimits] fail with [`HttpError::Ratelimited`]
+/// instead of sleeping.
 ///
 /// Occasionally for very high traffic bots, a global ratelimit may be reached
 /// which blocks all future requests until the global ratelimit is over,
@@ -87,6 +97,7 @@ pub struct RatelimitInfo {
 /// [`limit`]: Ratelimit::limit
 /// [`remaining`]: Ratelimit::remaining
 /// [`reset`]: Ratelimit::reset
+/// [fail on ratelimits]: super::RequestOptions::fail_on_ratelimit
 pub struct Ratelimiter {
     client: Client,
     global: Arc<Mutex<()>>,
@@ -94,7 +105,9 @@ pub struct Ratelimiter {
     // when the 'reset' passes.
     routes: Arc<RwLock<HashMap<Route, Arc<Mutex<Ratelimit>>>>>,
     token: String,
+    base_url: String,
     ratelimit_callback: Box<dyn Fn(RatelimitInfo) + Send + Sync>,
+    retry_policy: RetryPolicy,
 }
 
 impl fmt::Debug for Ratelimiter {
@@ -103,6 +116,8 @@ impl fmt::Debug for Ratelimiter {
             .field("client", &self.client)
             .field("global", &self.global)
             .field("routes", &self.routes)
+            .field("base_url", &self.base_url)
+            .field("retry_policy", &self.retry_policy)
             .finish()
     }
 }
@@ -123,10 +138,21 @@ impl Ratelimiter {
             global: Arc::default(),
             routes: Arc::default(),
             token,
+            base_url: constants::API_BASE_URL.to_string(),
             ratelimit_callback: Box::new(|_| {}),
+            retry_policy: RetryPolicy::default(),
         }
     }
 
+    /// Sets the base URL that request paths are resolved against.
+    ///
+    /// If using [`HttpBuilder`], this is set to the builder's base URL.
+    ///
+    /// [`HttpBuilder`]: super::HttpBuilder
+    pub fn set_base_url(&mut self, base_url: impl Into<String>) {
+        self.base_url = base_url.into();
+    }
+
     /// Sets a callback to be called when a route is rate limited.
     pub fn set_ratelimit_callback(
         &mut self,
@@ -135,6 +161,16 @@ impl Ratelimiter {
         self.ratelimit_callback = ratelimit_callback;
     }
 
+    /// Sets the policy for retrying requests that failed transiently.
+    ///
+    /// If using [`HttpBuilder`], this is set to the builder's retry policy,
+    /// if any.
+    ///
+    /// [`HttpBuilder`]: super::HttpBuilder
+    pub fn set_retry_policy(&mut self, retry_policy: RetryPolicy) {
+        self.retry_policy = retry_policy;
+    }
+
     /// The routes mutex is a HashMap of each [`Route`] and their respective
     /// ratelimit information.
     ///
@@ -167,6 +203,10 @@ impl Ratelimiter {
         Arc::clone(&self.routes)
     }
 
+    /// Performs a request, waiting for its route's ratelimit beforehand, and
+    /// retrying it if it got ratelimited or failed transiently, as configured
+    /// by the [`RetryPolicy`].
+    ///
     /// # Errors
     ///
     /// Only error kind that may be returned is [`Error::Http`].
@@ -176,6 +216,10 @@ impl Ratelimiter {
             mut req,
         } = req;
 
+        let fail_on_ratelimit =
+            RequestOptions::scoped().and_then(|options| options.fail_on_ratelimit).unwrap_or(false);
+        let mut attempts = 1;
+
         loop {
             // This will block if another thread hit the global ratelimit.
             drop(self.global.lock().await);
@@ -202,11 +246,58 @@ impl Ratelimiter {
             // - then, perform the request
             let bucket = Arc::clone(self.routes.write().await.entry(route).or_default());
 
-            bucket.lock().await.pre_hook(&req.route, &self.ratelimit_callback).await;
+            // Report how often the request was retried to the callback.
+            let retries = attempts - 1;
+            let ratelimit_callback = |info| {
+                (self.ratelimit_callback)(RatelimitInfo {
+                    retries,
+                    ..info
+                });
+            };
+
+            {
+                let mut ratelimit = bucket.lock().await;
+
+                if fail_on_ratelimit {
+                    if let Some(retry_after) = ratelimit.delay() {
+                        return Err(ratelimited(retry_after, method, path, false));
+                    }
+                }
+
+                ratelimit.pre_hook(&req.route, &ratelimit_callback).await;
+            }
+
+            let request = req
+                .build_with_base_url(&self.client, &self.token, &self.base_url)
+                .await?
+                .build()?;
+
+            let response = match self.client.execute(request).await {
+                Ok(response) => response,
+                Err(why) if self.retry_policy.retries_error(method, &why, attempts) => {
+                    debug!("Retrying request on route {:?} after error: {:?}", route, why);
+                    self.wait_for_retry(method, path, attempts, &bucket).await;
+                    attempts += 1;
+
+                    continue;
+                },
+                Err(why) => return Err(why.into()),
+            };
+
+            if self.retry_policy.retries_status(method, response.status(), attempts) {
+                debug!("Retrying request on route {:?} after status {}", route, response.status());
+
+                // Server errors may still carry ratelimit headers for the
+                // route, which have to be respected by the retry.
+                if route != Route::None {
+                    bucket.lock().await.update(&response)?;
+                }
 
-            let request = req.build(&self.client, &self.token, None).await?.build()?;
+                self.wait_for_retry(method, path, attempts, &bucket).await;
+                attempts += 1;
 
-            let response = self.client.execute(request).await?;
+                continue;
+            }
 
             // Check if the request got ratelimited by checking for status 429,
             // and if so, sleep for the value of the header 'retry-after' -
@@ -225,6 +316,20 @@ impl Ratelimiter {
                 return Ok(response);
             }
 
+            if fail_on_ratelimit && response.status() == StatusCode::TOO_MANY_REQUESTS {
+                let global = response.headers().get("x-ratelimit-global").is_some();
+
+                if !global {
+                    bucket.lock().await.update(&response)?;
+                }
+
+                if let Some(retry_after) = parse_header::<f64>(response.headers(), "retry-after")? {
+                    let retry_after = Duration::from_secs_f64(retry_after);
+
+                    return Err(ratelimited(retry_after, method, path, global));
+                }
+            }
+
             let redo = if response.headers().get("x-ratelimit-global").is_some() {
                 drop(self.global.lock().await);
 
@@ -239,6 +344,8 @@ impl Ratelimiter {
                             method,
                             path,
                             global: true,
+                            retries,
+                            transient: false,
                         });
                         sleep(Duration::from_secs_f64(retry_after)).await;
 
@@ -248,7 +355,7 @@ impl Ratelimiter {
                     },
                 )
             } else {
-                bucket.lock().await.post_hook(&response, &req.route, &self.ratelimit_callback).await
+                bucket.lock().await.post_hook(&response, &req.route, &ratelimit_callback).await
             };
 
             if !redo.unwrap_or(true) {
@@ -256,6 +363,30 @@ impl Ratelimiter {
             }
         }
     }
+
+    /// Waits before retrying a request that failed transiently for the
+    /// given number of times.
+    async fn wait_for_retry(
+        &self,
+        method: LightMethod,
+        path: String,
+        attempts: u32,
+        bucket: &Mutex<Ratelimit>,
+    ) {
+        let delay = self.retry_policy.delay(attempts);
+
+        (self.ratelimit_callback)(RatelimitInfo {
+            timeout: delay,
+            limit: bucket.lock().await.limit(),
+            method,
+            path,
+            global: false,
+            retries: attempts,
+            transient: true,
+        });
+
+        sleep(delay).await;
+    }
 }
 
 /// A set of data containing information about the ratelimits for a particular
@@ -320,6 +451,8 @@ impl Ratelimit {
                 method,
                 path: path.to_string(),
                 global: false,
+                retries: 0,
+                transient: false,
             });
 
             sleep(delay).await;
@@ -337,6 +470,34 @@ impl Ratelimit {
         route: &RouteInfo<'_>,
         ratelimit_callback: &(dyn Fn(RatelimitInfo) + Send + Sync),
     ) -> Result<bool> {
+        self.update(response)?;
+
+        Ok(if response.status() != StatusCode::TOO_MANY_REQUESTS {
+            false
+        } else if let Some(retry_after) = parse_header::<f64>(response.headers(), "retry-after")? {
+            let (method, route, path) = route.deconstruct();
+
+            debug!("Ratelimited on route {:?} for {:?}ms", route, retry_after);
+            ratelimit_callback(RatelimitInfo {
+                timeout: Duration::from_secs_f64(retry_after),
+                limit: self.limit,
+                method,
+                path: path.to_string(),
+                global: false,
+                retries: 0,
+                transient: false,
+            });
+
+            sleep(Duration::from_secs_f64(retry_after)).await;
+
+            true
+        } else {
+            false
+        })
+    }
+
+    /// Updates the ratelimit from the headers of a response to its route.
+    fn update(&mut self, response: &Response) -> Result<()> {
         if let Some(limit) = parse_header(response.headers(), "x-ratelimit-limit")? {
             self.limit = limit;
         }
@@ -361,26 +522,17 @@ impl Ratelimit {
             self.reset_after = Some(Duration::from_secs_f64(reset_after));
         }
 
-        Ok(if response.status() != StatusCode::TOO_MANY_REQUESTS {
-            false
-        } else if let Some(retry_after) = parse_header::<f64>(response.headers(), "retry-after")? {
-            let (method, route, path) = route.deconstruct();
-
-            debug!("Ratelimited on route {:?} for {:?}ms", route, retry_after);
-            ratelimit_callback(RatelimitInfo {
-                timeout: Duration::from_secs_f64(retry_after),
-                limit: self.limit,
-                method,
-                path: path.to_string(),
-                global: false,
-            });
+        Ok(())
+    }
 
-            sleep(Duration::from_secs_f64(retry_after)).await;
+    /// How long [`Self::pre_hook`] would sleep before the next request, if the
+    /// route is currently ratelimited.
+    fn delay(&self) -> Option<Duration> {
+        if self.limit() == 0 || self.remaining() != 0 {
+            return None;
+        }
 
-            true
-        } else {
-            false
-        })
+        self.reset?.duration_since(SystemTime::now()).ok()
     }
 
     /// The total number of requests that can be made in a period of time.
@@ -442,6 +594,15 @@ impl<'a> From<Request<'a>> for RatelimitedRequest<'a> {
     }
 }
 
+fn ratelimited(retry_after: Duration, method: LightMethod, path: String, global: bool) -> Error {
+    Error::Http(Box::new(HttpError::Ratelimited(RatelimitedError {
+        retry_after,
+        method,
+        path,
+        global,
+    })))
+}
+
 fn parse_header<T: FromStr>(headers: &HeaderMap, header: &str) -> Result<Option<T>> {
     let header = match headers.get(header) {
         Some(v) => v,
@@ -460,10 +621,11 @@ fn parse_header<T: FromStr>(headers: &HeaderMap, header: &str) -> Result<Option<
 mod tests {
     use std::error::Error as StdError;
     use std::result::Result as StdResult;
+    use std::time::{Duration, SystemTime};
 
     use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
 
-    use super::parse_header;
+    use super::{parse_header, Ratelimit};
     use crate::error::Error;
     use crate::http::HttpError;
 
@@ -523,4 +685,26 @@ mod tests {
         assert!(is_err!("x-bad-num", HttpError::RateLimitI64F64));
         assert!(is_err!("x-bad-unicode", HttpError::RateLimitUtf8));
     }
+
+    #[test]
+    fn test_delay() {
+        let mut ratelimit = Ratelimit {
+            limit: 5,
+            remaining: 0,
+            reset: Some(SystemTime::now() + Duration::from_secs(60)),
+            reset_after: Some(Duration::from_secs(60)),
+        };
+
+        let delay = ratelimit.delay().unwrap();
+        assert!(delay > Duration::from_secs(59) && delay <= Duration::from_secs(60));
+
+        ratelimit.remaining = 1;
+        assert_eq!(ratelimit.delay(), None);
+
+        ratelimit.remaining = 0;
+        ratelimit.reset = Some(SystemTime::now() - Duration::from_secs(1));
+        assert_eq!(ratelimit.delay(), None);
+
+        assert_eq!(Ratelimit::default().delay(), None);
+    }
 }
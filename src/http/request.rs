@@ -12,7 +12,7 @@ use reqwest::{Client, RequestBuilder as ReqwestRequestBuilder, Url};
 use tracing::instrument;
 
 use super::multipart::Multipart;
-use super::routing::RouteInfo;
+use super::routing::{relative_path, RouteInfo};
 use super::HttpError;
 use crate::constants;
 use crate::internal::prelude::*;
@@ -97,6 +97,49 @@ impl<'a> Request<'a> {
         client: &Client,
         token: &str,
         proxy: Option<&Url>,
+    ) -> Result<ReqwestRequestBuilder> {
+        let (_, _, mut path) = self.route.deconstruct();
+
+        if let Some(proxy) = proxy {
+            path = Cow::Owned(path.to_mut().replace("https://discord.com/", proxy.as_str()));
+        }
+
+        let url = Url::parse(&path).map_err(HttpError::Url)?;
+        self.build_url(client, token, url).await
+    }
+
+    /// Builds the request, replacing [`constants::API_BASE_URL`] in the
+    /// route's path with the given API `base_url`.
+    ///
+    /// Routes outside of the API, such as the Status API routes, are left
+    /// untouched.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`HttpError::Url`] if the resulting URL is invalid.
+    #[instrument(skip(token))]
+    pub async fn build_with_base_url(
+        &mut self,
+        client: &Client,
+        token: &str,
+        base_url: &str,
+    ) -> Result<ReqwestRequestBuilder> {
+        let (_, _, path) = self.route.deconstruct();
+
+        let url = match relative_path(&path) {
+            Some(path) => Url::parse(&format!("{}{}", base_url, path)),
+            None => Url::parse(&path),
+        };
+
+        let url = url.map_err(HttpError::Url)?;
+        self.build_url(client, token, url).await
+    }
+
+    async fn build_url(
+        &mut self,
+        client: &Client,
+        token: &str,
+        url: Url,
     ) -> Result<ReqwestRequestBuilder> {
         let Request {
             body,
@@ -105,14 +148,9 @@ impl<'a> Request<'a> {
             route: ref route_info,
         } = *self;
 
-        let (method, _, mut path) = route_info.deconstruct();
+        let (method, _, _) = route_info.deconstruct();
 
-        if let Some(proxy) = proxy {
-            path = Cow::Owned(path.to_mut().replace("https://discord.com/", proxy.as_str()));
-        }
-
-        let mut builder =
-            client.request(method.reqwest_method(), Url::parse(&path).map_err(HttpError::Url)?);
+        let mut builder = client.request(method.reqwest_method(), url);
 
         if let Some(bytes) = body {
             builder = builder.body(Vec::from(bytes));
@@ -177,3 +215,77 @@ impl<'a> Request<'a> {
         &mut self.route
     }
 }
+
+#[cfg(test)]
+mod test {
+    use reqwest::Client;
+
+    use super::*;
+    use crate::http::HttpBuilder;
+
+    async fn url_of(route: RouteInfo<'_>, base_url: &str) -> String {
+        let mut request = RequestBuilder::new(route).build();
+        let builder = request.build_with_base_url(&Client::new(), "token", base_url).await.unwrap();
+
+        builder.build().unwrap().url().to_string()
+    }
+
+    #[tokio::test]
+    async fn test_build_resolves_base_url() {
+        let url = url_of(
+            RouteInfo::GetChannel {
+                channel_id: 7,
+            },
+            "http://127.0.0.1:3000/api/v10",
+        )
+        .await;
+        assert_eq!(url, "http://127.0.0.1:3000/api/v10/channels/7");
+
+        let url = url_of(RouteInfo::GetUnresolvedIncidents, "http://127.0.0.1:3000/api/v10").await;
+        assert_eq!(url, "https://status.discord.com/api/v2/incidents/unresolved.json");
+    }
+
+    #[tokio::test]
+    async fn test_build_with_proxy() {
+        let mut request = RequestBuilder::new(RouteInfo::GetChannel {
+            channel_id: 7,
+        })
+        .build();
+        let proxy = Url::parse("http://127.0.0.1:3000/").unwrap();
+        let builder = request.build(&Client::new(), "token", Some(&proxy)).await.unwrap();
+
+        assert_eq!(
+            builder.build().unwrap().url().as_str(),
+            "http://127.0.0.1:3000/api/v10/channels/7"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "model")]
+    fn test_widget_image_url_for() {
+        use crate::model::guild::GuildWidgetStyle;
+        use crate::model::id::GuildId;
+
+        let http = HttpBuilder::new("token").base_url("http://localhost/discord").unwrap().build();
+        assert_eq!(
+            GuildId(3).widget_image_url_for(&http, GuildWidgetStyle::Shield),
+            "http://localhost/discord/guilds/3/widget.png?style=shield"
+        );
+        assert_eq!(
+            GuildId(3).widget_image_url(GuildWidgetStyle::Shield),
+            "https://discord.com/api/v10/guilds/3/widget.png?style=shield"
+        );
+    }
+
+    #[test]
+    fn test_builder_base_url() {
+        let http = HttpBuilder::new("token").build();
+        assert_eq!(http.base_url(), "https://discord.com/api/v10");
+
+        let http = HttpBuilder::new("token").proxy("http://127.0.0.1:3000").unwrap().build();
+        assert_eq!(http.base_url(), "http://127.0.0.1:3000/api/v10");
+
+        let http = HttpBuilder::new("token").base_url("http://localhost/discord/").unwrap().build();
+        assert_eq!(http.base_url(), "http://localhost/discord");
+    }
+}
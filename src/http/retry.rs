@@ -0,0 +1,240 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+use reqwest::{Error as ReqwestError, StatusCode};
+
+use super::LightMethod;
+
+/// When and how often requests that failed transiently are retried by the
+/// [`Ratelimiter`].
+///
+/// A failure is transient if the request timed out, the connection was lost,
+/// or Discord responded with one of the [retryable status codes]. Requests
+/// are only retried if their method is [retryable], as repeating others, such
+/// as `POST` requests, may perform their action twice.
+///
+/// Between attempts, the policy waits with an exponential backoff: the
+/// [base delay] is doubled after each attempt, up to the [maximum delay], and
+/// a random jitter of up to half of it is subtracted, so that clients that
+/// failed at the same time do not retry at the same time.
+///
+/// By default, requests are attempted up to 3 times, with a base delay of
+/// 500 milliseconds and a maximum delay of 10 seconds. `GET`, `PUT` and
+/// `DELETE` requests are retried on 500, 502, 503 and 504 responses.
+///
+/// # Examples
+///
+/// Retrying up to 5 times, including `PATCH` requests:
+///
+/// ```rust
+/// use serenity::http::{HttpBuilder, LightMethod, RetryPolicy};
+///
+/// let policy = RetryPolicy::new().max_attempts(5).methods(&[
+///     LightMethod::Get,
+///     LightMethod::Put,
+///     LightMethod::Delete,
+///     LightMethod::Patch,
+/// ]);
+///
+/// let http = HttpBuilder::new("token").retry_policy(policy).build();
+/// ```
+///
+/// [`Ratelimiter`]: super::ratelimiting::Ratelimiter
+/// [retryable status codes]: Self::status_codes
+/// [retryable]: Self::methods
+/// [base delay]: Self::base_delay
+/// [maximum delay]: Self::max_delay
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+    jitter: bool,
+    methods: Vec<LightMethod>,
+    status_codes: Vec<StatusCode>,
+}
+
+impl RetryPolicy {
+    /// Creates the default policy.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a policy that never retries requests.
+    pub fn none() -> Self {
+        Self::default().max_attempts(1)
+    }
+
+    /// Sets the maximum number of times a request is attempted, including
+    /// the first attempt. A value of `1` disables retries.
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+
+        self
+    }
+
+    /// Sets the delay before the first retry, which is doubled for each one
+    /// after.
+    pub fn base_delay(mut self, base_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+
+        self
+    }
+
+    /// Sets the maximum delay between two attempts.
+    pub fn max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+
+        self
+    }
+
+    /// Sets whether a random jitter is subtracted from the delays.
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+
+        self
+    }
+
+    /// Sets the methods of the requests that are retried after a connection
+    /// failure, a timeout or a retryable status code.
+    ///
+    /// **Note**: `POST` requests are not idempotent: if one timed out or
+    /// failed with a server error, it may still have been performed, and
+    /// retrying it may, for example, send a message twice.
+    pub fn methods(mut self, methods: &[LightMethod]) -> Self {
+        self.methods = methods.to_vec();
+
+        self
+    }
+
+    /// Sets the response status codes after which requests are retried.
+    pub fn status_codes(mut self, status_codes: &[StatusCode]) -> Self {
+        self.status_codes = status_codes.to_vec();
+
+        self
+    }
+
+    /// Whether a request with the given method, which was attempted `attempts`
+    /// times, is retried after the given response status.
+    pub(crate) fn retries_status(
+        &self,
+        method: LightMethod,
+        status: StatusCode,
+        attempts: u32,
+    ) -> bool {
+        attempts < self.max_attempts
+            && self.methods.contains(&method)
+            && self.status_codes.contains(&status)
+    }
+
+    /// Whether a request with the given method, which was attempted `attempts`
+    /// times, is retried after the given error.
+    pub(crate) fn retries_error(
+        &self,
+        method: LightMethod,
+        error: &ReqwestError,
+        attempts: u32,
+    ) -> bool {
+        attempts < self.max_attempts
+            && self.methods.contains(&method)
+            && (error.is_connect() || error.is_timeout() || error.is_request())
+    }
+
+    /// The delay before the next attempt, after the given number of attempts.
+    pub(crate) fn delay(&self, attempts: u32) -> Duration {
+        let exponent = attempts.saturating_sub(1).min(31);
+        let delay = self.base_delay.saturating_mul(1 << exponent).min(self.max_delay);
+
+        if self.jitter {
+            // A random number of thousandths of half of the delay.
+            let thousandths = (RandomState::new().build_hasher().finish() % 1001) as u32;
+
+            delay.saturating_sub(delay / 2 * thousandths / 1000)
+        } else {
+            delay
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(10),
+            jitter: true,
+            methods: vec![LightMethod::Get, LightMethod::Put, LightMethod::Delete],
+            status_codes: vec![
+                StatusCode::INTERNAL_SERVER_ERROR,
+                StatusCode::BAD_GATEWAY,
+                StatusCode::SERVICE_UNAVAILABLE,
+                StatusCode::GATEWAY_TIMEOUT,
+            ],
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use reqwest::StatusCode;
+
+    use super::RetryPolicy;
+    use crate::http::LightMethod;
+
+    #[test]
+    fn test_retries_status() {
+        let policy = RetryPolicy::new();
+
+        assert!(policy.retries_status(LightMethod::Get, StatusCode::BAD_GATEWAY, 1));
+        assert!(policy.retries_status(LightMethod::Get, StatusCode::BAD_GATEWAY, 2));
+        assert!(!policy.retries_status(LightMethod::Get, StatusCode::BAD_GATEWAY, 3));
+        assert!(!policy.retries_status(LightMethod::Get, StatusCode::NOT_FOUND, 1));
+        assert!(!policy.retries_status(LightMethod::Post, StatusCode::BAD_GATEWAY, 1));
+
+        let policy = RetryPolicy::none();
+        assert!(!policy.retries_status(LightMethod::Get, StatusCode::BAD_GATEWAY, 1));
+    }
+
+    #[tokio::test]
+    async fn test_retries_error() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        drop(listener);
+
+        let error = reqwest::get(format!("http://{}", addr)).await.unwrap_err();
+        assert!(error.is_connect());
+
+        let policy = RetryPolicy::new();
+        assert!(policy.retries_error(LightMethod::Get, &error, 1));
+        assert!(!policy.retries_error(LightMethod::Get, &error, 3));
+        assert!(!policy.retries_error(LightMethod::Post, &error, 1));
+    }
+
+    #[test]
+    fn test_delay() {
+        let policy = RetryPolicy::new()
+            .base_delay(Duration::from_secs(1))
+            .max_delay(Duration::from_secs(5))
+            .jitter(false);
+
+        assert_eq!(policy.delay(1), Duration::from_secs(1));
+        assert_eq!(policy.delay(2), Duration::from_secs(2));
+        assert_eq!(policy.delay(3), Duration::from_secs(4));
+        assert_eq!(policy.delay(4), Duration::from_secs(5));
+        assert_eq!(policy.delay(100), Duration::from_secs(5));
+
+        let jittered = policy.clone().jitter(true);
+
+        for attempts in 1..10 {
+            let delay = jittered.delay(attempts);
+            let max = policy.delay(attempts);
+
+            assert!(delay <= max && delay >= max / 2);
+        }
+    }
+}
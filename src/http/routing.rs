@@ -469,6 +469,12 @@ pub enum Route {
     None,
 }
 
+/// The paths returned by these functions are absolute URLs under
+/// [`constants::API_BASE_URL`]. The [`Http`] client performing the request
+/// replaces that prefix with its own base URL, if one is configured.
+///
+/// [`constants::API_BASE_URL`]: crate::constants::API_BASE_URL
+/// [`Http`]: super::Http
 impl Route {
     #[must_use]
     pub fn channel(channel_id: u64) -> String {
@@ -1135,6 +1141,11 @@ impl Route {
         api!("/applications/{}/commands", application_id)
     }
 
+    #[must_use]
+    pub fn application_commands_with_localizations(application_id: u64) -> String {
+        api!("/applications/{}/commands?with_localizations=true", application_id)
+    }
+
     #[must_use]
     pub fn application_guild_command(
         application_id: u64,
@@ -1163,6 +1174,18 @@ impl Route {
         api!("/applications/{}/guilds/{}/commands", application_id, guild_id)
     }
 
+    #[must_use]
+    pub fn application_guild_commands_with_localizations(
+        application_id: u64,
+        guild_id: u64,
+    ) -> String {
+        api!(
+            "/applications/{}/guilds/{}/commands?with_localizations=true",
+            application_id,
+            guild_id
+        )
+    }
+
     #[must_use]
     pub fn application_guild_commands_permissions(application_id: u64, guild_id: u64) -> String {
         api!("/applications/{}/guilds/{}/commands/permissions", application_id, guild_id)
@@ -1567,6 +1590,7 @@ pub enum RouteInfo<'a> {
     GetGateway,
     GetGlobalApplicationCommands {
         application_id: u64,
+        with_localizations: bool,
     },
     GetGlobalApplicationCommand {
         application_id: u64,
@@ -1581,6 +1605,7 @@ pub enum RouteInfo<'a> {
     GetGuildApplicationCommands {
         application_id: u64,
         guild_id: u64,
+        with_localizations: bool,
     },
     GetGuildApplicationCommand {
         application_id: u64,
@@ -1762,6 +1787,9 @@ pub enum RouteInfo<'a> {
 }
 
 impl<'a> RouteInfo<'a> {
+    /// Deconstructs the route into its method, ratelimiting bucket and path.
+    ///
+    /// The path is an absolute URL, see [`Route`] for details.
     #[must_use]
     pub fn deconstruct(&self) -> (LightMethod, Route, Cow<'_, str>) {
         match *self {
@@ -2595,10 +2623,15 @@ impl<'a> RouteInfo<'a> {
             },
             RouteInfo::GetGlobalApplicationCommands {
                 application_id,
+                with_localizations,
             } => (
                 LightMethod::Get,
                 Route::ApplicationsIdCommands(application_id),
-                Cow::from(Route::application_commands(application_id)),
+                Cow::from(if with_localizations {
+                    Route::application_commands_with_localizations(application_id)
+                } else {
+                    Route::application_commands(application_id)
+                }),
             ),
             RouteInfo::GetGlobalApplicationCommand {
                 application_id,
@@ -2621,10 +2654,15 @@ impl<'a> RouteInfo<'a> {
             RouteInfo::GetGuildApplicationCommands {
                 application_id,
                 guild_id,
+                with_localizations,
             } => (
                 LightMethod::Get,
                 Route::ApplicationsIdGuildsIdCommands(application_id),
-                Cow::from(Route::application_guild_commands(application_id, guild_id)),
+                Cow::from(if with_localizations {
+                    Route::application_guild_commands_with_localizations(application_id, guild_id)
+                } else {
+                    Route::application_guild_commands(application_id, guild_id)
+                }),
             ),
             RouteInfo::GetGuildApplicationCommand {
                 application_id,
@@ -2979,3 +3017,10 @@ impl<'a> RouteInfo<'a> {
         }
     }
 }
+
+/// Returns the part of a route's path following [`constants::API_BASE_URL`],
+/// or `None` if the route is not part of the API, such as the Status API
+/// routes.
+pub(crate) fn relative_path(path: &str) -> Option<&str> {
+    path.strip_prefix(constants::API_BASE_URL)
+}
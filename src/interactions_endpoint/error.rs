@@ -0,0 +1,48 @@
+use std::error::Error as StdError;
+use std::fmt;
+
+/// An error returned from the [`interactions_endpoint`] module.
+///
+/// This is always wrapped within the library's generic
+/// [`Error::InteractionsEndpoint`] variant.
+///
+/// [`interactions_endpoint`]: super
+/// [`Error::InteractionsEndpoint`]: crate::Error::InteractionsEndpoint
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// When the public key of the application is not a hex-encoded Ed25519
+    /// public key.
+    InvalidPublicKey,
+    /// When a request lacks one of the signature headers. The name of the
+    /// header is provided.
+    MissingHeader(&'static str),
+    /// When the signature of a request does not match its timestamp and body.
+    InvalidSignature,
+    /// When the timestamp of a request is malformed, or too far from the
+    /// current time, such as when a captured request is replayed.
+    InvalidTimestamp,
+    /// When the server failed to accept connections or to serve a request.
+    Hyper(hyper::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidPublicKey => f.write_str("Invalid Ed25519 public key"),
+            Self::MissingHeader(name) => write!(f, "Missing {} header", name),
+            Self::InvalidSignature => f.write_str("Invalid request signature"),
+            Self::InvalidTimestamp => f.write_str("Invalid or expired request timestamp"),
+            Self::Hyper(inner) => fmt::Display::fmt(inner, f),
+        }
+    }
+}
+
+impl StdError for Error {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            Self::Hyper(inner) => Some(inner),
+            _ => None,
+        }
+    }
+}
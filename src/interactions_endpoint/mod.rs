@@ -0,0 +1,411 @@
+//! A server receiving interactions over HTTP, as an alternative to the
+//! gateway.
+//!
+//! Discord can send interactions to an [interactions endpoint URL] set for
+//! the application instead of the gateway. Each request is signed, and must
+//! be answered with the response to the interaction.
+//!
+//! An [`InteractionsEndpoint`] verifies the signature of each request with a
+//! [`Verifier`], answers `PING`s sent by Discord to validate the URL, and
+//! passes all other interactions to an [`InteractionHandler`], whose returned
+//! [`CreateInteractionResponse`] is sent back as the response body. It can
+//! either be run as a standalone server with [`InteractionsEndpoint::serve`],
+//! or be embedded in an existing `hyper` or `tower` server, as it implements
+//! [`Service`].
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use std::net::SocketAddr;
+//!
+//! use serenity::async_trait;
+//! use serenity::builder::CreateInteractionResponse;
+//! use serenity::interactions_endpoint::{InteractionHandler, InteractionsEndpoint};
+//! use serenity::model::application::interaction::Interaction;
+//!
+//! struct Handler;
+//!
+//! #[async_trait]
+//! impl InteractionHandler for Handler {
+//!     async fn interaction(&self, _: Interaction) -> CreateInteractionResponse<'static> {
+//!         let mut response = CreateInteractionResponse::default();
+//!         response.interaction_response_data(|data| data.content("Pong!"));
+//!
+//!         response
+//!     }
+//! }
+//!
+//! # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+//! let endpoint = InteractionsEndpoint::new("<application public key>", Handler)?;
+//!
+//! endpoint.serve(SocketAddr::from(([0, 0, 0, 0], 8080))).await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [interactions endpoint URL]: https://discord.com/developers/docs/interactions/receiving-and-responding#receiving-an-interaction
+
+mod error;
+mod verify;
+
+use std::convert::Infallible;
+use std::fmt;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use hyper::body::HttpBody;
+use hyper::header::{HeaderValue, CONTENT_LENGTH, CONTENT_TYPE};
+use hyper::service::{make_service_fn, Service};
+use hyper::{Body, Method, Request, Response, Server, StatusCode};
+use tracing::{instrument, warn};
+
+pub use self::error::Error as InteractionsEndpointError;
+pub use self::verify::Verifier;
+use crate::builder::CreateInteractionResponse;
+use crate::internal::prelude::*;
+use crate::json::{self, json};
+use crate::model::application::interaction::Interaction;
+
+/// The header holding the signature of a request.
+pub const SIGNATURE_HEADER: &str = "X-Signature-Ed25519";
+/// The header holding the timestamp of a request, which is signed along with
+/// its body.
+pub const TIMESTAMP_HEADER: &str = "X-Signature-Timestamp";
+/// How far the timestamp of a request may be from the current time, in either
+/// direction. Requests outside of it are rejected, so that captured requests
+/// can not be replayed later.
+pub const TIMESTAMP_TOLERANCE: Duration = Duration::from_secs(60);
+/// The maximum size of a request body in bytes. Larger requests are rejected
+/// before they are read, as their signature can only be checked afterwards.
+pub const MAX_BODY_SIZE: usize = 1024 * 1024;
+
+/// The handler of the interactions received by an [`InteractionsEndpoint`].
+#[async_trait]
+pub trait InteractionHandler: Send + Sync {
+    /// Handles an interaction, returning the response that is sent back to
+    /// Discord.
+    ///
+    /// This is called for every interaction but `PING`s, which are answered
+    /// by the endpoint itself. The response must be returned within 3
+    /// seconds, otherwise the interaction fails; longer work should defer the
+    /// response and follow up using [`Http`].
+    ///
+    /// **Note**: Files added to the response are not sent, as the response
+    /// body is JSON. Attach them with a followup message instead.
+    ///
+    /// [`Http`]: crate::http::Http
+    async fn interaction(&self, interaction: Interaction) -> CreateInteractionResponse<'static>;
+}
+
+/// A server receiving interactions over HTTP.
+///
+/// Refer to the [module-level documentation] for details.
+///
+/// [module-level documentation]: self
+#[derive(Clone)]
+pub struct InteractionsEndpoint {
+    verifier: Verifier,
+    handler: Arc<dyn InteractionHandler>,
+}
+
+impl InteractionsEndpoint {
+    /// Creates an endpoint verifying requests with the hex-encoded public key
+    /// of the application, and passing interactions to the given handler.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InteractionsEndpointError::InvalidPublicKey`] if the public
+    /// key is invalid.
+    pub fn new<H: InteractionHandler + 'static>(public_key: &str, handler: H) -> Result<Self> {
+        Ok(Self {
+            verifier: Verifier::new(public_key)?,
+            handler: Arc::new(handler),
+        })
+    }
+
+    /// Handles a request sent to the endpoint, returning the response to send
+    /// back.
+    ///
+    /// Requests that are not `POST` requests are answered with a
+    /// `405 Method Not Allowed`, those without a valid signature or with an
+    /// expired timestamp with a `401 Unauthorized`, those whose body is larger than [`MAX_BODY_SIZE`]
+    /// with a `413 Payload Too Large`, and those whose body is not an
+    /// interaction with a `400 Bad Request`.
+    #[instrument(skip(self, request))]
+    pub async fn handle(&self, request: Request<Body>) -> Response<Body> {
+        if request.method() != Method::POST {
+            return status(StatusCode::METHOD_NOT_ALLOWED);
+        }
+
+        let (parts, body) = request.into_parts();
+
+        let content_length = parts
+            .headers
+            .get(CONTENT_LENGTH)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse::<usize>().ok());
+
+        if content_length.map_or(false, |length| length > MAX_BODY_SIZE) {
+            return status(StatusCode::PAYLOAD_TOO_LARGE);
+        }
+
+        let mut body = match read_body(body).await {
+            Ok(Some(body)) => body,
+            Ok(None) => return status(StatusCode::PAYLOAD_TOO_LARGE),
+            Err(why) => {
+                warn!("Failed to read interaction request body: {:?}", why);
+
+                return status(StatusCode::BAD_REQUEST);
+            },
+        };
+
+        let header = |name: &'static str| {
+            parts
+                .headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .ok_or(InteractionsEndpointError::MissingHeader(name))
+        };
+
+        let verified = header(SIGNATURE_HEADER)
+            .and_then(|signature| Ok((signature, header(TIMESTAMP_HEADER)?)))
+            .map_err(Error::from)
+            .and_then(|(signature, timestamp)| self.verifier.verify(signature, timestamp, &body));
+
+        if let Err(why) = verified {
+            warn!("Rejected interaction request: {}", why);
+
+            return status(StatusCode::UNAUTHORIZED);
+        }
+
+        let interaction = match json::from_slice::<Interaction>(&mut body) {
+            Ok(interaction) => interaction,
+            Err(why) => {
+                warn!("Failed to deserialize interaction: {:?}", why);
+
+                return status(StatusCode::BAD_REQUEST);
+            },
+        };
+
+        let body = match interaction {
+            Interaction::Ping(_) => json!({ "type": 1 }),
+            interaction => {
+                let response = self.handler.interaction(interaction).await;
+
+                Value::from(json::hashmap_to_json_map(response.0))
+            },
+        };
+
+        match json::to_string(&body) {
+            Ok(body) => {
+                let mut response = Response::new(Body::from(body));
+                response
+                    .headers_mut()
+                    .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+
+                response
+            },
+            Err(why) => {
+                warn!("Failed to serialize interaction response: {:?}", why);
+
+                status(StatusCode::INTERNAL_SERVER_ERROR)
+            },
+        }
+    }
+
+    /// Runs a standalone server on the given address, handling every request
+    /// it receives with [`Self::handle`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InteractionsEndpointError::Hyper`] if the address could not
+    /// be bound, or the server failed.
+    pub async fn serve(self, addr: SocketAddr) -> Result<()> {
+        let make_service = make_service_fn(move |_| {
+            let endpoint = self.clone();
+
+            async move { Ok::<_, Infallible>(endpoint) }
+        });
+
+        Server::try_bind(&addr)
+            .map_err(InteractionsEndpointError::Hyper)?
+            .serve(make_service)
+            .await
+            .map_err(|why| InteractionsEndpointError::Hyper(why).into())
+    }
+}
+
+impl Service<Request<Body>> for InteractionsEndpoint {
+    type Response = Response<Body>;
+    type Error = Infallible;
+    type Future = Pin<Box<dyn Future<Output = StdResult<Response<Body>, Infallible>> + Send>>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<StdResult<(), Infallible>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: Request<Body>) -> Self::Future {
+        let endpoint = self.clone();
+
+        Box::pin(async move { Ok(endpoint.handle(request).await) })
+    }
+}
+
+impl fmt::Debug for InteractionsEndpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InteractionsEndpoint")
+            .field("verifier", &self.verifier)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Reads a request body, returning `None` if it is larger than
+/// [`MAX_BODY_SIZE`].
+async fn read_body(mut body: Body) -> StdResult<Option<Vec<u8>>, hyper::Error> {
+    let mut bytes = Vec::new();
+
+    while let Some(chunk) = body.data().await {
+        let chunk = chunk?;
+
+        if bytes.len() + chunk.len() > MAX_BODY_SIZE {
+            return Ok(None);
+        }
+
+        bytes.extend_from_slice(&chunk);
+    }
+
+    Ok(Some(bytes))
+}
+
+fn status(status: StatusCode) -> Response<Body> {
+    let mut response = Response::new(Body::empty());
+    *response.status_mut() = status;
+
+    response
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use hyper::{Body, Request, StatusCode};
+    use ring::signature::{Ed25519KeyPair, KeyPair};
+
+    use super::verify::test::hex;
+    use super::*;
+
+    struct Handler;
+
+    #[async_trait]
+    impl InteractionHandler for Handler {
+        async fn interaction(&self, _: Interaction) -> CreateInteractionResponse<'static> {
+            let mut response = CreateInteractionResponse::default();
+            response.interaction_response_data(|data| data.content("Pong!"));
+
+            response
+        }
+    }
+
+    fn request(key_pair: &Ed25519KeyPair, body: &'static str) -> Request<Body> {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+        request_at(key_pair, body, &now.as_secs().to_string())
+    }
+
+    fn request_at(key_pair: &Ed25519KeyPair, body: &'static str, timestamp: &str) -> Request<Body> {
+        let signature = key_pair.sign(format!("{}{}", timestamp, body).as_bytes());
+
+        Request::post("/")
+            .header(SIGNATURE_HEADER, hex(signature.as_ref()))
+            .header(TIMESTAMP_HEADER, timestamp)
+            .body(Body::from(body))
+            .unwrap()
+    }
+
+    async fn body(response: Response<Body>) -> Value {
+        let mut body = hyper::body::to_bytes(response.into_body()).await.unwrap().to_vec();
+
+        json::from_slice(&mut body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_handle() {
+        let key_pair = Ed25519KeyPair::from_seed_unchecked(&[7; 32]).unwrap();
+        let endpoint =
+            InteractionsEndpoint::new(&hex(key_pair.public_key().as_ref()), Handler).unwrap();
+
+        let ping = r#"{"id":"1","application_id":"2","type":1,"token":"t","version":1}"#;
+        let response = endpoint.handle(request(&key_pair, ping)).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(body(response).await, json!({ "type": 1 }));
+
+        let mut tampered = request(&key_pair, ping);
+        *tampered.body_mut() = Body::from(ping.replace("\"1\"", "\"3\""));
+        let response = endpoint.handle(tampered).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let replayed = request_at(&key_pair, ping, "1700000000");
+        let response = endpoint.handle(replayed).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let unsigned = Request::post("/").body(Body::from(ping)).unwrap();
+        let response = endpoint.handle(unsigned).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = endpoint.handle(Request::get("/").body(Body::empty()).unwrap()).await;
+        assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+
+        let response = endpoint.handle(request(&key_pair, "{}")).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_handle_too_large() {
+        let key_pair = Ed25519KeyPair::from_seed_unchecked(&[7; 32]).unwrap();
+        let endpoint =
+            InteractionsEndpoint::new(&hex(key_pair.public_key().as_ref()), Handler).unwrap();
+
+        let declared = Request::post("/")
+            .header(CONTENT_LENGTH, MAX_BODY_SIZE + 1)
+            .body(Body::empty())
+            .unwrap();
+        let response = endpoint.handle(declared).await;
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+        // Bodies without a declared length are only read up to the limit.
+        let (mut sender, body) = Body::channel();
+        tokio::spawn(async move {
+            while sender.send_data(vec![b' '; 64 * 1024].into()).await.is_ok() {}
+        });
+        let response = endpoint.handle(Request::post("/").body(body).unwrap()).await;
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    #[tokio::test]
+    async fn test_handle_interaction() {
+        let key_pair = Ed25519KeyPair::from_seed_unchecked(&[7; 32]).unwrap();
+        let endpoint =
+            InteractionsEndpoint::new(&hex(key_pair.public_key().as_ref()), Handler).unwrap();
+
+        let command = r#"{
+            "id": "1",
+            "application_id": "2",
+            "type": 2,
+            "data": {"id": "3", "name": "ping", "type": 1},
+            "channel_id": "4",
+            "user": {"id": "5", "username": "user", "discriminator": "0001", "avatar": null},
+            "token": "t",
+            "version": 1,
+            "locale": "en-US"
+        }"#;
+
+        let response = endpoint.handle(request(&key_pair, command)).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[CONTENT_TYPE], "application/json");
+        assert_eq!(body(response).await, json!({ "type": 4, "data": { "content": "Pong!" } }));
+    }
+}
@@ -0,0 +1,173 @@
+use std::fmt;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use ring::signature::{UnparsedPublicKey, ED25519};
+
+use super::{InteractionsEndpointError, TIMESTAMP_TOLERANCE};
+use crate::internal::prelude::*;
+
+/// Verifies that requests to an interactions endpoint were signed by Discord.
+///
+/// Discord signs each request with the private key of the application: the
+/// `X-Signature-Ed25519` header holds the Ed25519 signature of the
+/// `X-Signature-Timestamp` header followed by the body, which is checked
+/// against the [public key] of the application. As a signed request stays
+/// valid, the timestamp must also be within [`TIMESTAMP_TOLERANCE`] of the
+/// current time, so that captured requests can not be replayed later.
+///
+/// [Discord docs](https://discord.com/developers/docs/interactions/receiving-and-responding#security-and-authorization).
+///
+/// [public key]: crate::model::application::CurrentApplicationInfo::verify_key
+#[derive(Clone)]
+pub struct Verifier {
+    public_key: UnparsedPublicKey<[u8; 32]>,
+}
+
+impl Verifier {
+    /// Creates a verifier from the hex-encoded public key of the application.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InteractionsEndpointError::InvalidPublicKey`] if the key is
+    /// not 32 hex-encoded bytes.
+    pub fn new(public_key: &str) -> Result<Self> {
+        let mut key = [0; 32];
+
+        if !decode_hex(public_key, &mut key) {
+            return Err(InteractionsEndpointError::InvalidPublicKey.into());
+        }
+
+        Ok(Self {
+            public_key: UnparsedPublicKey::new(&ED25519, key),
+        })
+    }
+
+    /// Verifies the hex-encoded `signature` of a request with the given
+    /// `timestamp` and `body`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InteractionsEndpointError::InvalidTimestamp`] if the
+    /// timestamp is not within [`TIMESTAMP_TOLERANCE`] of the current time,
+    /// and [`InteractionsEndpointError::InvalidSignature`] if the signature is
+    /// malformed or does not match.
+    pub fn verify(&self, signature: &str, timestamp: &str, body: &[u8]) -> Result<()> {
+        check_timestamp(timestamp, SystemTime::now())?;
+        self.verify_signature(signature, timestamp, body)
+    }
+
+    fn verify_signature(&self, signature: &str, timestamp: &str, body: &[u8]) -> Result<()> {
+        let mut signature_bytes = [0; 64];
+
+        if !decode_hex(signature, &mut signature_bytes) {
+            return Err(InteractionsEndpointError::InvalidSignature.into());
+        }
+
+        let mut message = Vec::with_capacity(timestamp.len() + body.len());
+        message.extend_from_slice(timestamp.as_bytes());
+        message.extend_from_slice(body);
+
+        self.public_key
+            .verify(&message, &signature_bytes)
+            .map_err(|_| InteractionsEndpointError::InvalidSignature.into())
+    }
+}
+
+impl fmt::Debug for Verifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Verifier").finish()
+    }
+}
+
+/// Checks that a timestamp in seconds since the Unix epoch is within
+/// [`TIMESTAMP_TOLERANCE`] of `now`.
+fn check_timestamp(timestamp: &str, now: SystemTime) -> Result<()> {
+    let timestamp = timestamp
+        .parse::<u64>()
+        .map(|secs| UNIX_EPOCH + Duration::from_secs(secs))
+        .map_err(|_| InteractionsEndpointError::InvalidTimestamp)?;
+
+    let difference = match now.duration_since(timestamp) {
+        Ok(difference) => difference,
+        Err(why) => why.duration(),
+    };
+
+    if difference > TIMESTAMP_TOLERANCE {
+        return Err(InteractionsEndpointError::InvalidTimestamp.into());
+    }
+
+    Ok(())
+}
+
+/// Decodes a hex string into `out`, returning whether it had exactly as many
+/// bytes.
+fn decode_hex(hex: &str, out: &mut [u8]) -> bool {
+    if hex.len() != out.len() * 2 {
+        return false;
+    }
+
+    let digit = |c: u8| (c as char).to_digit(16);
+
+    for (byte, pair) in out.iter_mut().zip(hex.as_bytes().chunks(2)) {
+        match (digit(pair[0]), digit(pair[1])) {
+            (Some(high), Some(low)) => *byte = (high * 16 + low) as u8,
+            _ => return false,
+        }
+    }
+
+    true
+}
+
+#[cfg(test)]
+pub(super) mod test {
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    use ring::signature::{Ed25519KeyPair, KeyPair};
+
+    use super::{check_timestamp, decode_hex, Verifier};
+
+    pub(crate) fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+
+    #[test]
+    fn test_decode_hex() {
+        let mut out = [0; 2];
+        assert!(decode_hex("0aFf", &mut out));
+        assert_eq!(out, [0x0a, 0xff]);
+
+        assert!(!decode_hex("0a", &mut out));
+        assert!(!decode_hex("0aFg", &mut out));
+        assert!(!decode_hex("0a+f", &mut out));
+    }
+
+    #[test]
+    fn test_verify() {
+        let key_pair = Ed25519KeyPair::from_seed_unchecked(&[7; 32]).unwrap();
+        let verifier = Verifier::new(&hex(key_pair.public_key().as_ref())).unwrap();
+
+        let signature = hex(key_pair.sign(b"1700000000{\"type\":1}").as_ref());
+        assert!(verifier.verify_signature(&signature, "1700000000", b"{\"type\":1}").is_ok());
+        assert!(verifier.verify_signature(&signature, "1700000001", b"{\"type\":1}").is_err());
+        assert!(verifier.verify_signature(&signature, "1700000000", b"{\"type\":2}").is_err());
+        assert!(verifier.verify_signature("00", "1700000000", b"{\"type\":1}").is_err());
+
+        // The signature is valid, but the timestamp is long expired.
+        assert!(verifier.verify(&signature, "1700000000", b"{\"type\":1}").is_err());
+
+        assert!(Verifier::new("not a key").is_err());
+    }
+
+    #[test]
+    fn test_check_timestamp() {
+        let now = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+
+        assert!(check_timestamp("1700000000", now).is_ok());
+        assert!(check_timestamp("1699999990", now).is_ok());
+        assert!(check_timestamp("1700000010", now).is_ok());
+        assert!(check_timestamp("1699990000", now).is_err());
+        assert!(check_timestamp("1700010000", now).is_err());
+        assert!(check_timestamp("not a timestamp", now).is_err());
+        assert!(check_timestamp("", SystemTime::now()).is_err());
+    }
+}
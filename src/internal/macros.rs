@@ -1,5 +1,7 @@
 //! A set of macros for easily working with internals.
 
+// Not affected by `HttpBuilder::base_url`, as the model methods using this
+// have no access to an `Http` client.
 #[cfg(any(feature = "model", feature = "utils"))]
 macro_rules! cdn {
     ($e:expr) => {
@@ -1,33 +1,42 @@
 use std::io::Read;
 
 use async_trait::async_trait;
-use async_tungstenite::tungstenite::{
-    handshake::client::{generate_key, Request},
-    Message,
-};
+use async_tungstenite::tungstenite::handshake::client::{generate_key, Request};
+use async_tungstenite::tungstenite::Message;
 use flate2::read::ZlibDecoder;
+use flate2::{Decompress, FlushDecompress};
 use futures::{SinkExt, StreamExt};
 use tokio::time::timeout;
 use tracing::{instrument, warn};
 use url::Url;
 
-use crate::gateway::{GatewayError, WsStream};
+use crate::gateway::{etf, GatewayEncoding, GatewayError, WsStream};
 use crate::internal::prelude::*;
 use crate::json::{from_str, to_string};
 
 #[async_trait]
 pub trait ReceiverExt {
-    async fn recv_json(&mut self) -> Result<Option<Value>>;
+    async fn recv_json(
+        &mut self,
+        inflater: Option<&mut Inflater>,
+        encoding: GatewayEncoding,
+    ) -> Result<Option<Value>>;
 }
 
 #[async_trait]
 pub trait SenderExt {
     async fn send_json(&mut self, value: &Value) -> Result<()>;
+
+    async fn send_encoded(&mut self, value: &Value, encoding: GatewayEncoding) -> Result<()>;
 }
 
 #[async_trait]
 impl ReceiverExt for WsStream {
-    async fn recv_json(&mut self) -> Result<Option<Value>> {
+    async fn recv_json(
+        &mut self,
+        inflater: Option<&mut Inflater>,
+        encoding: GatewayEncoding,
+    ) -> Result<Option<Value>> {
         const TIMEOUT: tokio::time::Duration = tokio::time::Duration::from_millis(500);
 
         let ws_message = match timeout(TIMEOUT, self.next()).await {
@@ -36,7 +45,7 @@ impl ReceiverExt for WsStream {
             Ok(None) | Err(_) => None,
         };
 
-        convert_ws_message(ws_message)
+        convert_ws_message(ws_message, inflater, encoding)
     }
 }
 
@@ -45,13 +54,117 @@ impl SenderExt for WsStream {
     async fn send_json(&mut self, value: &Value) -> Result<()> {
         Ok(to_string(value).map(Message::Text).map_err(Error::from).map(|m| self.send(m))?.await?)
     }
+
+    async fn send_encoded(&mut self, value: &Value, encoding: GatewayEncoding) -> Result<()> {
+        match encoding {
+            GatewayEncoding::Json => self.send_json(value).await,
+            GatewayEncoding::Etf => Ok(self.send(Message::Binary(etf::to_vec(value)?)).await?),
+        }
+    }
+}
+
+/// The suffix of a message that completes a payload in a zlib-stream.
+const ZLIB_SUFFIX: [u8; 4] = [0x00, 0x00, 0xff, 0xff];
+
+const DECOMPRESSION_MULTIPLIER: usize = 3;
+
+/// A persistent zlib context for `zlib-stream` transport compression.
+///
+/// The whole connection is a single zlib stream, so the same context must be
+/// used for every message received over it, and a new one for each new
+/// connection. A payload may be split over multiple messages, in which case
+/// it is complete once a message ends with [`ZLIB_SUFFIX`].
+pub(crate) struct Inflater {
+    decompress: Decompress,
+    buffer: Vec<u8>,
+}
+
+impl Inflater {
+    pub(crate) fn new() -> Self {
+        Self {
+            decompress: Decompress::new(true),
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Feeds a binary message into the stream, returning the decompressed
+    /// payload if the message completed one.
+    fn inflate(&mut self, bytes: &[u8]) -> Result<Option<Vec<u8>>> {
+        self.buffer.extend_from_slice(bytes);
+
+        if !self.buffer.ends_with(&ZLIB_SUFFIX) {
+            return Ok(None);
+        }
+
+        let mut decompressed = Vec::with_capacity(self.buffer.len() * DECOMPRESSION_MULTIPLIER);
+        let mut offset = 0;
+
+        loop {
+            let total_in = self.decompress.total_in();
+            let total_out = self.decompress.total_out();
+
+            self.decompress
+                .decompress_vec(&self.buffer[offset..], &mut decompressed, FlushDecompress::Sync)
+                .map_err(|why| {
+                    warn!("Err decompressing zlib-stream: {:?}", why);
+
+                    std::io::Error::new(std::io::ErrorKind::InvalidData, why)
+                })?;
+
+            let consumed = (self.decompress.total_in() - total_in) as usize;
+            let produced = self.decompress.total_out() - total_out;
+            offset += consumed;
+
+            // Only data up to the capacity of the output is decompressed at a
+            // time, so keep going while it is filled up.
+            if decompressed.len() == decompressed.capacity() {
+                decompressed.reserve(self.buffer.len() * DECOMPRESSION_MULTIPLIER);
+            } else if offset >= self.buffer.len() || (consumed == 0 && produced == 0) {
+                break;
+            }
+        }
+
+        self.buffer.clear();
+
+        Ok(Some(decompressed))
+    }
+}
+
+/// Decodes a complete, uncompressed payload.
+fn decode(bytes: Vec<u8>, encoding: GatewayEncoding) -> Result<Value> {
+    let decoded = match encoding {
+        GatewayEncoding::Json => String::from_utf8(bytes)
+            .map_err(|why| std::io::Error::new(std::io::ErrorKind::InvalidData, why).into())
+            .and_then(|mut payload| from_str(payload.as_mut_str())),
+        GatewayEncoding::Etf => etf::from_slice(&bytes),
+    };
+
+    decoded.map_err(|why| {
+        warn!("Err decoding {:?} payload: {:?}", encoding, why);
+
+        why
+    })
 }
 
 #[inline]
-pub(crate) fn convert_ws_message(message: Option<Message>) -> Result<Option<Value>> {
-    const DECOMPRESSION_MULTIPLIER: usize = 3;
+pub(crate) fn convert_ws_message(
+    message: Option<Message>,
+    inflater: Option<&mut Inflater>,
+    encoding: GatewayEncoding,
+) -> Result<Option<Value>> {
+    if let (Some(Message::Binary(bytes)), Some(inflater)) = (&message, inflater) {
+        return match inflater.inflate(bytes)? {
+            Some(decompressed) => decode(decompressed, encoding).map(Some),
+            None => Ok(None),
+        };
+    }
 
     Ok(match message {
+        // Without transport compression, ETF payloads are sent uncompressed
+        // as binary messages.
+        Some(Message::Binary(bytes)) if encoding == GatewayEncoding::Etf => {
+            Some(decode(bytes, encoding)?)
+        },
         Some(Message::Binary(bytes)) => {
             let mut decompressed = String::with_capacity(bytes.len() * DECOMPRESSION_MULTIPLIER);
 
@@ -88,8 +201,9 @@ pub(crate) async fn create_client(url: Url) -> Result<WsStream> {
         max_send_queue: None,
         accept_unmasked_frames: false,
     };
+    let host = host_header(&url).ok_or(Error::Gateway(GatewayError::BuildingUrl))?;
     let req = Request::get(url.as_str())
-        .header("Host", "gateway.discord.gg")
+        .header("Host", host)
         .header(
             "User-Agent",
             "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/108.0.0.0 Safari/537.36",
@@ -115,3 +229,61 @@ pub(crate) async fn create_client(url: Url) -> Result<WsStream> {
 
     Ok(stream)
 }
+
+/// Builds the value of the `Host` header for connecting to the given URL,
+/// including the port if it is not the default one of the scheme.
+fn host_header(url: &Url) -> Option<String> {
+    let host = url.host_str()?;
+
+    Some(match url.port() {
+        Some(port) => format!("{}:{}", host, port),
+        None => host.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Write;
+    use std::mem;
+
+    use flate2::write::ZlibEncoder;
+    use flate2::Compression;
+    use url::Url;
+
+    use super::{host_header, Inflater};
+
+    #[test]
+    fn test_inflate_zlib_stream() {
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        let mut inflater = Inflater::new();
+
+        // Payloads share the zlib context of the connection.
+        for payload in ["{\"op\":11}", "{\"op\":11,\"d\":null}"] {
+            encoder.write_all(payload.as_bytes()).unwrap();
+            encoder.flush().unwrap();
+            let compressed = mem::take(encoder.get_mut());
+
+            let decompressed = inflater.inflate(&compressed).unwrap();
+            assert_eq!(decompressed.as_deref(), Some(payload.as_bytes()));
+        }
+
+        // A payload may be split over multiple messages.
+        let payload = "x".repeat(10_000);
+        encoder.write_all(payload.as_bytes()).unwrap();
+        encoder.flush().unwrap();
+        let compressed = mem::take(encoder.get_mut());
+        let (first, second) = compressed.split_at(compressed.len() / 2);
+
+        assert_eq!(inflater.inflate(first).unwrap(), None);
+        assert_eq!(inflater.inflate(second).unwrap(), Some(payload.into_bytes()));
+    }
+
+    #[test]
+    fn test_host_header() {
+        let url = Url::parse("wss://gateway.discord.gg/?v=10").unwrap();
+        assert_eq!(host_header(&url).as_deref(), Some("gateway.discord.gg"));
+
+        let url = Url::parse("ws://127.0.0.1:8080/?v=10").unwrap();
+        assert_eq!(host_header(&url).as_deref(), Some("127.0.0.1:8080"));
+    }
+}
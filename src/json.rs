@@ -77,6 +77,28 @@ where
     Ok(simd_json::from_str(s)?)
 }
 
+#[cfg(all(
+    any(feature = "cache", feature = "testing", feature = "interactions_endpoint"),
+    not(feature = "simd-json")
+))]
+pub(crate) fn from_slice<T>(v: &mut [u8]) -> Result<T>
+where
+    T: DeserializeOwned,
+{
+    Ok(serde_json::from_slice(v)?)
+}
+
+#[cfg(all(
+    any(feature = "cache", feature = "testing", feature = "interactions_endpoint"),
+    feature = "simd-json"
+))]
+pub(crate) fn from_slice<T>(v: &mut [u8]) -> Result<T>
+where
+    T: DeserializeOwned,
+{
+    Ok(simd_json::from_slice(v)?)
+}
+
 #[cfg(not(feature = "simd-json"))]
 pub(crate) fn from_value<T>(v: Value) -> Result<T>
 where
@@ -1,6 +1,8 @@
 //! Models about OAuth2 applications.
 
 pub mod command;
+#[cfg(feature = "model")]
+pub mod command_sync;
 pub mod component;
 pub mod interaction;
 pub mod oauth;
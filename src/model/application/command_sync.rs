@@ -0,0 +1,564 @@
+//! Declaring application commands once and synchronising them with Discord.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::Deserialize;
+
+use super::command::{Command, CommandOptionType, CommandType};
+use crate::builder::CreateApplicationCommand;
+use crate::http::Http;
+use crate::internal::prelude::*;
+use crate::json::{self, Value};
+use crate::model::channel::ChannelType;
+use crate::model::id::{CommandId, GuildId};
+use crate::model::{ModelError, Permissions};
+
+/// A set of declared application commands, which can be synchronised with the
+/// commands registered on Discord.
+///
+/// Unlike [`Command::set_global_application_commands`], which overwrites every
+/// command on each call, synchronising first fetches the registered commands
+/// and compares them with the declared ones, and then only creates the new
+/// commands, edits those that changed, and deletes those that are no longer
+/// declared. Commands are identified by their name and kind, so renaming a
+/// command deletes it and creates a new one.
+///
+/// With [`Self::dry_run`], no change is made, and only the planned changes are
+/// returned.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # use serenity::http::Http;
+/// #
+/// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+/// #     let http = Http::new("token");
+/// use serenity::model::application::command::CommandOptionType;
+/// use serenity::model::application::command_sync::CommandRegistry;
+/// use serenity::model::Permissions;
+///
+/// let mut registry = CommandRegistry::new();
+/// registry.command(|command| command.name("ping").description("Checks the latency"));
+/// registry.command(|command| {
+///     command
+///         .name("ban")
+///         .description("Bans a member")
+///         .description_localized("de", "Bannt ein Mitglied")
+///         .default_member_permissions(Permissions::BAN_MEMBERS)
+///         .create_option(|option| {
+///             option
+///                 .name("member")
+///                 .description("The member to ban")
+///                 .kind(CommandOptionType::User)
+///                 .required(true)
+///         })
+/// });
+///
+/// for change in registry.sync_global(&http).await? {
+///     println!("{}", change);
+/// }
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct CommandRegistry {
+    commands: Vec<CreateApplicationCommand>,
+    dry_run: bool,
+}
+
+impl CommandRegistry {
+    /// Creates an empty registry.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Declares a command.
+    pub fn command<F>(&mut self, f: F) -> &mut Self
+    where
+        F: FnOnce(&mut CreateApplicationCommand) -> &mut CreateApplicationCommand,
+    {
+        let mut command = CreateApplicationCommand::default();
+        f(&mut command);
+
+        self.add_command(command)
+    }
+
+    /// Declares an already built command.
+    pub fn add_command(&mut self, command: CreateApplicationCommand) -> &mut Self {
+        self.commands.push(command);
+
+        self
+    }
+
+    /// Sets whether synchronising only reports the planned changes, without
+    /// making them.
+    ///
+    /// **Note**: This defaults to `false`.
+    pub fn dry_run(&mut self, dry_run: bool) -> &mut Self {
+        self.dry_run = dry_run;
+
+        self
+    }
+
+    /// Computes the changes needed for the `registered` global commands to
+    /// match the declared ones, without making any request.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ModelError::DuplicateCommand`] if two declared commands have
+    /// the same name and kind, or an [`Error::Json`] if a declared command
+    /// is missing its name.
+    pub fn plan(&self, registered: &[Command]) -> Result<Vec<CommandChange>> {
+        Ok(self.diff(registered, false)?.into_iter().map(|(change, _)| change).collect())
+    }
+
+    /// Synchronises the declared commands with the global commands of the
+    /// application, returning the changes that were made, or would be made
+    /// in a [dry run].
+    ///
+    /// **Note**: Changes to global commands may take up to an hour to appear
+    /// in clients.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Self::plan`], and an [`Error::Http`] if
+    /// fetching the registered commands or making a change failed. Changes
+    /// made before the failure are not reverted.
+    ///
+    /// [dry run]: Self::dry_run
+    pub async fn sync_global(&self, http: impl AsRef<Http>) -> Result<Vec<CommandChange>> {
+        let http = http.as_ref();
+        let registered = http.get_global_application_commands_with_localizations().await?;
+        let changes = self.diff(&registered, false)?;
+
+        if !self.dry_run {
+            for (change, payload) in &changes {
+                match change {
+                    CommandChange::Create {
+                        ..
+                    } => {
+                        http.create_global_application_command(payload).await?;
+                    },
+                    CommandChange::Edit {
+                        id, ..
+                    } => {
+                        http.edit_global_application_command(id.0, payload).await?;
+                    },
+                    CommandChange::Delete {
+                        id, ..
+                    } => http.delete_global_application_command(id.0).await?,
+                }
+            }
+        }
+
+        Ok(changes.into_iter().map(|(change, _)| change).collect())
+    }
+
+    /// Synchronises the declared commands with the commands of the
+    /// application in a guild, returning the changes that were made, or would
+    /// be made in a [dry run].
+    ///
+    /// The [`dm_permission`] of the declared commands is ignored, as it only
+    /// applies to global commands.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Self::sync_global`].
+    ///
+    /// [dry run]: Self::dry_run
+    /// [`dm_permission`]: CreateApplicationCommand::dm_permission
+    pub async fn sync_guild(
+        &self,
+        http: impl AsRef<Http>,
+        guild_id: impl Into<GuildId>,
+    ) -> Result<Vec<CommandChange>> {
+        let http = http.as_ref();
+        let guild_id = guild_id.into();
+        let registered = http.get_guild_application_commands_with_localizations(guild_id.0).await?;
+        let changes = self.diff(&registered, true)?;
+
+        if !self.dry_run {
+            for (change, payload) in &changes {
+                match change {
+                    CommandChange::Create {
+                        ..
+                    } => {
+                        http.create_guild_application_command(guild_id.0, payload).await?;
+                    },
+                    CommandChange::Edit {
+                        id, ..
+                    } => {
+                        http.edit_guild_application_command(guild_id.0, id.0, payload).await?;
+                    },
+                    CommandChange::Delete {
+                        id, ..
+                    } => http.delete_guild_application_command(guild_id.0, id.0).await?,
+                }
+            }
+        }
+
+        Ok(changes.into_iter().map(|(change, _)| change).collect())
+    }
+
+    /// Compares the declared commands with the registered ones, returning the
+    /// changes along with the payloads of the commands to create or edit, or
+    /// null for the commands to delete.
+    ///
+    /// Commands are deleted first, so that the command limit is not exceeded
+    /// by the ones replacing them.
+    fn diff(&self, registered: &[Command], guild: bool) -> Result<Vec<(CommandChange, Value)>> {
+        let mut declared = Vec::with_capacity(self.commands.len());
+
+        for command in &self.commands {
+            let payload = Value::from(json::hashmap_to_json_map(command.0.clone()));
+            let shape = CommandShape::new(json::from_value(payload.clone())?, guild);
+
+            if declared.iter().any(|(other, _): &(CommandShape, Value)| other.key() == shape.key())
+            {
+                return Err(ModelError::DuplicateCommand(shape.name).into());
+            }
+
+            declared.push((shape, payload));
+        }
+
+        let mut registered = registered
+            .iter()
+            .map(|command| {
+                let shape = CommandShape::new(json::from_value(json::to_value(command)?)?, guild);
+
+                Ok((shape.key(), (command.id, shape)))
+            })
+            .collect::<Result<HashMap<_, _>>>()?;
+
+        let mut changes = Vec::new();
+
+        for (shape, payload) in declared {
+            match registered.remove(&shape.key()) {
+                Some((_, existing)) if existing == shape => {},
+                Some((id, _)) => changes.push((
+                    CommandChange::Edit {
+                        id,
+                        name: shape.name,
+                        kind: shape.kind,
+                    },
+                    payload,
+                )),
+                None => changes.push((
+                    CommandChange::Create {
+                        name: shape.name,
+                        kind: shape.kind,
+                    },
+                    payload,
+                )),
+            }
+        }
+
+        let mut deleted = registered
+            .into_iter()
+            .map(|(_, (id, shape))| {
+                let change = CommandChange::Delete {
+                    id,
+                    name: shape.name,
+                    kind: shape.kind,
+                };
+
+                (change, json::NULL)
+            })
+            .collect::<Vec<_>>();
+        deleted.sort_by_key(|(change, _)| change.name().to_string());
+        deleted.extend(changes);
+
+        Ok(deleted)
+    }
+}
+
+/// A change made to the registered application commands by a
+/// [`CommandRegistry`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CommandChange {
+    /// A declared command that was not registered.
+    Create {
+        /// The name of the command.
+        name: String,
+        /// The type of the command.
+        kind: CommandType,
+    },
+    /// A registered command that differs from its declaration.
+    Edit {
+        /// The Id of the registered command.
+        id: CommandId,
+        /// The name of the command.
+        name: String,
+        /// The type of the command.
+        kind: CommandType,
+    },
+    /// A registered command that is no longer declared.
+    Delete {
+        /// The Id of the registered command.
+        id: CommandId,
+        /// The name of the command.
+        name: String,
+        /// The type of the command.
+        kind: CommandType,
+    },
+}
+
+impl CommandChange {
+    /// The name of the command.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Create {
+                name, ..
+            }
+            | Self::Edit {
+                name, ..
+            }
+            | Self::Delete {
+                name, ..
+            } => name,
+        }
+    }
+}
+
+impl fmt::Display for CommandChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Create {
+                name, ..
+            } => write!(f, "Create command `{}`", name),
+            Self::Edit {
+                id,
+                name,
+                ..
+            } => write!(f, "Edit command `{}` ({})", name, id),
+            Self::Delete {
+                id,
+                name,
+                ..
+            } => write!(f, "Delete command `{}` ({})", name, id),
+        }
+    }
+}
+
+fn chat_input() -> CommandType {
+    CommandType::ChatInput
+}
+
+/// The fields of a command that can be declared, used to compare declared and
+/// registered commands regardless of which optional fields are omitted.
+#[derive(Debug, Deserialize, PartialEq)]
+struct CommandShape {
+    name: String,
+    #[serde(rename = "type", default = "chat_input")]
+    kind: CommandType,
+    #[serde(default)]
+    description: String,
+    name_localizations: Option<HashMap<String, String>>,
+    description_localizations: Option<HashMap<String, String>>,
+    #[serde(default)]
+    options: Vec<OptionShape>,
+    default_member_permissions: Option<Permissions>,
+    dm_permission: Option<bool>,
+}
+
+impl CommandShape {
+    fn new(mut shape: Self, guild: bool) -> Self {
+        shape.name_localizations = shape.name_localizations.filter(|map| !map.is_empty());
+        shape.description_localizations =
+            shape.description_localizations.filter(|map| !map.is_empty());
+        shape.dm_permission = if guild { None } else { Some(shape.dm_permission.unwrap_or(true)) };
+
+        for option in &mut shape.options {
+            option.normalize();
+        }
+
+        shape
+    }
+
+    fn key(&self) -> (String, CommandType) {
+        (self.name.clone(), self.kind)
+    }
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct OptionShape {
+    #[serde(rename = "type")]
+    kind: CommandOptionType,
+    name: String,
+    name_localizations: Option<HashMap<String, String>>,
+    description: String,
+    description_localizations: Option<HashMap<String, String>>,
+    #[serde(default)]
+    required: bool,
+    #[serde(default)]
+    choices: Vec<ChoiceShape>,
+    #[serde(default)]
+    options: Vec<OptionShape>,
+    #[serde(default)]
+    channel_types: Vec<ChannelType>,
+    min_value: Option<f64>,
+    max_value: Option<f64>,
+    min_length: Option<u16>,
+    max_length: Option<u16>,
+    #[serde(default)]
+    autocomplete: bool,
+}
+
+impl OptionShape {
+    fn normalize(&mut self) {
+        self.name_localizations = self.name_localizations.take().filter(|map| !map.is_empty());
+        self.description_localizations =
+            self.description_localizations.take().filter(|map| !map.is_empty());
+
+        for choice in &mut self.choices {
+            choice.name_localizations =
+                choice.name_localizations.take().filter(|map| !map.is_empty());
+        }
+
+        for option in &mut self.options {
+            option.normalize();
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct ChoiceShape {
+    name: String,
+    name_localizations: Option<HashMap<String, String>>,
+    value: ChoiceValue,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(untagged)]
+enum ChoiceValue {
+    Number(f64),
+    String(String),
+}
+
+#[cfg(test)]
+mod test {
+    use super::{CommandChange, CommandRegistry};
+    use crate::json::{from_value, json};
+    use crate::model::application::command::{Command, CommandOptionType, CommandType};
+    use crate::model::id::CommandId;
+    use crate::model::{ModelError, Permissions};
+    use crate::Error;
+
+    fn registered(id: u64, name: &str, extra: crate::json::Value) -> Command {
+        let mut command = json!({
+            "id": id.to_string(),
+            "type": 1,
+            "application_id": "1",
+            "name": name,
+            "description": "A command",
+            "default_member_permissions": null,
+            "dm_permission": true,
+            "version": "1",
+        });
+
+        for (key, value) in extra.as_object().unwrap() {
+            command[key] = value.clone();
+        }
+
+        from_value(command).unwrap()
+    }
+
+    #[test]
+    fn test_plan() {
+        let mut registry = CommandRegistry::new();
+        registry
+            .command(|command| command.name("ping").description("A command"))
+            .command(|command| {
+                command
+                    .name("ban")
+                    .description("A command")
+                    .default_member_permissions(Permissions::BAN_MEMBERS)
+                    .create_option(|option| {
+                        option
+                            .name("days")
+                            .description("Days of messages to delete")
+                            .kind(CommandOptionType::Integer)
+                            .min_int_value(0)
+                            .max_int_value(7)
+                            .add_int_choice("None", 0)
+                    })
+            })
+            .command(|command| command.name("new").description("A command"))
+            .command(|command| command.name("Report").kind(CommandType::Message));
+
+        let ban_options = json!({
+            "default_member_permissions": "4",
+            "options": [{
+                "type": 4,
+                "name": "days",
+                "description": "Days of messages to delete",
+                "min_value": 0.0,
+                "max_value": 7,
+                "choices": [{"name": "None", "value": 0}],
+            }],
+        });
+
+        let unchanged = vec![
+            registered(1, "ping", json!({})),
+            registered(2, "ban", ban_options.clone()),
+            registered(3, "Report", json!({"type": 3, "description": ""})),
+        ];
+        assert_eq!(registry.plan(&unchanged).unwrap(), vec![CommandChange::Create {
+            name: "new".into(),
+            kind: CommandType::ChatInput,
+        }]);
+
+        let mut edited_options = ban_options;
+        edited_options["options"][0]["max_value"] = json!(14);
+        let changed = vec![
+            registered(1, "ping", json!({"description_localizations": {"de": "Ein Befehl"}})),
+            registered(2, "ban", edited_options),
+            registered(3, "Report", json!({"type": 3, "description": ""})),
+            registered(4, "old", json!({})),
+            registered(5, "new", json!({"type": 2, "description": ""})),
+        ];
+        assert_eq!(registry.plan(&changed).unwrap(), vec![
+            CommandChange::Delete {
+                id: CommandId(5),
+                name: "new".into(),
+                kind: CommandType::User,
+            },
+            CommandChange::Delete {
+                id: CommandId(4),
+                name: "old".into(),
+                kind: CommandType::ChatInput,
+            },
+            CommandChange::Edit {
+                id: CommandId(1),
+                name: "ping".into(),
+                kind: CommandType::ChatInput,
+            },
+            CommandChange::Edit {
+                id: CommandId(2),
+                name: "ban".into(),
+                kind: CommandType::ChatInput,
+            },
+            CommandChange::Create {
+                name: "new".into(),
+                kind: CommandType::ChatInput,
+            },
+        ]);
+    }
+
+    #[test]
+    fn test_plan_duplicates() {
+        let mut registry = CommandRegistry::new();
+        registry
+            .command(|command| command.name("ping").description("A command"))
+            .command(|command| command.name("ping").description("Another command"));
+
+        assert!(matches!(
+            registry.plan(&[]),
+            Err(Error::Model(ModelError::DuplicateCommand(name))) if name == "ping"
+        ));
+    }
+}
@@ -343,3 +343,9 @@ pub enum InteractionResponseType {
     Autocomplete = 8,
     Modal = 9,
 }
+
+impl Serialize for InteractionResponseType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(*self as u8)
+    }
+}
@@ -30,7 +30,7 @@ use crate::collector::{
     ReactionCollectorBuilder,
 };
 #[cfg(feature = "model")]
-use crate::http::{CacheHttp, Http, Typing};
+use crate::http::{payload, CacheHttp, Http, Typing};
 #[cfg(feature = "model")]
 use crate::json::{self, json};
 #[cfg(feature = "model")]
@@ -212,7 +212,7 @@ impl ChannelId {
         It: IntoIterator<Item = T>,
     {
         let ids =
-            message_ids.into_iter().map(|message_id| message_id.as_ref().0).collect::<Vec<u64>>();
+            message_ids.into_iter().map(|message_id| *message_id.as_ref()).collect::<Vec<_>>();
 
         let len = ids.len();
 
@@ -223,9 +223,11 @@ impl ChannelId {
         if ids.len() == 1 {
             self.delete_message(http, ids[0]).await
         } else {
-            let map = json!({ "messages": ids });
+            let payload = payload::DeleteMessages {
+                messages: ids,
+            };
 
-            http.as_ref().delete_messages(self.0, &map).await
+            http.as_ref().delete_messages(self.0, &payload).await
         }
     }
 
@@ -405,8 +407,8 @@ impl ChannelId {
     /// Attempts to find a [`Channel`] by its Id in the cache.
     #[cfg(feature = "cache")]
     #[inline]
-    pub fn to_channel_cached(self, cache: impl AsRef<Cache>) -> Option<Channel> {
-        cache.as_ref().channel(self)
+    pub async fn to_channel_cached(self, cache: impl AsRef<Cache>) -> Option<Channel> {
+        cache.as_ref().channel(self).await
     }
 
     /// First attempts to find a [`Channel`] by its Id in the cache,
@@ -422,7 +424,7 @@ impl ChannelId {
         #[cfg(feature = "cache")]
         {
             if let Some(cache) = cache_http.cache() {
-                if let Some(channel) = cache.channel(self) {
+                if let Some(channel) = cache.channel(self).await {
                     return Ok(channel);
                 }
             }
@@ -554,9 +556,8 @@ impl ChannelId {
 
     /// Returns the name of whatever channel this id holds.
     #[cfg(feature = "cache")]
-    #[allow(clippy::unused_async)]
     pub async fn name(self, cache: impl AsRef<Cache>) -> Option<String> {
-        let channel = self.to_channel_cached(cache)?;
+        let channel = self.to_channel_cached(cache).await?;
 
         Some(match channel {
             Channel::Guild(channel) => channel.name().to_string(),
@@ -3,9 +3,6 @@ use std::fmt;
 #[cfg(feature = "model")]
 use std::sync::Arc;
 
-#[cfg(feature = "cache")]
-use futures::stream::StreamExt;
-
 #[cfg(feature = "model")]
 use crate::builder::EditChannel;
 #[cfg(feature = "model")]
@@ -242,7 +239,8 @@ impl GuildChannel {
                     self.id,
                     Some(self.guild_id),
                     Permissions::CREATE_INSTANT_INVITE,
-                )?;
+                )
+                .await?;
             }
         }
 
@@ -284,7 +282,7 @@ impl GuildChannel {
     ///     kind: PermissionOverwriteType::Member(user_id),
     /// };
     /// // assuming the cache has been unlocked
-    /// let channel = cache.guild_channel(channel_id).ok_or(ModelError::ItemMissing)?;
+    /// let channel = cache.guild_channel(channel_id).await.ok_or(ModelError::ItemMissing)?;
     ///
     /// channel.create_permission(&http, &overwrite).await?;
     /// #   Ok(())
@@ -317,7 +315,7 @@ impl GuildChannel {
     ///     kind: PermissionOverwriteType::Member(user_id),
     /// };
     ///
-    /// let channel = cache.guild_channel(channel_id).ok_or(ModelError::ItemMissing)?;
+    /// let channel = cache.guild_channel(channel_id).await.ok_or(ModelError::ItemMissing)?;
     ///
     /// channel.create_permission(&http, &overwrite).await?;
     /// #     Ok(())
@@ -363,7 +361,8 @@ impl GuildChannel {
                     self.id,
                     Some(self.guild_id),
                     Permissions::MANAGE_CHANNELS,
-                )?;
+                )
+                .await?;
             }
         }
 
@@ -467,7 +466,8 @@ impl GuildChannel {
                     self.id,
                     Some(self.guild_id),
                     Permissions::MANAGE_CHANNELS,
-                )?;
+                )
+                .await?;
             }
         }
 
@@ -547,7 +547,7 @@ impl GuildChannel {
     /// use serenity::model::ModelError;
     ///
     /// // assuming the cache has been unlocked
-    /// let channel = cache.guild_channel(channel_id).ok_or(ModelError::ItemMissing)?;
+    /// let channel = cache.guild_channel(channel_id).await.ok_or(ModelError::ItemMissing)?;
     ///
     /// channel.edit_voice_state(&http, user_id, |v| v.suppress(false)).await?;
     /// #   Ok(())
@@ -597,7 +597,7 @@ impl GuildChannel {
     /// use serenity::model::ModelError;
     ///
     /// // assuming the cache has been unlocked
-    /// let channel = cache.guild_channel(channel_id).ok_or(ModelError::ItemMissing)?;
+    /// let channel = cache.guild_channel(channel_id).await.ok_or(ModelError::ItemMissing)?;
     ///
     /// // Send a request to speak
     /// channel.edit_own_voice_state(&http, |v| v.request_to_speak(true)).await?;
@@ -671,8 +671,8 @@ impl GuildChannel {
     /// Attempts to find this channel's guild in the Cache.
     #[cfg(feature = "cache")]
     #[inline]
-    pub fn guild(&self, cache: impl AsRef<Cache>) -> Option<Guild> {
-        cache.as_ref().guild(self.guild_id)
+    pub async fn guild(&self, cache: impl AsRef<Cache>) -> Option<Guild> {
+        cache.as_ref().guild(self.guild_id).await
     }
 
     /// Gets all of the channel's invites.
@@ -764,12 +764,13 @@ impl GuildChannel {
     /// #[serenity::async_trait]
     /// impl EventHandler for Handler {
     ///     async fn message(&self, context: Context, msg: Message) {
-    ///         let channel = match context.cache.guild_channel(msg.channel_id) {
+    ///         let channel = match context.cache.guild_channel(msg.channel_id).await {
     ///             Some(channel) => channel,
     ///             None => return,
     ///         };
     ///
-    ///         if let Ok(permissions) = channel.permissions_for_user(&context.cache, &msg.author) {
+    ///         if let Ok(permissions) = channel.permissions_for_user(&context.cache, &msg.author).await
+    ///         {
     ///             println!("The user's permissions: {:?}", permissions);
     ///         }
     ///     }
@@ -799,13 +800,15 @@ impl GuildChannel {
     /// #[serenity::async_trait]
     /// impl EventHandler for Handler {
     ///     async fn message(&self, context: Context, mut msg: Message) {
-    ///         let channel = match context.cache.guild_channel(msg.channel_id) {
+    ///         let channel = match context.cache.guild_channel(msg.channel_id).await {
     ///             Some(channel) => channel,
     ///             None => return,
     ///         };
     ///
     ///         let current_user_id = context.cache.current_user_id();
-    ///         if let Ok(permissions) = channel.permissions_for_user(&context.cache, current_user_id) {
+    ///         if let Ok(permissions) =
+    ///             channel.permissions_for_user(&context.cache, current_user_id).await
+    ///         {
     ///             if !permissions.contains(Permissions::ATTACH_FILES | Permissions::SEND_MESSAGES) {
     ///                 return;
     ///             }
@@ -847,15 +850,23 @@ impl GuildChannel {
     /// [Send Messages]: Permissions::SEND_MESSAGES
     #[cfg(feature = "cache")]
     #[inline]
-    pub fn permissions_for_user(
+    pub async fn permissions_for_user(
         &self,
         cache: impl AsRef<Cache>,
         user_id: impl Into<UserId>,
     ) -> Result<Permissions> {
-        let guild = self.guild(&cache).ok_or(Error::Model(ModelError::GuildNotFound))?;
-        let member =
-            guild.members.get(&user_id.into()).ok_or(Error::Model(ModelError::MemberNotFound))?;
-        guild.user_permissions_in(self, member)
+        let user_id = user_id.into();
+
+        cache
+            .as_ref()
+            .guild_field(self.guild_id, |guild| {
+                let member =
+                    guild.members.get(&user_id).ok_or(Error::Model(ModelError::MemberNotFound))?;
+
+                guild.user_permissions_in(self, member)
+            })
+            .await
+            .ok_or(Error::Model(ModelError::GuildNotFound))?
     }
 
     /// Calculates the permissions of a role.
@@ -872,15 +883,23 @@ impl GuildChannel {
     /// be found in the [`Cache`].
     #[cfg(feature = "cache")]
     #[inline]
-    pub fn permissions_for_role(
+    pub async fn permissions_for_role(
         &self,
         cache: impl AsRef<Cache>,
         role_id: impl Into<RoleId>,
     ) -> Result<Permissions> {
-        let guild = self.guild(&cache).ok_or(Error::Model(ModelError::GuildNotFound))?;
-        let role =
-            guild.roles.get(&role_id.into()).ok_or(Error::Model(ModelError::RoleNotFound))?;
-        guild.role_permissions_in(self, role)
+        let role_id = role_id.into();
+
+        cache
+            .as_ref()
+            .guild_field(self.guild_id, |guild| {
+                let role =
+                    guild.roles.get(&role_id).ok_or(Error::Model(ModelError::RoleNotFound))?;
+
+                guild.role_permissions_in(self, role)
+            })
+            .await
+            .ok_or(Error::Model(ModelError::GuildNotFound))?
     }
 
     /// Pins a [`Message`] to the channel.
@@ -1024,7 +1043,9 @@ impl GuildChannel {
             if let Some(cache) = cache_http.cache() {
                 let req = Permissions::SEND_MESSAGES;
 
-                if let Ok(false) = utils::user_has_perms(cache, self.id, Some(self.guild_id), req) {
+                if let Ok(false) =
+                    utils::user_has_perms(cache, self.id, Some(self.guild_id), req).await
+                {
                     return Err(Error::Model(ModelError::InvalidPermissions(req)));
                 }
             }
@@ -1064,6 +1085,7 @@ impl GuildChannel {
     /// # let cache = Cache::default();
     /// # let channel = cache
     /// #    .guild_channel(ChannelId(7))
+    /// #    .await
     /// #    .ok_or(ModelError::ItemMissing)?;
     /// // Initiate typing (assuming http is `Arc<Http>` and `channel` is bound)
     /// let typing = channel.start_typing(&http)?;
@@ -1129,41 +1151,29 @@ impl GuildChannel {
     #[cfg(feature = "cache")]
     #[inline]
     pub async fn members(&self, cache: impl AsRef<Cache>) -> Result<Vec<Member>> {
-        let cache = cache.as_ref();
-        let guild = cache.guild(self.guild_id).ok_or(ModelError::GuildNotFound)?;
-
-        match self.kind {
-            ChannelType::Voice | ChannelType::Stage => Ok(guild
-                .voice_states
-                .values()
-                .filter_map(|v| {
-                    v.channel_id.and_then(|c| {
-                        if c == self.id {
-                            guild.members.get(&v.user_id).cloned()
-                        } else {
-                            None
-                        }
+        cache
+            .as_ref()
+            .guild_field(self.guild_id, |guild| match self.kind {
+                ChannelType::Voice | ChannelType::Stage => Ok(guild
+                    .voice_states
+                    .values()
+                    .filter(|v| v.channel_id == Some(self.id))
+                    .filter_map(|v| guild.members.get(&v.user_id).cloned())
+                    .collect()),
+                ChannelType::News | ChannelType::Text => Ok(guild
+                    .members
+                    .values()
+                    .filter(|member| {
+                        guild
+                            .user_permissions_in(self, member)
+                            .map_or(false, |p| p.contains(Permissions::VIEW_CHANNEL))
                     })
-                })
-                .collect()),
-            ChannelType::News | ChannelType::Text => {
-                Ok(futures::stream::iter(guild.members.iter())
-                    .filter_map(|e| async move {
-                        if self
-                            .permissions_for_user(cache, e.0)
-                            .map(|p| p.contains(Permissions::VIEW_CHANNEL))
-                            .unwrap_or(false)
-                        {
-                            Some(e.1.clone())
-                        } else {
-                            None
-                        }
-                    })
-                    .collect::<Vec<Member>>()
-                    .await)
-            },
-            _ => Err(Error::from(ModelError::InvalidChannelType)),
-        }
+                    .cloned()
+                    .collect()),
+                _ => Err(Error::from(ModelError::InvalidChannelType)),
+            })
+            .await
+            .ok_or(ModelError::GuildNotFound)?
     }
 
     /// Returns a future that will await one message by this guild channel.
@@ -168,7 +168,8 @@ impl Message {
                         self.channel_id,
                         self.guild_id,
                         Permissions::MANAGE_MESSAGES,
-                    )?;
+                    )
+                    .await?;
                 }
             }
         }
@@ -229,7 +230,8 @@ impl Message {
                         self.channel_id,
                         self.guild_id,
                         Permissions::MANAGE_MESSAGES,
-                    )?;
+                    )
+                    .await?;
                 }
             }
         }
@@ -257,7 +259,8 @@ impl Message {
                     self.channel_id,
                     self.guild_id,
                     Permissions::MANAGE_MESSAGES,
-                )?;
+                )
+                .await?;
             }
         }
 
@@ -288,7 +291,8 @@ impl Message {
                     self.channel_id,
                     self.guild_id,
                     Permissions::MANAGE_MESSAGES,
-                )?;
+                )
+                .await?;
             }
         }
 
@@ -398,7 +402,7 @@ impl Message {
     /// Returns message content, but with user and role mentions replaced with
     /// names and everyone/here mentions cancelled.
     #[cfg(feature = "cache")]
-    pub fn content_safe(&self, cache: impl AsRef<Cache>) -> String {
+    pub async fn content_safe(&self, cache: impl AsRef<Cache>) -> String {
         let mut result = self.content.clone();
 
         // First replace all user mentions.
@@ -423,7 +427,7 @@ impl Message {
         for id in &self.mention_roles {
             let mention = id.mention().to_string();
 
-            if let Some(role) = id.to_role_cached(&cache) {
+            if let Some(role) = id.to_role_cached(&cache).await {
                 result = result.replace(&mention, &format!("@{}", role.name));
             } else {
                 result = result.replace(&mention, "@deleted-role");
@@ -473,8 +477,8 @@ impl Message {
     ///
     /// Requires the `cache` feature be enabled.
     #[cfg(feature = "cache")]
-    pub fn guild(&self, cache: impl AsRef<Cache>) -> Option<Guild> {
-        cache.as_ref().guild(self.guild_id?)
+    pub async fn guild(&self, cache: impl AsRef<Cache>) -> Option<Guild> {
+        cache.as_ref().guild(self.guild_id?).await
     }
 
     /// Returns a field to the [`Guild`] for the message if one is in the cache.
@@ -485,16 +489,16 @@ impl Message {
     ///
     /// Requires the `cache` feature be enabled.
     #[cfg(feature = "cache")]
-    pub fn guild_field<Ret, Fun>(
+    pub async fn guild_field<Ret, Fun>(
         &self,
         cache: impl AsRef<Cache>,
         field_accessor: Fun,
     ) -> Option<Ret>
     where
-        Ret: Clone,
-        Fun: FnOnce(&Guild) -> Ret,
+        Ret: Clone + Send,
+        Fun: FnOnce(&Guild) -> Ret + Send,
     {
-        cache.as_ref().guild_field(self.guild_id?, field_accessor)
+        cache.as_ref().guild_field(self.guild_id?, field_accessor).await
     }
 
     /// True if message was sent using direct messages.
@@ -522,7 +526,7 @@ impl Message {
         #[cfg(feature = "cache")]
         {
             if let Some(cache) = cache_http.cache() {
-                if let Some(member) = cache.member(guild_id, self.author.id) {
+                if let Some(member) = cache.member(guild_id, self.author.id).await {
                     return Ok(member);
                 }
             }
@@ -571,7 +575,8 @@ impl Message {
                         self.channel_id,
                         self.guild_id,
                         Permissions::MANAGE_MESSAGES,
-                    )?;
+                    )
+                    .await?;
                 }
             }
         }
@@ -617,7 +622,8 @@ impl Message {
                         self.channel_id,
                         self.guild_id,
                         Permissions::ADD_REACTIONS,
-                    )?;
+                    )
+                    .await?;
                 }
 
                 user_id = Some(cache.current_user_id());
@@ -735,7 +741,8 @@ impl Message {
                         self.channel_id,
                         self.guild_id,
                         Permissions::SEND_MESSAGES,
-                    )?;
+                    )
+                    .await?;
                 }
             }
         }
@@ -780,7 +787,8 @@ impl Message {
                     self.channel_id,
                     self.guild_id,
                     Permissions::MANAGE_MESSAGES,
-                )?;
+                )
+                .await?;
 
                 if self.author.id != cache.current_user_id() {
                     return Err(Error::Model(ModelError::NotAuthor));
@@ -852,7 +860,8 @@ impl Message {
                         self.channel_id,
                         self.guild_id,
                         Permissions::MANAGE_MESSAGES,
-                    )?;
+                    )
+                    .await?;
                 }
             }
         }
@@ -15,6 +15,8 @@ mod reaction;
 use std::error::Error as StdError;
 use std::fmt;
 
+#[cfg(all(feature = "cache", feature = "model", feature = "utils"))]
+use async_trait::async_trait;
 use serde::de::{Error as DeError, Unexpected};
 use serde::ser::{Serialize, SerializeStruct, Serializer};
 
@@ -623,15 +625,16 @@ impl fmt::Display for ChannelParseError {
 impl StdError for ChannelParseError {}
 
 #[cfg(all(feature = "cache", feature = "model", feature = "utils"))]
+#[async_trait]
 impl FromStrAndCache for Channel {
     type Err = ChannelParseError;
 
-    fn from_str<C>(cache: C, s: &str) -> StdResult<Self, Self::Err>
+    async fn from_str<C>(cache: C, s: &str) -> StdResult<Self, Self::Err>
     where
         C: AsRef<Cache> + Send + Sync,
     {
         match parse_channel(s) {
-            Some(x) => match ChannelId(x).to_channel_cached(&cache) {
+            Some(x) => match ChannelId(x).to_channel_cached(&cache).await {
                 Some(channel) => Ok(channel),
                 _ => Err(ChannelParseError::NotPresentInCache),
             },
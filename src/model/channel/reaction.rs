@@ -151,7 +151,8 @@ impl Reaction {
                         self.channel_id,
                         self.guild_id,
                         Permissions::MANAGE_MESSAGES,
-                    )?;
+                    )
+                    .await?;
                 }
             }
         }
@@ -185,7 +186,8 @@ impl Reaction {
                     self.channel_id,
                     self.guild_id,
                     Permissions::MANAGE_MESSAGES,
-                )?;
+                )
+                .await?;
             }
         }
         cache_http
@@ -170,6 +170,11 @@ pub enum Error {
     NoStickerFileSet,
     /// When attempting to send a message with over 3 stickers.
     StickerAmount,
+    /// When two application commands with the same name and kind are
+    /// declared in a [`CommandRegistry`]. The name of the command is provided.
+    ///
+    /// [`CommandRegistry`]: super::application::command_sync::CommandRegistry
+    DuplicateCommand(String),
 }
 
 impl Error {
@@ -215,6 +220,7 @@ impl fmt::Display for Error {
             Self::NoTokenSet => f.write_str("Token is not set."),
             Self::DeleteNitroSticker => f.write_str("Cannot delete an official sticker."),
             Self::NoStickerFileSet => f.write_str("Sticker file is not set."),
+            Self::DuplicateCommand(_) => f.write_str("Application command declared twice."),
             Self::StickerAmount => f.write_str("Too many stickers in a message."),
         }
     }
@@ -5,6 +5,7 @@ use std::convert::TryFrom;
 use std::fmt;
 
 use serde::de::{Error as DeError, IgnoredAny, MapAccess};
+use serde::ser::{Serialize, SerializeMap, Serializer};
 
 use super::application::component::ActionRow;
 use super::prelude::*;
@@ -721,9 +722,8 @@ pub struct GuildScheduledEventUserRemoveEvent {
 
 /// [Discord docs](https://discord.com/developers/docs/topics/gateway#payloads-gateway-payload-structure).
 #[allow(clippy::large_enum_variant)]
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone)]
 #[non_exhaustive]
-#[serde(untagged)]
 pub enum GatewayEvent {
     Dispatch(u64, Event),
     Heartbeat(u64),
@@ -809,6 +809,52 @@ impl<'de> Deserialize<'de> for GatewayEvent {
     }
 }
 
+/// Serializes the event into the same payload structure that it is
+/// deserialized from, i.e. the payload Discord sends over the gateway.
+impl Serialize for GatewayEvent {
+    fn serialize<S: Serializer>(&self, serializer: S) -> StdResult<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+
+        match self {
+            Self::Dispatch(seq, event) => {
+                map.serialize_entry("op", &OpCode::Event.num())?;
+                map.serialize_entry("s", seq)?;
+                map.serialize_entry("t", &event.event_type().name())?;
+
+                match event {
+                    Event::Unknown(unknown) => map.serialize_entry("d", &unknown.value)?,
+                    event => map.serialize_entry("d", event)?,
+                }
+            },
+            Self::Heartbeat(seq) => {
+                map.serialize_entry("op", &OpCode::Heartbeat.num())?;
+                map.serialize_entry("s", seq)?;
+                map.serialize_entry("d", seq)?;
+            },
+            Self::Reconnect => {
+                map.serialize_entry("op", &OpCode::Reconnect.num())?;
+                map.serialize_entry("d", &None::<()>)?;
+            },
+            Self::InvalidateSession(resumable) => {
+                map.serialize_entry("op", &OpCode::InvalidSession.num())?;
+                map.serialize_entry("d", resumable)?;
+            },
+            Self::Hello(interval) => {
+                let mut d = HashMap::with_capacity(1);
+                d.insert("heartbeat_interval", interval);
+
+                map.serialize_entry("op", &OpCode::Hello.num())?;
+                map.serialize_entry("d", &d)?;
+            },
+            Self::HeartbeatAck => {
+                map.serialize_entry("op", &OpCode::HeartbeatAck.num())?;
+            },
+        }
+
+        map.end()
+    }
+}
+
 /// Event received over a websocket connection
 ///
 /// [Discord docs](https://discord.com/developers/docs/topics/gateway#commands-and-events-gateway-events).
@@ -2257,3 +2303,35 @@ impl<'de> Deserialize<'de> for EventType {
         deserializer.deserialize_str(EventTypeVisitor)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::json::{from_value, json, to_value};
+
+    fn roundtrip(payload: Value) {
+        let event: GatewayEvent = from_value(payload.clone()).unwrap();
+
+        assert_eq!(to_value(&event).unwrap(), payload);
+    }
+
+    #[test]
+    fn test_gateway_event_roundtrip() {
+        roundtrip(json!({"op": 10, "d": {"heartbeat_interval": 41250}}));
+        roundtrip(json!({"op": 11}));
+        roundtrip(json!({"op": 9, "d": true}));
+        roundtrip(json!({"op": 7, "d": null}));
+        roundtrip(json!({
+            "op": 0,
+            "s": 3,
+            "t": "TYPING_START",
+            "d": {
+                "channel_id": "7",
+                "guild_id": null,
+                "timestamp": 1_600_000_000,
+                "user_id": "9",
+            },
+        }));
+        roundtrip(json!({"op": 0, "s": 4, "t": "SOME_NEW_EVENT", "d": {"a": 1}}));
+    }
+}
@@ -1,7 +1,7 @@
 use std::fmt;
 
 #[cfg(all(feature = "cache", feature = "model"))]
-use crate::cache::Cache;
+use crate::cache::{Cache, CacheBackendExt};
 #[cfg(feature = "cache")]
 use crate::http::Http;
 #[cfg(all(feature = "cache", feature = "model"))]
@@ -100,7 +100,7 @@ impl Emoji {
     #[inline]
     #[allow(clippy::trait_duplication_in_bounds)] // https://github.com/rust-lang/rust-clippy/issues/8757
     pub async fn delete<T: AsRef<Cache> + AsRef<Http>>(&self, cache_http: T) -> Result<()> {
-        match self.find_guild_id(&cache_http) {
+        match self.find_guild_id(&cache_http).await {
             Some(guild_id) => {
                 AsRef::<Http>::as_ref(&cache_http).delete_emoji(guild_id.0, self.id.0).await
             },
@@ -126,7 +126,7 @@ impl Emoji {
         cache_http: T,
         name: &str,
     ) -> Result<()> {
-        match self.find_guild_id(&cache_http) {
+        match self.find_guild_id(&cache_http).await {
             Some(guild_id) => {
                 let map = json!({
                     "name": name,
@@ -154,26 +154,21 @@ impl Emoji {
     /// # use serenity::cache::Cache;
     /// # use serenity::model::guild::Emoji;
     /// #
-    /// # fn run(cache: Cache, emoji: Emoji) {
+    /// # async fn run(cache: Cache, emoji: Emoji) {
     /// #
     /// // assuming emoji has been set already
-    /// if let Some(guild_id) = emoji.find_guild_id(&cache) {
+    /// if let Some(guild_id) = emoji.find_guild_id(&cache).await {
     ///     println!("{} is owned by {}", emoji.name, guild_id);
     /// }
     /// # }
     /// ```
     #[cfg(feature = "cache")]
-    #[must_use]
-    pub fn find_guild_id(&self, cache: impl AsRef<Cache>) -> Option<GuildId> {
-        for guild_entry in cache.as_ref().guilds.iter() {
-            let guild = guild_entry.value();
-
-            if guild.emojis.contains_key(&self.id) {
-                return Some(guild.id);
-            }
-        }
-
-        None
+    pub async fn find_guild_id(&self, cache: impl AsRef<Cache>) -> Option<GuildId> {
+        cache
+            .as_ref()
+            .backend
+            .find_map_guild(|guild| guild.emojis.contains_key(&self.id).then(|| guild.id))
+            .await
     }
 
     /// Generates a URL to the emoji's image.
@@ -33,14 +33,14 @@ use crate::collector::{
     ReactionCollectorBuilder,
 };
 #[cfg(feature = "model")]
-use crate::http::{CacheHttp, Http, UserPagination};
+use crate::http::routing::relative_path;
+#[cfg(feature = "model")]
+use crate::http::{payload, CacheHttp, Http, UserPagination};
 #[cfg(feature = "model")]
 use crate::internal::prelude::*;
 #[cfg(feature = "model")]
 use crate::json;
 #[cfg(feature = "model")]
-use crate::json::json;
-#[cfg(feature = "model")]
 use crate::json::prelude::*;
 #[cfg(feature = "model")]
 use crate::model::application::command::{Command, CommandPermission};
@@ -399,12 +399,13 @@ impl GuildId {
         name: &str,
         image: &str,
     ) -> Result<Emoji> {
-        let map = json!({
-            "name": name,
-            "image": image,
-        });
+        let payload = payload::CreateEmoji {
+            name: name.to_string(),
+            image: image.to_string(),
+            roles: Vec::new(),
+        };
 
-        http.as_ref().create_emoji(self.0, &map, None).await
+        http.as_ref().create_emoji(self.0, &payload, None).await
     }
 
     /// Creates an integration for the guild.
@@ -424,12 +425,12 @@ impl GuildId {
         kind: &str,
     ) -> Result<()> {
         let integration_id = integration_id.into();
-        let map = json!({
-            "id": integration_id.0,
-            "type": kind,
-        });
+        let payload = payload::CreateGuildIntegration {
+            id: integration_id,
+            kind: kind.to_string(),
+        };
 
-        http.as_ref().create_guild_integration(self.0, integration_id.0, &map, None).await
+        http.as_ref().create_guild_integration(self.0, integration_id.0, &payload, None).await
     }
 
     /// Creates a new role in the guild with the data set, if any.
@@ -672,11 +673,12 @@ impl GuildId {
         emoji_id: impl Into<EmojiId>,
         name: &str,
     ) -> Result<Emoji> {
-        let map = json!({
-            "name": name,
-        });
+        let payload = payload::EditEmoji {
+            name: Some(name.to_string()),
+            roles: None,
+        };
 
-        http.as_ref().edit_emoji(self.0, emoji_id.into().0, &map, None).await
+        http.as_ref().edit_emoji(self.0, emoji_id.into().0, &payload, None).await
     }
 
     /// Edits the properties of member of the guild, such as muting or
@@ -914,8 +916,8 @@ impl GuildId {
     /// Tries to find the [`Guild`] by its Id in the cache.
     #[cfg(feature = "cache")]
     #[inline]
-    pub fn to_guild_cached(self, cache: impl AsRef<Cache>) -> Option<Guild> {
-        cache.as_ref().guild(self)
+    pub async fn to_guild_cached(self, cache: impl AsRef<Cache>) -> Option<Guild> {
+        cache.as_ref().guild(self).await
     }
 
     /// Requests [`PartialGuild`] over REST API.
@@ -1079,7 +1081,7 @@ impl GuildId {
         #[cfg(feature = "cache")]
         {
             if let Some(cache) = cache_http.cache() {
-                if let Some(member) = cache.member(self.0, user_id) {
+                if let Some(member) = cache.member(self.0, user_id).await {
                     return Ok(member);
                 }
             }
@@ -1167,10 +1169,8 @@ impl GuildId {
 
     /// Returns the name of whatever guild this id holds.
     #[cfg(feature = "cache")]
-    #[must_use]
-    pub fn name(self, cache: impl AsRef<Cache>) -> Option<String> {
-        let guild = self.to_guild_cached(&cache)?;
-        Some(guild.name)
+    pub async fn name(self, cache: impl AsRef<Cache>) -> Option<String> {
+        cache.as_ref().guild_field(self, |guild| guild.name.clone()).await
     }
 
     /// Disconnects a member from a voice channel in the guild.
@@ -1206,11 +1206,11 @@ impl GuildId {
     /// [Kick Members]: Permissions::KICK_MEMBERS
     #[inline]
     pub async fn prune_count(self, http: impl AsRef<Http>, days: u16) -> Result<GuildPrune> {
-        let map = json!({
-            "days": days,
-        });
+        let query = payload::GetGuildPruneCount {
+            days: days.into(),
+        };
 
-        http.as_ref().get_guild_prune_count(self.0, &map).await
+        http.as_ref().get_guild_prune_count(self.0, &query).await
     }
 
     /// Re-orders the channels of the guild.
@@ -1234,17 +1234,16 @@ impl GuildId {
     where
         It: IntoIterator<Item = (ChannelId, u64)>,
     {
-        let items = channels
+        let positions = channels
             .into_iter()
-            .map(|(id, pos)| {
-                json!({
-                    "id": id,
-                    "position": pos,
-                })
+            .map(|(id, position)| payload::ChannelPosition {
+                id,
+                position: Some(position),
+                ..Default::default()
             })
             .collect::<Vec<_>>();
 
-        http.as_ref().edit_guild_channel_positions(self.0, &Value::from(items)).await
+        http.as_ref().edit_guild_channel_positions(self.0, &positions).await
     }
 
     /// Returns a list of [`Member`]s in a [`Guild`] whose username or nickname
@@ -1713,11 +1712,30 @@ impl GuildId {
     }
 
     /// Get the widget image URL.
+    ///
+    /// The URL is always under [`constants::API_BASE_URL`]. Use
+    /// [`Self::widget_image_url_for`] to get it under the base URL configured
+    /// for an [`Http`] client.
+    ///
+    /// [`constants::API_BASE_URL`]: crate::constants::API_BASE_URL
     #[must_use]
     pub fn widget_image_url(&self, style: GuildWidgetStyle) -> String {
         api!("/guilds/{}/widget.png?style={}", self.0, style)
     }
 
+    /// Get the widget image URL under the [base URL] of the given client.
+    ///
+    /// [base URL]: Http::base_url
+    #[must_use]
+    pub fn widget_image_url_for(&self, http: impl AsRef<Http>, style: GuildWidgetStyle) -> String {
+        let url = self.widget_image_url(style);
+
+        match relative_path(&url) {
+            Some(path) => format!("{}{}", http.as_ref().base_url(), path),
+            None => url,
+        }
+    }
+
     /// Gets the guild active threads.
     ///
     /// # Errors
@@ -206,40 +206,46 @@ impl Member {
 
     /// Determines the member's colour.
     #[cfg(feature = "cache")]
-    pub fn colour(&self, cache: impl AsRef<Cache>) -> Option<Colour> {
-        let guild_roles = cache.as_ref().guild_field(self.guild_id, |g| g.roles.clone())?;
-
-        let mut roles = self
-            .roles
-            .iter()
-            .filter_map(|role_id| guild_roles.get(role_id))
-            .collect::<Vec<&Role>>();
+    pub async fn colour(&self, cache: impl AsRef<Cache>) -> Option<Colour> {
+        cache
+            .as_ref()
+            .guild_field(self.guild_id, |guild| {
+                let mut roles = self
+                    .roles
+                    .iter()
+                    .filter_map(|role_id| guild.roles.get(role_id))
+                    .collect::<Vec<&Role>>();
 
-        roles.sort_by_key(|&b| Reverse(b));
+                roles.sort_by_key(|&b| Reverse(b));
 
-        let default = Colour::default();
+                let default = Colour::default();
 
-        roles.iter().find(|r| r.colour.0 != default.0).map(|r| r.colour)
+                roles.iter().find(|r| r.colour.0 != default.0).map(|r| r.colour)
+            })
+            .await?
     }
 
     /// Returns the "default channel" of the guild for the member.
     /// (This returns the first channel that can be read by the member, if there isn't
     /// one returns [`None`])
     #[cfg(feature = "cache")]
-    pub fn default_channel(&self, cache: impl AsRef<Cache>) -> Option<GuildChannel> {
-        let guild = self.guild_id.to_guild_cached(cache)?;
-
-        let member = guild.members.get(&self.user.id)?;
-
-        for channel in guild.channels.values() {
-            if let Channel::Guild(channel) = channel {
-                if guild.user_permissions_in(channel, member).ok()?.view_channel() {
-                    return Some(channel.clone());
+    pub async fn default_channel(&self, cache: impl AsRef<Cache>) -> Option<GuildChannel> {
+        cache
+            .as_ref()
+            .guild_field(self.guild_id, |guild| {
+                let member = guild.members.get(&self.user.id)?;
+
+                for channel in guild.channels.values() {
+                    if let Channel::Guild(channel) = channel {
+                        if guild.user_permissions_in(channel, member).ok()?.view_channel() {
+                            return Some(channel.clone());
+                        }
+                    }
                 }
-            }
-        }
 
-        None
+                None
+            })
+            .await?
     }
 
     /// Times the user out until `time`.
@@ -344,9 +350,18 @@ impl Member {
     /// position. If two or more roles have the same highest position, then the
     /// role with the lowest ID is the highest.
     #[cfg(feature = "cache")]
-    pub fn highest_role_info(&self, cache: impl AsRef<Cache>) -> Option<(RoleId, i64)> {
-        let guild_roles = cache.as_ref().guild_field(self.guild_id, |g| g.roles.clone())?;
+    pub async fn highest_role_info(&self, cache: impl AsRef<Cache>) -> Option<(RoleId, i64)> {
+        cache
+            .as_ref()
+            .guild_field(self.guild_id, |guild| self._highest_role_info(&guild.roles))
+            .await?
+    }
 
+    #[cfg(feature = "cache")]
+    pub(crate) fn _highest_role_info(
+        &self,
+        guild_roles: &HashMap<RoleId, Role>,
+    ) -> Option<(RoleId, i64)> {
         let mut highest = None;
 
         for role_id in &self.roles {
@@ -437,14 +452,14 @@ impl Member {
         #[cfg(feature = "cache")]
         {
             if let Some(cache) = cache_http.cache() {
-                if let Some(guild) = cache.guilds.get(&self.guild_id) {
+                if let Some(guild) = cache.guild(self.guild_id).await {
                     let req = Permissions::KICK_MEMBERS;
 
                     if !guild.has_perms(&cache_http, req).await {
                         return Err(Error::Model(ModelError::InvalidPermissions(req)));
                     }
 
-                    guild.check_hierarchy(cache, self.user.id)?;
+                    guild.check_hierarchy(cache, self.user.id).await?;
                 }
             }
         }
@@ -491,7 +506,7 @@ impl Member {
     /// ```rust,ignore
     /// // assuming there's a `member` variable gotten from anything.
     /// println!("The permission bits for the member are: {}",
-    /// member.permissions(&cache).expect("permissions").bits());
+    /// member.permissions(&cache).await.expect("permissions").bits());
     /// ```
     ///
     /// # Errors
@@ -502,10 +517,11 @@ impl Member {
     /// And/or returns [`ModelError::ItemMissing`] if the "default channel" of the guild is not
     /// found.
     #[cfg(feature = "cache")]
-    pub fn permissions(&self, cache: impl AsRef<Cache>) -> Result<Permissions> {
+    pub async fn permissions(&self, cache: impl AsRef<Cache>) -> Result<Permissions> {
         let perms_opt = cache
             .as_ref()
-            .guild_field(self.guild_id, |guild| guild._member_permission_from_member(self));
+            .guild_field(self.guild_id, |guild| guild._member_permission_from_member(self))
+            .await;
 
         match perms_opt {
             Some(perms) => Ok(perms),
@@ -587,15 +603,13 @@ impl Member {
     ///
     /// If role data can not be found for the member, then [`None`] is returned.
     #[cfg(feature = "cache")]
-    pub fn roles(&self, cache: impl AsRef<Cache>) -> Option<Vec<Role>> {
-        Some(
-            cache
-                .as_ref()
-                .guild_field(self.guild_id, |g| g.roles.clone())?
-                .into_values()
-                .filter(|role| self.roles.contains(&role.id))
-                .collect(),
-        )
+    pub async fn roles(&self, cache: impl AsRef<Cache>) -> Option<Vec<Role>> {
+        cache
+            .as_ref()
+            .guild_field(self.guild_id, |guild| {
+                guild.roles.values().filter(|role| self.roles.contains(&role.id)).cloned().collect()
+            })
+            .await
     }
 
     /// Unbans the [`User`] from the guild.
@@ -69,7 +69,7 @@ use crate::collector::{
 #[cfg(feature = "model")]
 use crate::constants::LARGE_THRESHOLD;
 #[cfg(feature = "model")]
-use crate::http::{CacheHttp, Http, UserPagination};
+use crate::http::{payload, CacheHttp, Http, UserPagination};
 use crate::json::prelude::*;
 use crate::json::{from_number, from_value};
 #[cfg(feature = "model")]
@@ -259,6 +259,9 @@ pub struct Guild {
     /// The stage instances in this guild.
     #[serde(default)]
     pub stage_instances: Vec<StageInstance>,
+    /// The scheduled events in this guild.
+    #[serde(default, rename = "guild_scheduled_events")]
+    pub scheduled_events: Vec<ScheduledEvent>,
     /// All active threads in this guild that current user has permission to view.
     #[serde(default)]
     pub threads: Vec<GuildChannel>,
@@ -377,10 +380,10 @@ impl Guild {
     }
 
     #[cfg(feature = "cache")]
-    fn check_hierarchy(&self, cache: impl AsRef<Cache>, other_user: UserId) -> Result<()> {
+    async fn check_hierarchy(&self, cache: impl AsRef<Cache>, other_user: UserId) -> Result<()> {
         let current_id = cache.as_ref().current_user().id;
 
-        if let Some(higher) = self.greater_member_hierarchy(&cache, other_user, current_id) {
+        if let Some(higher) = self.greater_member_hierarchy(&cache, other_user, current_id).await {
             if higher != current_id {
                 return Err(Error::Model(ModelError::Hierarchy));
             }
@@ -448,23 +451,22 @@ impl Guild {
     }
 
     #[cfg(feature = "cache")]
-    pub fn channel_id_from_name(
+    pub async fn channel_id_from_name(
         &self,
         cache: impl AsRef<Cache>,
         name: impl AsRef<str>,
     ) -> Option<ChannelId> {
         let name = name.as_ref();
-        let guild_channels = cache.as_ref().guild_channels(self.id)?;
 
-        for channel_entry in guild_channels.iter() {
-            let (id, channel) = channel_entry.pair();
-
-            if channel.name == name {
-                return Some(*id);
-            }
-        }
-
-        None
+        cache
+            .as_ref()
+            .guild_field(self.id, |guild| {
+                guild.channels.values().find_map(|channel| match channel {
+                    Channel::Guild(channel) if channel.name == name => Some(channel.id),
+                    _ => None,
+                })
+            })
+            .await?
     }
 
     /// Ban a [`User`] from the guild, deleting a number of
@@ -539,7 +541,7 @@ impl Guild {
                     return Err(Error::Model(ModelError::InvalidPermissions(req)));
                 }
 
-                self.check_hierarchy(cache, user)?;
+                self.check_hierarchy(cache, user).await?;
             }
         }
 
@@ -661,12 +663,12 @@ impl Guild {
         name: &str,
         icon: Option<&str>,
     ) -> Result<PartialGuild> {
-        let map = json!({
-            "icon": icon,
-            "name": name,
-        });
+        let payload = payload::CreateGuild {
+            name: name.to_string(),
+            icon: icon.map(ToString::to_string),
+        };
 
-        http.as_ref().create_guild(&map).await
+        http.as_ref().create_guild(&payload).await
     }
 
     /// Creates a new [`Channel`] in the guild.
@@ -1467,17 +1469,17 @@ impl Guild {
     /// [`position`]: Role::position
     #[cfg(feature = "cache")]
     #[inline]
-    pub fn greater_member_hierarchy(
+    pub async fn greater_member_hierarchy(
         &self,
         cache: impl AsRef<Cache>,
         lhs_id: impl Into<UserId>,
         rhs_id: impl Into<UserId>,
     ) -> Option<UserId> {
-        self._greater_member_hierarchy(&cache, lhs_id.into(), rhs_id.into())
+        self._greater_member_hierarchy(&cache, lhs_id.into(), rhs_id.into()).await
     }
 
     #[cfg(feature = "cache")]
-    fn _greater_member_hierarchy(
+    async fn _greater_member_hierarchy(
         &self,
         cache: impl AsRef<Cache>,
         lhs_id: UserId,
@@ -1495,8 +1497,10 @@ impl Guild {
             return Some(rhs_id);
         }
 
-        let lhs = self.members.get(&lhs_id)?.highest_role_info(&cache).unwrap_or((RoleId(0), 0));
-        let rhs = self.members.get(&rhs_id)?.highest_role_info(&cache).unwrap_or((RoleId(0), 0));
+        let lhs =
+            self.members.get(&lhs_id)?.highest_role_info(&cache).await.unwrap_or((RoleId(0), 0));
+        let rhs =
+            self.members.get(&rhs_id)?.highest_role_info(&cache).await.unwrap_or((RoleId(0), 0));
 
         // If LHS and RHS both have no top position or have the same role ID,
         // then no one wins.
@@ -2606,7 +2610,7 @@ impl Guild {
     /// impl EventHandler for Handler {
     ///     async fn message(&self, ctx: Context, msg: Message) {
     ///         if let Some(guild_id) = msg.guild_id {
-    ///             if let Some(guild) = guild_id.to_guild_cached(&ctx) {
+    ///             if let Some(guild) = guild_id.to_guild_cached(&ctx).await {
     ///                 if let Some(role) = guild.role_by_name("role_name") {
     ///                     println!("{:?}", role);
     ///                 }
@@ -2840,37 +2844,35 @@ impl<'de> Deserialize<'de> for Guild {
             .and_then(String::deserialize)
             .map_err(DeError::custom)?;
 
-        let welcome_screen = map
-            .remove("welcome_screen")
-            .map(GuildWelcomeScreen::deserialize)
-            .transpose()
-            .map_err(DeError::custom)?;
+        let welcome_screen = match map.remove("welcome_screen") {
+            Some(v) => Option::<GuildWelcomeScreen>::deserialize(v).map_err(DeError::custom)?,
+            None => None,
+        };
 
-        let approximate_member_count = map
-            .remove("approximate_member_count")
-            .map(u64::deserialize)
-            .transpose()
-            .map_err(DeError::custom)?;
+        let approximate_member_count = match map.remove("approximate_member_count") {
+            Some(v) => Option::<u64>::deserialize(v).map_err(DeError::custom)?,
+            None => None,
+        };
 
-        let approximate_presence_count = map
-            .remove("approximate_presence_count")
-            .map(u64::deserialize)
-            .transpose()
-            .map_err(DeError::custom)?;
+        let approximate_presence_count = match map.remove("approximate_presence_count") {
+            Some(v) => Option::<u64>::deserialize(v).map_err(DeError::custom)?,
+            None => None,
+        };
 
-        let max_video_channel_users = map
-            .remove("max_video_channel_users")
-            .map(u64::deserialize)
-            .transpose()
-            .map_err(DeError::custom)?;
+        let max_video_channel_users = match map.remove("max_video_channel_users") {
+            Some(v) => Option::<u64>::deserialize(v).map_err(DeError::custom)?,
+            None => None,
+        };
 
         let max_presences = match map.remove("max_presences") {
             Some(v) => Option::<u64>::deserialize(v).map_err(DeError::custom)?,
             None => None,
         };
 
-        let max_members =
-            map.remove("max_members").map(u64::deserialize).transpose().map_err(DeError::custom)?;
+        let max_members = match map.remove("max_members") {
+            Some(v) => Option::<u64>::deserialize(v).map_err(DeError::custom)?,
+            None => None,
+        };
 
         let discovery_splash = match map.remove("discovery_splash") {
             Some(v) => Option::<String>::deserialize(v).map_err(DeError::custom)?,
@@ -2914,6 +2916,11 @@ impl<'de> Deserialize<'de> for Guild {
             None => Vec::new(),
         };
 
+        let scheduled_events = match map.remove("guild_scheduled_events") {
+            Some(v) => Vec::<ScheduledEvent>::deserialize(v).map_err(DeError::custom)?,
+            None => Vec::new(),
+        };
+
         let threads = match map.remove("threads") {
             Some(v) => Vec::<GuildChannel>::deserialize(v).map_err(DeError::custom)?,
             None => Vec::new(),
@@ -2969,6 +2976,7 @@ impl<'de> Deserialize<'de> for Guild {
             widget_enabled,
             widget_channel_id,
             stage_instances,
+            scheduled_events,
             threads,
             stickers,
         })
@@ -3333,6 +3341,7 @@ mod test {
                 widget_channel_id: None,
                 public_updates_channel_id: None,
                 stage_instances: vec![],
+                scheduled_events: vec![],
                 threads: vec![],
                 stickers: hm7,
             }
@@ -358,23 +358,22 @@ impl PartialGuild {
     }
 
     #[cfg(feature = "cache")]
-    pub fn channel_id_from_name(
+    pub async fn channel_id_from_name(
         &self,
         cache: impl AsRef<Cache>,
         name: impl AsRef<str>,
     ) -> Option<ChannelId> {
         let name = name.as_ref();
-        let guild_channels = cache.as_ref().guild_channels(self.id)?;
 
-        for channel_entry in guild_channels.iter() {
-            let (id, channel) = channel_entry.pair();
-
-            if channel.name == name {
-                return Some(*id);
-            }
-        }
-
-        None
+        cache
+            .as_ref()
+            .guild_field(self.id, |guild| {
+                guild.channels.values().find_map(|channel| match channel {
+                    Channel::Guild(channel) if channel.name == name => Some(channel.id),
+                    _ => None,
+                })
+            })
+            .await?
     }
 
     /// Creates a [`GuildChannel`] in the guild.
@@ -1023,7 +1022,6 @@ impl PartialGuild {
     }
 
     #[cfg(feature = "cache")]
-    #[allow(clippy::unused_async)]
     async fn _greater_member_hierarchy(
         &self,
         cache: impl AsRef<Cache>,
@@ -1042,20 +1040,18 @@ impl PartialGuild {
             return Some(rhs_id);
         }
 
-        let lhs = cache
-            .as_ref()
-            .guild(self.id)?
-            .members
-            .get(&lhs_id)?
-            .highest_role_info(&cache)
-            .unwrap_or((RoleId(0), 0));
-        let rhs = cache
+        let (lhs, rhs) = cache
             .as_ref()
-            .guild(self.id)?
-            .members
-            .get(&rhs_id)?
-            .highest_role_info(&cache)
-            .unwrap_or((RoleId(0), 0));
+            .guild_field(self.id, |guild| {
+                let highest_role_info = |user_id| {
+                    let member = guild.members.get(&user_id)?;
+
+                    Some(member._highest_role_info(&guild.roles).unwrap_or((RoleId(0), 0)))
+                };
+
+                Some((highest_role_info(lhs_id)?, highest_role_info(rhs_id)?))
+            })
+            .await??;
 
         // If LHS and RHS both have no top position or have the same role ID,
         // then no one wins.
@@ -1572,7 +1568,7 @@ impl PartialGuild {
     /// impl EventHandler for Handler {
     ///     async fn message(&self, context: Context, msg: Message) {
     ///         if let Some(guild_id) = msg.guild_id {
-    ///             if let Some(guild) = guild_id.to_guild_cached(&context) {
+    ///             if let Some(guild) = guild_id.to_guild_cached(&context).await {
     ///                 if let Some(role) = guild.role_by_name("role_name") {
     ///                     println!("Obtained role's reference: {:?}", role);
     ///                 }
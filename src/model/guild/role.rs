@@ -3,12 +3,15 @@ use std::cmp::Ordering;
 use std::error::Error as StdError;
 use std::fmt;
 
+#[cfg(all(feature = "cache", feature = "model", feature = "utils"))]
+use async_trait::async_trait;
+
 #[cfg(feature = "model")]
 use crate::builder::EditRole;
-#[cfg(all(feature = "cache", feature = "model"))]
-use crate::cache::Cache;
 #[cfg(all(feature = "cache", feature = "model", feature = "utils"))]
 use crate::cache::FromStrAndCache;
+#[cfg(all(feature = "cache", feature = "model"))]
+use crate::cache::{Cache, CacheBackendExt};
 #[cfg(feature = "model")]
 use crate::http::Http;
 #[cfg(all(feature = "cache", feature = "model"))]
@@ -155,7 +158,7 @@ impl Role {
     ///
     /// ```rust,ignore
     /// # use serenity::model::id::RoleId;
-    /// # let role = RoleId(7).to_role_cached(&cache).unwrap();
+    /// # let role = RoleId(7).to_role_cached(&cache).await.unwrap();
     /// // assuming a `role` has already been bound
     /// role.edit(|r| r.hoist(true));
     /// ```
@@ -234,20 +237,8 @@ impl PartialOrd for Role {
 impl RoleId {
     /// Tries to find the [`Role`] by its Id in the cache.
     #[cfg(feature = "cache")]
-    pub fn to_role_cached(self, cache: impl AsRef<Cache>) -> Option<Role> {
-        for guild_entry in cache.as_ref().guilds.iter() {
-            let guild = guild_entry.value();
-
-            if !guild.roles.contains_key(&self) {
-                continue;
-            }
-
-            if let Some(role) = guild.roles.get(&self) {
-                return Some(role.clone());
-            }
-        }
-
-        None
+    pub async fn to_role_cached(self, cache: impl AsRef<Cache>) -> Option<Role> {
+        cache.as_ref().backend.find_map_guild(|guild| guild.roles.get(&self).cloned()).await
     }
 }
 
@@ -286,15 +277,16 @@ impl fmt::Display for RoleParseError {
 impl StdError for RoleParseError {}
 
 #[cfg(all(feature = "cache", feature = "model", feature = "utils"))]
+#[async_trait]
 impl FromStrAndCache for Role {
     type Err = RoleParseError;
 
-    fn from_str<CRL>(cache: CRL, s: &str) -> StdResult<Self, Self::Err>
+    async fn from_str<CRL>(cache: CRL, s: &str) -> StdResult<Self, Self::Err>
     where
         CRL: AsRef<Cache> + Send + Sync,
     {
         match parse_role(s) {
-            Some(x) => match RoleId(x).to_role_cached(&cache) {
+            Some(x) => match RoleId(x).to_role_cached(&cache).await {
                 Some(role) => Ok(role),
                 None => Err(RoleParseError::NotPresentInCache),
             },
@@ -100,7 +100,8 @@ impl Invite {
                     channel_id,
                     None,
                     Permissions::CREATE_INSTANT_INVITE,
-                )?;
+                )
+                .await?;
             }
         }
 
@@ -133,7 +134,8 @@ impl Invite {
                     self.channel.id,
                     guild_id,
                     Permissions::MANAGE_GUILD,
-                )?;
+                )
+                .await?;
             }
         }
 
@@ -360,7 +362,8 @@ impl RichInvite {
                     self.channel.id,
                     guild_id,
                     Permissions::MANAGE_GUILD,
-                )?;
+                )
+                .await?;
             }
         }
 
@@ -25,12 +25,10 @@ use crate::collector::{
 #[cfg(feature = "model")]
 use crate::http::GuildPagination;
 #[cfg(feature = "model")]
-use crate::http::{CacheHttp, Http};
+use crate::http::{payload, CacheHttp, Http};
 use crate::internal::prelude::*;
 #[cfg(feature = "model")]
 use crate::json;
-#[cfg(feature = "model")]
-use crate::json::json;
 use crate::json::to_string;
 #[cfg(feature = "model")]
 use crate::model::application::oauth::Scope;
@@ -947,9 +945,11 @@ impl User {
                     #[cfg(feature = "cache")]
                     {
                         if let Some(cache) = cache_http.cache() {
-                            if let Some(member) = cache.member(guild_id, self.id) {
-                                has_role = Some(member.roles.contains(&role));
-                            }
+                            has_role = cache
+                                .member_field(guild_id, self.id, |member| {
+                                    member.roles.contains(&role)
+                                })
+                                .await;
                         }
                     }
 
@@ -1050,10 +1050,10 @@ impl User {
         #[cfg(feature = "cache")]
         {
             if let Some(cache) = cache_http.cache() {
-                if let Some(guild) = guild_id.to_guild_cached(cache) {
-                    if let Some(member) = guild.members.get(&self.id) {
-                        return member.nick.clone();
-                    }
+                if let Some(nick) =
+                    cache.member_field(guild_id, self.id, |member| member.nick.clone()).await
+                {
+                    return nick;
                 }
             }
         }
@@ -1128,19 +1128,18 @@ impl UserId {
             }
         }
 
-        let map = json!({
-            "recipient_id": self.0,
-        });
+        let payload = payload::CreatePrivateChannel {
+            recipient_id: self,
+        };
 
-        cache_http.http().create_private_channel(&map).await
+        cache_http.http().create_private_channel(&payload).await
     }
 
     /// Attempts to find a [`User`] by its Id in the cache.
     #[cfg(feature = "cache")]
-    #[allow(clippy::unused_async)]
     #[inline]
     pub async fn to_user_cached(self, cache: impl AsRef<Cache>) -> Option<User> {
-        cache.as_ref().user(self)
+        cache.as_ref().user(self).await
     }
 
     /// First attempts to find a [`User`] by its Id in the cache,
@@ -1163,7 +1162,7 @@ impl UserId {
         #[cfg(feature = "cache")]
         {
             if let Some(cache) = cache_http.cache() {
-                if let Some(user) = cache.user(self) {
+                if let Some(user) = cache.user(self).await {
                     return Ok(user);
                 }
             }
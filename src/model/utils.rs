@@ -348,13 +348,13 @@ pub fn serialize_map_values<K, S: Serializer, V: Serialize>(
 /// the permissions are not in the cache.
 #[cfg(all(feature = "cache", feature = "model"))]
 #[inline]
-pub fn user_has_perms_cache(
+pub async fn user_has_perms_cache(
     cache: impl AsRef<Cache>,
     channel_id: ChannelId,
     guild_id: Option<GuildId>,
     permissions: Permissions,
 ) -> Result<()> {
-    if match user_has_perms(cache, channel_id, guild_id, permissions) {
+    if match user_has_perms(cache, channel_id, guild_id, permissions).await {
         Err(Error::Model(err)) => err.is_cache_err(),
         result => result?,
     } {
@@ -365,7 +365,7 @@ pub fn user_has_perms_cache(
 }
 
 #[cfg(all(feature = "cache", feature = "model"))]
-pub fn user_has_perms(
+pub async fn user_has_perms(
     cache: impl AsRef<Cache>,
     channel_id: ChannelId,
     guild_id: Option<GuildId>,
@@ -373,7 +373,7 @@ pub fn user_has_perms(
 ) -> Result<bool> {
     let cache = cache.as_ref();
 
-    let channel = match cache.channel(channel_id) {
+    let channel = match cache.channel(channel_id).await {
         Some(channel) => channel,
         None => return Err(Error::Model(ModelError::ChannelNotFound)),
     };
@@ -396,17 +396,17 @@ pub fn user_has_perms(
         },
     };
 
-    let guild = match cache.guild(guild_id) {
-        Some(guild) => guild,
-        None => return Err(Error::Model(ModelError::GuildNotFound)),
-    };
+    let user_id = cache.current_user_id();
 
-    let member = match guild.members.get(&cache.current_user().id) {
-        Some(member) => member,
-        None => return Err(Error::Model(ModelError::MemberNotFound)),
-    };
+    let perms = cache
+        .guild_field(guild_id, |guild| {
+            let member =
+                guild.members.get(&user_id).ok_or(Error::Model(ModelError::MemberNotFound))?;
 
-    let perms = guild.user_permissions_in(&guild_channel, member)?;
+            guild.user_permissions_in(&guild_channel, member)
+        })
+        .await
+        .ok_or(Error::Model(ModelError::GuildNotFound))??;
 
     permissions.remove(perms);
 
@@ -22,7 +22,7 @@ pub use typemap_rev::{TypeMap, TypeMapKey};
 #[cfg(feature = "client")]
 pub use crate::client::Context;
 #[cfg(all(feature = "client", feature = "gateway"))]
-pub use crate::client::{Client, ClientError, EventHandler, RawEventHandler};
+pub use crate::client::{Client, ClientError, EventHandler, EventMiddleware, RawEventHandler};
 pub use crate::error::Error as SerenityError;
 #[cfg(feature = "gateway")]
 pub use crate::gateway::GatewayError;
@@ -0,0 +1,223 @@
+//! A minimal gateway server handling the connection lifecycle of shards.
+
+use std::borrow::Cow;
+use std::io::Write;
+use std::mem;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use async_tungstenite::tokio::{accept_hdr_async, TokioAdapter};
+use async_tungstenite::tungstenite::handshake::server::{Request, Response};
+use async_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
+use async_tungstenite::tungstenite::protocol::CloseFrame;
+use async_tungstenite::tungstenite::Message;
+use async_tungstenite::WebSocketStream;
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use futures::{SinkExt, StreamExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+use tracing::{debug, warn};
+
+use super::State;
+use crate::constants::{self, OpCode};
+use crate::gateway::{etf, GatewayEncoding};
+use crate::internal::prelude::*;
+use crate::internal::tokio::spawn_named;
+use crate::json::{self, json};
+use crate::model::event::{Event, GatewayEvent, ReadyEvent, ResumedEvent};
+
+type ServerStream = WebSocketStream<TokioAdapter<TcpStream>>;
+
+/// A message from the [`MockServer`] to a session.
+///
+/// [`MockServer`]: super::MockServer
+#[derive(Clone, Debug)]
+pub(super) enum Outgoing {
+    Dispatch(Box<Event>),
+    Event(Box<GatewayEvent>),
+    Close(u16),
+}
+
+pub(super) async fn listen(state: Arc<State>, listener: TcpListener) {
+    // Sessions are aborted alongside the listener when the server is dropped.
+    let mut sessions = Vec::new();
+
+    loop {
+        match listener.accept().await {
+            Ok((stream, _)) => {
+                state.connections.fetch_add(1, Ordering::Relaxed);
+                let state = Arc::clone(&state);
+
+                sessions.push(AbortOnDrop(spawn_named("testing::gateway::session", async move {
+                    if let Err(why) = run_session(&state, stream).await {
+                        debug!("[Mock gateway] Session closed with error: {:?}", why);
+                    }
+                })));
+            },
+            Err(why) => warn!("[Mock gateway] Error accepting connection: {:?}", why),
+        }
+    }
+}
+
+struct AbortOnDrop(tokio::task::JoinHandle<()>);
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+struct Session {
+    ws: ServerStream,
+    encoding: GatewayEncoding,
+    /// The zlib stream of the connection, if the shard asked for `zlib-stream`
+    /// transport compression.
+    encoder: Option<ZlibEncoder<Vec<u8>>>,
+}
+
+impl Session {
+    async fn send(&mut self, event: &GatewayEvent) -> Result<()> {
+        let message = match self.encoding {
+            GatewayEncoding::Json => Message::Text(json::to_string(event)?),
+            GatewayEncoding::Etf => Message::Binary(etf::to_vec(event)?),
+        };
+
+        let message = match self.encoder.as_mut() {
+            Some(encoder) => {
+                // Flushing ends the message with the zlib-stream suffix.
+                encoder.write_all(&message.into_data())?;
+                encoder.flush()?;
+
+                Message::Binary(mem::take(encoder.get_mut()))
+            },
+            None => message,
+        };
+
+        self.ws.send(message).await?;
+
+        Ok(())
+    }
+}
+
+async fn run_session(state: &State, stream: TcpStream) -> Result<()> {
+    let mut zlib_stream = false;
+    let mut encoding = GatewayEncoding::Json;
+    let ws = accept_hdr_async(stream, |request: &Request, response: Response| {
+        for pair in request.uri().query().unwrap_or_default().split('&') {
+            match pair {
+                "compress=zlib-stream" => zlib_stream = true,
+                "encoding=etf" => encoding = GatewayEncoding::Etf,
+                _ => {},
+            }
+        }
+
+        Ok(response)
+    })
+    .await?;
+    let mut session = Session {
+        ws,
+        encoding,
+        encoder: zlib_stream.then(|| ZlibEncoder::new(Vec::new(), Compression::default())),
+    };
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let mut seq = 0;
+
+    session.send(&GatewayEvent::Hello(state.heartbeat_interval)).await?;
+
+    loop {
+        tokio::select! {
+            message = session.ws.next() => {
+                let payload: Value = match message {
+                    Some(Ok(Message::Text(mut text))) => json::from_str(&mut text)?,
+                    Some(Ok(Message::Binary(bytes))) => etf::from_slice(&bytes)?,
+                    Some(Ok(Message::Close(frame))) => {
+                        if let Some(frame) = frame {
+                            state.close_codes.lock().await.push(frame.code.into());
+                            state.notify.notify_waiters();
+                        }
+
+                        return Ok(());
+                    },
+                    None => return Ok(()),
+                    Some(Ok(_)) => continue,
+                    Some(Err(why)) => return Err(why.into()),
+                };
+
+                state.commands.lock().await.push(payload.clone());
+                state.notify.notify_waiters();
+
+                match payload.get("op").and_then(Value::as_u64) {
+                    Some(op) if op == OpCode::Heartbeat.num() => {
+                        session.send(&GatewayEvent::HeartbeatAck).await?;
+                    },
+                    Some(op) if op == OpCode::Identify.num() => {
+                        let session_id = format!("mock-session-{}", state.next_id());
+                        let ready = ready_event(state, &session_id, &payload["d"])?;
+                        state.session_ids.lock().await.push(session_id);
+
+                        seq = 1;
+                        session.send(&GatewayEvent::Dispatch(seq, ready)).await?;
+                        register(state, &tx).await;
+                    },
+                    Some(op) if op == OpCode::Resume.num() => {
+                        let session_id = payload["d"]["session_id"].as_str().unwrap_or_default();
+
+                        if state.session_ids.lock().await.iter().any(|id| id == session_id) {
+                            seq = payload["d"]["seq"].as_u64().unwrap_or_default() + 1;
+                            let resumed = Event::Resumed(ResumedEvent {
+                                trace: Vec::new(),
+                            });
+
+                            session.send(&GatewayEvent::Dispatch(seq, resumed)).await?;
+                            register(state, &tx).await;
+                        } else {
+                            session.send(&GatewayEvent::InvalidateSession(false)).await?;
+                        }
+                    },
+                    _ => {},
+                }
+            },
+            Some(outgoing) = rx.recv() => match outgoing {
+                Outgoing::Dispatch(event) => {
+                    seq += 1;
+                    session.send(&GatewayEvent::Dispatch(seq, *event)).await?;
+                },
+                Outgoing::Event(event) => session.send(&event).await?,
+                Outgoing::Close(code) => {
+                    session.ws.close(Some(CloseFrame {
+                        code: CloseCode::from(code),
+                        reason: Cow::Borrowed(""),
+                    }))
+                    .await?;
+
+                    return Ok(());
+                },
+            },
+        }
+    }
+}
+
+async fn register(state: &State, tx: &mpsc::UnboundedSender<Outgoing>) {
+    state.sessions.lock().await.push(tx.clone());
+    state.notify.notify_waiters();
+}
+
+fn ready_event(state: &State, session_id: &str, identify: &Value) -> Result<Event> {
+    let ready = json!({
+        "v": constants::GATEWAY_VERSION,
+        "user": state.current_user,
+        "guilds": [],
+        "session_id": session_id,
+        "resume_gateway_url": state.gateway_url,
+        "shard": identify.get("shard"),
+        "application": {
+            "id": state.current_user["id"],
+            "flags": 0,
+        },
+    });
+
+    Ok(Event::Ready(ReadyEvent {
+        ready: json::from_value(ready)?,
+    }))
+}
@@ -0,0 +1,225 @@
+//! A minimal HTTP/1.1 server answering REST requests.
+
+use std::collections::HashMap;
+use std::io;
+use std::sync::Arc;
+
+use reqwest::StatusCode;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tracing::{debug, warn};
+
+use super::{message_json, MockResponse, RecordedRequest, State};
+use crate::http::LightMethod;
+use crate::internal::prelude::*;
+use crate::internal::tokio::spawn_named;
+use crate::json::{self, json};
+
+pub(super) async fn listen(state: Arc<State>, listener: TcpListener) {
+    loop {
+        match listener.accept().await {
+            Ok((stream, _)) => {
+                let state = Arc::clone(&state);
+
+                spawn_named("testing::http::connection", async move {
+                    if let Err(why) = serve(&state, stream).await {
+                        debug!("[Mock HTTP] Connection closed with error: {:?}", why);
+                    }
+                });
+            },
+            Err(why) => warn!("[Mock HTTP] Error accepting connection: {:?}", why),
+        }
+    }
+}
+
+/// Serves requests on a connection until the client closes it.
+async fn serve(state: &State, stream: TcpStream) -> io::Result<()> {
+    let (reader, mut writer) = stream.into_split();
+    let mut reader = BufReader::new(reader);
+
+    while let Some(request) = read_request(&mut reader).await? {
+        debug!("[Mock HTTP] {:?} {}", request.method, request.path);
+
+        let response = respond(state, &request).await;
+
+        state.requests.lock().await.push(request);
+        state.notify.notify_waiters();
+
+        writer.write_all(&encode_response(&response)).await?;
+    }
+
+    Ok(())
+}
+
+/// Reads a request, returning `None` if the connection was closed.
+async fn read_request<R: AsyncBufRead + Unpin>(
+    reader: &mut R,
+) -> io::Result<Option<RecordedRequest>> {
+    let mut line = String::new();
+
+    if reader.read_line(&mut line).await? == 0 {
+        return Ok(None);
+    }
+
+    let mut parts = line.split_whitespace();
+    let method = match parts.next() {
+        Some("DELETE") => LightMethod::Delete,
+        Some("GET") => LightMethod::Get,
+        Some("PATCH") => LightMethod::Patch,
+        Some("POST") => LightMethod::Post,
+        Some("PUT") => LightMethod::Put,
+        other => return Err(invalid_data(format!("unsupported method {:?}", other))),
+    };
+    let path = parts.next().ok_or_else(|| invalid_data("missing path"))?.to_string();
+
+    let mut headers = HashMap::new();
+
+    loop {
+        line.clear();
+        reader.read_line(&mut line).await?;
+
+        let header = line.trim_end();
+
+        if header.is_empty() {
+            break;
+        }
+
+        if let Some((name, value)) = header.split_once(':') {
+            headers.insert(name.trim().to_lowercase(), value.trim().to_string());
+        }
+    }
+
+    let body = if headers.get("transfer-encoding").map_or(false, |x| x == "chunked") {
+        read_chunked_body(reader).await?
+    } else {
+        let length = headers.get("content-length").and_then(|x| x.parse().ok()).unwrap_or(0);
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body).await?;
+
+        body
+    };
+
+    Ok(Some(RecordedRequest {
+        method,
+        path,
+        headers,
+        body,
+    }))
+}
+
+async fn read_chunked_body<R: AsyncBufRead + Unpin>(reader: &mut R) -> io::Result<Vec<u8>> {
+    let mut body = Vec::new();
+    let mut line = String::new();
+
+    loop {
+        line.clear();
+        reader.read_line(&mut line).await?;
+
+        let size = line.trim_end().split(';').next().unwrap_or_default();
+        let size = usize::from_str_radix(size, 16).map_err(|_| invalid_data("bad chunk size"))?;
+
+        let mut chunk = vec![0; size + 2];
+        reader.read_exact(&mut chunk).await?;
+
+        if size == 0 {
+            return Ok(body);
+        }
+
+        body.extend_from_slice(&chunk[..size]);
+    }
+}
+
+fn invalid_data(message: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.into())
+}
+
+fn encode_response(response: &MockResponse) -> Vec<u8> {
+    let status = StatusCode::from_u16(response.status).unwrap_or(StatusCode::OK);
+    let mut encoded = format!(
+        "HTTP/1.1 {} {}\r\n",
+        status.as_u16(),
+        status.canonical_reason().unwrap_or_default()
+    );
+
+    // Like Discord, tell in a header how long ratelimited requests should wait.
+    if status == StatusCode::TOO_MANY_REQUESTS {
+        if let Some(retry_after) = response.body.as_ref().and_then(|body| body.get("retry_after")) {
+            encoded.push_str("Retry-After: ");
+            encoded.push_str(&retry_after.to_string());
+            encoded.push_str("\r\n");
+        }
+    }
+
+    match response.body.as_ref().and_then(|body| json::to_string(body).ok()) {
+        Some(body) => {
+            encoded.push_str("Content-Type: application/json\r\n");
+            encoded.push_str("Content-Length: ");
+            encoded.push_str(&body.len().to_string());
+            encoded.push_str("\r\n\r\n");
+            encoded.push_str(&body);
+        },
+        None => encoded.push_str("Content-Length: 0\r\n\r\n"),
+    }
+
+    encoded.into_bytes()
+}
+
+/// Answers a request with the response set via [`MockServer::respond`], or the
+/// default response of the route.
+///
+/// [`MockServer::respond`]: super::MockServer::respond
+async fn respond(state: &State, request: &RecordedRequest) -> MockResponse {
+    let key = (request.method, request.path.clone());
+
+    if let Some(response) = state.responses.lock().await.get(&key) {
+        return response.clone();
+    }
+
+    let segments = request.path.split('?').next().unwrap_or_default();
+    let segments = segments.trim_start_matches('/').split('/').collect::<Vec<_>>();
+
+    let ok = |body| MockResponse {
+        status: 200,
+        body: Some(body),
+    };
+    let no_content = MockResponse {
+        status: 204,
+        body: None,
+    };
+
+    match (request.method, segments.as_slice()) {
+        (LightMethod::Get, ["gateway"]) => ok(json!({
+            "url": state.gateway_url,
+        })),
+        (LightMethod::Get, ["gateway", "bot"]) => ok(json!({
+            "url": state.gateway_url,
+            "shards": 1,
+            "session_start_limit": {
+                "total": 1000,
+                "remaining": 1000,
+                "reset_after": 86_400_000,
+                "max_concurrency": 1,
+            },
+        })),
+        (LightMethod::Get, ["users", "@me"]) => ok(state.current_user.clone()),
+        (LightMethod::Post, ["channels", channel_id, "messages"]) => {
+            let channel_id = channel_id.parse().unwrap_or_default();
+            let content = json::from_slice::<Value>(&mut request.body.clone())
+                .ok()
+                .and_then(|body| body.get("content").and_then(|x| x.as_str().map(String::from)))
+                .unwrap_or_default();
+
+            ok(message_json(state, channel_id, state.current_user.clone(), &content))
+        },
+        (LightMethod::Post, ["channels", _, "typing"])
+        | (LightMethod::Put, ["channels", _, "messages", _, "reactions", ..])
+        | (LightMethod::Delete, _) => no_content,
+        _ => MockResponse {
+            status: 404,
+            body: Some(json!({
+                "code": 0,
+                "message": "404: Not Found",
+            })),
+        },
+    }
+}
@@ -0,0 +1,387 @@
+//! An in-process stand-in for Discord, used to test bots end-to-end.
+//!
+//! The [`MockServer`] speaks enough of Discord's REST API and gateway protocol
+//! for a real [`Client`] to connect to it: it answers the HELLO, IDENTIFY,
+//! RESUME and heartbeat handshake with the appropriate payloads, and answers
+//! common REST routes with plausible responses. Tests can then inject gateway
+//! events and assert on the REST requests the bot made in response.
+//!
+//! # Examples
+//!
+//! Testing that a bot replies to a `!ping` message:
+//!
+//! ```rust,no_run
+//! use serenity::http::routing::RouteInfo;
+//! use serenity::json::{json, Value};
+//! use serenity::model::prelude::*;
+//! use serenity::prelude::*;
+//! use serenity::testing::MockServer;
+//!
+//! struct Handler;
+//!
+//! #[serenity::async_trait]
+//! impl EventHandler for Handler {
+//!     async fn message(&self, ctx: Context, msg: Message) {
+//!         if msg.content == "!ping" {
+//!             let _ = msg.channel_id.say(&ctx, "Pong!").await;
+//!         }
+//!     }
+//! }
+//!
+//! # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+//! let server = MockServer::start().await?;
+//! let mut client =
+//!     server.client_builder(GatewayIntents::default()).event_handler(Handler).await?;
+//! tokio::spawn(async move { client.start().await });
+//!
+//! server.wait_until_ready().await;
+//! server.dispatch_message(ChannelId(1), "!ping").await?;
+//!
+//! let request = server
+//!     .wait_for_request(RouteInfo::CreateMessage {
+//!         channel_id: 1,
+//!     })
+//!     .await;
+//! assert_eq!(request.json::<Value>()?["content"], json!("Pong!"));
+//! #     Ok(())
+//! # }
+//! ```
+//!
+//! [`Client`]: crate::Client
+
+mod gateway;
+mod http;
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use serde::de::DeserializeOwned;
+use tokio::net::TcpListener;
+use tokio::sync::{mpsc, Mutex, Notify};
+use tokio::task::JoinHandle;
+
+use self::gateway::Outgoing;
+use crate::client::ClientBuilder;
+use crate::http::routing::{relative_path, RouteInfo};
+use crate::http::{Http, HttpBuilder, LightMethod};
+use crate::internal::prelude::*;
+use crate::internal::tokio::spawn_named;
+use crate::json::{self, json};
+use crate::model::event::{Event, GatewayEvent, MessageCreateEvent};
+use crate::model::gateway::GatewayIntents;
+use crate::model::id::ChannelId;
+use crate::model::user::CurrentUser;
+use crate::model::Timestamp;
+
+/// The token that clients created by the [`MockServer`] authenticate with.
+pub const MOCK_TOKEN: &str = "Bot mock-token";
+
+/// A request that a client made to the [`MockServer`]'s REST API.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct RecordedRequest {
+    /// The method of the request.
+    pub method: LightMethod,
+    /// The path of the request, relative to the API base URL and including
+    /// the query string.
+    pub path: String,
+    /// The headers of the request, with lowercased names.
+    pub headers: HashMap<String, String>,
+    /// The raw body of the request.
+    pub body: Vec<u8>,
+}
+
+impl RecordedRequest {
+    /// Whether the request was made to the given route.
+    #[must_use]
+    pub fn is(&self, route: &RouteInfo<'_>) -> bool {
+        let (method, _, path) = route.deconstruct();
+
+        self.method == method && relative_path(&path) == Some(self.path.as_str())
+    }
+
+    /// Deserializes the JSON body of the request.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Json`] if the body is not valid JSON for `T`.
+    pub fn json<T: DeserializeOwned>(&self) -> Result<T> {
+        json::from_slice(&mut self.body.clone())
+    }
+}
+
+/// A response that the [`MockServer`] answers a route with.
+#[derive(Clone, Debug)]
+struct MockResponse {
+    status: u16,
+    body: Option<Value>,
+}
+
+struct State {
+    api_url: String,
+    gateway_url: String,
+    heartbeat_interval: u64,
+    current_user: Value,
+    next_id: AtomicU64,
+    connections: AtomicU64,
+    requests: Mutex<Vec<RecordedRequest>>,
+    responses: Mutex<HashMap<(LightMethod, String), MockResponse>>,
+    commands: Mutex<Vec<Value>>,
+    sessions: Mutex<Vec<mpsc::UnboundedSender<Outgoing>>>,
+    session_ids: Mutex<Vec<String>>,
+    close_codes: Mutex<Vec<u16>>,
+    /// Notified whenever a request is recorded or the gateway's state changes.
+    notify: Notify,
+}
+
+impl State {
+    fn next_id(&self) -> u64 {
+        self.next_id.fetch_add(1, Ordering::Relaxed)
+    }
+
+    /// Sends a message to all sessions, dropping those that have gone away.
+    async fn broadcast(&self, outgoing: &Outgoing) {
+        self.sessions.lock().await.retain(|session| session.send(outgoing.clone()).is_ok());
+    }
+}
+
+/// A local stand-in for Discord's REST API and gateway.
+///
+/// The server listens on two local ports, one for the REST API and one for the
+/// gateway, and stops listening when dropped.
+///
+/// Refer to the [module-level documentation][`crate::testing`] for an example.
+pub struct MockServer {
+    state: Arc<State>,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl MockServer {
+    /// Starts listening on local ports.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] if a port could not be bound.
+    pub async fn start() -> Result<Self> {
+        let http_listener = TcpListener::bind("127.0.0.1:0").await?;
+        let gateway_listener = TcpListener::bind("127.0.0.1:0").await?;
+
+        let state = Arc::new(State {
+            api_url: format!("http://{}", http_listener.local_addr()?),
+            gateway_url: format!("ws://{}", gateway_listener.local_addr()?),
+            heartbeat_interval: 41250,
+            current_user: json!({
+                "id": "1000",
+                "username": "Mock Bot",
+                "discriminator": "0000",
+                "avatar": null,
+                "bot": true,
+                "mfa_enabled": false,
+                "verified": true,
+            }),
+            next_id: AtomicU64::new(1001),
+            connections: AtomicU64::new(0),
+            requests: Mutex::default(),
+            responses: Mutex::default(),
+            commands: Mutex::default(),
+            sessions: Mutex::default(),
+            session_ids: Mutex::default(),
+            close_codes: Mutex::default(),
+            notify: Notify::new(),
+        });
+
+        let tasks = vec![
+            spawn_named("testing::http", http::listen(Arc::clone(&state), http_listener)),
+            spawn_named("testing::gateway", gateway::listen(Arc::clone(&state), gateway_listener)),
+        ];
+
+        Ok(Self {
+            state,
+            tasks,
+        })
+    }
+
+    /// The base URL of the REST API, to be passed to [`HttpBuilder::base_url`].
+    #[must_use]
+    pub fn api_url(&self) -> &str {
+        &self.state.api_url
+    }
+
+    /// The URL of the gateway, to be passed to [`ClientBuilder::gateway_url`].
+    #[must_use]
+    pub fn gateway_url(&self) -> &str {
+        &self.state.gateway_url
+    }
+
+    /// The user that clients are logged in as.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the mocked user is not a valid [`CurrentUser`], which is a
+    /// bug in the library.
+    #[must_use]
+    pub fn current_user(&self) -> CurrentUser {
+        json::from_value(self.state.current_user.clone()).expect("Mocked user is valid")
+    }
+
+    /// Creates an [`Http`] client that performs requests against the server.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the server's own URL is invalid, which is a bug in the
+    /// library.
+    #[must_use]
+    pub fn http(&self) -> Http {
+        HttpBuilder::new(MOCK_TOKEN)
+            .base_url(self.api_url())
+            .expect("Mock server URL is valid")
+            .build()
+    }
+
+    /// Creates a [`ClientBuilder`] whose [`Http`] client and shards connect to
+    /// the server.
+    pub fn client_builder(&self, intents: GatewayIntents) -> ClientBuilder {
+        ClientBuilder::new_with_http(self.http(), intents).gateway_url(self.gateway_url())
+    }
+
+    /// Sets the response to answer a route with, replacing the default
+    /// response. If `body` is `None`, the response has no body.
+    pub async fn respond(&self, route: RouteInfo<'_>, status: u16, body: Option<Value>) {
+        let (method, _, path) = route.deconstruct();
+        let response = MockResponse {
+            status,
+            body,
+        };
+
+        let path = relative_path(&path).unwrap_or(&path).to_string();
+
+        self.state.responses.lock().await.insert((method, path), response);
+    }
+
+    /// All REST requests made so far, in the order they were received.
+    pub async fn requests(&self) -> Vec<RecordedRequest> {
+        self.state.requests.lock().await.clone()
+    }
+
+    /// Waits until a request is made to the given route, returning the first
+    /// such request. This also returns requests that were made before calling
+    /// this method.
+    pub async fn wait_for_request(&self, route: RouteInfo<'_>) -> RecordedRequest {
+        loop {
+            let notified = self.state.notify.notified();
+
+            if let Some(request) =
+                self.state.requests.lock().await.iter().find(|request| request.is(&route))
+            {
+                return request.clone();
+            }
+
+            notified.await;
+        }
+    }
+
+    /// All payloads that shards sent over the gateway so far, such as
+    /// IDENTIFYs, heartbeats and presence updates.
+    pub async fn gateway_commands(&self) -> Vec<Value> {
+        self.state.commands.lock().await.clone()
+    }
+
+    /// The number of connections that shards opened to the gateway so far.
+    #[must_use]
+    pub fn connections(&self) -> u64 {
+        self.state.connections.load(Ordering::Relaxed)
+    }
+
+    /// The codes of all close frames that shards closed their connection
+    /// with so far.
+    pub async fn close_codes(&self) -> Vec<u16> {
+        self.state.close_codes.lock().await.clone()
+    }
+
+    /// Waits until at least one shard has identified or resumed.
+    pub async fn wait_until_ready(&self) {
+        loop {
+            let notified = self.state.notify.notified();
+
+            if !self.state.sessions.lock().await.is_empty() {
+                return;
+            }
+
+            notified.await;
+        }
+    }
+
+    /// Dispatches an event to all connected shards, with the next sequence
+    /// number of each session.
+    pub async fn dispatch(&self, event: Event) {
+        self.state.broadcast(&Outgoing::Dispatch(Box::new(event))).await;
+    }
+
+    /// Sends a raw gateway event to all connected shards, such as
+    /// [`GatewayEvent::Reconnect`].
+    ///
+    /// Sequence numbers of [`GatewayEvent::Dispatch`] events sent this way are
+    /// not checked, prefer [`Self::dispatch`] for those.
+    pub async fn send(&self, event: GatewayEvent) {
+        self.state.broadcast(&Outgoing::Event(Box::new(event))).await;
+    }
+
+    /// Closes the connection of all connected shards with the given close
+    /// code.
+    pub async fn disconnect(&self, code: u16) {
+        self.state.broadcast(&Outgoing::Close(code)).await;
+        self.state.sessions.lock().await.clear();
+    }
+
+    /// Dispatches a [`Event::MessageCreate`] for a message sent by another
+    /// user in the given channel.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Json`] if the message could not be built.
+    pub async fn dispatch_message(&self, channel_id: ChannelId, content: &str) -> Result<()> {
+        let author = json!({
+            "id": self.state.next_id().to_string(),
+            "username": "Mock User",
+            "discriminator": "0001",
+            "avatar": null,
+        });
+        let message = message_json(&self.state, channel_id.0, author, content);
+
+        self.dispatch(Event::MessageCreate(MessageCreateEvent {
+            message: json::from_value(message)?,
+        }))
+        .await;
+
+        Ok(())
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        for task in &self.tasks {
+            task.abort();
+        }
+    }
+}
+
+/// Builds the JSON of a message as Discord would return it.
+fn message_json(state: &State, channel_id: u64, author: Value, content: &str) -> Value {
+    json!({
+        "id": state.next_id().to_string(),
+        "channel_id": channel_id.to_string(),
+        "author": author,
+        "content": content,
+        "timestamp": Timestamp::now(),
+        "edited_timestamp": null,
+        "tts": false,
+        "mention_everyone": false,
+        "mentions": [],
+        "mention_roles": [],
+        "attachments": [],
+        "embeds": [],
+        "pinned": false,
+        "type": 0,
+    })
+}
@@ -1,6 +1,8 @@
 use std::fmt;
 
 use super::ArgumentConvert;
+#[cfg(feature = "cache")]
+use crate::cache::CacheBackendExt;
 use crate::model::prelude::*;
 use crate::prelude::*;
 
@@ -51,14 +53,18 @@ async fn lookup_channel_global(
     }
 
     #[cfg(feature = "cache")]
-    if let Some(channel) = ctx.cache.channels.iter().find_map(|m| {
-        let channel = m.value();
-        if channel.name.eq_ignore_ascii_case(s) {
-            Some(channel.clone())
-        } else {
-            None
-        }
-    }) {
+    if let Some(channel) = ctx
+        .cache
+        .backend
+        .find_map_channel(|channel| {
+            if channel.name.eq_ignore_ascii_case(s) {
+                Some(channel.clone())
+            } else {
+                None
+            }
+        })
+        .await
+    {
         return Ok(Channel::Guild(channel));
     }
 
@@ -50,22 +50,22 @@ impl ArgumentConvert for Emoji {
         // Get Guild or PartialGuild
         let guild_id = guild_id.ok_or(EmojiParseError::OutsideGuild)?;
         #[cfg(feature = "cache")]
-        let guild = ctx.cache.guilds.get(&guild_id);
+        let emojis = ctx.cache.guild_field(guild_id, |guild| guild.emojis.clone()).await;
         #[cfg(not(feature = "cache"))]
-        let guild = ctx.http.get_guild(guild_id.0).await.ok();
-        let guild = guild.ok_or(EmojiParseError::FailedToRetrieveGuild)?;
+        let emojis = ctx.http.get_guild(guild_id.0).await.ok().map(|guild| guild.emojis);
+        let emojis = emojis.ok_or(EmojiParseError::FailedToRetrieveGuild)?;
 
         let direct_id = s.parse::<u64>().ok().map(EmojiId);
         let id_from_mention = crate::utils::parse_emoji(s).map(|e| e.id);
 
         if let Some(emoji_id) = direct_id.or(id_from_mention) {
-            if let Some(emoji) = guild.emojis.get(&emoji_id).cloned() {
+            if let Some(emoji) = emojis.get(&emoji_id).cloned() {
                 return Ok(emoji);
             }
         }
 
         if let Some(emoji) =
-            guild.emojis.values().find(|emoji| emoji.name.eq_ignore_ascii_case(s)).cloned()
+            emojis.values().find(|emoji| emoji.name.eq_ignore_ascii_case(s)).cloned()
         {
             return Ok(emoji);
         }
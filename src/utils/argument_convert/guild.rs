@@ -4,6 +4,8 @@
 use std::fmt;
 
 use super::ArgumentConvert;
+#[cfg(feature = "cache")]
+use crate::cache::CacheBackendExt;
 use crate::model::prelude::*;
 use crate::prelude::*;
 
@@ -39,21 +41,23 @@ impl ArgumentConvert for Guild {
         _channel_id: Option<ChannelId>,
         s: &str,
     ) -> Result<Self, Self::Err> {
-        let guilds = &ctx.cache.guilds;
+        let backend = &ctx.cache.backend;
 
-        let lookup_by_id = || guilds.get(&GuildId(s.parse().ok()?)).map(|g| g.clone());
+        if let Ok(guild_id) = s.parse() {
+            if let Some(guild) = backend.guild(GuildId(guild_id)).await {
+                return Ok(guild);
+            }
+        }
 
-        let lookup_by_name = || {
-            guilds.iter().find_map(|m| {
-                let guild = m.value();
+        backend
+            .find_map_guild(|guild| {
                 if guild.name.eq_ignore_ascii_case(s) {
                     Some(guild.clone())
                 } else {
                     None
                 }
             })
-        };
-
-        lookup_by_id().or_else(lookup_by_name).ok_or(GuildParseError::NotFoundOrMalformed)
+            .await
+            .ok_or(GuildParseError::NotFoundOrMalformed)
     }
 }
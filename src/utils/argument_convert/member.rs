@@ -1,6 +1,8 @@
 use std::fmt;
 
 use super::ArgumentConvert;
+#[cfg(feature = "cache")]
+use crate::cache::MemberQuery;
 use crate::model::prelude::*;
 use crate::prelude::*;
 
@@ -29,6 +31,62 @@ impl fmt::Display for MemberParseError {
     }
 }
 
+/// Returns the cached member with the lowest Id that matches, cloning only that one.
+#[cfg(feature = "cache")]
+async fn find_in_cache(
+    ctx: &Context,
+    guild_id: GuildId,
+    matches: impl Fn(&Member) -> bool + Send,
+) -> Option<Member> {
+    ctx.cache
+        .guild_field(guild_id, move |guild| {
+            guild.members.values().filter(|m| matches(m)).min_by_key(|m| m.user.id).cloned()
+        })
+        .await
+        .flatten()
+}
+
+#[cfg(feature = "cache")]
+async fn lookup_by_name_and_discrim_in_cache(
+    ctx: &Context,
+    guild_id: GuildId,
+    s: &str,
+) -> Option<Member> {
+    let (name, discrim) = crate::utils::parse_user_tag(s)?;
+
+    find_in_cache(ctx, guild_id, |m| {
+        m.user.name.eq_ignore_ascii_case(name) && m.user.discriminator == discrim
+    })
+    .await
+}
+
+#[cfg(feature = "cache")]
+async fn lookup_by_name_in_cache(ctx: &Context, guild_id: GuildId, s: &str) -> Option<Member> {
+    find_in_cache(ctx, guild_id, |m| {
+        m.user.name.eq_ignore_ascii_case(s)
+            || m.nick.as_ref().map_or(false, |nick| nick.eq_ignore_ascii_case(s))
+    })
+    .await
+}
+
+/// Returns the only cached member whose name or nickname starts with the string, if there is
+/// exactly one.
+#[cfg(feature = "cache")]
+async fn lookup_by_unique_prefix_in_cache(
+    ctx: &Context,
+    guild_id: GuildId,
+    s: &str,
+) -> Option<Member> {
+    let mut members =
+        ctx.cache.query_members(guild_id, &MemberQuery::new().name_prefix(s).limit(2)).await?;
+
+    if members.len() == 1 {
+        members.pop()
+    } else {
+        None
+    }
+}
+
 /// Look up a guild member by a string case-insensitively.
 ///
 /// Requires the cache feature to be enabled.
@@ -39,6 +97,9 @@ impl fmt::Display for MemberParseError {
 /// 3. [Lookup by name#discrim](`crate::utils::parse_user_tag`).
 /// 4. Lookup by name
 /// 5. Lookup by nickname
+/// 6. Lookup by the prefix of a name or nickname, if only one cached member matches it
+///
+/// Steps 3 to 5 look in the cache before asking Discord.
 #[async_trait::async_trait]
 impl ArgumentConvert for Member {
     type Err = MemberParseError;
@@ -63,6 +124,11 @@ impl ArgumentConvert for Member {
         // Following code is inspired by discord.py's MemberConvert::query_member_named
 
         // If string is a username+discriminator
+        #[cfg(feature = "cache")]
+        if let Some(member) = lookup_by_name_and_discrim_in_cache(ctx, guild_id, s).await {
+            return Ok(member);
+        }
+
         if let Some((name, discrim)) = crate::utils::parse_user_tag(s) {
             if let Ok(member_results) = guild_id.search_members(ctx, name, Some(100)).await {
                 if let Some(member) = member_results.into_iter().find(|m| {
@@ -74,6 +140,11 @@ impl ArgumentConvert for Member {
         }
 
         // If string is username or nickname
+        #[cfg(feature = "cache")]
+        if let Some(member) = lookup_by_name_in_cache(ctx, guild_id, s).await {
+            return Ok(member);
+        }
+
         if let Ok(member_results) = guild_id.search_members(ctx, s, Some(100)).await {
             if let Some(member) = member_results.into_iter().find(|m| {
                 m.user.name.eq_ignore_ascii_case(s)
@@ -83,6 +154,12 @@ impl ArgumentConvert for Member {
             }
         }
 
+        // If string is the start of a single member's username or nickname
+        #[cfg(feature = "cache")]
+        if let Some(member) = lookup_by_unique_prefix_in_cache(ctx, guild_id, s).await {
+            return Ok(member);
+        }
+
         Err(MemberParseError::NotFoundOrMalformed)
     }
 }
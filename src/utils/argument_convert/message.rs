@@ -68,7 +68,7 @@ impl ArgumentConvert for Message {
             .ok_or(MessageParseError::Malformed)?;
 
         #[cfg(feature = "cache")]
-        if let Some(msg) = ctx.cache.message(channel_id, message_id) {
+        if let Some(msg) = ctx.cache.message(channel_id, message_id).await {
             return Ok(msg);
         }
 
@@ -61,7 +61,7 @@ impl ArgumentConvert for Role {
         let guild_id = guild_id.ok_or(RoleParseError::NotInGuild)?;
 
         #[cfg(feature = "cache")]
-        let roles = ctx.cache.guild_roles(guild_id).ok_or(RoleParseError::NotInCache)?;
+        let roles = ctx.cache.guild_roles(guild_id).await.ok_or(RoleParseError::NotInCache)?;
         #[cfg(not(feature = "cache"))]
         let roles = ctx.http.get_guild_roles(guild_id.0).await.map_err(RoleParseError::Http)?;
 
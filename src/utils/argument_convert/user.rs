@@ -1,6 +1,8 @@
 use std::fmt;
 
 use super::ArgumentConvert;
+#[cfg(feature = "cache")]
+use crate::cache::CacheBackendExt;
 use crate::model::prelude::*;
 use crate::prelude::*;
 
@@ -25,41 +27,38 @@ impl fmt::Display for UserParseError {
 }
 
 #[cfg(feature = "cache")]
-fn lookup_by_global_cache(ctx: &Context, s: &str) -> Option<User> {
-    let users = &ctx.cache.users;
+async fn lookup_by_global_cache(ctx: &Context, s: &str) -> Option<User> {
+    let backend = &ctx.cache.backend;
 
-    let lookup_by_id = || users.get(&UserId(s.parse().ok()?)).map(|u| u.clone());
+    if let Ok(user_id) = s.parse() {
+        if let Some(user) = backend.user(UserId(user_id)).await {
+            return Some(user);
+        }
+    }
 
-    let lookup_by_mention =
-        || users.get(&UserId(crate::utils::parse_username(s)?)).map(|u| u.clone());
+    if let Some(user_id) = crate::utils::parse_username(s) {
+        if let Some(user) = backend.user(UserId(user_id)).await {
+            return Some(user);
+        }
+    }
 
-    let lookup_by_name_and_discrim = || {
-        let (name, discrim) = crate::utils::parse_user_tag(s)?;
-        users.iter().find_map(|m| {
-            let user = m.value();
-            if user.discriminator == discrim && user.name.eq_ignore_ascii_case(name) {
-                Some(user.clone())
-            } else {
-                None
-            }
-        })
-    };
+    if let Some((name, discrim)) = crate::utils::parse_user_tag(s) {
+        let user = backend
+            .find_map_user(|user| {
+                if user.discriminator == discrim && user.name.eq_ignore_ascii_case(name) {
+                    Some(user.clone())
+                } else {
+                    None
+                }
+            })
+            .await;
 
-    let lookup_by_name = || {
-        users.iter().find_map(|m| {
-            let user = m.value();
-            if user.name == s {
-                Some(user.clone())
-            } else {
-                None
-            }
-        })
-    };
+        if user.is_some() {
+            return user;
+        }
+    }
 
-    lookup_by_id()
-        .or_else(lookup_by_mention)
-        .or_else(lookup_by_name_and_discrim)
-        .or_else(lookup_by_name)
+    backend.find_map_user(|user| if user.name == s { Some(user.clone()) } else { None }).await
 }
 
 /// Look up a user by a string case-insensitively.
@@ -83,7 +82,7 @@ impl ArgumentConvert for User {
     ) -> Result<Self, Self::Err> {
         // Try to look up in global user cache via a variety of methods
         #[cfg(feature = "cache")]
-        if let Some(user) = lookup_by_global_cache(ctx, s) {
+        if let Some(user) = lookup_by_global_cache(ctx, s).await {
             return Ok(user);
         }
 
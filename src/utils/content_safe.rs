@@ -121,13 +121,16 @@ impl Default for ContentSafeOptions {
 /// ```rust
 /// # use serenity::client::Cache;
 /// #
+/// # async fn run() {
 /// # let cache = Cache::default();
 /// use serenity::utils::{content_safe, ContentSafeOptions};
 ///
 /// let with_mention = "@everyone";
-/// let without_mention = content_safe(&cache, &with_mention, &ContentSafeOptions::default(), &[]);
+/// let without_mention =
+///     content_safe(&cache, &with_mention, &ContentSafeOptions::default(), &[]).await;
 ///
 /// assert_eq!("@\u{200B}everyone".to_string(), without_mention);
+/// # }
 /// ```
 ///
 /// Filtering out mentions from a message.
@@ -137,17 +140,18 @@ impl Default for ContentSafeOptions {
 /// use serenity::model::channel::Message;
 /// use serenity::utils::{content_safe, ContentSafeOptions};
 ///
-/// fn filter_message(cache: &Cache, message: &Message) -> String {
+/// async fn filter_message(cache: &Cache, message: &Message) -> String {
 ///     content_safe(cache, &message.content, &ContentSafeOptions::default(), &message.mentions)
+///         .await
 /// }
 /// ```
-pub fn content_safe(
+pub async fn content_safe(
     cache: impl AsRef<Cache>,
     s: impl AsRef<str>,
     options: &ContentSafeOptions,
     users: &[User],
 ) -> String {
-    let mut content = clean_mentions(&cache, s, options, users);
+    let mut content = clean_mentions(&cache, s, options, users).await;
 
     if options.clean_here {
         content = content.replace("@here", "@\u{200B}here");
@@ -160,7 +164,7 @@ pub fn content_safe(
     content
 }
 
-fn clean_mentions(
+async fn clean_mentions(
     cache: impl AsRef<Cache>,
     s: impl AsRef<str>,
     options: &ContentSafeOptions,
@@ -200,7 +204,7 @@ fn clean_mentions(
                         // NOTE: numeric strings that are too large to fit into u64 will not parse
                         // correctly and will be left unchanged.
                         if let Ok(mention) = mention_str.parse() {
-                            content.push_str(&clean_mention(&cache, mention, options, users));
+                            content.push_str(&clean_mention(&cache, mention, options, users).await);
                             cleaned = true;
                         }
                     }
@@ -216,7 +220,7 @@ fn clean_mentions(
     content
 }
 
-fn clean_mention(
+async fn clean_mention(
     cache: impl AsRef<Cache>,
     mention: Mention,
     options: &ContentSafeOptions,
@@ -225,7 +229,7 @@ fn clean_mention(
     let cache = cache.as_ref();
     match mention {
         Mention::Channel(id) => {
-            if let Some(Channel::Guild(channel)) = id.to_channel_cached(cache) {
+            if let Some(Channel::Guild(channel)) = id.to_channel_cached(cache).await {
                 format!("#{}", channel.name).into()
             } else {
                 "#deleted-channel".into()
@@ -233,18 +237,22 @@ fn clean_mention(
         },
         Mention::Role(id) => id
             .to_role_cached(cache)
+            .await
             .map_or(Cow::Borrowed("@deleted-role"), |role| format!("@{}", role.name).into()),
         Mention::User(id) => {
             if let Some(guild_id) = options.guild_reference {
-                if let Some(guild) = cache.guild(guild_id) {
-                    if let Some(member) = guild.members.get(&id) {
-                        return if options.show_discriminator {
-                            format!("@{}", member.distinct())
+                let name = cache
+                    .member_field(guild_id, id, |member| {
+                        if options.show_discriminator {
+                            member.distinct()
                         } else {
-                            format!("@{}", member.display_name())
+                            member.display_name().into_owned()
                         }
-                        .into();
-                    }
+                    })
+                    .await;
+
+                if let Some(name) = name {
+                    return format!("@{}", name).into();
                 }
             }
 
@@ -258,6 +266,7 @@ fn clean_mention(
             };
             cache
                 .user(id)
+                .await
                 .as_ref()
                 .map(get_username)
                 .or_else(|| users.iter().find(|u| u.id == id).map(get_username))
@@ -281,8 +290,8 @@ mod tests {
     use crate::model::{Permissions, Timestamp};
     use crate::utils::Colour;
 
-    #[test]
-    fn test_content_safe() {
+    #[tokio::test]
+    async fn test_content_safe() {
         let user = User {
             id: UserId(100000000000000000),
             avatar: None,
@@ -351,6 +360,7 @@ mod tests {
             widget_enabled: Some(false),
             widget_channel_id: None,
             stage_instances: vec![],
+            scheduled_events: vec![],
             threads: vec![],
             stickers: HashMap::new(),
         };
@@ -420,9 +430,9 @@ mod tests {
 
         guild.members.insert(user.id, member.clone());
         guild.roles.insert(role.id, role);
-        cache.users.insert(user.id, user.clone());
-        cache.guilds.insert(guild.id, guild.clone());
-        cache.channels.insert(channel.id, channel);
+        cache.backend.insert_user(user.clone()).await;
+        cache.backend.insert_guild(guild.clone()).await;
+        cache.backend.insert_channel(channel).await;
 
         let with_user_mentions = "<@!100000000000000000> <@!000000000000000000> <@123> <@!123> \
         <@!123123123123123123123> <@123> <@123123123123123123> <@!invalid> \
@@ -438,49 +448,58 @@ mod tests {
 
         // User mentions
         let options = ContentSafeOptions::default();
-        assert_eq!(without_user_mentions, content_safe(&cache, with_user_mentions, &options, &[]));
+        assert_eq!(
+            without_user_mentions,
+            content_safe(&cache, with_user_mentions, &options, &[]).await
+        );
 
         let options = ContentSafeOptions::default();
         assert_eq!(
             format!("@{}#{:04}", user.name, user.discriminator),
-            content_safe(&cache, "<@!100000000000000000>", &options, &[])
+            content_safe(&cache, "<@!100000000000000000>", &options, &[]).await
         );
 
         let options = ContentSafeOptions::default();
         assert_eq!(
             format!("@{}#{:04}", user.name, user.discriminator),
-            content_safe(&cache, "<@100000000000000000>", &options, &[])
+            content_safe(&cache, "<@100000000000000000>", &options, &[]).await
         );
 
         let options = ContentSafeOptions::default();
-        assert_eq!("@invalid-user", content_safe(&cache, "<@100000000000000001>", &options, &[]));
+        assert_eq!(
+            "@invalid-user",
+            content_safe(&cache, "<@100000000000000001>", &options, &[]).await
+        );
 
         let options = ContentSafeOptions::default();
         assert_eq!(
             format!("@{}#{:04}", outside_cache_user.name, outside_cache_user.discriminator),
-            content_safe(&cache, "<@100000000000000001>", &options, &[outside_cache_user])
+            content_safe(&cache, "<@100000000000000001>", &options, &[outside_cache_user]).await
         );
 
         let options = options.show_discriminator(false);
         assert_eq!(
             format!("@{}", user.name),
-            content_safe(&cache, "<@!100000000000000000>", &options, &[])
+            content_safe(&cache, "<@!100000000000000000>", &options, &[]).await
         );
 
         let options = options.show_discriminator(false);
         assert_eq!(
             format!("@{}", user.name),
-            content_safe(&cache, "<@100000000000000000>", &options, &[])
+            content_safe(&cache, "<@100000000000000000>", &options, &[]).await
         );
 
         let options = options.display_as_member_from(guild.id);
         assert_eq!(
             format!("@{}", member.nick.unwrap()),
-            content_safe(&cache, "<@!100000000000000000>", &options, &[])
+            content_safe(&cache, "<@!100000000000000000>", &options, &[]).await
         );
 
         let options = options.clean_user(false);
-        assert_eq!(with_user_mentions, content_safe(&cache, with_user_mentions, &options, &[]));
+        assert_eq!(
+            with_user_mentions,
+            content_safe(&cache, with_user_mentions, &options, &[]).await
+        );
 
         // Channel mentions
         let with_channel_mentions = "<#> <#deleted-channel> #deleted-channel <#0> \
@@ -493,13 +512,13 @@ mod tests {
 
         assert_eq!(
             without_channel_mentions,
-            content_safe(&cache, with_channel_mentions, &options, &[])
+            content_safe(&cache, with_channel_mentions, &options, &[]).await
         );
 
         let options = options.clean_channel(false);
         assert_eq!(
             with_channel_mentions,
-            content_safe(&cache, with_channel_mentions, &options, &[])
+            content_safe(&cache, with_channel_mentions, &options, &[]).await
         );
 
         // Role mentions
@@ -511,10 +530,16 @@ mod tests {
         @ferris-club-member @deleted-role \
         <@&111111111111111111111111111111> <@&@deleted-role";
 
-        assert_eq!(without_role_mentions, content_safe(&cache, with_role_mentions, &options, &[]));
+        assert_eq!(
+            without_role_mentions,
+            content_safe(&cache, with_role_mentions, &options, &[]).await
+        );
 
         let options = options.clean_role(false);
-        assert_eq!(with_role_mentions, content_safe(&cache, with_role_mentions, &options, &[]));
+        assert_eq!(
+            with_role_mentions,
+            content_safe(&cache, with_role_mentions, &options, &[]).await
+        );
 
         // Everyone mentions
         let with_everyone_mention = "@everyone";
@@ -523,13 +548,13 @@ mod tests {
 
         assert_eq!(
             without_everyone_mention,
-            content_safe(&cache, with_everyone_mention, &options, &[])
+            content_safe(&cache, with_everyone_mention, &options, &[]).await
         );
 
         let options = options.clean_everyone(false);
         assert_eq!(
             with_everyone_mention,
-            content_safe(&cache, with_everyone_mention, &options, &[])
+            content_safe(&cache, with_everyone_mention, &options, &[]).await
         );
 
         // Here mentions
@@ -537,9 +562,12 @@ mod tests {
 
         let without_here_mention = "@\u{200B}here";
 
-        assert_eq!(without_here_mention, content_safe(&cache, with_here_mention, &options, &[]));
+        assert_eq!(
+            without_here_mention,
+            content_safe(&cache, with_here_mention, &options, &[]).await
+        );
 
         let options = options.clean_here(false);
-        assert_eq!(with_here_mention, content_safe(&cache, with_here_mention, &options, &[]));
+        assert_eq!(with_here_mention, content_safe(&cache, with_here_mention, &options, &[]).await);
     }
 }
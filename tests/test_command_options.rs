@@ -0,0 +1,113 @@
+#![cfg(feature = "interaction_framework")]
+
+use serenity::framework::interaction::{CommandOptions, OptionError};
+use serenity::json::{json, Value};
+use serenity::model::application::command::CommandOptionType;
+use serenity::model::application::interaction::application_command::CommandData;
+use serenity::model::id::ChannelId;
+
+#[derive(Debug, CommandOptions)]
+struct Announce {
+    /// Where to announce.
+    #[channel_types(text, news)]
+    channel: ChannelId,
+    #[name = "ping"]
+    #[description = "Who to ping."]
+    #[choice("Everyone", "everyone")]
+    #[choice("Here", "here")]
+    mention: Option<String>,
+    /// The message to announce.
+    #[autocomplete]
+    message: String,
+    /// How important the announcement is.
+    #[choice("Low", 0.5)]
+    #[choice("High", 2.0)]
+    priority: Option<f64>,
+}
+
+#[test]
+fn command_options_derive() {
+    let options = Announce::create_options()
+        .into_iter()
+        .map(|option| Value::from(serenity::json::hashmap_to_json_map(option.0)))
+        .collect::<Vec<_>>();
+
+    assert_eq!(options, [
+        json!({
+            "name": "channel",
+            "description": "Where to announce.",
+            "type": 7,
+            "required": true,
+            "channel_types": [0, 5],
+        }),
+        json!({
+            "name": "ping",
+            "description": "Who to ping.",
+            "type": 3,
+            "required": false,
+            "choices": [
+                {"name": "Everyone", "value": "everyone"},
+                {"name": "Here", "value": "here"},
+            ],
+        }),
+        json!({
+            "name": "message",
+            "description": "The message to announce.",
+            "type": 3,
+            "required": true,
+            "autocomplete": true,
+        }),
+        json!({
+            "name": "priority",
+            "description": "How important the announcement is.",
+            "type": 10,
+            "required": false,
+            "choices": [
+                {"name": "Low", "value": 0.5},
+                {"name": "High", "value": 2.0},
+            ],
+        }),
+    ]);
+
+    let data = |options: Value| -> CommandData {
+        serde_json::from_value(json!({
+            "id": "1",
+            "name": "announce",
+            "type": 1,
+            "options": options,
+            "resolved": {
+                "channels": {
+                    "3": {"id": "3", "name": "news", "type": 5, "permissions": "0"},
+                },
+            },
+        }))
+        .unwrap()
+    };
+
+    let announce = Announce::from_command_data(&data(json!([
+        {"name": "channel", "type": 7, "value": "3"},
+        {"name": "ping", "type": 3, "value": "here"},
+        {"name": "message", "type": 3, "value": "Hello"},
+    ])))
+    .unwrap();
+    assert_eq!(announce.channel, ChannelId(3));
+    assert_eq!(announce.mention.as_deref(), Some("here"));
+    assert_eq!(announce.message, "Hello");
+    assert_eq!(announce.priority, None);
+
+    let error = Announce::from_command_data(&data(json!([
+        {"name": "channel", "type": 7, "value": "3"},
+    ])))
+    .unwrap_err();
+    assert_eq!(error, OptionError::Missing("message"));
+
+    let error = Announce::from_command_data(&data(json!([
+        {"name": "channel", "type": 3, "value": "general"},
+        {"name": "message", "type": 3, "value": "Hello"},
+    ])))
+    .unwrap_err();
+    assert_eq!(error, OptionError::Mistyped {
+        name: "channel",
+        expected: CommandOptionType::Channel,
+    });
+}
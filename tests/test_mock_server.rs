@@ -0,0 +1,1070 @@
+#![cfg(feature = "testing")]
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serenity::client::bridge::gateway::{ChunkGuildFilter, SessionStore, ShardId};
+#[cfg(feature = "remote_shard_coordinator")]
+use serenity::client::bridge::gateway::{
+    RemoteShardCoordinator,
+    ShardCoordinator,
+    ShardCoordinatorServer,
+};
+use serenity::client::ShutdownReason;
+use serenity::framework::StandardFramework;
+use serenity::gateway::{GatewayEncoding, GatewayError, SessionInfo, TransportCompression};
+use serenity::http::routing::RouteInfo;
+use serenity::http::{HttpBuilder, HttpError, RequestOptions, RetryPolicy};
+use serenity::json::{json, Value};
+use serenity::model::application::command::CommandType;
+use serenity::model::application::command_sync::{CommandChange, CommandRegistry};
+use serenity::model::prelude::*;
+use serenity::prelude::*;
+use serenity::testing::MockServer;
+use tokio::sync::Notify;
+use tokio::time::timeout;
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+struct Handler;
+
+#[serenity::async_trait]
+impl EventHandler for Handler {
+    async fn message(&self, ctx: Context, msg: Message) {
+        if msg.content == "!ping" {
+            msg.channel_id.say(&ctx, "Pong!").await.unwrap();
+        }
+    }
+}
+
+#[tokio::test]
+async fn client_replies_to_dispatched_message() {
+    let server = MockServer::start().await.unwrap();
+    let mut client = server
+        .client_builder(GatewayIntents::default())
+        .framework(StandardFramework::new())
+        .event_handler(Handler)
+        .await
+        .unwrap();
+    tokio::spawn(async move { client.start().await });
+
+    timeout(TIMEOUT, server.wait_until_ready()).await.unwrap();
+    server.dispatch_message(ChannelId(7), "!ping").await.unwrap();
+
+    let route = RouteInfo::CreateMessage {
+        channel_id: 7,
+    };
+    let request = timeout(TIMEOUT, server.wait_for_request(route)).await.unwrap();
+    assert_eq!(request.json::<Value>().unwrap()["content"], json!("Pong!"));
+
+    let identify = server.gateway_commands().await;
+    assert!(identify.iter().any(|command| command["op"] == json!(2)));
+}
+
+#[tokio::test]
+async fn client_uses_zlib_stream_across_reconnects() {
+    let server = MockServer::start().await.unwrap();
+    let mut client = server
+        .client_builder(GatewayIntents::default())
+        .transport_compression(TransportCompression::ZlibStream)
+        .framework(StandardFramework::new())
+        .event_handler(Handler)
+        .await
+        .unwrap();
+    tokio::spawn(async move { client.start().await });
+
+    for channel_id in [7, 8] {
+        timeout(TIMEOUT, server.wait_until_ready()).await.unwrap();
+        server.dispatch_message(ChannelId(channel_id), "!ping").await.unwrap();
+
+        let route = RouteInfo::CreateMessage {
+            channel_id,
+        };
+        timeout(TIMEOUT, server.wait_for_request(route)).await.unwrap();
+
+        // The next connection must start with a fresh zlib context.
+        server.disconnect(4000).await;
+    }
+
+    let resume = server.gateway_commands().await;
+    assert!(resume.iter().any(|command| command["op"] == json!(6)));
+}
+
+#[tokio::test]
+async fn client_uses_etf_encoding() {
+    for compression in [TransportCompression::None, TransportCompression::ZlibStream] {
+        let server = MockServer::start().await.unwrap();
+        let mut client = server
+            .client_builder(GatewayIntents::default())
+            .gateway_encoding(GatewayEncoding::Etf)
+            .transport_compression(compression)
+            .framework(StandardFramework::new())
+            .event_handler(Handler)
+            .await
+            .unwrap();
+        tokio::spawn(async move { client.start().await });
+
+        timeout(TIMEOUT, server.wait_until_ready()).await.unwrap();
+        server.dispatch_message(ChannelId(7), "!ping").await.unwrap();
+
+        let route = RouteInfo::CreateMessage {
+            channel_id: 7,
+        };
+        timeout(TIMEOUT, server.wait_for_request(route)).await.unwrap();
+    }
+}
+
+/// Repeats every message in its own channel.
+struct Echo(ChannelId);
+
+#[serenity::async_trait]
+impl EventHandler for Echo {
+    async fn message(&self, ctx: Context, msg: Message) {
+        self.0.say(&ctx, &msg.content).await.unwrap();
+    }
+}
+
+struct Rewrite;
+
+#[serenity::async_trait]
+impl EventMiddleware for Rewrite {
+    async fn process(&self, _ctx: &Context, event: &mut Event) -> bool {
+        match event {
+            Event::MessageCreate(event) if event.message.content == "!drop" => false,
+            Event::MessageCreate(event) => {
+                event.message.content.push_str(" (rewritten)");
+                true
+            },
+            _ => true,
+        }
+    }
+}
+
+#[tokio::test]
+async fn client_dispatches_through_middleware_to_all_handlers() {
+    let server = MockServer::start().await.unwrap();
+    let mut client = server
+        .client_builder(GatewayIntents::default())
+        .framework(StandardFramework::new())
+        .middleware(Rewrite)
+        .event_handler(Echo(ChannelId(8)))
+        .event_handler(Echo(ChannelId(9)))
+        .await
+        .unwrap();
+    tokio::spawn(async move { client.start().await });
+
+    timeout(TIMEOUT, server.wait_until_ready()).await.unwrap();
+    server.dispatch_message(ChannelId(7), "!drop").await.unwrap();
+    server.dispatch_message(ChannelId(7), "hello").await.unwrap();
+
+    let route = RouteInfo::CreateMessage {
+        channel_id: 9,
+    };
+    timeout(TIMEOUT, server.wait_for_request(route)).await.unwrap();
+
+    // Handlers run in registration order, and never see the dropped message.
+    let echoes = server
+        .requests()
+        .await
+        .into_iter()
+        .filter(|request| request.path.ends_with("/messages"))
+        .map(|request| (request.path.clone(), request.json::<Value>().unwrap()["content"].clone()))
+        .collect::<Vec<_>>();
+    assert_eq!(echoes, vec![
+        ("/channels/8/messages".to_string(), json!("hello (rewritten)")),
+        ("/channels/9/messages".to_string(), json!("hello (rewritten)")),
+    ]);
+}
+
+/// Replies to every message after a delay, and records why it was shut down.
+#[derive(Default)]
+struct Slow {
+    started: Arc<Notify>,
+    reasons: Arc<Mutex<Vec<ShutdownReason>>>,
+}
+
+#[serenity::async_trait]
+impl EventHandler for Slow {
+    async fn message(&self, ctx: Context, msg: Message) {
+        self.started.notify_one();
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        msg.channel_id.say(&ctx, "done").await.unwrap();
+    }
+
+    async fn shutdown(&self, reason: ShutdownReason) {
+        self.reasons.lock().await.push(reason);
+    }
+}
+
+#[tokio::test]
+async fn client_shuts_down_gracefully() {
+    let server = MockServer::start().await.unwrap();
+    let handler = Slow::default();
+    let started = Arc::clone(&handler.started);
+    let reasons = Arc::clone(&handler.reasons);
+    let mut client = server
+        .client_builder(GatewayIntents::default())
+        .framework(StandardFramework::new())
+        .event_handler(handler)
+        .await
+        .unwrap();
+    let shutdown = client.shutdown_handle();
+    let client = tokio::spawn(async move { client.start().await });
+
+    timeout(TIMEOUT, server.wait_until_ready()).await.unwrap();
+    server.dispatch_message(ChannelId(7), "slow").await.unwrap();
+    timeout(TIMEOUT, started.notified()).await.unwrap();
+
+    assert!(shutdown.shutdown(TIMEOUT).await);
+
+    // The running handler finished before the client stopped.
+    let route = RouteInfo::CreateMessage {
+        channel_id: 7,
+    };
+    assert!(server.requests().await.iter().any(|request| request.is(&route)));
+    timeout(TIMEOUT, client).await.unwrap().unwrap().unwrap();
+
+    assert_eq!(*reasons.lock().await, vec![ShutdownReason::Requested]);
+    assert_eq!(server.close_codes().await, vec![4000]);
+}
+
+/// Starts a request in a spawned task for every message.
+#[derive(Default)]
+struct Spawning {
+    started: Arc<Notify>,
+}
+
+#[serenity::async_trait]
+impl EventHandler for Spawning {
+    async fn message(&self, ctx: Context, _msg: Message) {
+        tokio::spawn(async move { ctx.http.get_channel(7).await });
+        self.started.notify_one();
+    }
+}
+
+#[tokio::test]
+async fn shutdown_waits_for_http_requests() {
+    let server = MockServer::start().await.unwrap();
+    let body = json!({
+        "message": "You are being rate limited.",
+        "retry_after": 60.0,
+        "global": false,
+    });
+    let route = RouteInfo::GetChannel {
+        channel_id: 7,
+    };
+    server.respond(route.clone(), 429, Some(body)).await;
+
+    let handler = Spawning::default();
+    let started = Arc::clone(&handler.started);
+    let mut client = server
+        .client_builder(GatewayIntents::default())
+        .framework(StandardFramework::new())
+        .event_handler(handler)
+        .await
+        .unwrap();
+    let shutdown = client.shutdown_handle();
+    let http = Arc::clone(&client.cache_and_http.http);
+    tokio::spawn(async move { client.start().await });
+
+    timeout(TIMEOUT, server.wait_until_ready()).await.unwrap();
+    server.dispatch_message(ChannelId(7), "spawn").await.unwrap();
+    timeout(TIMEOUT, started.notified()).await.unwrap();
+    timeout(TIMEOUT, server.wait_for_request(route)).await.unwrap();
+
+    // The handler finished, but the request it spawned waits on a ratelimit.
+    assert!(!shutdown.shutdown(Duration::from_millis(500)).await);
+    assert_eq!(http.in_flight_requests(), 1);
+}
+
+#[derive(Clone, Default)]
+struct MemorySessionStore(Arc<std::sync::Mutex<HashMap<ShardId, (u64, SessionInfo)>>>);
+
+#[serenity::async_trait]
+impl SessionStore for MemorySessionStore {
+    async fn load(
+        &self,
+        shard_id: ShardId,
+        shard_total: u64,
+    ) -> serenity::Result<Option<SessionInfo>> {
+        let sessions = self.0.lock().unwrap();
+        let session = sessions.get(&shard_id).filter(|(total, _)| *total == shard_total);
+
+        Ok(session.map(|(_, session)| session.clone()))
+    }
+
+    async fn save(
+        &self,
+        shard_id: ShardId,
+        shard_total: u64,
+        session: &SessionInfo,
+    ) -> serenity::Result<()> {
+        self.0.lock().unwrap().insert(shard_id, (shard_total, session.clone()));
+        Ok(())
+    }
+
+    async fn remove(&self, shard_id: ShardId) -> serenity::Result<()> {
+        self.0.lock().unwrap().remove(&shard_id);
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn restarted_client_resumes_saved_session() {
+    let server = MockServer::start().await.unwrap();
+    let store = MemorySessionStore::default();
+
+    for restarted in [false, true] {
+        let mut builder = server.client_builder(GatewayIntents::default());
+
+        // After the restart, the shard can only connect to the gateway through
+        // the resume gateway URL of the saved session.
+        if restarted {
+            builder = builder.gateway_url("ws://127.0.0.1:1");
+        }
+
+        let mut client =
+            builder.framework(StandardFramework::new()).session_store(store.clone()).await.unwrap();
+        let shutdown = client.shutdown_handle();
+        tokio::spawn(async move { client.start().await });
+
+        timeout(TIMEOUT, server.wait_until_ready()).await.unwrap();
+        assert!(shutdown.shutdown(TIMEOUT).await);
+        server.disconnect(1000).await;
+    }
+
+    let commands = server.gateway_commands().await;
+    let ops = |op| commands.iter().filter(|command| command["op"] == json!(op)).collect::<Vec<_>>();
+    let (_, session) = store.0.lock().unwrap().get(&ShardId(0)).cloned().unwrap();
+
+    // The restored session is resumed over the first connection.
+    assert_eq!(server.connections(), 2);
+    assert_eq!(ops(2).len(), 1);
+    assert_eq!(ops(6).len(), 1);
+    assert_eq!(ops(6)[0]["d"]["session_id"], json!(session.session_id));
+    assert_eq!(session.resume_gateway_url.as_deref(), Some(server.gateway_url()));
+}
+
+async fn respond_session_start_limit(server: &MockServer, remaining: u64, max_concurrency: u64) {
+    let gateway = json!({
+        "url": server.gateway_url(),
+        "shards": 4,
+        "session_start_limit": {
+            "total": 1000,
+            "remaining": remaining,
+            "reset_after": 1000,
+            "max_concurrency": max_concurrency,
+        },
+    });
+
+    server.respond(RouteInfo::GetBotGateway, 200, Some(gateway)).await;
+}
+
+async fn identify_count(server: &MockServer) -> usize {
+    server.gateway_commands().await.iter().filter(|command| command["op"] == json!(2)).count()
+}
+
+#[tokio::test]
+async fn shards_start_in_max_concurrency_buckets() {
+    let server = MockServer::start().await.unwrap();
+    respond_session_start_limit(&server, 1000, 4).await;
+
+    let mut client = server
+        .client_builder(GatewayIntents::default())
+        .framework(StandardFramework::new())
+        .await
+        .unwrap();
+    tokio::spawn(async move { client.start_autosharded().await });
+
+    // One bucket per shard, so none of them wait for another to start.
+    timeout(Duration::from_secs(3), async {
+        while identify_count(&server).await < 4 {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    })
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn shards_wait_for_session_start_limit_reset() {
+    let server = MockServer::start().await.unwrap();
+    respond_session_start_limit(&server, 0, 1).await;
+
+    let mut client = server
+        .client_builder(GatewayIntents::default())
+        .framework(StandardFramework::new())
+        .await
+        .unwrap();
+    tokio::spawn(async move { client.start().await });
+
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    assert_eq!(identify_count(&server).await, 0);
+
+    respond_session_start_limit(&server, 1000, 1).await;
+    timeout(TIMEOUT, server.wait_until_ready()).await.unwrap();
+}
+
+#[tokio::test]
+async fn resuming_shards_do_not_use_session_starts() {
+    let server = MockServer::start().await.unwrap();
+    let gateway = json!({
+        "url": server.gateway_url(),
+        "shards": 2,
+        "session_start_limit": {
+            "total": 1000,
+            "remaining": 1,
+            "reset_after": 60_000,
+            "max_concurrency": 2,
+        },
+    });
+    server.respond(RouteInfo::GetBotGateway, 200, Some(gateway)).await;
+
+    let store = MemorySessionStore::default();
+    store.0.lock().unwrap().insert(ShardId(0), (2, SessionInfo::new("saved-session", 5)));
+
+    let mut client = server
+        .client_builder(GatewayIntents::default())
+        .framework(StandardFramework::new())
+        .session_store(store)
+        .await
+        .unwrap();
+    tokio::spawn(async move { client.start_shards(2).await });
+
+    // Shard 0 resumes, so the only session start left goes to shard 1 instead
+    // of waiting for the limit to reset.
+    timeout(TIMEOUT, async {
+        while server.connections() < 2 {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    })
+    .await
+    .unwrap();
+
+    let commands = server.gateway_commands().await;
+    assert!(commands.iter().any(|command| command["op"] == json!(6)));
+}
+
+#[tokio::test]
+async fn shutdown_interrupts_session_start_limit_wait() {
+    let server = MockServer::start().await.unwrap();
+    respond_session_start_limit(&server, 0, 1).await;
+
+    let mut client = server
+        .client_builder(GatewayIntents::default())
+        .framework(StandardFramework::new())
+        .await
+        .unwrap();
+    let shutdown = client.shutdown_handle();
+    let client = tokio::spawn(async move { client.start().await });
+
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert!(shutdown.shutdown(TIMEOUT).await);
+    timeout(TIMEOUT, client).await.unwrap().unwrap().unwrap();
+
+    // The queuer stopped waiting, so the shard is not started once the limit
+    // resets.
+    respond_session_start_limit(&server, 1000, 1).await;
+    tokio::time::sleep(Duration::from_millis(1500)).await;
+    assert_eq!(server.connections(), 0);
+}
+
+#[tokio::test]
+async fn reconnect_interrupts_member_requests() {
+    let server = MockServer::start().await.unwrap();
+    let mut client = server
+        .client_builder(GatewayIntents::GUILD_MEMBERS)
+        .framework(StandardFramework::new())
+        .await
+        .unwrap();
+    let shard_manager = Arc::clone(&client.shard_manager);
+    tokio::spawn(async move { client.start().await });
+
+    timeout(TIMEOUT, server.wait_until_ready()).await.unwrap();
+    let messenger = {
+        let manager = shard_manager.lock().await;
+        let runners = manager.runners.lock().await;
+        runners[&ShardId(0)].runner_tx.clone()
+    };
+    let request = tokio::spawn(async move {
+        messenger.request_members(GuildId(1), None, ChunkGuildFilter::None).await
+    });
+
+    timeout(TIMEOUT, async {
+        while !server.gateway_commands().await.iter().any(|command| command["op"] == json!(8)) {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    })
+    .await
+    .unwrap();
+    server.disconnect(4000).await;
+
+    // The chunks are never sent, so the request fails instead of waiting
+    // forever.
+    match timeout(TIMEOUT, request).await.unwrap().unwrap() {
+        Err(serenity::Error::Gateway(GatewayError::MemberRequestInterrupted)) => {},
+        result => panic!("Expected an interrupted request, got {:?}", result),
+    }
+}
+
+#[cfg(feature = "remote_shard_coordinator")]
+#[tokio::test]
+async fn clients_claim_shards_from_coordinator() {
+    let server = MockServer::start().await.unwrap();
+    respond_session_start_limit(&server, 1000, 2).await;
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    tokio::spawn(ShardCoordinatorServer::new().serve_tcp(listener));
+
+    for _ in 0..2 {
+        let mut client = server
+            .client_builder(GatewayIntents::default())
+            .framework(StandardFramework::new())
+            .shard_coordinator(RemoteShardCoordinator::connect_tcp(address).await.unwrap())
+            .await
+            .unwrap();
+        tokio::spawn(async move { client.start_coordinated(2, 1).await });
+    }
+
+    timeout(TIMEOUT, async {
+        while identify_count(&server).await < 2 {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    })
+    .await
+    .unwrap();
+
+    // Both shards are claimed until their clients disconnect.
+    let coordinator = RemoteShardCoordinator::connect_tcp(address).await.unwrap();
+    assert!(coordinator.claim_shards(2, 1).await.is_err());
+}
+
+#[tokio::test]
+async fn http_uses_configured_responses() {
+    let server = MockServer::start().await.unwrap();
+    let http = server.http();
+
+    let user = http.get_current_user().await.unwrap();
+    assert_eq!(user.id, server.current_user().id);
+
+    let route = RouteInfo::GetChannel {
+        channel_id: 7,
+    };
+    assert!(http.get_channel(7).await.is_err());
+
+    server.respond(route.clone(), 500, None).await;
+    assert!(http.get_channel(7).await.is_err());
+
+    // The server error is retried twice by the default retry policy.
+    let requests = server.requests().await;
+    assert_eq!(requests.iter().filter(|request| request.is(&route)).count(), 4);
+}
+
+#[tokio::test]
+async fn http_retries_transient_failures() {
+    let server = MockServer::start().await.unwrap();
+    let policy = RetryPolicy::new().max_attempts(3).base_delay(Duration::from_millis(10));
+    let mut http =
+        HttpBuilder::new("token").base_url(server.api_url()).unwrap().retry_policy(policy).build();
+
+    let retries = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let retries_clone = Arc::clone(&retries);
+    http.ratelimiter.set_ratelimit_callback(Box::new(move |info| {
+        assert!(info.transient);
+        retries_clone.lock().unwrap().push(info.retries);
+    }));
+
+    let get_route = RouteInfo::GetChannel {
+        channel_id: 7,
+    };
+    server.respond(get_route.clone(), 503, None).await;
+    assert!(http.get_channel(7).await.is_err());
+
+    // POST requests are not idempotent, and so are not retried.
+    let post_route = RouteInfo::CreateMessage {
+        channel_id: 7,
+    };
+    server.respond(post_route.clone(), 503, None).await;
+    assert!(http.send_message(7, &json!({"content": "Hello"})).await.is_err());
+
+    let requests = server.requests().await;
+    assert_eq!(requests.iter().filter(|request| request.is(&get_route)).count(), 3);
+    assert_eq!(requests.iter().filter(|request| request.is(&post_route)).count(), 1);
+    assert_eq!(*retries.lock().unwrap(), vec![1, 2]);
+}
+
+#[tokio::test]
+async fn http_fails_fast_on_ratelimits() {
+    let server = MockServer::start().await.unwrap();
+    let http = server.http();
+
+    let route = RouteInfo::GetChannel {
+        channel_id: 7,
+    };
+    let body = json!({
+        "message": "You are being rate limited.",
+        "retry_after": 60.0,
+        "global": false,
+    });
+    server.respond(route.clone(), 429, Some(body)).await;
+
+    let options = RequestOptions::new().fail_on_ratelimit(true);
+    match options.scope(http.get_channel(7)).await {
+        Err(serenity::Error::Http(why)) => match *why {
+            HttpError::Ratelimited(ratelimited) => {
+                assert_eq!(ratelimited.retry_after, Duration::from_secs(60));
+                assert_eq!(ratelimited.path, "https://discord.com/api/v10/channels/7");
+                assert!(!ratelimited.global);
+            },
+            why => panic!("Expected a ratelimit error, got {:?}", why),
+        },
+        result => panic!("Expected a ratelimit error, got {:?}", result),
+    }
+
+    // Otherwise, the request waits to be retried until it times out.
+    let options = RequestOptions::new().timeout(Duration::from_millis(100));
+    match options.scope(http.get_channel(7)).await {
+        Err(serenity::Error::Http(why)) => {
+            assert!(
+                matches!(*why, HttpError::Timeout(timeout) if timeout == Duration::from_millis(100))
+            );
+        },
+        result => panic!("Expected a timeout, got {:?}", result),
+    }
+
+    let requests = server.requests().await;
+    assert_eq!(requests.iter().filter(|request| request.is(&route)).count(), 2);
+}
+
+fn command_json(id: u64, name: &str, description: &str) -> Value {
+    json!({
+        "id": id.to_string(),
+        "type": 1,
+        "application_id": "1",
+        "guild_id": "7",
+        "name": name,
+        "description": description,
+        "default_member_permissions": null,
+        "version": "1",
+    })
+}
+
+#[tokio::test]
+async fn command_registry_syncs_changed_commands() {
+    let server = MockServer::start().await.unwrap();
+    let http = server.http();
+    http.set_application_id(1);
+
+    let list_route = RouteInfo::GetGuildApplicationCommands {
+        application_id: 1,
+        guild_id: 7,
+        with_localizations: true,
+    };
+    let registered = json!([
+        command_json(2, "ping", "Checks the latency"),
+        command_json(3, "help", "Shows the commands"),
+        command_json(4, "old", "Is no longer declared"),
+    ]);
+    server.respond(list_route, 200, Some(registered)).await;
+
+    let create_route = RouteInfo::CreateGuildApplicationCommand {
+        application_id: 1,
+        guild_id: 7,
+    };
+    let edit_route = RouteInfo::EditGuildApplicationCommand {
+        application_id: 1,
+        guild_id: 7,
+        command_id: 3,
+    };
+    let delete_route = RouteInfo::DeleteGuildApplicationCommand {
+        application_id: 1,
+        guild_id: 7,
+        command_id: 4,
+    };
+    let created = command_json(5, "new", "Is newly declared");
+    server.respond(create_route.clone(), 200, Some(created)).await;
+    let edited = command_json(3, "help", "Lists the commands");
+    server.respond(edit_route.clone(), 200, Some(edited)).await;
+
+    let mut registry = CommandRegistry::new();
+    registry
+        .command(|command| command.name("ping").description("Checks the latency"))
+        .command(|command| command.name("help").description("Lists the commands"))
+        .command(|command| command.name("new").description("Is newly declared"));
+
+    let expected = vec![
+        CommandChange::Delete {
+            id: CommandId(4),
+            name: "old".into(),
+            kind: CommandType::ChatInput,
+        },
+        CommandChange::Edit {
+            id: CommandId(3),
+            name: "help".into(),
+            kind: CommandType::ChatInput,
+        },
+        CommandChange::Create {
+            name: "new".into(),
+            kind: CommandType::ChatInput,
+        },
+    ];
+
+    // A dry run only fetches the registered commands.
+    registry.dry_run(true);
+    assert_eq!(registry.sync_guild(&http, GuildId(7)).await.unwrap(), expected);
+    assert_eq!(server.requests().await.len(), 1);
+
+    registry.dry_run(false);
+    assert_eq!(registry.sync_guild(&http, GuildId(7)).await.unwrap(), expected);
+
+    let requests = server.requests().await;
+    assert_eq!(requests.len(), 5);
+    assert!(requests[2].is(&delete_route));
+    assert!(requests[3].is(&edit_route));
+    assert_eq!(requests[3].json::<Value>().unwrap()["description"], "Lists the commands");
+    assert!(requests[4].is(&create_route));
+    assert_eq!(requests[4].json::<Value>().unwrap()["name"], "new");
+}
+
+#[tokio::test]
+async fn command_registry_compares_localizations() {
+    let server = MockServer::start().await.unwrap();
+    let http = server.http();
+    http.set_application_id(1);
+
+    // The localizations are only returned when requested.
+    let list_route = RouteInfo::GetGuildApplicationCommands {
+        application_id: 1,
+        guild_id: 7,
+        with_localizations: true,
+    };
+    let mut ping = command_json(2, "ping", "Checks the latency");
+    ping["name_localizations"] = json!({"de": "ping"});
+    ping["description_localizations"] = json!({"de": "Prüft die Latenz"});
+    let mut help = command_json(3, "help", "Shows the commands");
+    help["description_localizations"] = json!({"de": "Zeigt die Befehle"});
+    server.respond(list_route.clone(), 200, Some(json!([ping, help]))).await;
+
+    let mut registry = CommandRegistry::new();
+    registry
+        .command(|command| {
+            command
+                .name("ping")
+                .name_localized("de", "ping")
+                .description("Checks the latency")
+                .description_localized("de", "Prüft die Latenz")
+        })
+        .command(|command| {
+            command
+                .name("help")
+                .description("Shows the commands")
+                .description_localized("de", "Listet die Befehle auf")
+        })
+        .dry_run(true);
+
+    let expected = vec![CommandChange::Edit {
+        id: CommandId(3),
+        name: "help".into(),
+        kind: CommandType::ChatInput,
+    }];
+    assert_eq!(registry.sync_guild(&http, GuildId(7)).await.unwrap(), expected);
+
+    let requests = server.requests().await;
+    assert_eq!(requests.len(), 1);
+    assert!(requests[0].is(&list_route));
+}
+
+#[cfg(feature = "interaction_framework")]
+mod interaction_framework {
+    use serenity::framework::interaction::{CommandOptions, InteractionFramework};
+    use serenity::framework::standard::macros::check;
+    use serenity::framework::standard::{
+        Args,
+        CommandOptions as StandardOptions,
+        CommandResult,
+        DispatchError,
+        Reason,
+    };
+    use serenity::model::application::interaction::application_command::{
+        ApplicationCommandInteraction,
+        CommandDataOption,
+    };
+    use serenity::model::application::interaction::message_component::MessageComponentInteraction;
+
+    use super::*;
+
+    async fn respond(
+        ctx: &Context,
+        interaction: &ApplicationCommandInteraction,
+        content: String,
+    ) -> CommandResult {
+        interaction
+            .create_interaction_response(&ctx.http, |response| {
+                response.interaction_response_data(|data| data.content(content))
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    #[derive(CommandOptions)]
+    struct AddRole {
+        /// The name of the role.
+        #[max_length(100)]
+        role: String,
+        /// How long the role is kept, in days.
+        #[min_value(1)]
+        #[max_value(30)]
+        days: Option<i64>,
+    }
+
+    async fn add_role(
+        ctx: &Context,
+        interaction: &ApplicationCommandInteraction,
+        options: &[CommandDataOption],
+    ) -> CommandResult {
+        let options = AddRole::from_options(options)?;
+        assert_eq!(options.days, None);
+
+        respond(ctx, interaction, format!("Added role {}", options.role)).await
+    }
+
+    async fn dispatch_error(
+        ctx: &Context,
+        interaction: &ApplicationCommandInteraction,
+        error: DispatchError,
+        name: &str,
+    ) {
+        let content = match error {
+            DispatchError::Ratelimited(_) => format!("`{}` is ratelimited", name),
+            error => format!("`{}` failed: {:?}", name, error),
+        };
+
+        respond(ctx, interaction, content).await.unwrap();
+    }
+
+    #[check]
+    #[name = "Moderator"]
+    async fn moderator_check(
+        _: &Context,
+        msg: &Message,
+        _: &mut Args,
+        _: &StandardOptions,
+    ) -> Result<(), Reason> {
+        let is_moderator =
+            msg.member.as_ref().map_or(false, |member| member.roles.contains(&RoleId(7)));
+
+        if is_moderator {
+            Ok(())
+        } else {
+            Err(Reason::User(format!("{} is not a moderator", msg.content)))
+        }
+    }
+
+    async fn delete(ctx: &Context, interaction: &MessageComponentInteraction) -> CommandResult {
+        let id = interaction.data.custom_id.trim_start_matches("delete:").parse::<u64>()?;
+        interaction.channel_id.delete_message(&ctx.http, id).await?;
+
+        Ok(())
+    }
+
+    fn interaction(id: u64, kind: u8, data: Value) -> Value {
+        json!({
+            "id": id.to_string(),
+            "application_id": "1",
+            "type": kind,
+            "data": data,
+            "guild_id": "2",
+            "channel_id": "3",
+            "member": {
+                "user": {"id": "4", "username": "user", "discriminator": "0001", "avatar": null},
+                "roles": [],
+                "joined_at": "2022-01-01T00:00:00Z",
+                "deaf": false,
+                "mute": false,
+                "permissions": "0",
+            },
+            "token": "token",
+            "version": 1,
+            "locale": "en-US",
+            "app_permissions": "0",
+            "guild_locale": "en-US",
+        })
+    }
+
+    fn add_role_command(id: u64) -> InteractionCreateEvent {
+        let interaction = interaction(
+            id,
+            2,
+            json!({
+                "id": "5",
+                "name": "config",
+                "type": 1,
+                "options": [{
+                    "name": "roles",
+                    "type": 2,
+                    "options": [{
+                        "name": "add",
+                        "type": 1,
+                        "options": [{"name": "role", "type": 3, "value": "moderator"}],
+                    }],
+                }],
+            }),
+        );
+
+        serde_json::from_value(interaction).unwrap()
+    }
+
+    async fn response_content(server: &MockServer, interaction_id: u64) -> Value {
+        let route = RouteInfo::CreateInteractionResponse {
+            interaction_id,
+            interaction_token: "token",
+        };
+        let request = timeout(TIMEOUT, server.wait_for_request(route)).await.unwrap();
+
+        request.json::<Value>().unwrap()["data"]["content"].clone()
+    }
+
+    #[tokio::test]
+    async fn interaction_framework_routes_interactions() {
+        let server = MockServer::start().await.unwrap();
+        let framework = InteractionFramework::new()
+            .bucket("config", |b| b.delay(60))
+            .command(|command| {
+                command
+                    .name("config")
+                    .description("Configures the bot")
+                    .bucket("config")
+                    .create_subcommand(|group| {
+                        group.name("roles").description("Configures roles").create_subcommand(
+                            |subcommand| {
+                                subcommand
+                                    .name("add")
+                                    .description("Adds a role")
+                                    .options_from::<AddRole>()
+                                    .handler(|ctx, interaction, options| {
+                                        Box::pin(add_role(ctx, interaction, options))
+                                    })
+                            },
+                        )
+                    })
+            })
+            .component("delete", |ctx, interaction| Box::pin(delete(ctx, interaction)))
+            .on_dispatch_error(|ctx, interaction, error, name| {
+                Box::pin(dispatch_error(ctx, interaction, error, name))
+            });
+
+        let definitions = framework.create_application_commands();
+        assert_eq!(definitions.len(), 1);
+        assert_eq!(definitions[0].0["options"][0]["options"][0]["name"], "add");
+        assert_eq!(
+            definitions[0].0["options"][0]["options"][0]["options"],
+            json!([
+                {
+                    "name": "role",
+                    "description": "The name of the role.",
+                    "type": 3,
+                    "required": true,
+                    "max_length": 100,
+                },
+                {
+                    "name": "days",
+                    "description": "How long the role is kept, in days.",
+                    "type": 4,
+                    "required": false,
+                    "min_value": 1,
+                    "max_value": 30,
+                },
+            ])
+        );
+
+        let mut client = server
+            .client_builder(GatewayIntents::default())
+            .framework(StandardFramework::new())
+            .event_handler(framework)
+            .await
+            .unwrap();
+        tokio::spawn(async move { client.start().await });
+        timeout(TIMEOUT, server.wait_until_ready()).await.unwrap();
+
+        server.dispatch(Event::InteractionCreate(add_role_command(10))).await;
+        assert_eq!(response_content(&server, 10).await, "Added role moderator");
+
+        // The second invocation is within the delay of the bucket.
+        server.dispatch(Event::InteractionCreate(add_role_command(11))).await;
+        assert_eq!(response_content(&server, 11).await, "`config roles add` is ratelimited");
+
+        let mut component =
+            interaction(12, 3, json!({"custom_id": "delete:99", "component_type": 2}));
+        component["message"] = json!({
+            "id": "98",
+            "channel_id": "3",
+            "author": {"id": "1", "username": "bot", "discriminator": "0001", "avatar": null},
+            "content": "",
+            "timestamp": "2022-01-01T00:00:00Z",
+            "edited_timestamp": null,
+            "tts": false,
+            "mention_everyone": false,
+            "mentions": [],
+            "mention_roles": [],
+            "attachments": [],
+            "embeds": [],
+            "pinned": false,
+            "type": 0,
+        });
+        server.dispatch(Event::InteractionCreate(serde_json::from_value(component).unwrap())).await;
+
+        let route = RouteInfo::DeleteMessage {
+            channel_id: 3,
+            message_id: 99,
+        };
+        timeout(TIMEOUT, server.wait_for_request(route)).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn interaction_framework_runs_standard_checks() {
+        let server = MockServer::start().await.unwrap();
+        let framework = InteractionFramework::new()
+            .command(|command| {
+                command
+                    .name("config")
+                    .description("Configures the bot")
+                    .standard_check(&MODERATOR_CHECK)
+                    .create_subcommand(|group| {
+                        group.name("roles").description("Configures roles").create_subcommand(
+                            |subcommand| {
+                                subcommand
+                                    .name("add")
+                                    .description("Adds a role")
+                                    .options_from::<AddRole>()
+                                    .handler(|ctx, interaction, options| {
+                                        Box::pin(add_role(ctx, interaction, options))
+                                    })
+                            },
+                        )
+                    })
+            })
+            .on_dispatch_error(|ctx, interaction, error, name| {
+                Box::pin(dispatch_error(ctx, interaction, error, name))
+            });
+
+        let mut client = server
+            .client_builder(GatewayIntents::default())
+            .framework(StandardFramework::new())
+            .event_handler(framework)
+            .await
+            .unwrap();
+        tokio::spawn(async move { client.start().await });
+        timeout(TIMEOUT, server.wait_until_ready()).await.unwrap();
+
+        // The check is given a message standing in for the interaction.
+        server.dispatch(Event::InteractionCreate(add_role_command(10))).await;
+        assert_eq!(
+            response_content(&server, 10).await,
+            "`config roles add` failed: CheckFailed(\"Moderator\", User(\"/config roles add is \
+             not a moderator\"))"
+        );
+    }
+}